@@ -0,0 +1,180 @@
+//! Private notes about a document, kept next to it in a sidecar file.
+//!
+//! This module provides:
+//! - `Annotation`: A note, optionally anchored to a source line and heading
+//! - `Annotations`: The notes of one document with sidecar persistence
+//!   (`doc.md` → `doc.md.arto-notes.json`), so the document itself is never
+//!   modified
+//! - `ANNOTATIONS_CHANGED`: Broadcast channel for cross-window sync
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+
+/// Suffix appended to the document file name for its sidecar
+const SIDECAR_SUFFIX: &str = ".arto-notes.json";
+
+/// A note about a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    /// Note text
+    pub text: String,
+    /// 1-based source line the note is anchored to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Id of the heading of the anchored section, preferred over `line` when
+    /// it still exists since it survives edits above it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading_id: Option<String>,
+    /// When the note was added
+    pub created_at: DateTime<Utc>,
+}
+
+/// Notes of one document (saved to its sidecar file)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Annotations {
+    /// Notes, anchored ones ordered by line, followed by unanchored ones in
+    /// the order they were added
+    pub notes: Vec<Annotation>,
+}
+
+impl Annotations {
+    /// Get the sidecar path for a document
+    pub fn sidecar_path(document: &Path) -> PathBuf {
+        let mut name = document
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        name.push(SIDECAR_SUFFIX);
+        document.with_file_name(name)
+    }
+
+    /// Load the notes of a document, or none if it has no sidecar
+    pub fn load(document: &Path) -> Result<Self> {
+        let path = Self::sidecar_path(document);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Save the notes of a document
+    ///
+    /// The sidecar is removed once the last note is deleted, so documents
+    /// without notes don't leave files behind.
+    pub fn save(&self, document: &Path) -> Result<()> {
+        let path = Self::sidecar_path(document);
+
+        tracing::debug!(path = %path.display(), count = self.notes.len(), "Saving annotations");
+
+        if self.notes.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))?;
+            }
+            return Ok(());
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Add a note, keeping anchored notes in line order
+    pub fn add(&mut self, annotation: Annotation) {
+        let index = match annotation.line {
+            Some(line) => self
+                .notes
+                .iter()
+                .position(|note| note.line.is_none_or(|other| other > line))
+                .unwrap_or(self.notes.len()),
+            None => self.notes.len(),
+        };
+        self.notes.insert(index, annotation);
+    }
+
+    /// Remove the note at `index`
+    pub fn remove(&mut self, index: usize) {
+        if index < self.notes.len() {
+            self.notes.remove(index);
+        }
+    }
+}
+
+/// Broadcast channel for annotation changes
+///
+/// Carries the document whose notes were saved, so other windows showing it
+/// reload them from its sidecar.
+pub static ANNOTATIONS_CHANGED: LazyLock<broadcast::Sender<PathBuf>> =
+    LazyLock::new(|| broadcast::channel(10).0);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn note(text: &str, line: Option<usize>) -> Annotation {
+        Annotation {
+            text: text.to_string(),
+            line,
+            heading_id: None,
+            created_at: DateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            Annotations::sidecar_path(Path::new("/docs/guide.md")),
+            PathBuf::from("/docs/guide.md.arto-notes.json")
+        );
+    }
+
+    #[test]
+    fn test_add_keeps_anchored_notes_in_line_order() {
+        let mut annotations = Annotations::default();
+        annotations.add(note("loose", None));
+        annotations.add(note("line 10", Some(10)));
+        annotations.add(note("line 3", Some(3)));
+        annotations.add(note("also line 10", Some(10)));
+
+        let texts: Vec<&str> = annotations
+            .notes
+            .iter()
+            .map(|note| note.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["line 3", "line 10", "also line 10", "loose"]);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let document = temp_dir.path().join("doc.md");
+        fs::write(&document, "# Doc").unwrap();
+
+        assert_eq!(
+            Annotations::load(&document).unwrap(),
+            Annotations::default()
+        );
+
+        let mut annotations = Annotations::default();
+        annotations.add(note("Check this", Some(1)));
+        annotations.save(&document).unwrap();
+        assert!(Annotations::sidecar_path(&document).exists());
+        assert_eq!(Annotations::load(&document).unwrap(), annotations);
+
+        // Deleting the last note removes the sidecar
+        annotations.remove(0);
+        annotations.save(&document).unwrap();
+        assert!(!Annotations::sidecar_path(&document).exists());
+        assert_eq!(fs::read_to_string(&document).unwrap(), "# Doc");
+    }
+}
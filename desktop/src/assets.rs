@@ -21,3 +21,14 @@ pub fn get_default_markdown_content() -> String {
     //template.replace("../assets/arto-header-welcome.png", &header_data_url)
     template.replace("../assets/arto-header-welcome.png", header_str)
 }
+
+/// Read the bundled application stylesheet (inlined into exported documents)
+pub fn main_style_css() -> String {
+    asset_path(MAIN_STYLE)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_else(|| {
+            tracing::warn!("Failed to read the application stylesheet");
+            String::new()
+        })
+}
@@ -0,0 +1,180 @@
+//! Backlink index: which documents link to a given file.
+//!
+//! Scans markdown files under a root directory and caches the local files
+//! each one links to (markdown links and frontmatter `related` entries).
+//! Scans skip what the file explorer hides and stop early in very large
+//! trees.
+//! Cached entries are revalidated by modification time and dropped
+//! explicitly when the directory watcher reports changes.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+use crate::markdown::extract_local_links;
+use crate::utils::file::{is_markdown_file, walk_files, ScanOptions, MAX_SCANNED_ENTRIES};
+
+#[derive(Debug, Clone)]
+struct CachedLinks {
+    modified: SystemTime,
+    related_key: String,
+    /// Canonical paths of the existing files the document links to
+    targets: Vec<PathBuf>,
+}
+
+static LINK_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedLinks>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Documents linking to a file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Backlinks {
+    pub files: Vec<PathBuf>,
+    /// The scan stopped early, so some linking documents may be missing
+    pub partial: bool,
+}
+
+/// Find all markdown files under `root` that link to `target`.
+///
+/// Links are compared after resolving them relative to the linking file, so
+/// `../a.md` and `a.md` from different directories match the same file.
+/// Results are sorted by path and never include `target` itself.
+pub fn find_backlinks(
+    root: &Path,
+    target: &Path,
+    related_key: &str,
+    options: &ScanOptions,
+) -> Backlinks {
+    let Ok(target) = fs::canonicalize(target) else {
+        return Backlinks::default();
+    };
+    let mut files = Vec::new();
+    let partial = walk_files(root, options, MAX_SCANNED_ENTRIES, |file| {
+        if is_markdown_file(&file) && links_for_file(&file, related_key).contains(&target) {
+            files.push(file);
+        }
+        ControlFlow::Continue(())
+    });
+
+    files.retain(|file| fs::canonicalize(file).is_ok_and(|file| file != target));
+    files.sort();
+    Backlinks { files, partial }
+}
+
+/// Drop cached links for every file under `dir`
+pub fn invalidate_link_cache(dir: &Path) {
+    LINK_CACHE.lock().retain(|path, _| !path.starts_with(dir));
+}
+
+/// Get the link targets of a single file, using the cache when it is unchanged
+fn links_for_file(path: &Path, related_key: &str) -> Vec<PathBuf> {
+    let Ok(modified) = fs::metadata(path).and_then(|meta| meta.modified()) else {
+        return Vec::new();
+    };
+
+    if let Some(cached) = LINK_CACHE.lock().get(path) {
+        if cached.modified == modified && cached.related_key == related_key {
+            return cached.targets.clone();
+        }
+    }
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let targets: Vec<PathBuf> = fs::read_to_string(path)
+        .map(|content| extract_local_links(&content, related_key))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|link| fs::canonicalize(base_dir.join(link)).ok())
+        .collect();
+    LINK_CACHE.lock().insert(
+        path.to_path_buf(),
+        CachedLinks {
+            modified,
+            related_key: related_key.to_string(),
+            targets: targets.clone(),
+        },
+    );
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_backlinks() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("target.md"), "# Target\n\n[self](target.md)").unwrap();
+        fs::write(root.join("a.md"), "See [target](target.md#intro).").unwrap();
+        fs::write(
+            root.join("sub/b.md"),
+            "---\nrelated: [../target.md]\n---\n# B",
+        )
+        .unwrap();
+        fs::write(root.join("sub/c.md"), "[elsewhere](target.md)").unwrap();
+        fs::write(root.join("d.md"), "[remote](https://example.com/target.md)").unwrap();
+
+        let backlinks = find_backlinks(
+            root,
+            &root.join("target.md"),
+            "related",
+            &ScanOptions::default(),
+        );
+
+        assert_eq!(
+            backlinks.files,
+            vec![root.join("a.md"), root.join("sub/b.md")]
+        );
+        assert!(!backlinks.partial);
+    }
+
+    #[test]
+    fn test_find_backlinks_sees_updates_after_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let target = root.join("target.md");
+        let file = root.join("a.md");
+        fs::write(&target, "# Target").unwrap();
+        fs::write(&file, "[target](target.md)").unwrap();
+        let options = ScanOptions::default();
+        assert_eq!(
+            find_backlinks(root, &target, "related", &options).files,
+            vec![file.clone()]
+        );
+
+        fs::write(&file, "No links").unwrap();
+        invalidate_link_cache(root);
+
+        assert!(find_backlinks(root, &target, "related", &options)
+            .files
+            .is_empty());
+    }
+
+    #[test]
+    fn test_find_backlinks_skips_ignored_directories() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for dir in ["node_modules/pkg", "build", "docs"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "build/\n").unwrap();
+        fs::write(root.join("target.md"), "# Target").unwrap();
+        fs::write(root.join("node_modules/pkg/a.md"), "[t](../../target.md)").unwrap();
+        fs::write(root.join("build/b.md"), "[t](../target.md)").unwrap();
+        fs::write(root.join("docs/c.md"), "[t](../target.md)").unwrap();
+
+        let options = ScanOptions {
+            ignore_patterns: vec!["node_*".to_string()],
+            respect_gitignore: true,
+            ..ScanOptions::default()
+        };
+        let backlinks = find_backlinks(root, &root.join("target.md"), "related", &options);
+
+        assert_eq!(backlinks.files, vec![root.join("docs/c.md")]);
+    }
+}
@@ -6,6 +6,7 @@
 //! - `BOOKMARKS`: Global static for app-wide bookmark access
 //! - `BOOKMARKS_CHANGED`: Broadcast channel for cross-window sync
 
+use crate::config::CONFIG;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -22,6 +23,9 @@ pub struct Bookmark {
     /// Custom display name (if None, use file/directory name)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Sidebar root captured when the bookmark was created (files only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<PathBuf>,
 }
 
 impl Bookmark {
@@ -30,6 +34,7 @@ impl Bookmark {
         Self {
             path: path.into(),
             name: None,
+            root: None,
         }
     }
 
@@ -52,6 +57,19 @@ impl Bookmark {
     pub fn exists(&self) -> bool {
         self.path.exists()
     }
+
+    /// Get the root directory to restore when jumping to this bookmark
+    ///
+    /// Returns `None` when no root was captured. If the captured root no longer
+    /// exists, the bookmarked file's parent directory is used instead.
+    pub fn jump_root(&self) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        if root.is_dir() {
+            Some(root.clone())
+        } else {
+            self.path.parent().map(Path::to_path_buf)
+        }
+    }
 }
 
 /// Bookmarks storage (saved to bookmarks.json)
@@ -128,14 +146,18 @@ impl Bookmarks {
 
     /// Toggle bookmark (add if not present, remove if present)
     ///
+    /// `root` is stored with a newly added bookmark and ignored on removal.
     /// Returns `true` if the path is now bookmarked, `false` if removed.
-    pub fn toggle(&mut self, path: impl Into<PathBuf>) -> bool {
+    pub fn toggle(&mut self, path: impl Into<PathBuf>, root: Option<PathBuf>) -> bool {
         let path = path.into();
         if self.contains(&path) {
             self.remove(&path);
             false
         } else {
-            self.items.push(Bookmark::new(path));
+            self.items.push(Bookmark {
+                root,
+                ..Bookmark::new(path)
+            });
             true
         }
     }
@@ -200,11 +222,18 @@ pub static BOOKMARKS_CHANGED: LazyLock<broadcast::Sender<()>> =
 /// 2. Save to disk
 /// 3. Broadcast the change to all windows
 ///
+/// `current_root` is the sidebar root of the calling window. It is recorded
+/// with file bookmarks when `sidebar.bookmarkRoot` is enabled.
+///
 /// Returns `true` if the path is now bookmarked, `false` if removed.
-pub fn toggle_bookmark(path: impl AsRef<Path>) -> bool {
+pub fn toggle_bookmark(path: impl AsRef<Path>, current_root: Option<&Path>) -> bool {
+    let path = path.as_ref();
+    let root = current_root
+        .filter(|_| CONFIG.read().sidebar.bookmark_root && !path.is_dir())
+        .map(Path::to_path_buf);
     let result = {
         let mut bookmarks = BOOKMARKS.write();
-        let result = bookmarks.toggle(path.as_ref().to_path_buf());
+        let result = bookmarks.toggle(path.to_path_buf(), root);
         bookmarks.save();
         result
     };
@@ -244,6 +273,7 @@ mod tests {
         let bookmark_with_name = Bookmark {
             path: PathBuf::from("/path/to/file.md"),
             name: Some("My Notes".to_string()),
+            root: None,
         };
         assert_eq!(bookmark_with_name.display_name(), "My Notes");
     }
@@ -286,16 +316,47 @@ mod tests {
         let mut bookmarks = Bookmarks::default();
 
         // Toggle on
-        let result = bookmarks.toggle("/path/to/file.md");
+        let result = bookmarks.toggle("/path/to/file.md", None);
         assert!(result);
         assert!(bookmarks.contains(Path::new("/path/to/file.md")));
 
         // Toggle off
-        let result = bookmarks.toggle("/path/to/file.md");
+        let result = bookmarks.toggle("/path/to/file.md", None);
         assert!(!result);
         assert!(!bookmarks.contains(Path::new("/path/to/file.md")));
     }
 
+    #[test]
+    fn test_bookmarks_toggle_with_root() {
+        let mut bookmarks = Bookmarks::default();
+
+        assert!(bookmarks.toggle("/project/docs/file.md", Some(PathBuf::from("/project"))));
+        assert_eq!(bookmarks.items[0].root, Some(PathBuf::from("/project")));
+    }
+
+    #[test]
+    fn test_bookmark_jump_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("docs").join("file.md");
+
+        // No captured root: keep the current sidebar root
+        assert_eq!(Bookmark::new(&file_path).jump_root(), None);
+
+        // Existing root is restored as-is
+        let bookmark = Bookmark {
+            root: Some(temp_dir.path().to_path_buf()),
+            ..Bookmark::new(&file_path)
+        };
+        assert_eq!(bookmark.jump_root(), Some(temp_dir.path().to_path_buf()));
+
+        // Missing root falls back to the file's parent
+        let bookmark = Bookmark {
+            root: Some(temp_dir.path().join("removed")),
+            ..Bookmark::new(&file_path)
+        };
+        assert_eq!(bookmark.jump_root(), Some(temp_dir.path().join("docs")));
+    }
+
     #[test]
     fn test_bookmarks_reorder_forward() {
         // Drag from earlier to later position
@@ -374,5 +435,7 @@ mod tests {
         assert_eq!(parsed.items.len(), 1);
         assert_eq!(parsed.items[0].path, PathBuf::from("/path/to/file.md"));
         assert_eq!(parsed.items[0].name, Some("My File".to_string()));
+        assert_eq!(parsed.items[0].root, None);
+        assert!(!json.contains("root"));
     }
 }
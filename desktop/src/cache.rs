@@ -1,6 +1,17 @@
 use std::fs;
 use std::path::PathBuf;
 
+mod images;
+mod memory;
+
+pub use images::{get_image, store_image, CacheHeaders};
+pub use memory::CacheStats;
+
+/// Size and effectiveness of each cache, by name (shown with `cache.showMetrics`)
+pub fn stats() -> Vec<(&'static str, CacheStats)> {
+    images::stats()
+}
+
 /// Build identifier embedded at compile time (changes on every rebuild)
 const BUILD_ID: &str = compile_time::datetime_str!();
 
@@ -0,0 +1,420 @@
+//! On-disk cache for downloaded remote images.
+//!
+//! Each image is stored in its own file, named by the SHA-256 of its URL,
+//! next to an `index.json` recording content type, size, expiry and last
+//! access. Entries are served until they expire; when the cache grows past
+//! `imageCache.maxSizeMb`, the least recently used entries are evicted.
+//!
+//! Recently used images are also kept in memory, within `cache.memoryBudgetMb`,
+//! so documents re-rendered on reload don't read them from disk again.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use super::memory::{CacheStats, MemoryCache};
+use crate::config::CONFIG;
+
+const INDEX_FILENAME: &str = "index.json";
+
+static IMAGE_CACHE: LazyLock<Option<Mutex<ImageCache>>> =
+    LazyLock::new(|| image_cache_dir().map(|dir| Mutex::new(ImageCache::open(dir))));
+
+static MEMORY_IMAGES: LazyLock<Mutex<MemoryCache<MemoryImage>>> =
+    LazyLock::new(|| Mutex::new(MemoryCache::default()));
+
+/// An image served from the cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedImage {
+    pub bytes: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+/// Caching headers of the response an image was downloaded with
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheHeaders<'a> {
+    pub cache_control: Option<&'a str>,
+    pub expires: Option<&'a str>,
+}
+
+/// An image kept in memory along with the end of its lifetime
+#[derive(Debug, Clone)]
+struct MemoryImage {
+    image: CachedImage,
+    expires_at: DateTime<Utc>,
+}
+
+/// Look up a fresh cached copy of the image at `url`
+pub fn get_image(url: &str) -> Option<CachedImage> {
+    if !CONFIG.read().image_cache.enabled {
+        return None;
+    }
+    let now = Utc::now();
+    if let Some(cached) = MEMORY_IMAGES
+        .lock()
+        .get(url, |cached| cached.expires_at > now)
+    {
+        return Some(cached.image);
+    }
+    let (image, expires_at) = IMAGE_CACHE.as_ref()?.lock().get(url, now)?;
+    remember_image(url, image.clone(), expires_at);
+    Some(image)
+}
+
+/// Store a downloaded image, unless its caching headers forbid it
+pub fn store_image(url: &str, bytes: &[u8], content_type: Option<&str>, headers: CacheHeaders<'_>) {
+    let config = CONFIG.read().image_cache.clone();
+    if !config.enabled {
+        return;
+    }
+
+    let now = Utc::now();
+    let ttl = TimeDelta::hours(config.ttl_hours.min(i64::MAX as u64 / 3600) as i64);
+    let Some(expires_at) = expires_at(headers, now, ttl) else {
+        tracing::debug!(%url, "Response is not cacheable, skipping image cache");
+        return;
+    };
+    let image = CachedImage {
+        bytes: bytes.to_vec(),
+        content_type: content_type.map(str::to_string),
+    };
+    remember_image(url, image, expires_at);
+
+    let Some(cache) = IMAGE_CACHE.as_ref() else {
+        return;
+    };
+    let max_bytes = config.max_size_mb.saturating_mul(1024 * 1024);
+    cache
+        .lock()
+        .insert(url, bytes, content_type, expires_at, now, max_bytes);
+}
+
+/// Size and effectiveness of the in-memory and on-disk image caches
+pub fn stats() -> Vec<(&'static str, CacheStats)> {
+    let disk = IMAGE_CACHE
+        .as_ref()
+        .map(|cache| cache.lock().stats())
+        .unwrap_or_default();
+    vec![
+        ("Images (memory)", MEMORY_IMAGES.lock().stats()),
+        ("Images (disk)", disk),
+    ]
+}
+
+/// Keep an image in memory within `cache.memoryBudgetMb`
+fn remember_image(url: &str, image: CachedImage, expires_at: DateTime<Utc>) {
+    let budget = CONFIG.read().cache.memory_budget_bytes();
+    let size = (image.bytes.len() + image.content_type.as_ref().map_or(0, String::len)) as u64;
+    MEMORY_IMAGES
+        .lock()
+        .insert(url, MemoryImage { image, expires_at }, size, budget);
+}
+
+/// Path: ~/Library/Caches/arto-images
+///
+/// Kept apart from the WebView cache directories, which are cleared on upgrade.
+fn image_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("arto-images"))
+}
+
+fn cache_key(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+}
+
+/// When a response stops being fresh, or `None` if it must not be cached.
+///
+/// `Cache-Control` takes precedence over `Expires`; `ttl` applies when the
+/// response has neither.
+fn expires_at(
+    headers: CacheHeaders<'_>,
+    now: DateTime<Utc>,
+    ttl: TimeDelta,
+) -> Option<DateTime<Utc>> {
+    if let Some(cache_control) = headers.cache_control {
+        let mut max_age = None;
+        for directive in cache_control.split(',') {
+            let directive = directive.trim().to_ascii_lowercase();
+            if directive == "no-store" || directive == "no-cache" {
+                return None;
+            }
+            if let Some(value) = directive.strip_prefix("max-age=") {
+                max_age = value.trim_matches('"').parse::<i64>().ok();
+            }
+        }
+        if let Some(max_age) = max_age {
+            return (max_age > 0).then(|| now + TimeDelta::seconds(max_age));
+        }
+    }
+
+    if let Some(expires) = headers.expires {
+        // An invalid date (often "0" or "-1") means already expired
+        let expires = DateTime::parse_from_rfc2822(expires.trim()).ok()?;
+        let expires = expires.with_timezone(&Utc);
+        return (expires > now).then_some(expires);
+    }
+
+    Some(now + ttl)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    content_type: Option<String>,
+    size: u64,
+    expires_at: DateTime<Utc>,
+    last_access: DateTime<Utc>,
+}
+
+struct ImageCache {
+    dir: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl ImageCache {
+    fn open(dir: PathBuf) -> Self {
+        let entries = fs::read_to_string(dir.join(INDEX_FILENAME))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            dir,
+            entries,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// Look up a fresh image, along with when it expires
+    fn get(&mut self, url: &str, now: DateTime<Utc>) -> Option<(CachedImage, DateTime<Utc>)> {
+        let key = cache_key(url);
+        let Some(entry) = self.entries.get_mut(&key) else {
+            self.misses += 1;
+            return None;
+        };
+
+        if entry.expires_at <= now {
+            self.misses += 1;
+            self.remove(&key);
+            self.save_index();
+            return None;
+        }
+
+        match fs::read(self.dir.join(&key)) {
+            Ok(bytes) => {
+                entry.last_access = now;
+                let image = CachedImage {
+                    bytes,
+                    content_type: entry.content_type.clone(),
+                };
+                let expires_at = entry.expires_at;
+                self.hits += 1;
+                self.save_index();
+                Some((image, expires_at))
+            }
+            Err(e) => {
+                tracing::debug!(%url, ?e, "Cached image is missing, dropping entry");
+                self.misses += 1;
+                self.remove(&key);
+                self.save_index();
+                None
+            }
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            bytes: self.entries.values().map(|entry| entry.size).sum(),
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    fn insert(
+        &mut self,
+        url: &str,
+        bytes: &[u8],
+        content_type: Option<&str>,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_bytes: u64,
+    ) {
+        if bytes.len() as u64 > max_bytes {
+            return;
+        }
+        if let Err(e) = fs::create_dir_all(&self.dir) {
+            tracing::warn!(path = %self.dir.display(), ?e, "Failed to create image cache directory");
+            return;
+        }
+
+        let key = cache_key(url);
+        if let Err(e) = fs::write(self.dir.join(&key), bytes) {
+            tracing::warn!(%url, ?e, "Failed to write cached image");
+            return;
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                content_type: content_type.map(str::to_string),
+                size: bytes.len() as u64,
+                expires_at,
+                last_access: now,
+            },
+        );
+        self.evict(max_bytes);
+        self.save_index();
+    }
+
+    /// Remove least recently used entries until the cache fits in `max_bytes`
+    fn evict(&mut self, max_bytes: u64) {
+        let mut total: u64 = self.entries.values().map(|entry| entry.size).sum();
+        if total <= max_bytes {
+            return;
+        }
+
+        let mut keys: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_access, key.clone()))
+            .collect();
+        keys.sort();
+        for (_, key) in keys {
+            if total <= max_bytes {
+                break;
+            }
+            if let Some(entry) = self.entries.get(&key) {
+                total -= entry.size;
+            }
+            self.remove(&key);
+            self.evictions += 1;
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if self.entries.remove(key).is_some() {
+            let _ = fs::remove_file(self.dir.join(key));
+        }
+    }
+
+    fn save_index(&self) {
+        let path = self.dir.join(INDEX_FILENAME);
+        let result = serde_json::to_string(&self.entries)
+            .map_err(std::io::Error::other)
+            .and_then(|json| fs::write(&path, json));
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), ?e, "Failed to save image cache index");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn headers<'a>(cache_control: Option<&'a str>, expires: Option<&'a str>) -> CacheHeaders<'a> {
+        CacheHeaders {
+            cache_control,
+            expires,
+        }
+    }
+
+    #[test]
+    fn test_expires_at_honors_cache_headers() {
+        let now = at(0);
+        let ttl = TimeDelta::hours(1);
+
+        assert_eq!(expires_at(headers(None, None), now, ttl), Some(at(3600)));
+        assert_eq!(
+            expires_at(headers(Some("public, max-age=60"), None), now, ttl),
+            Some(at(60))
+        );
+        assert_eq!(expires_at(headers(Some("max-age=0"), None), now, ttl), None);
+        assert_eq!(expires_at(headers(Some("no-store"), None), now, ttl), None);
+        assert_eq!(expires_at(headers(Some("No-Cache"), None), now, ttl), None);
+
+        // Cache-Control wins over Expires
+        let expires = "Tue, 14 Nov 2023 22:16:40 GMT"; // at(200)
+        assert_eq!(
+            expires_at(headers(Some("max-age=10"), Some(expires)), now, ttl),
+            Some(at(10))
+        );
+        assert_eq!(
+            expires_at(headers(None, Some(expires)), now, ttl),
+            Some(at(200))
+        );
+        assert_eq!(expires_at(headers(None, Some(expires)), at(300), ttl), None);
+        assert_eq!(expires_at(headers(None, Some("0")), now, ttl), None);
+    }
+
+    #[test]
+    fn test_image_cache_roundtrip_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = ImageCache::open(dir.path().to_path_buf());
+        let url = "https://example.com/a.png";
+
+        cache.insert(url, b"png", Some("image/png"), at(100), at(0), 1024);
+        assert_eq!(
+            cache.get(url, at(50)),
+            Some((
+                CachedImage {
+                    bytes: b"png".to_vec(),
+                    content_type: Some("image/png".to_string()),
+                },
+                at(100)
+            ))
+        );
+
+        // The index survives reopening
+        let mut reopened = ImageCache::open(dir.path().to_path_buf());
+        assert!(reopened.get(url, at(60)).is_some());
+
+        assert_eq!(reopened.get(url, at(100)), None);
+        assert!(!dir.path().join(cache_key(url)).exists());
+    }
+
+    #[test]
+    fn test_image_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = ImageCache::open(dir.path().to_path_buf());
+        let expires = at(1000);
+
+        cache.insert("https://example.com/a", &[0; 4], None, expires, at(0), 10);
+        cache.insert("https://example.com/b", &[0; 4], None, expires, at(1), 10);
+        // Reading "a" makes "b" the least recently used
+        assert!(cache.get("https://example.com/a", at(2)).is_some());
+        cache.insert("https://example.com/c", &[0; 4], None, expires, at(3), 10);
+
+        assert!(cache.get("https://example.com/a", at(4)).is_some());
+        assert!(cache.get("https://example.com/b", at(4)).is_none());
+        assert!(cache.get("https://example.com/c", at(4)).is_some());
+
+        // Images larger than the whole cache are not stored
+        cache.insert("https://example.com/d", &[0; 11], None, expires, at(5), 10);
+        assert!(cache.get("https://example.com/d", at(6)).is_none());
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                entries: 2,
+                bytes: 8,
+                hits: 3,
+                misses: 2,
+                evictions: 1,
+            }
+        );
+    }
+}
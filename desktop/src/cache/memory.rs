@@ -0,0 +1,202 @@
+//! In-memory LRU cache with size accounting.
+//!
+//! Each entry records its size in bytes; inserting past the budget evicts the
+//! least recently used entries first. Lookups and evictions are counted so
+//! the cache's effectiveness can be inspected (see `cache.showMetrics`).
+
+use std::collections::HashMap;
+
+/// Size and effectiveness of a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups served from the cache, or `None` before any lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+struct MemoryEntry<V> {
+    value: V,
+    size: u64,
+    last_access: u64,
+}
+
+pub struct MemoryCache<V> {
+    entries: HashMap<String, MemoryEntry<V>>,
+    bytes: u64,
+    /// Logical clock ordering accesses for LRU eviction
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<V> Default for MemoryCache<V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            bytes: 0,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+}
+
+impl<V: Clone> MemoryCache<V> {
+    /// Look up `key`, dropping the entry when `is_fresh` rejects it
+    pub fn get(&mut self, key: &str, is_fresh: impl FnOnce(&V) -> bool) -> Option<V> {
+        let fresh = self.entries.get(key).map(|entry| is_fresh(&entry.value));
+        match fresh {
+            Some(true) => {
+                self.hits += 1;
+                self.clock += 1;
+                let entry = self.entries.get_mut(key)?;
+                entry.last_access = self.clock;
+                Some(entry.value.clone())
+            }
+            Some(false) => {
+                self.misses += 1;
+                self.remove(key);
+                None
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store `value` as taking `size` bytes, evicting least recently used
+    /// entries until the cache fits in `budget`
+    ///
+    /// Values larger than the whole budget are not stored.
+    pub fn insert(&mut self, key: &str, value: V, size: u64, budget: u64) {
+        self.remove(key);
+        if size > budget {
+            self.evict(budget);
+            return;
+        }
+        self.clock += 1;
+        self.bytes += size;
+        self.entries.insert(
+            key.to_string(),
+            MemoryEntry {
+                value,
+                size,
+                last_access: self.clock,
+            },
+        );
+        self.evict(budget);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            bytes: self.bytes,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.bytes -= entry.size;
+        }
+    }
+
+    /// Remove least recently used entries until the cache fits in `budget`
+    fn evict(&mut self, budget: u64) {
+        if self.bytes <= budget {
+            return;
+        }
+
+        let mut keys: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_access, key.clone()))
+            .collect();
+        keys.sort();
+        for (_, key) in keys {
+            if self.bytes <= budget {
+                break;
+            }
+            self.remove(&key);
+            self.evictions += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let mut cache = MemoryCache::default();
+        cache.insert("a", 1, 4, 10);
+        cache.insert("b", 2, 4, 10);
+        // Reading "a" makes "b" the least recently used
+        assert_eq!(cache.get("a", |_| true), Some(1));
+        cache.insert("c", 3, 4, 10);
+
+        assert_eq!(cache.get("a", |_| true), Some(1));
+        assert_eq!(cache.get("b", |_| true), None);
+        assert_eq!(cache.get("c", |_| true), Some(3));
+
+        // Values larger than the whole budget are not stored
+        cache.insert("d", 4, 11, 10);
+        assert_eq!(cache.get("d", |_| true), None);
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                entries: 2,
+                bytes: 8,
+                hits: 3,
+                misses: 2,
+                evictions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_memory_cache_replaces_and_drops_stale_entries() {
+        let mut cache = MemoryCache::default();
+        cache.insert("a", 1, 4, 10);
+        cache.insert("a", 2, 6, 10);
+        assert_eq!(cache.stats().bytes, 6);
+
+        assert_eq!(cache.get("a", |value| *value != 2), None);
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().bytes, 0);
+
+        // A smaller budget evicts on the next insert
+        cache.insert("b", 3, 4, 10);
+        cache.insert("c", 4, 4, 4);
+        assert_eq!(cache.get("b", |_| true), None);
+        assert_eq!(cache.get("c", |_| true), Some(4));
+    }
+
+    #[test]
+    fn test_cache_stats_hit_rate() {
+        assert_eq!(CacheStats::default().hit_rate(), None);
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            ..Default::default()
+        };
+        assert_eq!(stats.hit_rate(), Some(0.75));
+    }
+}
@@ -1,6 +1,9 @@
 pub mod app;
 pub mod bookmark_button;
+pub mod clipboard_prompt;
 pub mod content;
+pub mod document_styles;
+pub mod escape;
 pub mod header;
 pub mod icon;
 pub mod main_app;
@@ -11,3 +14,4 @@ pub mod search_bar;
 pub mod sidebar;
 pub mod tab;
 pub mod theme_selector;
+pub mod toast;
@@ -1,6 +1,6 @@
 use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
 use dioxus::desktop::tao::event::{DeviceEvent, ElementState, Event as TaoEvent, WindowEvent};
-use dioxus::desktop::{use_muda_event_handler, use_wry_event_handler, window};
+use dioxus::desktop::{use_asset_handler, use_muda_event_handler, use_wry_event_handler, window};
 use dioxus::document;
 use dioxus::html::HasFileData;
 use dioxus::prelude::*;
@@ -8,9 +8,12 @@ use dioxus_core::use_drop;
 use mouse_position::mouse_position::Mouse;
 use std::path::PathBuf;
 
+use super::clipboard_prompt::{check_clipboard_on_focus, ClipboardPrompt};
 use super::content::{
     close_context_menu, use_search_handler, Content, ContentContextMenu, CONTENT_CONTEXT_MENU,
 };
+use super::document_styles::use_document_styles;
+use super::escape::use_escape_handler;
 use super::header::Header;
 use super::icon::{Icon, IconName};
 use super::right_sidebar::RightSidebar;
@@ -18,7 +21,9 @@ use super::right_sidebar::RightSidebarTab;
 use super::search_bar::SearchBar;
 use super::sidebar::Sidebar;
 use super::tab::TabBar;
+use super::toast::Toast;
 use crate::assets::MAIN_SCRIPT;
+use crate::config::{DropOpenBehavior, ReaderProfile, CONFIG};
 use crate::drag;
 use crate::events::{
     ActiveDragUpdate, ACTIVE_DRAG_UPDATE, OPEN_DIRECTORY_IN_WINDOW, OPEN_FILE_IN_WINDOW,
@@ -26,6 +31,8 @@ use crate::events::{
 use crate::menu;
 use crate::state::{AppState, PersistedState, Tab};
 use crate::theme::Theme;
+use crate::utils::file_operations;
+use crate::utils::media::{serve_media, MEDIA_ASSET_HANDLER};
 
 /// Left mouse button ID for DeviceEvent::Button (platform-dependent raw value)
 const MOUSE_BUTTON_LEFT: u32 = 0;
@@ -42,6 +49,8 @@ pub fn App(
     toc_width: f64,
     toc_tab: RightSidebarTab,
     zoom_level: f64,
+    #[props(default)] reader_profile: Option<ReaderProfile>,
+    #[props(default)] background_tabs: Vec<Tab>, // Further tabs opened behind `tab`
 ) -> Element {
     // Initialize application state with the provided tab
     let mut state = use_context_provider(|| {
@@ -49,6 +58,7 @@ pub fn App(
 
         // Initialize with provided tab (preserves history)
         app_state.tabs.write()[0] = tab;
+        app_state.tabs.write().extend(background_tabs);
 
         // Apply initial sidebar settings from params (including directory)
         {
@@ -58,6 +68,7 @@ pub fn App(
             sidebar.open = sidebar_open;
             sidebar.width = sidebar_width;
             sidebar.show_all_files = sidebar_show_all_files;
+            sidebar.restore_view();
         }
 
         // Apply initial right sidebar settings from params
@@ -70,6 +81,11 @@ pub fn App(
         // Apply initial zoom level from params
         app_state.zoom_level.set(zoom_level);
 
+        // A reader profile takes precedence over the individual settings above
+        if let Some(profile) = reader_profile {
+            app_state.set_reader_profile(profile);
+        }
+
         let metrics = crate::window::metrics::capture_window_metrics(&window().window);
         *app_state.position.write() = LogicalPosition::new(metrics.position.x, metrics.position.y);
         *app_state.size.write() = LogicalSize::new(metrics.size.width, metrics.size.height);
@@ -89,6 +105,15 @@ pub fn App(
 
     // Initialize JavaScript main module (theme listeners, etc.)
     use_hook(|| {
+        let (typewriter, code_copy, code_ruler, search) = {
+            let config = CONFIG.read();
+            (
+                serde_json::to_string(&config.typewriter).unwrap_or_else(|_| "{}".into()),
+                serde_json::to_string(&config.code_copy).unwrap_or_else(|_| "{}".into()),
+                serde_json::to_string(&config.code_ruler).unwrap_or_else(|_| "{}".into()),
+                serde_json::to_string(&config.search).unwrap_or_else(|_| "{}".into()),
+            )
+        };
         spawn(async move {
             let _ = document::eval(&format!(
                 r#"
@@ -96,6 +121,10 @@ pub fn App(
                     try {{
                         const {{ init }} = await import("{MAIN_SCRIPT}");
                         init();
+                        window.Arto.scroll.configure({typewriter});
+                        window.Arto.codeCopy.configure({code_copy});
+                        window.Arto.codeRuler.configure({code_ruler});
+                        window.Arto.search.configure({search});
                     }} catch (error) {{
                         console.error("Failed to load main module:", error);
                     }}
@@ -109,6 +138,19 @@ pub fn App(
     // Setup search handlers at App level (window-wide feature)
     use_search_handler(state);
 
+    // Escape closes menus, search, drags (and optionally the tab) in order
+    use_escape_handler(state);
+
+    // Apply configurable document styling (live-updated on config changes)
+    use_document_styles();
+
+    // Serve local audio/video files embedded in rendered documents
+    use_asset_handler(MEDIA_ASSET_HANDLER, |request, responder| {
+        tokio::task::spawn_blocking(move || {
+            responder.respond(serve_media(&request));
+        });
+    });
+
     // Handle menu events (only state-dependent events, not global ones)
     use_muda_event_handler(move |event| {
         // Only handle state-dependent events
@@ -145,6 +187,13 @@ pub fn App(
                 );
             }
         }
+        TaoEvent::WindowEvent {
+            event: WindowEvent::Focused(true),
+            window_id,
+            ..
+        } if window_id == &window().id() => {
+            check_clipboard_on_focus(state);
+        }
         // DeviceEvent: Global mouse tracking for tab drag
         // These events are delivered regardless of window focus, enabling cross-window drag
         TaoEvent::DeviceEvent {
@@ -189,6 +238,8 @@ pub fn App(
         if let Some(tab) = tabs.get(active_index) {
             let title = crate::utils::window_title::generate_window_title(&tab.content);
             window().set_title(&title);
+            // Expose the active file as a draggable title bar proxy icon
+            crate::utils::proxy_icon::set_represented_file(&window().window, tab.file());
         }
     });
 
@@ -266,8 +317,25 @@ pub fn App(
         let window_metrics = crate::window::metrics::capture_window_metrics(&window().window);
         persisted.window_position = window_metrics.position;
         persisted.window_size = window_metrics.size;
+        persisted.directory_geometries = PersistedState::load().directory_geometries;
+        if CONFIG.read().directory.remember_window_geometry {
+            if let Some(directory) = persisted.directory.clone() {
+                persisted.remember_directory_geometry(
+                    &directory,
+                    window_metrics.position,
+                    window_metrics.size,
+                );
+            }
+        }
         persisted.save();
 
+        // Read marks advance in memory while scrolling; persist them here
+        if CONFIG.read().reading_progress.dim_read_sections {
+            if let Err(e) = crate::read_marks::READ_MARKS.read().save() {
+                crate::components::right_sidebar::warn_save_failure("reading positions", &e);
+            }
+        }
+
         // Close child windows
         crate::window::close_child_windows_for_parent(window_id);
     });
@@ -310,6 +378,10 @@ pub fn App(
                 DragDropOverlay {}
             }
 
+            Toast {}
+
+            ClipboardPrompt {}
+
             // Content context menu (rendered at App level to prevent FileViewer re-renders)
             if let Some(menu_state) = CONTENT_CONTEXT_MENU.read().as_ref() {
                 ContentContextMenu {
@@ -327,11 +399,14 @@ pub fn App(
 }
 
 /// Handle dropped files/directories - opens markdown files or sets directory as root
+///
+/// Files open according to `drop.openFiles`; holding Shift forces new tabs.
 async fn handle_dropped_files(evt: Event<DragData>, mut state: AppState) {
     let files = evt.files();
     if files.is_empty() {
         return;
     }
+    let behavior = CONFIG.read().drop.behavior(evt.modifiers().shift());
 
     for file_data in files {
         let path = file_data.path();
@@ -355,6 +430,10 @@ async fn handle_dropped_files(evt: Event<DragData>, mut state: AppState) {
         );
 
         if resolved_path.is_dir() {
+            // Bundles such as `.app` are folders, but not ones to browse
+            if file_operations::divert_bundle(&resolved_path, |notice| state.show_toast(notice)) {
+                continue;
+            }
             // If it's a directory, set it as root and show the sidebar
             tracing::info!("Setting dropped directory as root: {:?}", resolved_path);
             state.set_root_directory(resolved_path);
@@ -365,7 +444,15 @@ async fn handle_dropped_files(evt: Event<DragData>, mut state: AppState) {
         } else {
             // Open any file (not just markdown)
             tracing::info!("Opening dropped file: {:?}", resolved_path);
-            state.open_file(resolved_path);
+            match behavior {
+                DropOpenBehavior::Current => state.open_file(resolved_path),
+                DropOpenBehavior::New => {
+                    state.add_file_tab(resolved_path, true);
+                }
+                DropOpenBehavior::Background => {
+                    state.add_file_tab(resolved_path, false);
+                }
+            }
         }
     }
 }
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use crate::bookmarks::{toggle_bookmark, BOOKMARKS, BOOKMARKS_CHANGED};
 use crate::components::icon::{Icon, IconName};
+use crate::state::AppState;
 
 /// Reusable bookmark toggle button
 #[component]
@@ -15,6 +16,7 @@ pub fn BookmarkButton(
     #[props(default = 14)]
     size: u32,
 ) -> Element {
+    let state = use_context::<AppState>();
     let path_for_check = path.clone();
     let mut is_bookmarked = use_signal(move || BOOKMARKS.read().contains(&path_for_check));
 
@@ -34,7 +36,8 @@ pub fn BookmarkButton(
         let path = path.clone();
         move |evt: Event<MouseData>| {
             evt.stop_propagation();
-            toggle_bookmark(&path);
+            let root = state.sidebar.read().root_directory.clone();
+            toggle_bookmark(&path, root.as_deref());
         }
     };
 
@@ -0,0 +1,99 @@
+use dioxus::desktop::window;
+use dioxus::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::icon::{Icon, IconName};
+use crate::config::CONFIG;
+use crate::state::{AppState, Tab};
+use crate::utils::clipboard;
+use crate::utils::clipboard_detect::{detect_clipboard_markdown, ClipboardMarkdown};
+
+/// Delay before reading the clipboard after focus, so rapid focus changes
+/// (e.g. switching through windows) don't each trigger a check
+const FOCUS_SETTLE_DELAY: Duration = Duration::from_millis(300);
+
+/// Hash of the clipboard text last checked, shared across windows so the same
+/// content is only offered once
+static LAST_CLIPBOARD_HASH: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Check the clipboard after the window gains focus and offer to open any
+/// markdown found there. Does nothing unless enabled in the configuration.
+pub fn check_clipboard_on_focus(mut state: AppState) {
+    if !CONFIG.read().clipboard.detect_on_focus {
+        return;
+    }
+
+    spawn(async move {
+        tokio::time::sleep(FOCUS_SETTLE_DELAY).await;
+        if !window().is_focused() {
+            return;
+        }
+        let Some(text) = clipboard::read_text() else {
+            return;
+        };
+
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let hash = hasher.finish();
+        if LAST_CLIPBOARD_HASH.lock().unwrap().replace(hash) == Some(hash) {
+            return;
+        }
+
+        if let Some(markdown) = detect_clipboard_markdown(&text) {
+            state.clipboard_offer.set(Some(markdown));
+        }
+    });
+}
+
+/// Prompt offering to open markdown detected on the clipboard.
+///
+/// Set by `check_clipboard_on_focus`; stays until opened or dismissed.
+#[component]
+pub fn ClipboardPrompt() -> Element {
+    let mut state = use_context::<AppState>();
+    let Some(offer) = state.clipboard_offer.read().clone() else {
+        return rsx! {};
+    };
+
+    let message = match &offer {
+        ClipboardMarkdown::File(path) => {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            format!("Open {} from the clipboard?", name)
+        }
+        ClipboardMarkdown::Text(_) => "Open markdown from the clipboard?".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: "clipboard-prompt",
+            role: "status",
+            Icon { name: IconName::Copy, size: 14 }
+            span { class: "clipboard-prompt-message", "{message}" }
+            button {
+                class: "clipboard-prompt-open",
+                onclick: move |_| {
+                    state.clipboard_offer.set(None);
+                    match offer.clone() {
+                        ClipboardMarkdown::File(path) => state.open_file(path),
+                        ClipboardMarkdown::Text(text) => {
+                            state.add_tab(Tab::with_inline_content(text), true);
+                        }
+                    }
+                },
+                "Open"
+            }
+            button {
+                class: "clipboard-prompt-dismiss",
+                title: "Dismiss",
+                onclick: move |_| state.clipboard_offer.set(None),
+                Icon { name: IconName::Close, size: 14 }
+            }
+        }
+    }
+}
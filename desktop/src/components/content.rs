@@ -5,16 +5,26 @@ mod file_viewer;
 mod inline_viewer;
 mod no_file_view;
 mod preferences_view;
+mod read_dimming;
+mod reading_progress;
 mod search_handler;
+mod sibling_files_nav;
+mod side_pane;
+mod source_pane;
 
 use dioxus::prelude::*;
 
+use crate::config::{ReaderProfile, CONFIG};
 use crate::state::{AppState, TabContent};
 use file_error_view::FileErrorView;
 use file_viewer::FileViewer;
 use inline_viewer::InlineViewer;
 use no_file_view::NoFileView;
 use preferences_view::PreferencesView;
+use read_dimming::use_read_dimming;
+use reading_progress::ReadingProgress;
+use side_pane::SidePane;
+use source_pane::SourcePane;
 
 // Re-export for menu system
 pub use preferences_view::set_preferences_tab_to_about;
@@ -35,6 +45,22 @@ pub fn Content() -> Element {
     // Without this, any write to state.tabs (even for other tabs) would trigger a re-render
     // of Content and its children, potentially disrupting scroll position.
     let content = use_memo(move || state.current_tab().map(|tab| tab.content));
+    let split_preview = use_memo(move || {
+        state
+            .current_tab()
+            .is_some_and(|tab| tab.split_preview && matches!(tab.content, TabContent::File(_)))
+    });
+
+    // Second file shown next to the current one ("Open to the Side")
+    let side_file = use_memo(move || {
+        state.current_tab().and_then(|tab| match tab.content {
+            TabContent::File(_) => tab.side_file,
+            _ => None,
+        })
+    });
+
+    // Raw source of the current file, filled in by FileViewer for the split preview
+    let source = use_signal(String::new);
 
     // Use CSS zoom property for vector-based scaling (not transform: scale)
     // This ensures fonts and images remain sharp at any zoom level.
@@ -43,42 +69,99 @@ pub fn Content() -> Element {
     // miscalculate scrollHeight, producing extra blank space at the bottom.
     let zoom_style = format!("zoom: {};", zoom_level());
 
+    // Font and column width of the applied reader profile, if any
+    let (reader_font, reader_style) = reader_profile_style(state.reader_profile.read().as_ref());
+
     // Set up scroll position tracking via JavaScript
     use_scroll_position_tracker(state);
+    use_zoom_anchor_restore(state);
+
+    // Dim sections above the furthest position read (file tabs only)
+    let dimmed_file = use_memo(move || match content() {
+        Some(TabContent::File(file)) if CONFIG.read().reading_progress.dim_read_sections => {
+            Some(file)
+        }
+        _ => None,
+    });
+    use_read_dimming(state, dimmed_file);
+
+    let reading_progress = CONFIG.read().reading_progress.clone();
+    let show_reading_progress = reading_progress.enabled
+        && matches!(
+            content(),
+            Some(TabContent::File(_)) | Some(TabContent::Inline(_))
+        );
 
     rsx! {
         div {
-            class: "content",
+            class: if split_preview() { "content-area split-preview" } else { "content-area" },
+            class: if reader_font { "reader-font" },
+            style: "{reader_style}",
+
+            if split_preview() {
+                SourcePane { source }
+            }
+
+            if show_reading_progress {
+                ReadingProgress { config: reading_progress }
+            }
 
-            // Apply zoom wrapper to all content (user content gets zoomed, system UI doesn't need it but wrapper is harmless)
             div {
-                style: "{zoom_style}",
-
-                match content() {
-                    Some(TabContent::File(file)) => {
-                        rsx! { FileViewer { file } }
-                    },
-                    Some(TabContent::Inline(markdown)) => {
-                        rsx! { InlineViewer { markdown } }
-                    },
-                    Some(TabContent::FileError(file, error)) => {
-                        let filename = file
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("Unknown file")
-                            .to_string();
-                        rsx! { FileErrorView { filename, error_message: error } }
-                    },
-                    Some(TabContent::Preferences) => {
-                        rsx! { PreferencesView {} }
-                    },
-                    _ => rsx! { NoFileView {} },
+                class: "content",
+
+                // Apply zoom wrapper to all content (user content gets zoomed, system UI doesn't need it but wrapper is harmless)
+                div {
+                    style: "{zoom_style}",
+
+                    match content() {
+                        Some(TabContent::File(file)) => {
+                            rsx! { FileViewer { file, source } }
+                        },
+                        Some(TabContent::Inline(markdown)) => {
+                            rsx! { InlineViewer { markdown } }
+                        },
+                        Some(TabContent::FileError(file, error)) => {
+                            let filename = file
+                                .file_name()
+                                .and_then(|n| n.to_str())
+                                .unwrap_or("Unknown file")
+                                .to_string();
+                            rsx! { FileErrorView { file, filename, error_message: error } }
+                        },
+                        Some(TabContent::Preferences) => {
+                            rsx! { PreferencesView {} }
+                        },
+                        _ => rsx! { NoFileView {} },
+                    }
                 }
             }
+
+            if let Some(file) = side_file() {
+                SidePane { file, zoom_style: zoom_style.clone() }
+            }
         }
     }
 }
 
+/// CSS variables for a reader profile's font and content width, and whether
+/// it sets a font
+fn reader_profile_style(profile: Option<&ReaderProfile>) -> (bool, String) {
+    let Some(profile) = profile else {
+        return (false, String::new());
+    };
+    let mut style = String::new();
+    let font = profile.font_family.as_deref().filter(|f| !f.is_empty());
+    if let Some(font) = font {
+        style.push_str(&format!("--reader-font-family: {};", font.replace(';', "")));
+    }
+    match profile.content_width {
+        Some(0) => style.push_str("--reader-content-width: none;"),
+        Some(width) => style.push_str(&format!("--reader-content-width: {width}px;")),
+        None => {}
+    }
+    (font.is_some(), style)
+}
+
 /// Hook to track scroll position via JavaScript and update state.
 /// Uses a passive scroll listener that sends position updates to Rust.
 fn use_scroll_position_tracker(mut state: AppState) {
@@ -113,3 +196,14 @@ fn use_scroll_position_tracker(mut state: AppState) {
         });
     });
 }
+
+/// Scroll the block captured by `AppState::zoom_to` back to the focal line
+/// once the new zoom level has been rendered
+fn use_zoom_anchor_restore(mut state: AppState) {
+    use_effect(move || {
+        let _ = (state.zoom_level)();
+        if let Some(anchor) = state.pending_zoom_anchor.take() {
+            let _ = document::eval(&format!("window.Arto.zoomAnchor.restore({});", anchor));
+        }
+    });
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::components::icon::{Icon, IconName};
+use crate::config::CONFIG;
+use crate::read_marks::set_document_read;
 use crate::state::AppState;
 
 /// Context type for right-click detection
@@ -35,6 +37,9 @@ pub struct ContextMenuData {
     /// The selected text (captured at context menu open time)
     #[serde(default)]
     pub selected_text: String,
+    /// Whether the click is in the side pane ("Open to the Side")
+    #[serde(default)]
+    pub side_pane: bool,
 }
 
 #[derive(Props, Clone, PartialEq)]
@@ -136,6 +141,37 @@ pub fn ContentContextMenu(props: ContentContextMenuProps) -> Element {
                 },
             }
 
+            if let Some(file) = props
+                .current_file
+                .clone()
+                .filter(|_| CONFIG.read().reading_progress.dim_read_sections)
+            {
+                ContextMenuItem {
+                    label: "Mark All Read",
+                    icon: Some(IconName::Check),
+                    on_click: {
+                        let file = file.clone();
+                        let on_close = props.on_close;
+                        move |_| {
+                            set_document_read(&file, true);
+                            on_close.call(());
+                        }
+                    },
+                }
+
+                ContextMenuItem {
+                    label: "Mark All Unread",
+                    icon: Some(IconName::Refresh),
+                    on_click: {
+                        let on_close = props.on_close;
+                        move |_| {
+                            set_document_read(&file, false);
+                            on_close.call(());
+                        }
+                    },
+                }
+            }
+
             // === Section 3: Context-specific items ===
             if has_context_specific {
                 ContextMenuSeparator {}
@@ -264,6 +300,20 @@ fn LinkContextItems(href: String, base_dir: PathBuf, on_close: EventHandler<()>)
             },
         }
 
+        ContextMenuItem {
+            label: "Open Link to the Side",
+            on_click: {
+                let target_path = target_path.clone();
+                let on_close = on_close;
+                move |_| {
+                    if let Ok(canonical) = target_path.canonicalize() {
+                        state.open_file_to_side(canonical);
+                    }
+                    on_close.call(());
+                }
+            },
+        }
+
         ContextMenuItem {
             label: "Copy Link Path",
             icon: Some(IconName::Copy),
@@ -1,9 +1,17 @@
 use dioxus::prelude::*;
+use std::path::PathBuf;
 
 use crate::components::icon::{Icon, IconName};
+use crate::utils::file_operations;
+
+/// Error shown for files that were read but contain binary data
+pub const BINARY_FILE_ERROR: &str = "This file isn't text";
 
 #[component]
-pub fn FileErrorView(filename: String, error_message: String) -> Element {
+pub fn FileErrorView(file: PathBuf, filename: String, error_message: String) -> Element {
+    let is_binary = error_message == BINARY_FILE_ERROR;
+    let reveal_file = file.clone();
+
     rsx! {
         div {
             class: "no-file file-error",
@@ -15,7 +23,7 @@ pub fn FileErrorView(filename: String, error_message: String) -> Element {
                 }
                 h2 {
                     class: "no-file-title file-error-title",
-                    "Cannot Open File"
+                    if is_binary { "This File Isn't Text" } else { "Cannot Open File" }
                 }
                 p {
                     class: "no-file-description file-error-filename",
@@ -31,21 +39,42 @@ pub fn FileErrorView(filename: String, error_message: String) -> Element {
                         }
                         span {
                             class: "no-file-hint-text",
-                            "This file cannot be opened. It may be a binary file or an unsupported format."
+                            if is_binary {
+                                "Only text documents and images can be shown here."
+                            } else {
+                                "This file cannot be opened. It may be a binary file or an unsupported format."
+                            }
                         }
                     }
-                    div {
-                        class: "no-file-hint",
-                        span {
-                            class: "no-file-hint-icon",
-                            Icon { name: IconName::AlertCircle, size: 20 }
-                        }
-                        span {
-                            class: "no-file-hint-text",
-                            "Error: {error_message}"
+                    if !is_binary {
+                        div {
+                            class: "no-file-hint",
+                            span {
+                                class: "no-file-hint-icon",
+                                Icon { name: IconName::AlertCircle, size: 20 }
+                            }
+                            span {
+                                class: "no-file-hint-text",
+                                "Error: {error_message}"
+                            }
                         }
                     }
                 }
+                div {
+                    class: "file-error-actions",
+                    button {
+                        class: "file-error-action",
+                        onclick: move |_| file_operations::reveal_in_finder(&reveal_file),
+                        Icon { name: IconName::FolderOpen, size: 16 }
+                        span { "Reveal in Finder" }
+                    }
+                    button {
+                        class: "file-error-action",
+                        onclick: move |_| file_operations::open_with_default_app(&file),
+                        Icon { name: IconName::ExternalLink, size: 16 }
+                        span { "Open with Default App" }
+                    }
+                }
             }
         }
     }
@@ -1,12 +1,22 @@
 use dioxus::prelude::*;
+use dioxus_core::Task;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use super::context_menu::ContextMenuData;
 use super::context_menu_state::{open_context_menu, ContentContextMenuState};
-use crate::markdown::render_to_html_with_toc;
+use super::file_error_view::BINARY_FILE_ERROR;
+use super::sibling_files_nav::SiblingFilesNav;
+use crate::config::{PartialWriteMode, ReloadScrollMode, CONFIG};
+use crate::lint::lint_markdown;
+use crate::markdown::{
+    document_preview, remember_frontmatter_toggle, render_image_file, render_to_html_with_toc,
+};
+use crate::rst::render_rst_to_html_with_toc;
 use crate::state::{AppState, TabContent};
-use crate::utils::file::is_markdown_file;
+use crate::utils::file::{decode_text, is_image_file, is_markdown_file, is_rst_file};
+use crate::utils::file_operations::open_with_default_app;
 use crate::watcher::FILE_WATCHER;
 
 /// Data structure for markdown link clicks from JavaScript
@@ -16,17 +26,31 @@ struct LinkClickData {
     button: u32,
     /// Current scroll position at the time of click (for history preservation)
     scroll_position: f64,
+    #[serde(default)]
+    alt_key: bool,
+    #[serde(default)]
+    shift_key: bool,
+    /// Whether the link is in the side pane (resolved against the side file)
+    #[serde(default)]
+    side_pane: bool,
 }
 
 /// Mouse button constants
 const LEFT_CLICK: u32 = 0;
 const MIDDLE_CLICK: u32 = 1;
 
+/// How often to check whether auto-refresh got enabled while it is off
+const AUTO_REFRESH_RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 #[component]
-pub fn FileViewer(file: PathBuf) -> Element {
+pub fn FileViewer(file: PathBuf, source: Signal<String>) -> Element {
     let state = use_context::<AppState>();
     let html = use_signal(String::new);
     let reload_trigger = use_signal(|| 0usize);
+    // Scroll anchor captured before a reload (JSON), see `prepare_reload`
+    let reload_anchor = use_signal(|| None::<String>);
+    // Whether the file is being written and rendering waits for it to settle
+    let writing = use_signal(|| false);
 
     // Get base directory for link resolution
     let base_dir = file
@@ -35,19 +59,40 @@ pub fn FileViewer(file: PathBuf) -> Element {
         .unwrap_or_else(|| PathBuf::from("."));
 
     // Setup component hooks
-    use_file_loader(file.clone(), html, reload_trigger, state);
-    use_file_watcher(file.clone(), reload_trigger, state);
+    use_file_loader(
+        file.clone(),
+        html,
+        source,
+        reload_trigger,
+        reload_anchor,
+        writing,
+        state,
+    );
+    use_file_watcher(file.clone(), reload_trigger, reload_anchor, state);
+    use_auto_refresh(file.clone(), reload_trigger, reload_anchor, state);
     use_link_click_handler(file.clone(), state);
+    use_link_preview_handler(file.clone());
+    use_frontmatter_tag_handler(state);
+    use_frontmatter_toggle_handler(state);
     use_mermaid_window_handler();
-    use_context_menu_handler(file.clone(), base_dir);
+    use_context_menu_handler(file.clone(), base_dir, state);
 
     rsx! {
         div {
             class: "markdown-viewer",
+            if writing() {
+                div {
+                    class: "file-writing-notice",
+                    "File is being written, waiting…"
+                }
+            }
             article {
                 class: "markdown-body",
                 dangerous_inner_html: "{html}"
             }
+            if CONFIG.read().sibling_files.prompt_at_end {
+                SiblingFilesNav { file: file.clone() }
+            }
             // Context menu is rendered at App level to avoid re-rendering content
         }
     }
@@ -57,31 +102,78 @@ pub fn FileViewer(file: PathBuf) -> Element {
 fn use_file_loader(
     file: PathBuf,
     html: Signal<String>,
+    source: Signal<String>,
     reload_trigger: Signal<usize>,
+    mut reload_anchor: Signal<Option<String>>,
+    writing: Signal<bool>,
     mut state: AppState,
 ) {
+    let mut load_task = use_signal(|| None::<Task>);
+
     use_effect(use_reactive!(|file, reload_trigger| {
         let mut html = html;
+        let mut source = source;
+        let mut writing = writing;
         let _ = reload_trigger();
         let file = file.clone();
 
+        // A newer change supersedes a load still waiting for the file to settle
+        if let Some(task) = load_task.write().take() {
+            task.cancel();
+        }
+
         // Handle scroll position SYNCHRONOUSLY before spawning async task.
         // This ensures the onRenderComplete callback is registered before
         // MutationObserver triggers #executeBatchRender().
-        handle_scroll_position(&mut state);
+        handle_scroll_position(&mut state, reload_anchor.take());
+
+        let task = spawn(async move {
+            let partial_writes = CONFIG.read().partial_writes.clone();
+            if let Some(settle) = partial_writes.settle_window() {
+                let notice = partial_writes.mode == PartialWriteMode::Notice;
+                wait_for_settled_file(&file, settle, partial_writes.max_wait(), || {
+                    if notice {
+                        writing.set(true);
+                    }
+                })
+                .await;
+            }
+            writing.set(false);
 
-        spawn(async move {
             tracing::info!("Loading and rendering file: {:?}", &file);
 
-            // Try to read as string (UTF-8 text file)
-            match tokio::fs::read_to_string(file.as_path()).await {
+            let modified = file_stamp(&file).await.and_then(|(modified, _)| modified);
+            state.file_modified.set(modified);
+
+            // Images are shown as they are; other binary data is never rendered as text
+            let content = match tokio::fs::read(file.as_path()).await {
+                Ok(bytes) if is_image_file(&file) => {
+                    html.set(render_image_file(&file, &bytes));
+                    source.set(String::new());
+                    state.toc_headings.set(Vec::new());
+                    state.lint_issues.set(Vec::new());
+                    return;
+                }
+                Ok(bytes) => decode_text(bytes).ok_or_else(|| {
+                    if CONFIG.read().binary_files.opens_externally(&file) {
+                        open_with_default_app(&file);
+                    }
+                    BINARY_FILE_ERROR.to_string()
+                }),
+                Err(e) => Err(format!("{:?}", e)),
+            };
+
+            match content {
                 Ok(content) => {
                     // Check if file has markdown extension
                     if is_markdown_file(&file) {
                         // Render as markdown with TOC heading extraction
                         match render_to_html_with_toc(&content, &file) {
                             Ok((rendered, headings)) => {
+                                let lint_config = CONFIG.read().lint.clone();
+                                state.lint_issues.set(lint_markdown(&content, &lint_config));
                                 html.set(rendered);
+                                source.set(content);
                                 state.toc_headings.set(headings);
                                 tracing::trace!("Rendered as Markdown: {:?}", &file);
                             }
@@ -99,8 +191,35 @@ fn use_file_loader(
                                 );
                                 html.set(plain_html);
                                 state.toc_headings.set(Vec::new());
+                                state.lint_issues.set(Vec::new());
                             }
                         }
+                    } else if CONFIG.read().rst.enabled && is_rst_file(&file) {
+                        // Best-effort reStructuredText; the split preview shows the raw source
+                        match render_rst_to_html_with_toc(&content, &file) {
+                            Ok((rendered, headings)) => {
+                                html.set(rendered);
+                                source.set(content);
+                                state.toc_headings.set(headings);
+                                tracing::trace!("Rendered as reStructuredText: {:?}", &file);
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    "reStructuredText rendering failed for {:?}, rendering as plain text: {}",
+                                    &file,
+                                    e
+                                );
+                                let escaped_content = html_escape::encode_text(&content);
+                                let plain_html = format!(
+                                    r#"<pre class="plain-text-viewer">{}</pre>"#,
+                                    escaped_content
+                                );
+                                html.set(plain_html);
+                                source.set(String::new());
+                                state.toc_headings.set(Vec::new());
+                            }
+                        }
+                        state.lint_issues.set(Vec::new());
                     } else {
                         // Non-markdown file, render as plain text directly
                         tracing::info!("Rendering non-markdown file as plain text: {:?}", &file);
@@ -110,17 +229,18 @@ fn use_file_loader(
                             escaped_content
                         );
                         html.set(plain_html);
+                        source.set(String::new());
                         state.toc_headings.set(Vec::new());
+                        state.lint_issues.set(Vec::new());
                     }
 
                     // Re-apply search highlighting after content changes
                     // This preserves search state across tab switches
                     reapply_search().await;
                 }
-                Err(e) => {
-                    // Failed to read as UTF-8 text (likely binary file)
-                    tracing::error!("Failed to read file {:?} as text: {}", file, e);
-                    let error_msg = format!("{:?}", e);
+                Err(error_msg) => {
+                    // Failed to read the file, or it isn't text
+                    tracing::error!("Failed to read file {:?} as text: {}", file, error_msg);
 
                     // Update tab content to FileError
                     let file_clone = file.clone();
@@ -128,12 +248,50 @@ fn use_file_loader(
                         tab.content = TabContent::FileError(file_clone, error_msg);
                     });
                     html.set(String::new());
+                    source.set(String::new());
                 }
             }
         });
+        load_task.set(Some(task));
     }));
 }
 
+/// Wait until `file` has stopped changing
+///
+/// Files last modified longer than `settle` ago are read right away. Otherwise
+/// `on_writing` is called and the file is polled until its size and
+/// modification time stay the same for `settle`, or `max_wait` has passed.
+/// Watcher notifications during the wait restart the load, so only the final
+/// content is rendered.
+async fn wait_for_settled_file(
+    file: &Path,
+    settle: Duration,
+    max_wait: Duration,
+    mut on_writing: impl FnMut(),
+) {
+    let started = Instant::now();
+    let mut stamp = file_stamp(file).await;
+    let recently_modified = stamp
+        .and_then(|(modified, _)| modified)
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < settle);
+    if !recently_modified {
+        return;
+    }
+
+    tracing::debug!(?file, "File is being written, waiting for it to settle");
+    on_writing();
+    while started.elapsed() < max_wait {
+        tokio::time::sleep(settle).await;
+        let next = file_stamp(file).await;
+        if next == stamp {
+            return;
+        }
+        stamp = next;
+    }
+    tracing::debug!(?file, "File did not settle, rendering it anyway");
+}
+
 /// Handle scroll position when navigating to a file.
 ///
 /// If pending_scroll_position is set (from back/forward navigation or tab switch),
@@ -141,8 +299,12 @@ fn use_file_loader(
 /// 1. Immediately when DOM content changes (MutationObserver, before browser paint)
 /// 2. After Mermaid/KaTeX rendering completes (adjusts for content height changes)
 ///
+/// When the file reloaded after a change, `reload_anchor` (captured by
+/// `prepare_reload`) is resolved against the new content instead of reusing
+/// the raw offset.
+///
 /// Otherwise, reset to top immediately (for new navigation like clicking a link).
-fn handle_scroll_position(state: &mut AppState) {
+fn handle_scroll_position(state: &mut AppState, reload_anchor: Option<String>) {
     let pending_scroll = state.pending_scroll_position.take();
 
     if let Some(scroll) = pending_scroll {
@@ -158,9 +320,13 @@ fn handle_scroll_position(state: &mut AppState) {
         //          update but before browser paint, preventing visible scroll flash.
         // Phase 2: onRenderComplete fires after Mermaid/KaTeX render, adjusting for any
         //          content height changes from dynamic rendering.
+        let target_js = match reload_anchor {
+            Some(anchor) => format!("window.Arto.scrollAnchor.resolve({})", anchor),
+            None => scroll.to_string(),
+        };
         let scroll_js = format!(
             r#"(() => {{
-                const target = {};
+                const target = () => {};
                 const container = document.querySelector('.markdown-body');
                 let observer;
                 if (container) {{
@@ -169,7 +335,7 @@ fn handle_scroll_position(state: &mut AppState) {
                             observer.disconnect();
                             observer = null;
                         }}
-                        document.querySelector('.content')?.scrollTo(0, target);
+                        document.querySelector('.content')?.scrollTo(0, target());
                     }});
                     observer.observe(container, {{ childList: true }});
                     // Fallback: ensure the observer is disconnected even if no mutation occurs.
@@ -185,10 +351,10 @@ fn handle_scroll_position(state: &mut AppState) {
                         observer.disconnect();
                         observer = null;
                     }}
-                    document.querySelector('.content')?.scrollTo(0, target);
+                    document.querySelector('.content')?.scrollTo(0, target());
                 }});
             }})();"#,
-            scroll
+            target_js
         );
         let _ = document::eval(&scroll_js);
         tracing::debug!(scroll, "Scheduled two-phase scroll position restoration");
@@ -266,9 +432,15 @@ async fn reapply_search() {
 }
 
 /// Hook to watch file for changes and trigger reload
-fn use_file_watcher(file: PathBuf, reload_trigger: Signal<usize>, mut state: AppState) {
+fn use_file_watcher(
+    file: PathBuf,
+    reload_trigger: Signal<usize>,
+    reload_anchor: Signal<Option<String>>,
+    mut state: AppState,
+) {
     use_effect(use_reactive!(|file| {
         let mut reload_trigger = reload_trigger;
+        let mut reload_anchor = reload_anchor;
         let file = file.clone();
 
         spawn(async move {
@@ -287,10 +459,7 @@ fn use_file_watcher(file: PathBuf, reload_trigger: Signal<usize>, mut state: App
 
             while watcher.recv().await.is_some() {
                 tracing::info!("File change detected, reloading: {:?}", file_path);
-                // Save current scroll position before reloading so it can be restored
-                // after the content re-renders (reuses the back/forward restoration mechanism)
-                let scroll = *state.current_scroll_position.read();
-                state.pending_scroll_position.set(Some(scroll));
+                prepare_reload(&mut state, &mut reload_anchor).await;
                 reload_trigger.set(reload_trigger() + 1);
             }
 
@@ -305,14 +474,112 @@ fn use_file_watcher(file: PathBuf, reload_trigger: Signal<usize>, mut state: App
     }));
 }
 
+/// Hook to poll the file for changes when auto-refresh is enabled.
+///
+/// Fallback for filesystems whose change events the watcher can't rely on.
+/// Only reloads when the file's modification time or size changed, and skips
+/// changes the watcher already picked up.
+fn use_auto_refresh(
+    file: PathBuf,
+    reload_trigger: Signal<usize>,
+    reload_anchor: Signal<Option<String>>,
+    mut state: AppState,
+) {
+    let mut poll_task = use_signal(|| None::<Task>);
+
+    use_effect(use_reactive!(|file| {
+        let mut reload_trigger = reload_trigger;
+        let mut reload_anchor = reload_anchor;
+        let file = file.clone();
+
+        if let Some(task) = poll_task.write().take() {
+            task.cancel();
+        }
+
+        let task = spawn(async move {
+            let mut last_stamp = file_stamp(&file).await;
+            let mut last_trigger = *reload_trigger.peek();
+
+            loop {
+                let interval = CONFIG.read().auto_refresh.poll_interval();
+                tokio::time::sleep(interval.unwrap_or(AUTO_REFRESH_RECHECK_INTERVAL)).await;
+                if interval.is_none() {
+                    continue;
+                }
+
+                let stamp = file_stamp(&file).await;
+                let trigger = *reload_trigger.peek();
+                if trigger != last_trigger {
+                    // Already reloaded by the watcher
+                    last_trigger = trigger;
+                    last_stamp = stamp;
+                    continue;
+                }
+                if stamp == last_stamp {
+                    continue;
+                }
+
+                tracing::info!("File change detected by polling, reloading: {:?}", file);
+                last_stamp = stamp;
+                last_trigger = trigger + 1;
+                prepare_reload(&mut state, &mut reload_anchor).await;
+                reload_trigger.set(last_trigger);
+            }
+        });
+        poll_task.set(Some(task));
+    }));
+}
+
+/// Remember the reader's place before reloading the file after a change
+///
+/// The current offset is saved so it can be restored after the content
+/// re-renders (reuses the back/forward restoration mechanism). Unless
+/// `reload.scroll` is "offset", a scroll anchor is captured as well and
+/// resolved against the new content instead.
+async fn prepare_reload(state: &mut AppState, reload_anchor: &mut Signal<Option<String>>) {
+    let scroll = *state.current_scroll_position.read();
+    state.pending_scroll_position.set(Some(scroll));
+
+    let mode = CONFIG.read().reload.scroll;
+    if mode == ReloadScrollMode::Offset {
+        reload_anchor.set(None);
+        return;
+    }
+    let mode = serde_json::to_string(&mode).unwrap_or_default();
+    let anchor = document::eval(&format!(
+        "return window.Arto.scrollAnchor.capture({});",
+        mode
+    ))
+    .await;
+    reload_anchor.set(
+        anchor
+            .ok()
+            .filter(|anchor| !anchor.is_null())
+            .map(|anchor| anchor.to_string()),
+    );
+}
+
+/// Modification time and size of a file, used to detect changes when polling
+async fn file_stamp(file: &Path) -> Option<(Option<SystemTime>, u64)> {
+    let metadata = tokio::fs::metadata(file).await.ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
 /// Hook to setup JavaScript handler for markdown link clicks
 fn use_link_click_handler(file: PathBuf, state: AppState) {
     use_effect(use_reactive!(|file| {
         let file = file.clone();
         let mut eval_provider = document::eval(indoc::indoc! {r#"
-            window.handleMarkdownLinkClick = (path, button) => {
+            window.handleMarkdownLinkClick = (path, button, event) => {
                 const scrollPosition = document.querySelector('.content')?.scrollTop || 0;
-                dioxus.send({ path, button, scroll_position: scrollPosition });
+                dioxus.send({
+                    path,
+                    button,
+                    scroll_position: scrollPosition,
+                    alt_key: !!event?.altKey,
+                    shift_key: !!event?.shiftKey,
+                    side_pane: !!event?.target?.closest?.('.side-pane'),
+                });
             };
         "#});
 
@@ -331,16 +598,123 @@ fn use_link_click_handler(file: PathBuf, state: AppState) {
     }));
 }
 
+/// Hook to answer link preview requests from hovering markdown links
+fn use_link_preview_handler(file: PathBuf) {
+    use_effect(use_reactive!(|file| {
+        let config = CONFIG.read().link_preview.clone();
+        if !config.enabled {
+            let _ = document::eval("window.Arto.linkPreview.setup(null);");
+            return;
+        }
+
+        let mut eval_provider = document::eval(&format!(
+            "window.Arto.linkPreview.setup({{ delayMs: {} }}, (href) => dioxus.send(href));",
+            config.delay_ms
+        ));
+
+        let base_dir = file
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        spawn(async move {
+            while let Ok(href) = eval_provider.recv::<String>().await {
+                let target = base_dir.join(&href);
+                let excerpt_length = config.excerpt_length;
+                let preview =
+                    tokio::task::spawn_blocking(move || link_preview(&target, excerpt_length))
+                        .await
+                        .ok()
+                        .flatten();
+                let js = format!(
+                    "window.Arto.linkPreview.show({}, {});",
+                    serde_json::to_string(&href).unwrap_or_default(),
+                    preview.unwrap_or(serde_json::Value::Null)
+                );
+                let _ = document::eval(&js);
+            }
+        });
+    }));
+}
+
+/// Read the title and excerpt of a linked markdown file for its hover preview
+fn link_preview(target: &Path, excerpt_length: usize) -> Option<serde_json::Value> {
+    if !is_markdown_file(target) {
+        return None;
+    }
+    let markdown = std::fs::read_to_string(target).ok()?;
+    let preview = document_preview(&markdown, excerpt_length);
+    Some(serde_json::json!({
+        "title": preview.title,
+        "excerpt": preview.excerpt,
+    }))
+}
+
+/// Hook to handle clicks on frontmatter tag chips
+fn use_frontmatter_tag_handler(mut state: AppState) {
+    use_hook(|| {
+        let mut eval_provider = document::eval(indoc::indoc! {r#"
+            window.handleFrontmatterTagClick = (tag) => {
+                dioxus.send(tag);
+            };
+        "#});
+
+        spawn(async move {
+            while let Ok(tag) = eval_provider.recv::<String>().await {
+                tracing::info!(%tag, "Frontmatter tag clicked");
+                state.filter_by_tag(tag);
+            }
+        });
+    });
+}
+
+/// Hook to remember frontmatter tables the user expands or collapses, per
+/// document (the main file or the side pane's)
+fn use_frontmatter_toggle_handler(state: AppState) {
+    use_hook(|| {
+        let mut eval_provider = document::eval(indoc::indoc! {r#"
+            window.handleFrontmatterToggle = (open, event) => {
+                dioxus.send([open, !!event?.target?.closest?.('.side-pane')]);
+            };
+        "#});
+
+        spawn(async move {
+            while let Ok((open, side_pane)) = eval_provider.recv::<(bool, bool)>().await {
+                let document = state.current_tab().and_then(|tab| {
+                    if side_pane {
+                        tab.side_file
+                    } else {
+                        tab.file().map(Path::to_path_buf)
+                    }
+                });
+                if let Some(document) = document {
+                    remember_frontmatter_toggle(&document, open);
+                }
+            }
+        });
+    });
+}
+
 /// Handle a markdown link click event
 fn handle_link_click(click_data: LinkClickData, base_dir: &Path, state: &mut AppState) {
     let LinkClickData {
         path,
         button,
         scroll_position,
+        alt_key,
+        shift_key,
+        side_pane,
     } = click_data;
 
     tracing::info!("Markdown link clicked: {} (button: {})", path, button);
 
+    // Links in the side pane are relative to the side file
+    let side_base_dir = side_pane
+        .then(|| state.current_tab().and_then(|tab| tab.side_file))
+        .flatten()
+        .and_then(|side_file| side_file.parent().map(Path::to_path_buf));
+    let base_dir = side_base_dir.as_deref().unwrap_or(base_dir);
+
     // Resolve and normalize the path
     let target_path = base_dir.join(&path);
     let Ok(canonical_path) = target_path.canonicalize() else {
@@ -355,6 +729,10 @@ fn handle_link_click(click_data: LinkClickData, base_dir: &Path, state: &mut App
             // Open in new tab (always create a new tab for middle-click)
             state.add_file_tab(canonical_path, true);
         }
+        LEFT_CLICK if side_pane || CONFIG.read().open_to_side.matches(alt_key, shift_key) => {
+            // Links in the side pane keep navigating the side pane
+            state.open_file_to_side(canonical_path);
+        }
         LEFT_CLICK => {
             // Save current scroll position to history before navigating
             state.save_current_scroll_position(scroll_position);
@@ -397,7 +775,7 @@ fn use_mermaid_window_handler() {
 ///
 /// Uses global state to avoid re-rendering FileViewer when menu state changes.
 /// This preserves text selection in the content.
-fn use_context_menu_handler(file: PathBuf, base_dir: PathBuf) {
+fn use_context_menu_handler(file: PathBuf, base_dir: PathBuf, state: AppState) {
     use_effect(use_reactive!(|file, base_dir| {
         let file = file.clone();
         let base_dir = base_dir.clone();
@@ -413,11 +791,26 @@ fn use_context_menu_handler(file: PathBuf, base_dir: PathBuf) {
         spawn(async move {
             while let Ok(data) = eval_provider.recv::<ContextMenuData>().await {
                 tracing::debug!(?data, "Context menu triggered");
+                // Links in the side pane are relative to the side file
+                let side_file = data
+                    .side_pane
+                    .then(|| state.current_tab().and_then(|tab| tab.side_file))
+                    .flatten();
+                let (current_file, base_dir) = match side_file {
+                    Some(side_file) => {
+                        let side_dir = side_file
+                            .parent()
+                            .map(Path::to_path_buf)
+                            .unwrap_or_else(|| base_dir.clone());
+                        (side_file, side_dir)
+                    }
+                    None => (file.clone(), base_dir.clone()),
+                };
                 // Write to global state (doesn't subscribe FileViewer)
                 open_context_menu(ContentContextMenuState {
                     data,
-                    current_file: Some(file.clone()),
-                    base_dir: base_dir.clone(),
+                    current_file: Some(current_file),
+                    base_dir,
                 });
             }
         });
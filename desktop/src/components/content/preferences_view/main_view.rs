@@ -4,8 +4,9 @@ use super::tabs::{
     window_size_tab::WindowSizeTab,
 };
 use crate::components::icon::{Icon, IconName};
-use crate::config::{Config, CONFIG};
-use crate::state::AppState;
+use crate::config::{Config, CONFIG, CONFIG_CHANGED};
+use crate::state::{AppState, PersistedState};
+use crate::utils::file_operations;
 use dioxus::prelude::*;
 use parking_lot::RwLock;
 use std::sync::LazyLock;
@@ -22,6 +23,21 @@ pub enum PreferencesTab {
     About,
 }
 
+impl PreferencesTab {
+    /// Restore the settings shown on this tab to their defaults
+    fn reset_section(self, config: &mut Config) {
+        match self {
+            Self::Theme => config.theme = Default::default(),
+            Self::WindowPosition => config.window_position = Default::default(),
+            Self::WindowSize => config.window_size = Default::default(),
+            Self::Sidebar => config.sidebar = Default::default(),
+            Self::RightSidebar => config.right_sidebar = Default::default(),
+            Self::Directory => config.directory = Default::default(),
+            Self::About => {}
+        }
+    }
+}
+
 /// Remember the last selected tab in memory
 static LAST_PREFERENCES_TAB: LazyLock<RwLock<PreferencesTab>> =
     LazyLock::new(|| RwLock::new(PreferencesTab::default()));
@@ -64,6 +80,7 @@ pub fn PreferencesView() -> Element {
                 save_status.set(SaveStatus::Idle);
             } else {
                 *CONFIG.write() = cfg.clone();
+                CONFIG_CHANGED.send(()).ok();
                 has_changes.set(false);
                 save_status.set(SaveStatus::Saved);
                 // Reset to idle after showing success
@@ -73,6 +90,48 @@ pub fn PreferencesView() -> Element {
         });
     };
 
+    // Resetting a section only changes the unsaved draft, so it goes through
+    // "Save Changes" like any other edit
+    let handle_reset_section = move |_| {
+        config.with_mut(|cfg| active_tab().reset_section(cfg));
+        has_changes.set(true);
+    };
+
+    // Resetting everything is applied immediately after confirmation
+    let handle_reset_all = move |_| {
+        let mut state = state;
+        spawn(async move {
+            let answer = rfd::AsyncMessageDialog::new()
+                .set_level(rfd::MessageLevel::Warning)
+                .set_title("Reset All Preferences")
+                .set_description(
+                    "Restore all preferences to their defaults? Remembered window \
+                     positions, sizes and sidebar state are forgotten as well.",
+                )
+                .set_buttons(rfd::MessageButtons::OkCancel)
+                .show()
+                .await;
+            if answer != rfd::MessageDialogResult::Ok {
+                return;
+            }
+
+            let defaults = Config::default();
+            if let Err(e) = defaults.save() {
+                tracing::error!("Failed to reset configuration: {:?}", e);
+                state.show_toast("Failed to reset preferences");
+                return;
+            }
+            PersistedState::default().save();
+            *CONFIG.write() = defaults.clone();
+            CONFIG_CHANGED.send(()).ok();
+
+            state.current_theme.set(defaults.theme.default_theme);
+            config.set(defaults);
+            has_changes.set(false);
+            state.show_toast("Preferences reset to defaults");
+        });
+    };
+
     let current_tab = active_tab();
     let current_save_status = *save_status.read();
 
@@ -163,6 +222,32 @@ pub fn PreferencesView() -> Element {
                     // Header with save status
                     div {
                         class: "preferences-settings-header",
+                        div {
+                            class: "reset-actions",
+                            if current_tab != PreferencesTab::About {
+                                button {
+                                    class: "reset-button",
+                                    title: "Restore this section's defaults (not saved until you save changes)",
+                                    onclick: handle_reset_section,
+                                    "Reset Section"
+                                }
+                            }
+                            button {
+                                class: "reset-button",
+                                title: "Restore all preferences and remembered window state to defaults",
+                                onclick: handle_reset_all,
+                                "Reset All to Defaults…"
+                            }
+                            button {
+                                class: "reset-button",
+                                title: "Open config.json in your editor (edits apply after restarting Arto)",
+                                onclick: move |_| match Config::ensure_file() {
+                                    Ok(path) => file_operations::open_in_editor(path),
+                                    Err(e) => tracing::error!(%e, "Failed to write configuration file"),
+                                },
+                                "Open Config File"
+                            }
+                        }
                         div {
                             class: "save-status",
                             match current_save_status {
@@ -1,4 +1,6 @@
+use crate::cache::{self, CacheStats};
 use crate::components::icon::{Icon, IconName};
+use crate::config::CONFIG;
 use dioxus::prelude::*;
 
 const ARTO_ICON: Asset = asset!("/assets/arto-app.png");
@@ -6,6 +8,7 @@ const ARTO_ICON: Asset = asset!("/assets/arto-app.png");
 #[component]
 pub fn AboutTab() -> Element {
     let version_text = format!("Version {}", env!("ARTO_BUILD_VERSION"));
+    let show_metrics = CONFIG.read().cache.show_metrics;
 
     rsx! {
         div {
@@ -58,6 +61,10 @@ pub fn AboutTab() -> Element {
                     }
                 }
 
+                if show_metrics {
+                    CacheMetrics {}
+                }
+
                 // Footer
                 div {
                     class: "about-footer",
@@ -68,3 +75,89 @@ pub fn AboutTab() -> Element {
         }
     }
 }
+
+/// Size and effectiveness of each cache (`cache.showMetrics`)
+#[component]
+fn CacheMetrics() -> Element {
+    let mut refresh = use_signal(|| 0_usize);
+    let _ = refresh();
+    let stats = cache::stats();
+
+    rsx! {
+        div {
+            class: "about-cache-metrics",
+            table {
+                thead {
+                    tr {
+                        th { "Cache" }
+                        th { "Entries" }
+                        th { "Size" }
+                        th { "Hits" }
+                        th { "Misses" }
+                        th { "Evictions" }
+                        th { "Hit Rate" }
+                    }
+                }
+                tbody {
+                    for (name, stats) in stats {
+                        CacheMetricsRow { key: "{name}", name, stats }
+                    }
+                }
+            }
+            button {
+                class: "about-cache-refresh",
+                onclick: move |_| refresh += 1,
+                "Refresh"
+            }
+        }
+    }
+}
+
+#[component]
+fn CacheMetricsRow(name: &'static str, stats: CacheStats) -> Element {
+    let size = format_bytes(stats.bytes);
+    let hit_rate = stats
+        .hit_rate()
+        .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+
+    rsx! {
+        tr {
+            td { "{name}" }
+            td { "{stats.entries}" }
+            td { "{size}" }
+            td { "{stats.hits}" }
+            td { "{stats.misses}" }
+            td { "{stats.evictions}" }
+            td { "{hit_rate}" }
+        }
+    }
+}
+
+/// Human-readable byte count (binary units)
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(64 * 1024 * 1024), "64.0 MB");
+    }
+}
@@ -1,5 +1,5 @@
 use super::super::form_controls::{DirectoryPicker, OptionCardItem, OptionCards};
-use crate::config::{Config, NewWindowBehavior, StartupBehavior};
+use crate::config::{Config, NewWindowBehavior, OpenDirectoryBehavior, StartupBehavior};
 use dioxus::prelude::*;
 use std::path::PathBuf;
 
@@ -99,6 +99,99 @@ pub fn DirectoryTab(
                     },
                 }
             }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "On Open Directory" }
+                    p { class: "preference-description", "What to do when the directory is already open in another window." }
+                }
+                OptionCards {
+                    name: "dir-open-directory".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: OpenDirectoryBehavior::ChangeRoot,
+                            title: "Change Root".to_string(),
+                            description: Some("Open it in the current window".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: OpenDirectoryBehavior::FocusExisting,
+                            title: "Focus Existing".to_string(),
+                            description: Some("Switch to the window showing it".to_string()),
+                        },
+                    ],
+                    selected: directory.on_open_directory,
+                    on_change: move |new_behavior| {
+                        config.write().directory.on_open_directory = new_behavior;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Window Placement" }
+                    p { class: "preference-description", "Where windows opened for a directory are placed." }
+                }
+                OptionCards {
+                    name: "dir-window-geometry".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Window Settings".to_string(),
+                            description: Some("Use the window size and position settings".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Per Directory".to_string(),
+                            description: Some("Reuse the last size and position for the directory".to_string()),
+                        },
+                    ],
+                    selected: directory.remember_window_geometry,
+                    on_change: move |new_state| {
+                        config.write().directory.remember_window_geometry = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Single-File Folders" }
+                    p { class: "preference-description", "What to show when the root directory contains only one markdown file." }
+                }
+                OptionCards {
+                    name: "dir-open-single-file".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Sidebar Only".to_string(),
+                            description: Some("Just set the root directory".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Open File".to_string(),
+                            description: Some("Also open the file in an empty tab".to_string()),
+                        },
+                    ],
+                    selected: directory.open_single_markdown_file,
+                    on_change: move |new_state| {
+                        config.write().directory.open_single_markdown_file = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
         }
     }
 }
@@ -103,6 +103,37 @@ pub fn RightSidebarTab(
 
             h3 { class: "preference-section-title", "Behavior" }
 
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Follow Scroll Position" }
+                    p { class: "preference-description", "Whether the Contents tab highlights the heading you are reading." }
+                }
+                OptionCards {
+                    name: "right-sidebar-scroll-spy".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Highlight".to_string(),
+                            description: Some("Highlight and reveal the current heading".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Off".to_string(),
+                            description: Some("No highlighting".to_string()),
+                        },
+                    ],
+                    selected: right_sidebar.scroll_spy,
+                    on_change: move |new_state| {
+                        config.write().right_sidebar.scroll_spy = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
             div {
                 class: "preference-item",
                 div {
@@ -100,6 +100,192 @@ pub fn SidebarTab(
                 }
             }
 
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Ignored Files" }
+                    p { class: "preference-description", "Whether entries ignored by .gitignore files are hidden from the file explorer." }
+                }
+                OptionCards {
+                    name: "sidebar-respect-gitignore".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Show".to_string(),
+                            description: Some("List every entry".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Hide".to_string(),
+                            description: Some("Respect .gitignore".to_string()),
+                        },
+                    ],
+                    selected: sidebar.respect_gitignore,
+                    on_change: move |new_state| {
+                        config.write().sidebar.respect_gitignore = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Follow Symlinks" }
+                    p { class: "preference-description", "Whether symlinked files and directories are resolved to their target when opened or set as root." }
+                }
+                OptionCards {
+                    name: "sidebar-follow-symlinks".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Follow".to_string(),
+                            description: Some("Open the symlink target".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Don't Follow".to_string(),
+                            description: Some("Ignore symlinks when opening".to_string()),
+                        },
+                    ],
+                    selected: sidebar.follow_symlinks,
+                    on_change: move |new_state| {
+                        config.write().sidebar.follow_symlinks = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Directory Expansion" }
+                    p { class: "preference-description", "Whether several directories at the same level can be expanded at once." }
+                }
+                OptionCards {
+                    name: "sidebar-accordion-mode".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Multiple".to_string(),
+                            description: Some("Keep siblings expanded".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Accordion".to_string(),
+                            description: Some("Collapse siblings on expand".to_string()),
+                        },
+                    ],
+                    selected: sidebar.accordion_mode,
+                    on_change: move |new_state| {
+                        config.write().sidebar.accordion_mode = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Bookmark Root" }
+                    p { class: "preference-description", "Whether file bookmarks remember the sidebar root they were created in." }
+                }
+                OptionCards {
+                    name: "sidebar-bookmark-root".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "File Only".to_string(),
+                            description: Some("Keep the current root on jump".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "File and Root".to_string(),
+                            description: Some("Restore the root on jump".to_string()),
+                        },
+                    ],
+                    selected: sidebar.bookmark_root,
+                    on_change: move |new_state| {
+                        config.write().sidebar.bookmark_root = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "View per Directory" }
+                    p { class: "preference-description", "Whether the sort order and file filter are remembered for each root directory." }
+                }
+                OptionCards {
+                    name: "sidebar-remember-view".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Shared".to_string(),
+                            description: Some("Same view in every directory".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Per Directory".to_string(),
+                            description: Some("Restore each directory's view".to_string()),
+                        },
+                    ],
+                    selected: sidebar.remember_view_per_directory,
+                    on_change: move |new_state| {
+                        config.write().sidebar.remember_view_per_directory = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "History on New Project" }
+                    p { class: "preference-description", "Whether back/forward keeps directories from before a root outside the current one." }
+                }
+                OptionCards {
+                    name: "sidebar-reset-history".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Keep".to_string(),
+                            description: Some("Go back to the previous project".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Reset".to_string(),
+                            description: Some("Start over for unrelated roots".to_string()),
+                        },
+                    ],
+                    selected: sidebar.reset_history_on_unrelated_root,
+                    on_change: move |new_state| {
+                        config.write().sidebar.reset_history_on_unrelated_root = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
             h3 { class: "preference-section-title", "Behavior" }
 
             div {
@@ -0,0 +1,71 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::read_marks::{READ_MARKS, READ_MARKS_CHANGED};
+use crate::state::AppState;
+
+/// Hook to dim the parts of a document above its furthest-scrolled position
+///
+/// The furthest position is advanced from `current_scroll_position` and kept
+/// per document in [`READ_MARKS`]. Top-level blocks of `.markdown-body` whose
+/// bottom edge lies above it get the `read-dimmed` class. Does nothing when
+/// `file` is `None` (the feature is disabled or no file is shown).
+pub fn use_read_dimming(state: AppState, file: Memo<Option<PathBuf>>) {
+    let mut furthest = use_signal(|| 0.0_f64);
+    let mut marks_revision = use_signal(|| 0_u64);
+
+    // Pick up "mark all read/unread" from any window
+    use_hook(|| {
+        spawn(async move {
+            let mut rx = READ_MARKS_CHANGED.subscribe();
+            while rx.recv().await.is_ok() {
+                marks_revision += 1;
+            }
+        });
+    });
+
+    // Load the stored read mark when the document (or the marks) change
+    use_effect(move || {
+        let _ = marks_revision.read();
+        let stored = file()
+            .map(|path| READ_MARKS.read().furthest(&path))
+            .unwrap_or(0.0);
+        furthest.set(stored);
+    });
+
+    // Advance the read mark as the reader scrolls down
+    use_effect(move || {
+        let scroll = *state.current_scroll_position.read();
+        let Some(path) = file.peek().clone() else {
+            return;
+        };
+        if READ_MARKS.write().advance(&path, scroll) {
+            furthest.set(scroll);
+        }
+    });
+
+    // Apply the dimming; re-run after a new document has been rendered
+    use_effect(move || {
+        let _ = state.toc_headings.read();
+        let threshold = if file.read().is_some() {
+            furthest().to_string()
+        } else {
+            "null".to_string()
+        };
+        let _ = document::eval(&format!(
+            r#"
+            (() => {{
+                const content = document.querySelector('.content');
+                const body = content && content.querySelector('.markdown-body');
+                if (!body) return;
+                const threshold = {threshold};
+                const top = content.getBoundingClientRect().top - content.scrollTop;
+                for (const el of body.children) {{
+                    const bottom = el.getBoundingClientRect().bottom - top;
+                    el.classList.toggle('read-dimmed', threshold !== null && bottom <= threshold);
+                }}
+            }})();
+            "#
+        ));
+    });
+}
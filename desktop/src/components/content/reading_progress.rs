@@ -0,0 +1,219 @@
+use dioxus::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::config::{ReadingProgressConfig, ReadingProgressPosition};
+use crate::markdown::HeadingInfo;
+use crate::state::AppState;
+
+/// Deepest heading level shown as a chapter marker on the bar
+const MARKER_MAX_LEVEL: u8 = 2;
+
+/// Scroll geometry of the `.content` element, measured in the webview
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContentLayout {
+    /// Largest possible `scrollTop` (scroll height minus viewport height)
+    max_scroll: f64,
+    /// `scrollTop` at which each heading (by id) reaches the top of the viewport
+    heading_offsets: HashMap<String, f64>,
+}
+
+/// A heading marker on the progress bar
+#[derive(Debug, Clone, PartialEq)]
+struct ProgressMarker {
+    id: String,
+    text: String,
+    /// Position along the bar in percent
+    percent: f64,
+}
+
+/// Thin bar showing how far through the current document the reader is
+///
+/// Progress is derived from `current_scroll_position` and the scrollable
+/// height of `.content`; markers come from the extracted TOC headings.
+#[component]
+pub fn ReadingProgress(config: ReadingProgressConfig) -> Element {
+    let state = use_context::<AppState>();
+    let layout = use_signal(ContentLayout::default);
+
+    use_content_layout_tracker(state, layout);
+
+    let percent = progress_percent(
+        *state.current_scroll_position.read(),
+        layout.read().max_scroll,
+    );
+    let markers = if config.heading_markers {
+        heading_markers(&state.toc_headings.read(), &layout.read())
+    } else {
+        Vec::new()
+    };
+    let position_class = match config.position {
+        ReadingProgressPosition::Top => "reading-progress-top",
+        ReadingProgressPosition::Right => "reading-progress-right",
+    };
+
+    rsx! {
+        div {
+            class: "reading-progress {position_class}",
+            style: "--reading-progress: {percent}%;",
+
+            div { class: "reading-progress-fill" }
+
+            for marker in markers {
+                button {
+                    key: "{marker.id}",
+                    class: "reading-progress-marker",
+                    style: "--reading-progress-marker: {marker.percent}%;",
+                    title: "{marker.text}",
+                    onclick: {
+                        let id = marker.id.clone();
+                        move |_| {
+                            let js = format!(
+                                r#"window.Arto.scroll.toElement(document.getElementById('{}'));"#,
+                                id
+                            );
+                            let _ = document::eval(&js);
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Hook to measure the scrollable height and heading offsets of `.content`.
+/// Re-measures whenever the headings change or the content is resized
+/// (e.g. images loading, diagrams rendering, window resizing).
+fn use_content_layout_tracker(state: AppState, mut layout: Signal<ContentLayout>) {
+    use_effect(move || {
+        // Subscribe so that a newly rendered document is measured again
+        let _ = state.toc_headings.read();
+
+        let mut eval = document::eval(indoc::indoc! {r#"
+            const content = document.querySelector('.content');
+            if (content) {
+                const measure = () => {
+                    const top = content.getBoundingClientRect().top - content.scrollTop;
+                    const maxScroll = Math.max(content.scrollHeight - content.clientHeight, 0);
+                    const headingOffsets = {};
+                    content.querySelectorAll('h1[id], h2[id], h3[id], h4[id], h5[id], h6[id]').forEach((el) => {
+                        headingOffsets[el.id] = el.getBoundingClientRect().top - top;
+                    });
+                    dioxus.send({ maxScroll, headingOffsets });
+                };
+
+                // Replace the observer left by a previous document
+                if (window.__artoReadingProgressObserver) {
+                    window.__artoReadingProgressObserver.disconnect();
+                }
+                window.__artoReadingProgressObserver = new ResizeObserver(measure);
+                window.__artoReadingProgressObserver.observe(content);
+                if (content.firstElementChild) {
+                    window.__artoReadingProgressObserver.observe(content.firstElementChild);
+                }
+
+                measure();
+            }
+        "#});
+
+        spawn(async move {
+            while let Ok(measured) = eval.recv::<ContentLayout>().await {
+                layout.set(measured);
+            }
+        });
+    });
+}
+
+/// Percentage of the document scrolled past
+///
+/// A document that fits in the viewport is fully visible, so it counts as read.
+fn progress_percent(scroll: f64, max_scroll: f64) -> f64 {
+    if max_scroll <= 0.0 {
+        return 100.0;
+    }
+    (scroll / max_scroll * 100.0).clamp(0.0, 100.0)
+}
+
+/// Place chapter-level headings along the bar using their measured offsets
+fn heading_markers(headings: &[HeadingInfo], layout: &ContentLayout) -> Vec<ProgressMarker> {
+    if layout.max_scroll <= 0.0 {
+        return Vec::new();
+    }
+    headings
+        .iter()
+        .filter(|heading| heading.level <= MARKER_MAX_LEVEL)
+        .filter_map(|heading| {
+            let offset = layout.heading_offsets.get(&heading.id)?;
+            Some(ProgressMarker {
+                id: heading.id.clone(),
+                text: heading.text.clone(),
+                percent: progress_percent(*offset, layout.max_scroll),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(level: u8, id: &str) -> HeadingInfo {
+        HeadingInfo {
+            level,
+            text: id.to_uppercase(),
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_progress_percent() {
+        assert_eq!(progress_percent(0.0, 1000.0), 0.0);
+        assert_eq!(progress_percent(250.0, 1000.0), 25.0);
+        assert_eq!(progress_percent(1200.0, 1000.0), 100.0);
+        // Short documents are always fully read
+        assert_eq!(progress_percent(0.0, 0.0), 100.0);
+    }
+
+    #[test]
+    fn test_heading_markers() {
+        let layout = ContentLayout {
+            max_scroll: 1000.0,
+            heading_offsets: HashMap::from([
+                ("intro".to_string(), 0.0),
+                ("details".to_string(), 400.0),
+                ("usage".to_string(), 500.0),
+                ("appendix".to_string(), 1500.0),
+            ]),
+        };
+        let headings = [
+            heading(1, "intro"),
+            heading(3, "details"),
+            heading(2, "usage"),
+            heading(2, "appendix"),
+            heading(2, "unmeasured"),
+        ];
+
+        let markers = heading_markers(&headings, &layout);
+
+        // H3 is below the marker level; unmeasured headings are skipped
+        assert_eq!(
+            markers
+                .iter()
+                .map(|m| (m.id.as_str(), m.percent))
+                .collect::<Vec<_>>(),
+            vec![("intro", 0.0), ("usage", 50.0), ("appendix", 100.0)]
+        );
+        assert_eq!(markers[1].text, "USAGE");
+    }
+
+    #[test]
+    fn test_heading_markers_without_scrollable_content() {
+        let layout = ContentLayout {
+            max_scroll: 0.0,
+            heading_offsets: HashMap::from([("intro".to_string(), 0.0)]),
+        };
+
+        assert!(heading_markers(&[heading(1, "intro")], &layout).is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::components::icon::{Icon, IconName};
+use crate::components::sidebar::file_explorer::{sibling_file, ListingOptions};
+use crate::config::CONFIG;
+use crate::state::AppState;
+
+/// Links to the previous and next documents of the directory, shown at the
+/// end of a document when `siblingFiles.promptAtEnd` is set
+#[component]
+pub fn SiblingFilesNav(file: PathBuf) -> Element {
+    let mut state = use_context::<AppState>();
+
+    // Read the directory off the UI thread, again when the listing changes
+    let siblings = use_resource(use_reactive!(|file| {
+        let options = ListingOptions::current(&state);
+        let wrap = CONFIG.read().sibling_files.wrap_around;
+        async move {
+            tokio::task::spawn_blocking(move || {
+                (
+                    sibling_file(&file, &options, false, wrap),
+                    sibling_file(&file, &options, true, wrap),
+                )
+            })
+            .await
+            .unwrap_or_default()
+        }
+    }));
+
+    let Some((previous, next)) = siblings.read().clone() else {
+        return rsx! {};
+    };
+    if previous.is_none() && next.is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav {
+            class: "sibling-files-nav",
+            if let Some(previous) = previous {
+                button {
+                    class: "sibling-files-nav-previous",
+                    title: "Previous file in directory",
+                    onclick: move |_| state.open_sibling_file(false),
+                    Icon { name: IconName::ChevronLeft, size: 16 }
+                    span { "{file_name(&previous)}" }
+                }
+            }
+            if let Some(next) = next {
+                button {
+                    class: "sibling-files-nav-next",
+                    title: "Next file in directory",
+                    onclick: move |_| state.open_sibling_file(true),
+                    span { "{file_name(&next)}" }
+                    Icon { name: IconName::ChevronRight, size: 16 }
+                }
+            }
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
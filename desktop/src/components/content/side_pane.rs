@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::components::icon::{Icon, IconName};
+use crate::markdown::render_to_html;
+use crate::state::AppState;
+use crate::utils::file::is_markdown_file;
+use crate::watcher::FILE_WATCHER;
+
+/// Rendered view of a second file next to the current one ("Open to the Side")
+///
+/// The side file is rendered read-only and re-rendered when it changes on
+/// disk. Links inside it navigate the side pane (see `handle_link_click`).
+#[component]
+pub fn SidePane(file: PathBuf, zoom_style: String) -> Element {
+    let mut state = use_context::<AppState>();
+    let html = use_signal(String::new);
+    let reload_trigger = use_signal(|| 0usize);
+
+    use_side_file_loader(file.clone(), html, reload_trigger);
+    use_side_file_watcher(file.clone(), reload_trigger);
+
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let full_path = file.display().to_string();
+
+    rsx! {
+        div {
+            class: "side-pane",
+
+            div {
+                class: "side-pane-header",
+                span {
+                    class: "side-pane-title",
+                    title: "{full_path}",
+                    "{name}"
+                }
+                button {
+                    class: "side-pane-button",
+                    title: "Open in New Tab",
+                    onclick: {
+                        let file = file.clone();
+                        move |_| {
+                            state.close_side_pane();
+                            state.add_file_tab(file.clone(), true);
+                        }
+                    },
+                    Icon { name: IconName::Add, size: 14 }
+                }
+                button {
+                    class: "side-pane-button",
+                    title: "Close side pane",
+                    onclick: move |_| state.close_side_pane(),
+                    Icon { name: IconName::Close, size: 14 }
+                }
+            }
+
+            div {
+                class: "side-pane-content",
+                div {
+                    style: "{zoom_style}",
+                    div {
+                        class: "markdown-viewer",
+                        article {
+                            class: "markdown-body",
+                            dangerous_inner_html: "{html}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Hook to load and render the side file
+fn use_side_file_loader(file: PathBuf, html: Signal<String>, reload_trigger: Signal<usize>) {
+    use_effect(use_reactive!(|file, reload_trigger| {
+        let _ = reload_trigger();
+        let mut html = html;
+        let file = file.clone();
+
+        spawn(async move {
+            let rendered = match tokio::fs::read_to_string(&file).await {
+                Ok(content) if is_markdown_file(&file) => render_to_html(&content, &file)
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to render side file {:?}: {}", &file, e);
+                        plain_text_html(&content)
+                    }),
+                Ok(content) => plain_text_html(&content),
+                Err(e) => {
+                    tracing::error!("Failed to read side file {:?}: {}", &file, e);
+                    format!(
+                        r#"<p class="error">Failed to read {}: {}</p>"#,
+                        html_escape::encode_text(&file.display().to_string()),
+                        html_escape::encode_text(&e.to_string())
+                    )
+                }
+            };
+            html.set(rendered);
+        });
+    }));
+}
+
+/// Hook to re-render the side file when it changes on disk
+fn use_side_file_watcher(file: PathBuf, reload_trigger: Signal<usize>) {
+    use_effect(use_reactive!(|file| {
+        let mut reload_trigger = reload_trigger;
+        let file = file.clone();
+
+        spawn(async move {
+            let mut watcher = match FILE_WATCHER.watch(file.clone()).await {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    tracing::error!("Failed to register file watcher for {:?}: {:?}", file, e);
+                    return;
+                }
+            };
+
+            while watcher.recv().await.is_some() {
+                tracing::info!("Side file change detected, reloading: {:?}", file);
+                reload_trigger.set(reload_trigger() + 1);
+            }
+        });
+    }));
+}
+
+fn plain_text_html(content: &str) -> String {
+    format!(
+        r#"<pre class="plain-text-viewer">{}</pre>"#,
+        html_escape::encode_text(content)
+    )
+}
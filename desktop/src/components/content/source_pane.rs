@@ -0,0 +1,58 @@
+use dioxus::core::use_drop;
+use dioxus::prelude::*;
+
+/// Read-only view of the raw markdown shown next to the rendered view in split preview
+///
+/// Scrolling is kept in sync with the rendered view by the renderer's
+/// `splitPreview` module, which matches each source line against the
+/// `data-source-line` attributes emitted by the markdown renderer.
+#[component]
+pub fn SourcePane(source: Signal<String>) -> Element {
+    let lines_html = use_memo(move || render_source_lines(&source.read()));
+
+    // (Re)attach scroll sync whenever the displayed source changes
+    use_effect(move || {
+        let _ = lines_html.read();
+        let _ = document::eval("window.Arto.splitPreview.setup();");
+    });
+
+    use_drop(|| {
+        let _ = document::eval("window.Arto.splitPreview.teardown();");
+    });
+
+    rsx! {
+        div {
+            class: "source-pane",
+            dangerous_inner_html: "{lines_html}",
+        }
+    }
+}
+
+/// Render source text as numbered lines
+fn render_source_lines(source: &str) -> String {
+    source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            format!(
+                r#"<div class="source-pane-line" data-line="{number}"><span class="source-pane-line-number">{number}</span><span class="source-pane-line-text">{text}</span></div>"#,
+                number = index + 1,
+                text = html_escape::encode_text(line),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_source_lines() {
+        let html = render_source_lines("# Title\n\n<b>");
+
+        assert_eq!(html.matches(r#"class="source-pane-line""#).count(), 3);
+        assert!(html.contains(r#"data-line="3""#));
+        assert!(html.contains("&lt;b&gt;"));
+    }
+}
@@ -0,0 +1,567 @@
+//! Configurable styling applied to rendered documents.
+//!
+//! Settings that map to plain CSS are turned into a stylesheet injected into
+//! the window (a `<style>` element) instead of being baked into the rendered
+//! HTML, so saving preferences restyles every open window immediately:
+//! - Heading type scale: CSS variables (`--heading-1-size`,
+//!   `--heading-1-margin-top`, ...) used by the markdown viewer stylesheet for
+//!   H1-H6, which falls back to GitHub's values.
+//! - Ordered list numbering: rules keyed on the `ol-depth-N` classes added to
+//!   ordered lists while rendering.
+//! - Dark-mode images: a filter for opted-in (`dark-invert`) and, if
+//!   configured, SVG images while the dark theme is active.
+//! - Code ruler: a vertical line at the configured column of code blocks.
+//! - Code wrapping: whether long code lines wrap, always or in narrow windows.
+//! - Font files: `@font-face` rules embedding the configured body and code
+//!   fonts as data URLs, and the rules using them. These live in their own
+//!   `<style>` element that is only rewritten when the font settings change,
+//!   and encoded files are cached, so other settings don't re-ship the fonts.
+
+use base64::Engine;
+use dioxus::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::SystemTime;
+
+use crate::config::{
+    CodeRulerConfig, CodeWrapConfig, CodeWrapMode, DarkImageMode, DarkImagesConfig, FontsConfig,
+    HeadingScaleConfig, OrderedListConfig, OrderedListNumbering, CONFIG, CONFIG_CHANGED,
+};
+
+/// Id of the injected `<style>` element
+const STYLE_ELEMENT_ID: &str = "arto-document-styles";
+
+/// Id of the injected `<style>` element holding the font file rules
+const FONT_STYLE_ELEMENT_ID: &str = "arto-document-fonts";
+
+/// Deepest list nesting level that gets its own outline rule
+const MAX_OUTLINE_DEPTH: usize = 9;
+
+/// Largest font file embedded into the window (10 MiB)
+const MAX_FONT_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// A font file read as a data URL, with its CSS `format()` name
+type FontData = (Arc<str>, &'static str);
+
+#[derive(Debug, Clone)]
+struct CachedFont {
+    modified: SystemTime,
+    len: u64,
+    font: Result<FontData, String>,
+}
+
+/// Font files read by [`font_data_url`], reused while their modification
+/// time and size stay the same
+static FONT_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedFont>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Hook to inject the document stylesheet and keep it in sync with the
+/// configuration saved from any window
+pub fn use_document_styles() {
+    use_hook(|| {
+        spawn(async move {
+            let mut applied_fonts = None;
+            apply_document_styles(&mut applied_fonts);
+            let mut rx = CONFIG_CHANGED.subscribe();
+            while rx.recv().await.is_ok() {
+                apply_document_styles(&mut applied_fonts);
+            }
+        });
+    });
+}
+
+fn apply_document_styles(applied_fonts: &mut Option<FontsConfig>) {
+    let (css, fonts) = {
+        let config = CONFIG.read();
+        let css = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            heading_scale_css(&config.heading_scale),
+            ordered_list_css(&config.ordered_lists),
+            dark_images_css(&config.dark_images),
+            code_ruler_css(&config.code_ruler),
+            code_wrap_css(&config.code_wrap),
+        );
+        (css, config.fonts.clone())
+    };
+    set_style_element(STYLE_ELEMENT_ID, &css);
+    if let Some(font_css) = changed_font_files_css(applied_fonts, &fonts) {
+        set_style_element(FONT_STYLE_ELEMENT_ID, &font_css);
+    }
+}
+
+/// The font rules for `fonts`, or `None` if they are already applied
+fn changed_font_files_css(
+    applied: &mut Option<FontsConfig>,
+    fonts: &FontsConfig,
+) -> Option<String> {
+    if applied.as_ref() == Some(fonts) {
+        return None;
+    }
+    *applied = Some(fonts.clone());
+    Some(font_files_css(fonts).0)
+}
+
+/// Replace the content of the `<style>` element `id`, creating it if needed
+fn set_style_element(id: &str, css: &str) {
+    let css = serde_json::to_string(css).unwrap_or_default();
+    let _ = document::eval(&format!(
+        r#"
+        (() => {{
+            let style = document.getElementById('{id}');
+            if (!style) {{
+                style = document.createElement('style');
+                style.id = '{id}';
+                document.head.appendChild(style);
+            }}
+            style.textContent = {css};
+        }})();
+        "#
+    ));
+}
+
+/// Build the `:root` rule declaring the heading variables
+fn heading_scale_css(config: &HeadingScaleConfig) -> String {
+    let declarations: String = config
+        .levels()
+        .iter()
+        .enumerate()
+        .map(|(index, style)| {
+            let level = index + 1;
+            format!(
+                "--heading-{level}-size: {}em; --heading-{level}-margin-top: {}px; --heading-{level}-margin-bottom: {}px; ",
+                style.size, style.margin_top, style.margin_bottom
+            )
+        })
+        .collect();
+    format!(":root {{ {}}}", declarations)
+}
+
+/// Build the rules numbering ordered lists by nesting depth
+fn ordered_list_css(config: &OrderedListConfig) -> String {
+    match config.numbering {
+        OrderedListNumbering::Flat => String::new(),
+        OrderedListNumbering::Outline => {
+            // Only keep valid-looking keywords; the values come from user config
+            let styles: Vec<&str> = config
+                .outline_styles
+                .iter()
+                .map(|style| style.trim())
+                .filter(|style| {
+                    !style.is_empty()
+                        && style.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+                .collect();
+            if styles.is_empty() {
+                return String::new();
+            }
+            (1..=MAX_OUTLINE_DEPTH)
+                .map(|depth| {
+                    format!(
+                        ".markdown-body ol.ol-depth-{depth} {{ list-style-type: {}; }}\n",
+                        styles[(depth - 1) % styles.len()]
+                    )
+                })
+                .collect()
+        }
+        OrderedListNumbering::Legal => concat!(
+            ".markdown-body ol { counter-reset: arto-legal var(--ol-start, 0); }\n",
+            ".markdown-body ol > li { list-style-type: none; counter-increment: arto-legal; }\n",
+            ".markdown-body ol > li::before { content: counters(arto-legal, \".\") \". \"; }\n",
+        )
+        .to_string(),
+    }
+}
+
+/// Build the rule filtering light-background images in dark mode
+fn dark_images_css(config: &DarkImagesConfig) -> String {
+    // The filter is user config; refuse anything that could end the rule
+    let filter = config.filter.trim();
+    if filter.is_empty() || filter.contains(['{', '}', ';', '<']) {
+        return String::new();
+    }
+    let selectors: &[&str] = match config.mode {
+        DarkImageMode::Off => return String::new(),
+        DarkImageMode::OptIn => &["img.dark-invert"],
+        DarkImageMode::Diagrams => &[
+            "img.dark-invert",
+            "img[src^=\"data:image/svg+xml\"]",
+            "img[src$=\".svg\" i]",
+        ],
+    };
+    let selector = selectors
+        .iter()
+        .map(|selector| {
+            format!("[data-theme=\"dark\"] .markdown-body {selector}:not(.dark-no-invert)")
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("{selector} {{ filter: {filter}; }}\n")
+}
+
+/// Build the rule drawing the ruler in code blocks
+///
+/// The ruler is a background gradient of the `pre`, so it scrolls with wide
+/// code and stays out of the text that gets copied.
+fn code_ruler_css(config: &CodeRulerConfig) -> String {
+    if !config.enabled || config.column == 0 {
+        return String::new();
+    }
+    // Columns are counted from the code block's 16px padding
+    let position = format!("calc(16px + {}ch)", config.column);
+    format!(
+        concat!(
+            ".markdown-body pre:not([class*=\"preprocessed-\"]) {{ ",
+            "background-image: linear-gradient(to right, transparent {position}, ",
+            "var(--code-ruler-color) {position}, var(--code-ruler-color) calc({position} + 1px), ",
+            "transparent calc({position} + 1px)); ",
+            "background-attachment: local; }}\n",
+        ),
+        position = position
+    )
+}
+
+/// Build the rule wrapping long lines in code blocks
+///
+/// Blocks flagged `wrap`/`nowrap` carry a class that the markdown viewer
+/// stylesheet matches more specifically, so the flag wins over this rule.
+fn code_wrap_css(config: &CodeWrapConfig) -> String {
+    let rule = ".markdown-body pre code { white-space: pre-wrap; overflow-wrap: anywhere; }";
+    match config.mode {
+        CodeWrapMode::Scroll => String::new(),
+        CodeWrapMode::Wrap => format!("{rule}\n"),
+        CodeWrapMode::Narrow => {
+            format!(
+                "@media (max-width: {}px) {{ {rule} }}\n",
+                config.narrow_width
+            )
+        }
+    }
+}
+
+/// Build the `@font-face` rules for the configured font files and the rules
+/// applying them to body text and code
+///
+/// Files that can't be loaded are skipped, leaving the default font, and
+/// returned alongside the rules.
+fn font_files_css(config: &FontsConfig) -> (String, Vec<PathBuf>) {
+    let fonts = [
+        (
+            config.body_font_file.as_deref(),
+            "arto-body-font",
+            ".markdown-viewer .markdown-body",
+        ),
+        (
+            config.code_font_file.as_deref(),
+            "arto-code-font",
+            ".markdown-viewer .markdown-body :is(code, pre, kbd, samp)",
+        ),
+    ];
+    let mut css = String::new();
+    let mut failed = Vec::new();
+    for (path, family, selector) in fonts {
+        let Some(path) = path else {
+            continue;
+        };
+        match font_data_url(path) {
+            Ok((data_url, format)) => css.push_str(&format!(
+                "@font-face {{ font-family: \"{family}\"; src: url(\"{data_url}\") format(\"{format}\"); }}\n{selector} {{ font-family: \"{family}\"; }}\n"
+            )),
+            Err(error) => {
+                tracing::warn!(?path, %error, "Failed to load font file");
+                failed.push(path.to_path_buf());
+            }
+        }
+    }
+    (css, failed)
+}
+
+/// Build the font rules for an exported document, with a warning for each
+/// font that can't be embedded
+///
+/// Only the configured font files are embedded. A reader profile's font is
+/// installed on this system, so the export falls back to the default font.
+pub fn export_fonts_css(config: &FontsConfig, profile_font: Option<&str>) -> (String, Vec<String>) {
+    let (css, failed) = font_files_css(config);
+    let mut warnings: Vec<String> = failed
+        .iter()
+        .map(|path| {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            format!("couldn't embed {}", name.to_string_lossy())
+        })
+        .collect();
+    if let Some(font) = profile_font.filter(|font| !font.is_empty()) {
+        warnings.push(format!("system font {font} isn't embedded"));
+    }
+    (css, warnings)
+}
+
+/// Read a font file as a data URL, with its CSS `format()` name
+///
+/// The file's signature must match a supported font format and it may be at
+/// most [`MAX_FONT_FILE_SIZE`] bytes. The result is cached until the file's
+/// modification time or size changes.
+fn font_data_url(path: &Path) -> Result<FontData, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    let modified = metadata.modified().map_err(|e| e.to_string())?;
+    let len = metadata.len();
+    if let Some(cached) = FONT_CACHE.lock().get(path) {
+        if cached.modified == modified && cached.len == len {
+            return cached.font.clone();
+        }
+    }
+
+    let font = read_font_file(path, len);
+    FONT_CACHE.lock().insert(
+        path.to_path_buf(),
+        CachedFont {
+            modified,
+            len,
+            font: font.clone(),
+        },
+    );
+    font
+}
+
+fn read_font_file(path: &Path, size: u64) -> Result<FontData, String> {
+    if size > MAX_FONT_FILE_SIZE {
+        return Err(format!(
+            "font file is too large ({} bytes, max {} bytes)",
+            size, MAX_FONT_FILE_SIZE
+        ));
+    }
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let (mime_type, format) = match bytes.get(..4) {
+        Some(b"wOF2") => ("font/woff2", "woff2"),
+        Some(b"wOFF") => ("font/woff", "woff"),
+        Some(b"OTTO") => ("font/otf", "opentype"),
+        Some(b"\x00\x01\x00\x00" | b"true") => ("font/ttf", "truetype"),
+        Some(b"ttcf") => ("font/collection", "collection"),
+        _ => return Err("not a WOFF2, WOFF, TrueType or OpenType font".to_string()),
+    };
+    let data = base64::prelude::BASE64_STANDARD.encode(&bytes);
+    Ok((format!("data:{};base64,{}", mime_type, data).into(), format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HeadingLevelStyle;
+
+    #[test]
+    fn test_heading_scale_css() {
+        let config = HeadingScaleConfig {
+            h1: HeadingLevelStyle {
+                size: 3.0,
+                margin_top: 32.0,
+                margin_bottom: 8.0,
+            },
+            ..HeadingScaleConfig::default()
+        };
+
+        let css = heading_scale_css(&config);
+
+        assert!(css.starts_with(":root {"));
+        assert!(css.contains("--heading-1-size: 3em;"));
+        assert!(css.contains("--heading-1-margin-top: 32px;"));
+        assert!(css.contains("--heading-1-margin-bottom: 8px;"));
+        assert!(css.contains("--heading-2-size: 1.5em;"));
+        assert!(css.contains("--heading-6-size: 0.85em;"));
+    }
+
+    #[test]
+    fn test_ordered_list_css_flat() {
+        assert_eq!(ordered_list_css(&OrderedListConfig::default()), "");
+    }
+
+    #[test]
+    fn test_ordered_list_css_outline_cycles_by_depth() {
+        let config = OrderedListConfig {
+            numbering: OrderedListNumbering::Outline,
+            outline_styles: vec![
+                "decimal".to_string(),
+                "lower-alpha".to_string(),
+                "red; } body { display: none".to_string(),
+            ],
+        };
+
+        let css = ordered_list_css(&config);
+
+        assert!(css.contains(".markdown-body ol.ol-depth-1 { list-style-type: decimal; }"));
+        assert!(css.contains(".markdown-body ol.ol-depth-2 { list-style-type: lower-alpha; }"));
+        assert!(css.contains(".markdown-body ol.ol-depth-3 { list-style-type: decimal; }"));
+        assert!(css.contains(&format!("ol-depth-{}", MAX_OUTLINE_DEPTH)));
+        assert!(!css.contains("display: none"));
+    }
+
+    #[test]
+    fn test_ordered_list_css_legal() {
+        let config = OrderedListConfig {
+            numbering: OrderedListNumbering::Legal,
+            ..OrderedListConfig::default()
+        };
+
+        let css = ordered_list_css(&config);
+
+        assert!(css.contains("counter-reset: arto-legal var(--ol-start, 0)"));
+        assert!(css.contains(r#"counters(arto-legal, ".")"#));
+    }
+
+    #[test]
+    fn test_dark_images_css() {
+        let mut config = DarkImagesConfig::default();
+        assert_eq!(dark_images_css(&config), "");
+
+        config.mode = DarkImageMode::OptIn;
+        let css = dark_images_css(&config);
+        assert!(css.contains(
+            r#"[data-theme="dark"] .markdown-body img.dark-invert:not(.dark-no-invert)"#
+        ));
+        assert!(!css.contains("svg"));
+        assert!(css.contains("filter: invert(0.88) hue-rotate(180deg);"));
+
+        config.mode = DarkImageMode::Diagrams;
+        let css = dark_images_css(&config);
+        assert!(css.contains(r#"img[src$=".svg" i]:not(.dark-no-invert)"#));
+
+        config.filter = "none; } body { display: none".to_string();
+        assert_eq!(dark_images_css(&config), "");
+    }
+
+    #[test]
+    fn test_font_files_css() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let body_font = temp_dir.path().join("Reading.woff2");
+        std::fs::write(&body_font, b"wOF2\x00\x01").unwrap();
+        let not_a_font = temp_dir.path().join("Code.ttf");
+        std::fs::write(&not_a_font, b"<html>").unwrap();
+
+        let (css, failed) = font_files_css(&FontsConfig {
+            body_font_file: Some(body_font),
+            code_font_file: Some(not_a_font.clone()),
+        });
+
+        assert!(css.contains(
+            r#"@font-face { font-family: "arto-body-font"; src: url("data:font/woff2;base64,d09GMgAB") format("woff2"); }"#
+        ));
+        assert!(
+            css.contains(r#".markdown-viewer .markdown-body { font-family: "arto-body-font"; }"#)
+        );
+        assert!(!css.contains("arto-code-font"));
+        assert_eq!(failed, vec![not_a_font]);
+        assert_eq!(
+            font_files_css(&FontsConfig::default()),
+            (String::new(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_font_files_css_reuses_unchanged_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let font = temp_dir.path().join("Cached.woff2");
+        std::fs::write(&font, b"wOF2\x00\x01").unwrap();
+        let modified = std::fs::metadata(&font).unwrap().modified().unwrap();
+        let config = FontsConfig {
+            body_font_file: Some(font.clone()),
+            code_font_file: None,
+        };
+        let (css, _) = font_files_css(&config);
+
+        // Same size and modification time: the cached data is used
+        std::fs::write(&font, b"wOFF\x00\x01").unwrap();
+        let file = std::fs::File::options().write(true).open(&font).unwrap();
+        file.set_modified(modified).unwrap();
+        assert_eq!(font_files_css(&config).0, css);
+
+        // A changed file is read again
+        std::fs::write(&font, b"wOFF\x00\x01\x02").unwrap();
+        assert!(font_files_css(&config).0.contains("data:font/woff;base64,"));
+    }
+
+    #[test]
+    fn test_changed_font_files_css_skips_unchanged_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let font = temp_dir.path().join("Body.woff2");
+        std::fs::write(&font, b"wOF2\x00\x01").unwrap();
+        let config = FontsConfig {
+            body_font_file: Some(font.clone()),
+            code_font_file: None,
+        };
+        let mut applied = None;
+
+        let css = changed_font_files_css(&mut applied, &config).unwrap();
+        assert!(css.contains("arto-body-font"));
+
+        // An unchanged config doesn't touch the file, even if it's gone
+        std::fs::remove_file(&font).unwrap();
+        assert_eq!(changed_font_files_css(&mut applied, &config), None);
+
+        assert_eq!(
+            changed_font_files_css(&mut applied, &FontsConfig::default()),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn test_export_fonts_css() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let code_font = temp_dir.path().join("Mono.otf");
+        std::fs::write(&code_font, b"OTTO").unwrap();
+        let config = FontsConfig {
+            body_font_file: Some(temp_dir.path().join("Missing.woff2")),
+            code_font_file: Some(code_font),
+        };
+
+        let (css, warnings) = export_fonts_css(&config, Some("Georgia, serif"));
+        assert!(css.contains(r#"font-family: "arto-code-font"; src: url("data:font/otf;base64,"#));
+        assert_eq!(
+            warnings,
+            vec![
+                "couldn't embed Missing.woff2",
+                "system font Georgia, serif isn't embedded"
+            ]
+        );
+
+        let (css, warnings) = export_fonts_css(&FontsConfig::default(), Some(""));
+        assert_eq!(css, "");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_code_ruler_css() {
+        assert_eq!(code_ruler_css(&CodeRulerConfig::default()), "");
+
+        let config = CodeRulerConfig {
+            enabled: true,
+            column: 72,
+            ..CodeRulerConfig::default()
+        };
+        let css = code_ruler_css(&config);
+
+        assert!(css.starts_with(".markdown-body pre:not([class*=\"preprocessed-\"])"));
+        assert!(css.contains("calc(16px + 72ch)"));
+        assert!(css.contains("background-attachment: local;"));
+    }
+
+    #[test]
+    fn test_code_wrap_css() {
+        assert_eq!(code_wrap_css(&CodeWrapConfig::default()), "");
+
+        let wrap = CodeWrapConfig {
+            mode: CodeWrapMode::Wrap,
+            ..CodeWrapConfig::default()
+        };
+        assert!(
+            code_wrap_css(&wrap).starts_with(".markdown-body pre code { white-space: pre-wrap;")
+        );
+
+        let narrow = CodeWrapConfig {
+            mode: CodeWrapMode::Narrow,
+            narrow_width: 600,
+        };
+        let css = code_wrap_css(&narrow);
+        assert!(css.starts_with("@media (max-width: 600px) { .markdown-body pre code {"));
+        assert!(css.ends_with("} }\n"));
+    }
+}
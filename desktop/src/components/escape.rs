@@ -0,0 +1,152 @@
+//! Window-wide Escape key handling.
+//!
+//! Each press of Escape performs one step, innermost UI first, so the key
+//! never does more than expected when several overlays are open:
+//!
+//! 1. Close the open context menu or popover
+//! 2. Close the search bar
+//! 3. Cancel an active tab drag (the tab returns to its window)
+//! 4. Close the current tab (opt-in via `escape.closeTab`)
+
+use dioxus::prelude::*;
+
+use super::content::close_context_menu;
+use super::tab::cancel_active_drag;
+use crate::config::CONFIG;
+use crate::drag;
+use crate::state::AppState;
+
+/// Backdrops rendered behind open context menus and popovers
+const MENU_BACKDROP_SELECTOR: &str = ".context-menu-backdrop, .color-palette-backdrop";
+
+/// A step of the Escape chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EscapeAction {
+    CloseContextMenu,
+    CloseSearch,
+    CancelDrag,
+    CloseTab,
+}
+
+/// UI state the Escape chain looks at
+#[derive(Debug, Clone, Copy, Default)]
+struct EscapeContext {
+    context_menu_open: bool,
+    search_open: bool,
+    dragging: bool,
+    /// Closing the tab is enabled and the current tab is not pinned
+    can_close_tab: bool,
+}
+
+impl EscapeContext {
+    /// The first step of the chain that applies, if any
+    fn action(&self) -> Option<EscapeAction> {
+        if self.context_menu_open {
+            Some(EscapeAction::CloseContextMenu)
+        } else if self.search_open {
+            Some(EscapeAction::CloseSearch)
+        } else if self.dragging {
+            Some(EscapeAction::CancelDrag)
+        } else if self.can_close_tab {
+            Some(EscapeAction::CloseTab)
+        } else {
+            None
+        }
+    }
+}
+
+/// Hook to handle Escape anywhere in the window
+pub fn use_escape_handler(mut state: AppState) {
+    use_hook(|| {
+        // Escape that ends an IME composition is left to the input
+        let mut eval_provider = document::eval(&indoc::formatdoc! {r#"
+            document.addEventListener("keydown", (event) => {{
+                if (event.key !== "Escape" || event.isComposing) {{
+                    return;
+                }}
+                dioxus.send(document.querySelector('{MENU_BACKDROP_SELECTOR}') !== null);
+            }});
+        "#});
+
+        spawn(async move {
+            while let Ok(context_menu_open) = eval_provider.recv::<bool>().await {
+                handle_escape(&mut state, context_menu_open);
+            }
+        });
+    });
+}
+
+fn handle_escape(state: &mut AppState, context_menu_open: bool) {
+    let active_tab = *state.active_tab.read();
+    let context = EscapeContext {
+        context_menu_open,
+        search_open: *state.search_open.read(),
+        dragging: drag::is_active_drag(),
+        can_close_tab: CONFIG.read().escape.close_tab
+            && state
+                .tabs
+                .read()
+                .get(active_tab)
+                .is_some_and(|tab| !tab.pinned),
+    };
+
+    let Some(action) = context.action() else {
+        return;
+    };
+    tracing::debug!(?action, "Handling Escape");
+    match action {
+        EscapeAction::CloseContextMenu => {
+            // Menus owned by other components close when their backdrop is clicked
+            close_context_menu();
+            let _ = document::eval(&format!(
+                "document.querySelectorAll('{MENU_BACKDROP_SELECTOR}').forEach((backdrop) => backdrop.click());"
+            ));
+        }
+        EscapeAction::CloseSearch => state.toggle_search(),
+        EscapeAction::CancelDrag => {
+            cancel_active_drag(state, dioxus::desktop::window().id());
+        }
+        EscapeAction::CloseTab => {
+            state.close_tab(active_tab);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_action_precedence() {
+        let all = EscapeContext {
+            context_menu_open: true,
+            search_open: true,
+            dragging: true,
+            can_close_tab: true,
+        };
+        assert_eq!(all.action(), Some(EscapeAction::CloseContextMenu));
+
+        let context = EscapeContext {
+            context_menu_open: false,
+            ..all
+        };
+        assert_eq!(context.action(), Some(EscapeAction::CloseSearch));
+
+        let context = EscapeContext {
+            search_open: false,
+            ..context
+        };
+        assert_eq!(context.action(), Some(EscapeAction::CancelDrag));
+
+        let context = EscapeContext {
+            dragging: false,
+            ..context
+        };
+        assert_eq!(context.action(), Some(EscapeAction::CloseTab));
+    }
+
+    #[test]
+    fn test_escape_does_nothing_without_open_ui() {
+        assert_eq!(EscapeContext::default().action(), None);
+    }
+}
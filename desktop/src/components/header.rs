@@ -1,9 +1,13 @@
+use chrono::{DateTime, Local};
 use dioxus::prelude::*;
+use std::time::{Duration, SystemTime};
 
 use crate::components::bookmark_button::BookmarkButton;
 use crate::components::icon::{Icon, IconName};
 use crate::components::theme_selector::ThemeSelector;
+use crate::config::{CopyPathFormat, ModifiedTimeFormat, CONFIG};
 use crate::state::AppState;
+use crate::utils::file::path_relative_to;
 
 #[component]
 pub fn Header() -> Element {
@@ -30,6 +34,18 @@ pub fn Header() -> Element {
 
     let is_sidebar_open = state.sidebar.read().open;
 
+    // Re-render every minute so the relative modified time stays current
+    let mut minute_tick = use_signal(|| 0usize);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            if CONFIG.read().header.modified_time == ModifiedTimeFormat::Relative {
+                minute_tick += 1;
+            }
+        }
+    });
+    let _ = minute_tick();
+
     let on_back = move |_| {
         state.save_scroll_and_go_back();
     };
@@ -41,16 +57,43 @@ pub fn Header() -> Element {
     let is_reloading = use_signal(|| false);
     let mut is_reloading_write = is_reloading;
 
-    let on_reload = move |_| {
+    // Shift-click performs the action that is not the configured default
+    let header_config = CONFIG.read().header.clone();
+    let modified = file_path
+        .is_some()
+        .then(|| *state.file_modified.read())
+        .flatten()
+        .and_then(|modified| {
+            let label = format_modified(modified, SystemTime::now(), header_config.modified_time)?;
+            Some((label, format_absolute(modified)))
+        });
+    let reload_all_by_default = header_config.reload_all_tabs;
+    let relative_by_default = header_config.copy_path == CopyPathFormat::Relative;
+    let reload_title = if reload_all_by_default {
+        "Reload all tabs (Shift-click: reload this file)"
+    } else {
+        "Reload file (Shift-click: reload all tabs)"
+    };
+    let copy_title = if relative_by_default {
+        "Copy path relative to the project (Shift-click: copy full path)"
+    } else {
+        "Copy full path (Shift-click: copy path relative to the project)"
+    };
+
+    let on_reload = move |evt: MouseEvent| {
         // Set reloading state
         is_reloading_write.set(true);
 
-        state.update_current_tab(|tab| {
-            if let Some(path) = tab.file() {
-                // Reload by reassigning the same file path
-                tab.content = crate::state::TabContent::File(path.to_owned());
-            }
-        });
+        if evt.modifiers().shift() != reload_all_by_default {
+            state.reload_all_tabs();
+        } else {
+            state.update_current_tab(|tab| {
+                if let Some(path) = tab.file() {
+                    // Reload by reassigning the same file path
+                    tab.content = crate::state::TabContent::File(path.to_owned());
+                }
+            });
+        }
 
         // Reset reloading state after animation
         spawn(async move {
@@ -105,6 +148,15 @@ pub fn Header() -> Element {
                     "{file}"
                 }
 
+                // Last-modified time of the loaded file (header.modifiedTime)
+                if let Some((label, absolute)) = modified {
+                    span {
+                        class: "file-modified",
+                        title: "Modified {absolute}",
+                        "{label}"
+                    }
+                }
+
                 div {
                     class: "file-action-buttons",
 
@@ -116,11 +168,17 @@ pub fn Header() -> Element {
                         button {
                             class: "nav-button copy-button",
                             class: if *is_copied.read() { "copied" },
-                            title: "Copy full path",
+                            title: copy_title,
                             onclick: {
-                                let path_str = path.to_string_lossy().to_string();
-                                move |_| {
-                                    crate::utils::clipboard::copy_text(&path_str);
+                                let path = path.to_path_buf();
+                                move |evt: MouseEvent| {
+                                    let copied = if evt.modifiers().shift() != relative_by_default {
+                                        let root = state.sidebar.read().root_directory.clone();
+                                        path_relative_to(&path, root.as_deref()).to_path_buf()
+                                    } else {
+                                        path.clone()
+                                    };
+                                    crate::utils::clipboard::copy_text(copied.to_string_lossy());
                                     // Show success feedback
                                     is_copied.set(true);
                                     spawn(async move {
@@ -140,7 +198,7 @@ pub fn Header() -> Element {
                             class: "nav-button reload-button",
                             class: if *is_reloading.read() { "reloading" },
                             onclick: on_reload,
-                            title: "Reload file",
+                            title: reload_title,
                             Icon { name: IconName::Refresh, size: 14 }
                         }
                     }
@@ -192,3 +250,88 @@ pub fn Header() -> Element {
         }
     }
 }
+
+/// Label of a file's last-modified time, or `None` when it is hidden
+fn format_modified(
+    modified: SystemTime,
+    now: SystemTime,
+    format: ModifiedTimeFormat,
+) -> Option<String> {
+    match format {
+        ModifiedTimeFormat::Hidden => None,
+        ModifiedTimeFormat::Absolute => Some(format_absolute(modified)),
+        ModifiedTimeFormat::Relative => {
+            // A clock that went backwards counts as "just now"
+            let age = now.duration_since(modified).unwrap_or_default();
+            Some(format_relative(age).unwrap_or_else(|| format_absolute(modified)))
+        }
+    }
+}
+
+fn format_absolute(modified: SystemTime) -> String {
+    DateTime::<Local>::from(modified)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// "5 minutes ago" and the like, or `None` for a month or more
+fn format_relative(age: Duration) -> Option<String> {
+    let minutes = age.as_secs() / 60;
+    let (count, unit) = match minutes {
+        0 => return Some("just now".to_string()),
+        1..60 => (minutes, "minute"),
+        60..1440 => (minutes / 60, "hour"),
+        1440..43200 => (minutes / 1440, "day"),
+        _ => return None,
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    Some(format!("{count} {unit}{plural} ago"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative() {
+        let minutes = |n: u64| Duration::from_secs(n * 60);
+
+        assert_eq!(
+            format_relative(Duration::from_secs(59)).unwrap(),
+            "just now"
+        );
+        assert_eq!(format_relative(minutes(1)).unwrap(), "1 minute ago");
+        assert_eq!(format_relative(minutes(59)).unwrap(), "59 minutes ago");
+        assert_eq!(format_relative(minutes(60)).unwrap(), "1 hour ago");
+        assert_eq!(format_relative(minutes(3 * 60 + 5)).unwrap(), "3 hours ago");
+        assert_eq!(format_relative(minutes(24 * 60)).unwrap(), "1 day ago");
+        assert_eq!(
+            format_relative(minutes(29 * 24 * 60)).unwrap(),
+            "29 days ago"
+        );
+        assert_eq!(format_relative(minutes(30 * 24 * 60)), None);
+    }
+
+    #[test]
+    fn test_format_modified() {
+        let now = SystemTime::now();
+        let modified = now - Duration::from_secs(120);
+
+        assert_eq!(
+            format_modified(modified, now, ModifiedTimeFormat::Hidden),
+            None
+        );
+        assert_eq!(
+            format_modified(modified, now, ModifiedTimeFormat::Relative).unwrap(),
+            "2 minutes ago"
+        );
+        assert_eq!(
+            format_modified(now, modified, ModifiedTimeFormat::Relative).unwrap(),
+            "just now"
+        );
+        assert_eq!(
+            format_modified(modified, now, ModifiedTimeFormat::Absolute).unwrap(),
+            format_absolute(modified)
+        );
+    }
+}
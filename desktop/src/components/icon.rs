@@ -10,6 +10,7 @@ pub enum IconName {
     AlertTriangle,
     ArrowsDiagonal,
     ArrowsMove,
+    BrandGit,
     BrandGithub,
     Bug,
     Check,
@@ -18,6 +19,7 @@ pub enum IconName {
     ChevronRight,
     ChevronUp,
     Click,
+    Clock,
     Close,
     Command,
     Copy,
@@ -31,6 +33,7 @@ pub enum IconName {
     FolderOpen,
     Gear,
     InfoCircle,
+    Link,
     List,
     Moon,
     Photo,
@@ -41,6 +44,7 @@ pub enum IconName {
     SelectAll,
     Server,
     Sidebar,
+    SortByName,
     Star,
     StarFilled,
     Sun,
@@ -56,6 +60,7 @@ impl fmt::Display for IconName {
             IconName::AlertTriangle => "alert-triangle",
             IconName::ArrowsDiagonal => "arrows-diagonal",
             IconName::ArrowsMove => "arrows-move",
+            IconName::BrandGit => "brand-git",
             IconName::BrandGithub => "brand-github",
             IconName::Bug => "bug",
             IconName::Check => "check",
@@ -64,6 +69,7 @@ impl fmt::Display for IconName {
             IconName::ChevronRight => "chevron-right",
             IconName::ChevronUp => "chevron-up",
             IconName::Click => "click",
+            IconName::Clock => "clock",
             IconName::Close => "x",
             IconName::Command => "command",
             IconName::Copy => "copy",
@@ -77,6 +83,7 @@ impl fmt::Display for IconName {
             IconName::FolderOpen => "folder-open",
             IconName::Gear => "settings",
             IconName::InfoCircle => "info-circle",
+            IconName::Link => "link",
             IconName::List => "list",
             IconName::Moon => "moon",
             IconName::Photo => "photo",
@@ -87,6 +94,7 @@ impl fmt::Display for IconName {
             IconName::SelectAll => "select-all",
             IconName::Server => "server",
             IconName::Sidebar => "layout-sidebar",
+            IconName::SortByName => "sort-ascending-letters",
             IconName::Star => "star",
             IconName::StarFilled => "star-filled",
             IconName::Sun => "sun",
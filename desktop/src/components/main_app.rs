@@ -48,12 +48,19 @@ pub fn MainApp() -> Element {
 
     // Resolve initial tab and directory from event
     let is_first_window = true;
-    let (tab, directory_override) = match &first_event {
-        Some(OpenEvent::File(path)) => (Tab::new(path.clone()), None),
-        Some(OpenEvent::Directory(path)) => (Tab::default(), Some(path.clone())),
+    let (tab, directory_override, background_tabs) = match &first_event {
+        Some(OpenEvent::File(path)) => (Tab::new(path.clone()), None, vec![]),
+        Some(OpenEvent::Directory(path)) => (Tab::default(), Some(path.clone()), vec![]),
         _ => {
             let welcome_content = crate::assets::get_default_markdown_content();
-            (Tab::with_inline_content(welcome_content), None)
+            let welcome = Tab::with_inline_content(welcome_content);
+            // The home document (if any) opens in place of, or in front of, the welcome screen
+            let home = settings::get_home_document_preference();
+            match home.document {
+                Some(document) if home.with_welcome => (Tab::new(document), None, vec![welcome]),
+                Some(document) => (Tab::new(document), None, vec![]),
+                None => (welcome, None, vec![]),
+            }
         }
     };
 
@@ -63,6 +70,7 @@ pub fn MainApp() -> Element {
     let sidebar_pref = settings::get_sidebar_preference(is_first_window);
     let toc_pref = settings::get_toc_preference(is_first_window);
     let zoom_pref = settings::get_zoom_preference(is_first_window);
+    let profile_pref = settings::get_reader_profile_preference(is_first_window);
 
     // Directory resolution: override (from event) → config → tab parent → home → root
     let directory = directory_override
@@ -86,6 +94,8 @@ pub fn MainApp() -> Element {
             toc_width: toc_pref.width,
             toc_tab: toc_pref.tab,
             zoom_level: zoom_pref.zoom_level,
+            reader_profile: profile_pref.profile,
+            background_tabs: background_tabs,
         }
     }
 }
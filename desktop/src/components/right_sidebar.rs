@@ -1,13 +1,22 @@
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+mod backlinks_tab;
 mod contents_tab;
+mod lint_tab;
+mod notes_tab;
 mod search_tab;
 mod tab_bar;
+mod tags_tab;
 
-use contents_tab::ContentsTab;
+use backlinks_tab::BacklinksTab;
+use contents_tab::{use_toc_scroll_spy, ContentsTab};
+use lint_tab::LintTab;
+pub use notes_tab::warn_save_failure;
+use notes_tab::NotesTab;
 use search_tab::SearchTab;
 use tab_bar::TabBar;
+use tags_tab::TagsTab;
 
 use crate::markdown::HeadingInfo;
 use crate::state::AppState;
@@ -18,6 +27,34 @@ pub enum RightSidebarTab {
     #[default]
     Contents,
     Search,
+    Tags,
+    Backlinks,
+    Lint,
+    Notes,
+}
+
+impl RightSidebarTab {
+    /// All tabs in the order they appear in the tab bar
+    pub const ALL: [RightSidebarTab; 6] = [
+        RightSidebarTab::Contents,
+        RightSidebarTab::Search,
+        RightSidebarTab::Tags,
+        RightSidebarTab::Backlinks,
+        RightSidebarTab::Lint,
+        RightSidebarTab::Notes,
+    ];
+
+    /// The tab after this one, wrapping around to the first
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The tab before this one, wrapping around to the last
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
 }
 
 #[derive(Props, Clone, PartialEq)]
@@ -34,8 +71,12 @@ pub fn RightSidebar(props: RightSidebarProps) -> Element {
     let zoom_level = state.zoom_level;
     let is_resizing = use_signal(|| false);
 
+    // Highlight the heading at the scroll position in the Contents tab
+    use_toc_scroll_spy(state);
+
     // Get data for each tab
     let headings = props.headings.clone();
+    let active_heading = state.active_heading.read().clone();
 
     let outer_style = if is_open {
         format!("width: {}px;", width)
@@ -73,8 +114,12 @@ pub fn RightSidebar(props: RightSidebarProps) -> Element {
                     class: "right-sidebar-content",
 
                     match active_tab {
-                        RightSidebarTab::Contents => rsx! { ContentsTab { headings } },
+                        RightSidebarTab::Contents => rsx! { ContentsTab { headings, active_heading } },
                         RightSidebarTab::Search => rsx! { SearchTab {} },
+                        RightSidebarTab::Tags => rsx! { TagsTab {} },
+                        RightSidebarTab::Backlinks => rsx! { BacklinksTab {} },
+                        RightSidebarTab::Lint => rsx! { LintTab {} },
+                        RightSidebarTab::Notes => rsx! { NotesTab {} },
                     }
                 }
             }
@@ -146,3 +191,18 @@ fn RightSidebarResizeHandle(is_resizing: Signal<bool>) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_right_sidebar_tab_cycles() {
+        assert_eq!(RightSidebarTab::Contents.next(), RightSidebarTab::Search);
+        assert_eq!(RightSidebarTab::Lint.next(), RightSidebarTab::Notes);
+        assert_eq!(RightSidebarTab::Notes.next(), RightSidebarTab::Contents);
+        assert_eq!(RightSidebarTab::Contents.previous(), RightSidebarTab::Notes);
+        assert_eq!(RightSidebarTab::Tags.previous(), RightSidebarTab::Search);
+        assert_eq!(RightSidebarTab::Tags.next(), RightSidebarTab::Backlinks);
+    }
+}
@@ -0,0 +1,116 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::backlinks::{find_backlinks, Backlinks};
+use crate::components::icon::{Icon, IconName};
+use crate::config::CONFIG;
+use crate::state::AppState;
+use crate::utils::file::ScanOptions;
+
+#[component]
+pub fn BacklinksTab() -> Element {
+    let state = use_context::<AppState>();
+    let file = use_memo(move || {
+        state
+            .current_tab()
+            .and_then(|tab| tab.file().map(PathBuf::from))
+    });
+    let root = state.sidebar.read().root_directory.clone();
+
+    // Scan the root for files linking to the current one (off the UI thread)
+    let backlinks = use_resource(move || {
+        let file = file();
+        let root = state.sidebar.read().root_directory.clone();
+        async move {
+            let (Some(file), Some(root)) = (file, root) else {
+                return Backlinks::default();
+            };
+            let (related_key, options) = {
+                let config = CONFIG.read();
+                (
+                    config.frontmatter.related_key.clone(),
+                    ScanOptions::from_sidebar(&config.sidebar),
+                )
+            };
+            tokio::task::spawn_blocking(move || {
+                find_backlinks(&root, &file, &related_key, &options)
+            })
+            .await
+            .unwrap_or_default()
+        }
+    });
+
+    rsx! {
+        div {
+            class: "right-sidebar-backlinks",
+
+            match (file(), root) {
+                (None, _) => rsx! {
+                    div {
+                        class: "right-sidebar-backlinks-empty",
+                        "Open a file to see the documents linking to it"
+                    }
+                },
+                (Some(_), None) => rsx! {
+                    div {
+                        class: "right-sidebar-backlinks-empty",
+                        "No directory open"
+                    }
+                },
+                (Some(_), Some(root)) => rsx! {
+                    match backlinks.read().as_ref() {
+                        None => rsx! {
+                            div { class: "right-sidebar-backlinks-empty", "Scanning..." }
+                        },
+                        Some(backlinks) => rsx! {
+                            if backlinks.files.is_empty() {
+                                div { class: "right-sidebar-backlinks-empty", "No documents link here" }
+                            } else {
+                                div {
+                                    class: "right-sidebar-backlinks-header",
+                                    "Linked from {backlinks.files.len()} "
+                                    if backlinks.files.len() == 1 { "document" } else { "documents" }
+                                }
+                                ul {
+                                    class: "right-sidebar-backlinks-list",
+                                    for file in backlinks.files.iter() {
+                                        BacklinkItem { file: file.clone(), root: root.clone() }
+                                    }
+                                }
+                            }
+                            if backlinks.partial {
+                                div {
+                                    class: "right-sidebar-backlinks-partial",
+                                    "Partial results: the directory is too large to scan fully"
+                                }
+                            }
+                        },
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn BacklinkItem(file: PathBuf, root: PathBuf) -> Element {
+    let mut state = use_context::<AppState>();
+    let label = file
+        .strip_prefix(&root)
+        .unwrap_or(&file)
+        .to_string_lossy()
+        .to_string();
+
+    rsx! {
+        li {
+            class: "right-sidebar-backlinks-item",
+            button {
+                class: "right-sidebar-backlinks-item-button",
+                title: "{file.display()}",
+                onclick: move |_| state.open_file(&file),
+                Icon { name: IconName::File, size: 14 }
+                span { "{label}" }
+            }
+        }
+    }
+}
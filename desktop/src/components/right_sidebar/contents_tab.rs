@@ -1,10 +1,21 @@
 use dioxus::document;
 use dioxus::prelude::*;
 
+use crate::config::CONFIG;
 use crate::markdown::HeadingInfo;
+use crate::state::AppState;
 
 #[component]
-pub fn ContentsTab(headings: Vec<HeadingInfo>) -> Element {
+pub fn ContentsTab(headings: Vec<HeadingInfo>, active_heading: Option<String>) -> Element {
+    // Keep the highlighted entry visible in the list
+    use_effect(use_reactive!(|active_heading| {
+        if active_heading.is_some() {
+            let _ = document::eval(
+                "document.querySelector('.right-sidebar-contents-item.active')?.scrollIntoView({ block: 'nearest' });",
+            );
+        }
+    }));
+
     rsx! {
         div {
             class: "right-sidebar-contents",
@@ -18,7 +29,10 @@ pub fn ContentsTab(headings: Vec<HeadingInfo>) -> Element {
                 ul {
                     class: "right-sidebar-contents-list",
                     for heading in headings.iter() {
-                        HeadingItem { heading: heading.clone() }
+                        HeadingItem {
+                            active: active_heading.as_deref() == Some(heading.id.as_str()),
+                            heading: heading.clone(),
+                        }
                     }
                 }
             }
@@ -27,13 +41,14 @@ pub fn ContentsTab(headings: Vec<HeadingInfo>) -> Element {
 }
 
 #[component]
-fn HeadingItem(heading: HeadingInfo) -> Element {
+fn HeadingItem(heading: HeadingInfo, active: bool) -> Element {
     let id = heading.id.clone();
     let level = heading.level;
 
     rsx! {
         li {
             class: "right-sidebar-contents-item",
+            class: if active { "active" },
             "data-level": "{level}",
 
             button {
@@ -42,14 +57,7 @@ fn HeadingItem(heading: HeadingInfo) -> Element {
                     let id = id.clone();
                     spawn(async move {
                         let js = format!(
-                            r#"
-                            (() => {{
-                                const el = document.getElementById('{}');
-                                if (el) {{
-                                    el.scrollIntoView({{ behavior: 'smooth', block: 'start' }});
-                                }}
-                            }})();
-                            "#,
+                            "window.Arto.scroll.toElement(document.getElementById('{}'));",
                             id
                         );
                         let _ = document::eval(&js).await;
@@ -60,3 +68,40 @@ fn HeadingItem(heading: HeadingInfo) -> Element {
         }
     }
 }
+
+/// Hook to track the heading at the scroll position (TOC scroll-spy)
+///
+/// The renderer's `scrollSpy` module reports the id of the last heading
+/// scrolled past, throttled while scrolling. It is re-checked whenever the
+/// headings change, i.e. after a document has been rendered.
+pub fn use_toc_scroll_spy(mut state: AppState) {
+    use_hook(|| {
+        if !CONFIG.read().right_sidebar.scroll_spy {
+            return;
+        }
+        // The main module is loaded asynchronously on startup
+        let mut eval_provider = document::eval(indoc::indoc! {r#"
+            const start = () => {
+                if (!window.Arto?.scrollSpy) {
+                    setTimeout(start, 50);
+                    return;
+                }
+                window.Arto.scrollSpy.setup((id) => dioxus.send(id));
+            };
+            start();
+        "#});
+
+        spawn(async move {
+            while let Ok(id) = eval_provider.recv::<Option<String>>().await {
+                state.active_heading.set(id);
+            }
+        });
+    });
+
+    use_effect(move || {
+        let _ = state.toc_headings.read();
+        if CONFIG.read().right_sidebar.scroll_spy {
+            let _ = document::eval("window.Arto?.scrollSpy?.refresh();");
+        }
+    });
+}
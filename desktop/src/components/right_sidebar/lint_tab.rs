@@ -0,0 +1,64 @@
+use dioxus::document;
+use dioxus::prelude::*;
+
+use crate::config::CONFIG;
+use crate::lint::LintIssue;
+use crate::state::AppState;
+
+#[component]
+pub fn LintTab() -> Element {
+    let state = use_context::<AppState>();
+    let issues = state.lint_issues.read().clone();
+    let enabled = CONFIG.read().lint.enabled;
+
+    rsx! {
+        div {
+            class: "right-sidebar-lint",
+
+            if !enabled {
+                div {
+                    class: "right-sidebar-lint-empty",
+                    "Linting is disabled in the configuration"
+                }
+            } else if issues.is_empty() {
+                div {
+                    class: "right-sidebar-lint-empty",
+                    "No issues found"
+                }
+            } else {
+                ul {
+                    class: "right-sidebar-lint-list",
+                    for issue in issues.iter() {
+                        LintItem { issue: issue.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LintItem(issue: LintIssue) -> Element {
+    let line = issue.line;
+    let severity = issue.severity.as_str();
+
+    rsx! {
+        li {
+            class: "right-sidebar-lint-item",
+
+            button {
+                class: "right-sidebar-lint-item-button",
+                title: "Go to line {line}",
+                onclick: move |_| {
+                    spawn(async move {
+                        let js = format!("window.Arto.splitPreview.scrollToLine({})", line);
+                        let _ = document::eval(&js).await;
+                    });
+                },
+                span { class: "right-sidebar-lint-severity {severity}", "{severity}" }
+                span { class: "right-sidebar-lint-line", "{line}" }
+                span { class: "right-sidebar-lint-message", "{issue.message}" }
+            }
+        }
+    }
+}
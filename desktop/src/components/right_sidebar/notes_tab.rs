@@ -0,0 +1,310 @@
+use dioxus::core::use_drop;
+use dioxus::document;
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::annotations::{Annotation, Annotations, ANNOTATIONS_CHANGED};
+use crate::components::icon::{Icon, IconName};
+use crate::config::CONFIG;
+use crate::state::AppState;
+
+#[component]
+pub fn NotesTab() -> Element {
+    let mut state = use_context::<AppState>();
+    let mut annotations = use_signal(Annotations::default);
+    let mut draft = use_signal(String::new);
+    let mut anchored = use_signal(|| true);
+
+    let file = use_memo(move || {
+        state
+            .current_tab()
+            .and_then(|tab| tab.file().map(|file| file.to_path_buf()))
+    });
+
+    // Load the notes whenever the document changes, keeping the unfinished
+    // note of the previous document first
+    let mut loaded_file = use_signal(|| None::<PathBuf>);
+    use_effect(move || {
+        let file = file();
+        let previous = std::mem::replace(&mut *loaded_file.write(), file.clone());
+        if previous != file {
+            let text = std::mem::take(&mut *draft.write());
+            if let Err(error) = flush_draft(previous.as_deref(), &text) {
+                tracing::error!(?error, "Failed to save unfinished note");
+                state.show_toast("Failed to save unfinished note");
+            }
+        }
+        annotations.set(load_annotations(file.as_deref()));
+    });
+
+    // Keep the unfinished note when the tab or its window closes
+    use_drop(move || {
+        if let Err(error) = flush_draft(loaded_file.peek().as_deref(), &draft.peek()) {
+            warn_save_failure("unfinished note", &error);
+        }
+    });
+
+    // Reload when another window saves notes for the same document
+    use_hook(|| {
+        spawn(async move {
+            let mut rx = ANNOTATIONS_CHANGED.subscribe();
+            while let Ok(changed) = rx.recv().await {
+                if file.peek().as_ref() == Some(&changed) {
+                    annotations.set(load_annotations(Some(&changed)));
+                }
+            }
+        });
+    });
+
+    let add_note = move |_| {
+        let text = draft.peek().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let anchor_to_view = *anchored.peek();
+        spawn(async move {
+            let (line, heading_id) = if anchor_to_view {
+                capture_anchor().await
+            } else {
+                (None, None)
+            };
+            let mut updated = annotations.peek().clone();
+            updated.add(Annotation {
+                text,
+                line,
+                heading_id,
+                created_at: chrono::Utc::now(),
+            });
+            save_annotations(state, annotations, file.peek().clone(), updated);
+            draft.set(String::new());
+        });
+    };
+
+    let notes = annotations.read().notes.clone();
+
+    rsx! {
+        div {
+            class: "right-sidebar-notes",
+
+            if file().is_none() {
+                div {
+                    class: "right-sidebar-notes-empty",
+                    "Open a file to add notes"
+                }
+            } else {
+                div {
+                    class: "right-sidebar-notes-form",
+                    textarea {
+                        class: "right-sidebar-notes-input",
+                        placeholder: "Add a note…",
+                        rows: 3,
+                        value: "{draft}",
+                        oninput: move |evt| draft.set(evt.value()),
+                        onkeydown: move |evt| {
+                            // Cmd/Ctrl+Enter adds the note
+                            if evt.key() == Key::Enter
+                                && (evt.modifiers().meta() || evt.modifiers().ctrl())
+                            {
+                                evt.prevent_default();
+                                add_note(());
+                            }
+                        },
+                    }
+                    div {
+                        class: "right-sidebar-notes-actions",
+                        label {
+                            class: "right-sidebar-notes-anchor-toggle",
+                            title: "Attach the note to the part of the document at the top of the view",
+                            input {
+                                r#type: "checkbox",
+                                checked: anchored(),
+                                onchange: move |evt| anchored.set(evt.checked()),
+                            }
+                            "Anchor to current position"
+                        }
+                        button {
+                            class: "right-sidebar-notes-add",
+                            disabled: draft().trim().is_empty(),
+                            onclick: move |_| add_note(()),
+                            "Add"
+                        }
+                    }
+                }
+
+                if notes.is_empty() {
+                    div {
+                        class: "right-sidebar-notes-empty",
+                        "No notes for this document"
+                    }
+                } else {
+                    ul {
+                        class: "right-sidebar-notes-list",
+                        for (index, note) in notes.into_iter().enumerate() {
+                            NoteItem {
+                                key: "{index}-{note.created_at}",
+                                note,
+                                on_delete: move |_| {
+                                    let mut updated = annotations.peek().clone();
+                                    updated.remove(index);
+                                    save_annotations(state, annotations, file.peek().clone(), updated);
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NoteItem(note: Annotation, on_delete: EventHandler<()>) -> Element {
+    let line = note.line;
+    let heading_id = note.heading_id.clone();
+    let created = note
+        .created_at
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+
+    rsx! {
+        li {
+            class: "right-sidebar-notes-item",
+            div {
+                class: "right-sidebar-notes-meta",
+                if let Some(line) = line {
+                    button {
+                        class: "right-sidebar-notes-line",
+                        title: "Go to line {line}",
+                        onclick: move |_| {
+                            let heading_id = heading_id.clone();
+                            spawn(async move {
+                                jump_to_anchor(line, heading_id).await;
+                            });
+                        },
+                        "Line {line}"
+                    }
+                }
+                span { class: "right-sidebar-notes-date", "{created}" }
+                button {
+                    class: "right-sidebar-notes-delete",
+                    title: "Delete note",
+                    onclick: move |_| on_delete.call(()),
+                    Icon { name: IconName::Close, size: 12 }
+                }
+            }
+            div { class: "right-sidebar-notes-text", "{note.text}" }
+        }
+    }
+}
+
+fn load_annotations(file: Option<&Path>) -> Annotations {
+    let Some(file) = file else {
+        return Annotations::default();
+    };
+    Annotations::load(file).unwrap_or_else(|error| {
+        tracing::warn!(?error, "Failed to load annotations");
+        Annotations::default()
+    })
+}
+
+/// Save the notes of `file` and show them, telling other windows
+fn save_annotations(
+    mut state: AppState,
+    mut annotations: Signal<Annotations>,
+    file: Option<PathBuf>,
+    updated: Annotations,
+) {
+    let Some(file) = file else {
+        return;
+    };
+    match updated.save(&file) {
+        Ok(()) => {
+            annotations.set(updated);
+            ANNOTATIONS_CHANGED.send(file).ok();
+        }
+        Err(error) => {
+            tracing::error!(?error, "Failed to save annotations");
+            state.show_toast("Failed to save notes");
+        }
+    }
+}
+
+/// Save a note that was typed but not added yet, unanchored
+///
+/// Does nothing for an empty draft or when `annotations.saveDraftOnClose` is
+/// off.
+fn flush_draft(file: Option<&Path>, draft: &str) -> anyhow::Result<()> {
+    let text = draft.trim();
+    let Some(file) = file else {
+        return Ok(());
+    };
+    if text.is_empty() || !CONFIG.read().annotations.save_draft_on_close {
+        return Ok(());
+    }
+
+    let mut annotations = Annotations::load(file)?;
+    annotations.add(Annotation {
+        text: text.to_string(),
+        line: None,
+        heading_id: None,
+        created_at: chrono::Utc::now(),
+    });
+    annotations.save(file)?;
+    ANNOTATIONS_CHANGED.send(file.to_path_buf()).ok();
+    Ok(())
+}
+
+/// Report something that couldn't be saved while closing a window
+///
+/// A toast would disappear with the window, so unless
+/// `annotations.warnOnSaveFailure` is off a dialog is shown instead.
+pub fn warn_save_failure(what: &str, error: &anyhow::Error) {
+    tracing::error!(?error, "Failed to save {what} on close");
+    if !CONFIG.read().annotations.warn_on_save_failure {
+        return;
+    }
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Couldn't Save")
+        .set_description(format!("Your {what} couldn't be saved:\n\n{error:#}"))
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+/// Source line and heading at the top of the rendered view
+async fn capture_anchor() -> (Option<usize>, Option<String>) {
+    let anchor = document::eval(
+        r#"
+        const line = window.Arto.scrollAnchor.capture("source_line");
+        const heading = window.Arto.scrollAnchor.capture("heading");
+        return [line ? line.line : null, heading ? heading.headingId : null];
+        "#,
+    )
+    .await;
+    let Ok(serde_json::Value::Array(values)) = anchor else {
+        return (None, None);
+    };
+    let line = values
+        .first()
+        .and_then(|line| line.as_f64())
+        .map(|line| (line.floor() as usize).max(1));
+    let heading_id = values.get(1).and_then(|id| id.as_str()).map(str::to_string);
+    (line, heading_id)
+}
+
+/// Scroll to the anchored heading, or to the source line if it is gone
+async fn jump_to_anchor(line: usize, heading_id: Option<String>) {
+    let heading_id = serde_json::to_string(&heading_id).unwrap_or_default();
+    let js = format!(
+        r#"
+        const heading = {heading_id} && document.getElementById({heading_id});
+        if (heading) {{
+            window.Arto.scroll.toElement(heading);
+        }} else {{
+            window.Arto.splitPreview.scrollToLine({line});
+        }}
+        "#
+    );
+    let _ = document::eval(&js).await;
+}
@@ -21,6 +21,34 @@ pub fn TabBar(active_tab: RightSidebarTab, on_change: EventHandler<RightSidebarT
                 onclick: move |_| on_change.call(RightSidebarTab::Search),
                 span { "Search" }
             }
+
+            // Tags tab
+            button {
+                class: if active_tab == RightSidebarTab::Tags { "right-sidebar-tab active" } else { "right-sidebar-tab" },
+                onclick: move |_| on_change.call(RightSidebarTab::Tags),
+                span { "Tags" }
+            }
+
+            // Backlinks tab
+            button {
+                class: if active_tab == RightSidebarTab::Backlinks { "right-sidebar-tab active" } else { "right-sidebar-tab" },
+                onclick: move |_| on_change.call(RightSidebarTab::Backlinks),
+                span { "Backlinks" }
+            }
+
+            // Lint tab
+            button {
+                class: if active_tab == RightSidebarTab::Lint { "right-sidebar-tab active" } else { "right-sidebar-tab" },
+                onclick: move |_| on_change.call(RightSidebarTab::Lint),
+                span { "Lint" }
+            }
+
+            // Notes tab
+            button {
+                class: if active_tab == RightSidebarTab::Notes { "right-sidebar-tab active" } else { "right-sidebar-tab" },
+                onclick: move |_| on_change.call(RightSidebarTab::Notes),
+                span { "Notes" }
+            }
         }
     }
 }
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::components::icon::{Icon, IconName};
+use crate::config::CONFIG;
+use crate::state::AppState;
+use crate::tags::{find_files_with_tag, TaggedFiles};
+use crate::utils::file::ScanOptions;
+
+#[component]
+pub fn TagsTab() -> Element {
+    let mut state = use_context::<AppState>();
+    let tag = state.tag_filter.read().clone();
+    let root = state.sidebar.read().root_directory.clone();
+
+    // Scan the root for files with the selected tag (off the UI thread)
+    let matches = use_resource(move || {
+        let tag = state.tag_filter.read().clone();
+        let root = state.sidebar.read().root_directory.clone();
+        async move {
+            let (Some(tag), Some(root)) = (tag, root) else {
+                return TaggedFiles::default();
+            };
+            let (tags_key, options) = {
+                let config = CONFIG.read();
+                (
+                    config.frontmatter.tags_key.clone(),
+                    ScanOptions::from_sidebar(&config.sidebar),
+                )
+            };
+            tokio::task::spawn_blocking(move || {
+                find_files_with_tag(&root, &tag, &tags_key, &options)
+            })
+            .await
+            .unwrap_or_default()
+        }
+    });
+
+    rsx! {
+        div {
+            class: "right-sidebar-tags",
+
+            match (tag, root) {
+                (None, _) => rsx! {
+                    div {
+                        class: "right-sidebar-tags-empty",
+                        "Click a frontmatter tag to list files with that tag"
+                    }
+                },
+                (Some(_), None) => rsx! {
+                    div {
+                        class: "right-sidebar-tags-empty",
+                        "No directory open"
+                    }
+                },
+                (Some(tag), Some(root)) => rsx! {
+                    div {
+                        class: "right-sidebar-tags-header",
+                        span { class: "frontmatter-tag", "{tag}" }
+                        button {
+                            class: "right-sidebar-tags-clear",
+                            title: "Clear tag filter",
+                            onclick: move |_| state.tag_filter.set(None),
+                            Icon { name: IconName::Close, size: 14 }
+                        }
+                    }
+                    match matches.read().as_ref() {
+                        None => rsx! {
+                            div { class: "right-sidebar-tags-empty", "Scanning..." }
+                        },
+                        Some(tagged) => rsx! {
+                            if tagged.files.is_empty() {
+                                div { class: "right-sidebar-tags-empty", "No files with this tag" }
+                            } else {
+                                ul {
+                                    class: "right-sidebar-tags-list",
+                                    for file in tagged.files.iter() {
+                                        TaggedFileItem { file: file.clone(), root: root.clone() }
+                                    }
+                                }
+                            }
+                            if tagged.partial {
+                                div {
+                                    class: "right-sidebar-tags-partial",
+                                    "Partial results: the directory is too large to scan fully"
+                                }
+                            }
+                        },
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn TaggedFileItem(file: PathBuf, root: PathBuf) -> Element {
+    let mut state = use_context::<AppState>();
+    let label = file
+        .strip_prefix(&root)
+        .unwrap_or(&file)
+        .to_string_lossy()
+        .to_string();
+    let is_active = state
+        .current_tab()
+        .and_then(|tab| tab.file().map(|f| f == file))
+        .unwrap_or(false);
+
+    rsx! {
+        li {
+            class: "right-sidebar-tags-item",
+            button {
+                class: "right-sidebar-tags-item-button",
+                class: if is_active { "active" },
+                title: "{file.display()}",
+                onclick: move |_| state.open_file(&file),
+                Icon { name: IconName::File, size: 14 }
+                span { "{label}" }
+            }
+        }
+    }
+}
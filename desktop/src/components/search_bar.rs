@@ -168,14 +168,11 @@ pub fn SearchBar() -> Element {
                                 let _ = document::eval(JS_FIND).await;
                             });
                         },
+                        // Escape is handled window-wide (see components/escape.rs)
                         onkeydown: move |evt| {
-                            match evt.key() {
-                                Key::Enter => {
-                                    let direction = if evt.modifiers().shift() { "prev" } else { "next" };
-                                    navigate(direction);
-                                }
-                                Key::Escape => state.toggle_search(),
-                                _ => {}
+                            if evt.key() == Key::Enter {
+                                let direction = if evt.modifiers().shift() { "prev" } else { "next" };
+                                navigate(direction);
                             }
                         },
                     }
@@ -19,6 +19,7 @@ pub fn SidebarContextMenu(
     kind: SidebarItemKind,
     on_close: EventHandler<()>,
     on_open: EventHandler<()>,
+    on_open_to_side: EventHandler<()>,
     on_open_in_new_window: EventHandler<()>,
     on_move_to_window: EventHandler<WindowId>,
     on_toggle_bookmark: EventHandler<()>,
@@ -64,6 +65,13 @@ pub fn SidebarContextMenu(
                 on_click: move |_| on_open.call(()),
             }
 
+            if is_file {
+                ContextMenuItem {
+                    label: "Open to the Side",
+                    on_click: move |_| on_open_to_side.call(()),
+                }
+            }
+
             ContextMenuItem {
                 label: "Open in New Window",
                 on_click: move |_| on_open_in_new_window.call(()),
@@ -1,37 +1,129 @@
 use dioxus::desktop::window;
 use dioxus::prelude::*;
-use std::cmp::Ordering;
+use parking_lot::Mutex;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
 use super::context_menu::{SidebarContextMenu, SidebarItemKind};
+use super::file_search::{FileSearchInput, FileSearchResults};
 use super::quick_access::QuickAccess;
 use crate::components::bookmark_button::BookmarkButton;
 use crate::components::icon::{Icon, IconName};
+use crate::config::{PartialsConfig, CONFIG};
+use crate::directory_views::ExplorerSort;
+use crate::partials::is_partial_file;
 use crate::state::AppState;
-use crate::utils::{file::is_markdown_file, file_operations};
+use crate::utils::file::{self, is_markdown_file, is_rst_file, ScanOptions, MAX_SCANNED_ENTRIES};
+use crate::utils::file_operations;
+use crate::utils::fuzzy;
+use crate::utils::gitignore::Gitignore;
 use crate::watcher::FILE_WATCHER;
 
-// Sort entries: directories first, then files, both alphabetically
-fn sort_entries(items: &mut [PathBuf]) {
-    items.sort_by(|a, b| {
-        let a_is_dir = a.is_dir();
-        let b_is_dir = b.is_dir();
+// Sort entries: directories first, then files, both by name or newest first
+fn sort_entries(items: &mut [PathBuf], sort: ExplorerSort) {
+    items.sort_by_cached_key(|path| {
+        let modified = match sort {
+            ExplorerSort::Name => None,
+            ExplorerSort::Modified => fs::metadata(path).and_then(|m| m.modified()).ok(),
+        };
+        (
+            !path.is_dir(),
+            Reverse(modified),
+            path.file_name().map(|n| n.to_owned()),
+        )
+    });
+}
+
+/// Last listing of each directory, shown while a fresh read is in progress
+static LISTING_CACHE: LazyLock<Mutex<HashMap<PathBuf, Vec<PathBuf>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// What the explorer lists, captured before reading a directory off the UI thread
+#[derive(Debug, Clone)]
+pub struct ListingOptions {
+    sort: ExplorerSort,
+    show_all_files: bool,
+    show_rst: bool,
+    partials: PartialsConfig,
+    ignore_patterns: Vec<String>,
+    /// Whether `.gitignore` rules hide entries
+    respect_gitignore: bool,
+    /// Sidebar root, where `.gitignore` files are read from outside a repository
+    root: Option<PathBuf>,
+}
 
-        match (a_is_dir, b_is_dir) {
-            (true, false) => Ordering::Less,
-            (false, true) => Ordering::Greater,
-            _ => a.file_name().cmp(&b.file_name()),
+impl ListingOptions {
+    pub fn current(state: &AppState) -> Self {
+        let sidebar = state.sidebar.read();
+        let config = CONFIG.read();
+        Self {
+            sort: sidebar.sort,
+            show_all_files: sidebar.show_all_files,
+            show_rst: config.rst.enabled,
+            partials: config.partials.clone(),
+            ignore_patterns: config.sidebar.ignore_patterns.clone(),
+            respect_gitignore: config.sidebar.respect_gitignore && !sidebar.show_ignored_files,
+            root: sidebar.root_directory.clone(),
         }
-    });
+    }
+
+    /// Walk rules for searching, skipping what the tree hides
+    fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            include_hidden: true,
+            ignore_patterns: self.ignore_patterns.clone(),
+            respect_gitignore: self.respect_gitignore,
+        }
+    }
+
+    fn is_listed(&self, path: &Path) -> bool {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if self
+            .ignore_patterns
+            .iter()
+            .any(|pattern| file::matches_name_pattern(name, pattern))
+        {
+            return false;
+        }
+        if path.is_dir() {
+            return true;
+        }
+
+        let is_markdown = is_markdown_file(path);
+        // Hide include-only partials if configured
+        if is_markdown && self.partials.hide_in_explorer && is_partial_file(path, &self.partials) {
+            return false;
+        }
+        // reStructuredText files are listed like markdown when they can be rendered
+        self.show_all_files || is_markdown || (self.show_rst && is_rst_file(path))
+    }
 }
 
-// Read and sort directory entries
-fn read_sorted_entries(path: &PathBuf) -> Vec<PathBuf> {
+// Read, filter and sort directory entries (blocking; call off the UI thread)
+fn read_sorted_entries(path: &Path, options: &ListingOptions) -> Vec<PathBuf> {
+    let gitignore = options
+        .respect_gitignore
+        .then(|| Gitignore::for_directory(path, options.root.as_deref()));
     match fs::read_dir(path) {
         Ok(entries) => {
-            let mut items: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
-            sort_entries(&mut items);
+            let mut items: Vec<_> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|path| {
+                    !gitignore
+                        .as_ref()
+                        .is_some_and(|gitignore| gitignore.is_ignored(path, path.is_dir()))
+                })
+                .filter(|path| options.is_listed(path))
+                .collect();
+            sort_entries(&mut items, options.sort);
             items
         }
         Err(err) => {
@@ -41,6 +133,125 @@ fn read_sorted_entries(path: &PathBuf) -> Vec<PathBuf> {
     }
 }
 
+/// The document listed before or after `file` in its directory, in the
+/// explorer's order (blocking; call off the UI thread)
+///
+/// Only documents Arto renders count; with `wrap` the first file follows the
+/// last one.
+pub fn sibling_file(
+    file: &Path,
+    options: &ListingOptions,
+    forward: bool,
+    wrap: bool,
+) -> Option<PathBuf> {
+    let documents: Vec<PathBuf> = read_sorted_entries(file.parent()?, options)
+        .into_iter()
+        .filter(|path| is_markdown_file(path) || (options.show_rst && is_rst_file(path)))
+        .collect();
+    adjacent_entry(&documents, file, forward, wrap).cloned()
+}
+
+/// A file whose path matches the search query
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileMatch {
+    pub path: PathBuf,
+    /// Path relative to the searched directory
+    pub relative: String,
+    pub score: i64,
+}
+
+impl FileMatch {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Directory of the file relative to the searched directory ("" at the top)
+    pub fn directory(&self) -> String {
+        Path::new(&self.relative)
+            .parent()
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Files matching a search query, best first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub matches: Vec<FileMatch>,
+    /// The walk stopped early, so some files weren't searched
+    pub partial: bool,
+}
+
+/// Files under `root` whose relative path fuzzy-matches `query`, best first
+/// (blocking; call off the UI thread)
+///
+/// Walks the tree with the explorer's listing rules, so ignored patterns,
+/// `.gitignore` and the markdown-only filter apply. Ties go to the shorter
+/// path.
+pub fn search_files(
+    root: &Path,
+    query: &str,
+    options: &ListingOptions,
+    limit: usize,
+) -> SearchResults {
+    let mut matches = Vec::new();
+    let partial = file::walk_files(root, &options.scan_options(), MAX_SCANNED_ENTRIES, |path| {
+        if !options.is_listed(&path) {
+            return ControlFlow::Continue(());
+        }
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .into_owned();
+        if let Some(score) = fuzzy::fuzzy_score(query, &relative) {
+            matches.push(FileMatch {
+                path,
+                relative,
+                score,
+            });
+        }
+        ControlFlow::Continue(())
+    });
+
+    matches.sort_by(|a, b| {
+        (Reverse(a.score), a.relative.len(), &a.relative).cmp(&(
+            Reverse(b.score),
+            b.relative.len(),
+            &b.relative,
+        ))
+    });
+    matches.truncate(limit);
+    SearchResults { matches, partial }
+}
+
+/// The entry before or after `current` in `entries`
+fn adjacent_entry<'a>(
+    entries: &'a [PathBuf],
+    current: &Path,
+    forward: bool,
+    wrap: bool,
+) -> Option<&'a PathBuf> {
+    let index = entries.iter().position(|entry| entry == current)?;
+    let len = entries.len();
+    let next = match (forward, index) {
+        (true, index) if index + 1 < len => index + 1,
+        (false, index) if index > 0 => index - 1,
+        _ if wrap && len > 1 => {
+            if forward {
+                0
+            } else {
+                len - 1
+            }
+        }
+        _ => return None,
+    };
+    entries.get(next)
+}
+
 #[component]
 pub fn FileExplorer() -> Element {
     let state = use_context::<AppState>();
@@ -52,6 +263,10 @@ pub fn FileExplorer() -> Element {
     // Watch directory for file system changes
     use_directory_watcher(root_directory.clone(), refresh_counter);
 
+    // Fuzzy filename search; results replace the tree while there is a query
+    let search_query = use_signal(String::new);
+    let query = search_query.read().trim().to_string();
+
     rsx! {
         div {
             class: "left-sidebar-explorer",
@@ -59,7 +274,12 @@ pub fn FileExplorer() -> Element {
 
             if let Some(root) = root_directory {
                 DirectoryNavigation { current_dir: root.clone(), refresh_counter }
-                DirectoryTree { path: root, refresh_counter }
+                FileSearchInput { query: search_query }
+                if query.is_empty() {
+                    DirectoryTree { path: root, refresh_counter }
+                } else {
+                    FileSearchResults { root, query }
+                }
             } else {
                 div {
                     class: "left-sidebar-explorer-empty",
@@ -78,9 +298,12 @@ fn DirectoryNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -
     let mut state = use_context::<AppState>();
     let sidebar = state.sidebar.read();
     let show_all_files = sidebar.show_all_files;
+    let show_ignored_files = sidebar.show_ignored_files;
+    let sort = sidebar.sort;
     let can_go_back = sidebar.can_go_back();
     let can_go_forward = sidebar.can_go_forward();
     drop(sidebar);
+    let respect_gitignore = CONFIG.read().sidebar.respect_gitignore;
 
     let has_parent = current_dir.parent().is_some();
 
@@ -280,22 +503,60 @@ fn DirectoryNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -
                 }
             }
 
-            // Toolbar buttons container (visibility toggle only)
+            // Toolbar buttons container (sort and visibility toggles)
             div {
                 class: "left-sidebar-header-toolbar",
 
+                // Sort order toggle button
+                button {
+                    class: "left-sidebar-header-toolbar-button",
+                    title: match sort {
+                        ExplorerSort::Name => "Sorted by name (click to sort by last modified)",
+                        ExplorerSort::Modified => "Sorted by last modified (click to sort by name)",
+                    },
+                    onclick: move |_| {
+                        state.sidebar.write().set_sort(sort.toggled());
+                    },
+                    Icon {
+                        name: match sort {
+                            ExplorerSort::Name => IconName::SortByName,
+                            ExplorerSort::Modified => IconName::Clock,
+                        },
+                        size: 20,
+                    }
+                }
+
                 // File visibility toggle button
                 button {
                     class: "left-sidebar-header-toolbar-button",
-                    title: if show_all_files { "Hide non-markdown files" } else { "Show all files" },
+                    title: if show_all_files { "Hide non-markdown files (⌘⇧.)" } else { "Show all files (⌘⇧.)" },
                     onclick: move |_| {
-                        state.sidebar.write().show_all_files = !show_all_files;
+                        state.sidebar.write().set_show_all_files(!show_all_files);
                     },
                     Icon {
                         name: if show_all_files { IconName::Eye } else { IconName::EyeOff },
                         size: 20,
                     }
                 }
+
+                // Ignored files toggle button (sidebar.respectGitignore)
+                if respect_gitignore {
+                    button {
+                        class: if show_ignored_files {
+                            "left-sidebar-header-toolbar-button active"
+                        } else {
+                            "left-sidebar-header-toolbar-button"
+                        },
+                        title: if show_ignored_files { "Hide files ignored by .gitignore" } else { "Show files ignored by .gitignore" },
+                        onclick: move |_| {
+                            state.sidebar.write().show_ignored_files = !show_ignored_files;
+                        },
+                        Icon {
+                            name: IconName::BrandGit,
+                            size: 20,
+                        }
+                    }
+                }
             }
         }
     }
@@ -303,14 +564,66 @@ fn DirectoryNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -
 
 #[component]
 fn DirectoryTree(path: PathBuf, refresh_counter: Signal<u32>) -> Element {
-    let entries = read_sorted_entries(&path);
-
     rsx! {
         div {
             class: "left-sidebar-tree",
             key: "{refresh_counter}",
-            for entry in entries {
-                FileTreeNode { path: entry, depth: 0, refresh_counter }
+            DirectoryEntries { path, depth: 0, refresh_counter }
+        }
+    }
+}
+
+/// Entries of one directory, listed a page at a time
+///
+/// The directory is read on a blocking thread so large folders never stall
+/// rendering; the previous listing of the same directory is shown meanwhile.
+/// Only the first `sidebar.pageSize` entries are rendered, followed by an
+/// "N more…" row that reveals the next page.
+#[component]
+fn DirectoryEntries(path: PathBuf, depth: usize, refresh_counter: Signal<u32>) -> Element {
+    let state = use_context::<AppState>();
+    let page_size = CONFIG.read().sidebar.page_size;
+    let mut visible = use_signal(|| page_size);
+
+    let entries = use_resource(use_reactive!(|path| {
+        let options = ListingOptions::current(&state);
+        async move {
+            let entries = {
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || read_sorted_entries(&path, &options))
+                    .await
+                    .unwrap_or_default()
+            };
+            LISTING_CACHE.lock().insert(path, entries.clone());
+            entries
+        }
+    }));
+
+    let entries = entries
+        .read()
+        .clone()
+        .or_else(|| LISTING_CACHE.lock().get(&path).cloned())
+        .unwrap_or_default();
+    let shown = if page_size == 0 {
+        entries.len()
+    } else {
+        visible().min(entries.len())
+    };
+    let remaining = entries.len() - shown;
+    // Line up with the icons of the listed entries
+    let indent_style = format!("padding-left: {}px", depth * 20 + 20);
+
+    rsx! {
+        for entry in entries.into_iter().take(shown) {
+            FileTreeNode { path: entry, depth, refresh_counter }
+        }
+        if remaining > 0 {
+            div {
+                class: "left-sidebar-tree-more",
+                style: "{indent_style}",
+                title: "Show the next {remaining.min(page_size)} entries",
+                onclick: move |_| visible += page_size,
+                "{remaining} more…"
             }
         }
     }
@@ -321,8 +634,17 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
     let mut state = use_context::<AppState>();
 
     let is_dir = path.is_dir();
-    let is_expanded = state.sidebar.read().expanded_dirs.contains(&path);
-    let show_all_files = state.sidebar.read().show_all_files;
+    let is_symlink = file::is_symlink(&path);
+    // Never descend into a link that points back at an ancestor
+    let is_symlink_loop = is_dir && file::is_symlink_loop(&path);
+    let is_expanded = !is_symlink_loop && state.sidebar.read().expanded_dirs.contains(&path);
+    let symlink_title = if is_symlink_loop {
+        "Symlink loop (points to a parent directory)".to_string()
+    } else {
+        fs::read_link(&path)
+            .map(|target| format!("Symlink to {}", target.display()))
+            .unwrap_or_default()
+    };
 
     let name = path
         .file_name()
@@ -330,12 +652,9 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
         .unwrap_or("Unknown")
         .to_string();
 
-    let is_markdown = !is_dir && is_markdown_file(&path);
-
-    // Hide non-markdown files if show_all_files is disabled
-    if !show_all_files && !is_dir && !is_markdown {
-        return rsx! {};
-    }
+    // Files that cannot be rendered are only listed when showing all files
+    let is_document =
+        !is_dir && (is_markdown_file(&path) || (CONFIG.read().rst.enabled && is_rst_file(&path)));
 
     let current_tab = state.current_tab();
     let is_active = current_tab
@@ -393,24 +712,36 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
         }
     };
 
+    // Handler for "Open to the Side"
+    let handle_open_to_side = {
+        let path = path.clone();
+        move |_| {
+            state.open_file_to_side(&path);
+            show_context_menu.set(false);
+        }
+    };
+
     // Handler for "Open in New Window"
     let handle_open_in_new_window = {
         let path = path.clone();
         move |_| {
             let path = path.clone();
             spawn(async move {
-                let (tab, directory) = if is_dir {
-                    (crate::state::Tab::default(), Some(path))
+                let params = crate::window::main::CreateMainWindowConfigParams::default();
+                let (tab, directory, params) = if is_dir {
+                    let params = params.with_directory_geometry(&path);
+                    (crate::state::Tab::default(), Some(path), params)
                 } else {
                     (
                         crate::state::Tab::new(&path),
                         path.parent().map(|p| p.to_path_buf()),
+                        params,
                     )
                 };
 
                 let params = crate::window::main::CreateMainWindowConfigParams {
                     directory,
-                    ..Default::default()
+                    ..params
                 };
                 crate::window::main::create_main_window(tab, params).await;
             });
@@ -472,7 +803,8 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
     let handle_toggle_bookmark = {
         let path = path.clone();
         move |_| {
-            crate::bookmarks::toggle_bookmark(&path);
+            let root = state.sidebar.read().root_directory.clone();
+            crate::bookmarks::toggle_bookmark(&path, root.as_deref());
             show_context_menu.set(false);
         }
     };
@@ -513,7 +845,9 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
                             let path = path.clone();
                             move |evt| {
                                 evt.stop_propagation();
-                                state.toggle_directory_expansion(&path);
+                                if !is_symlink_loop {
+                                    state.toggle_directory_expansion(&path);
+                                }
                             }
                         },
                         Icon {
@@ -542,6 +876,14 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
                             class: "left-sidebar-tree-label",
                             "{name}"
                         }
+                        if is_symlink {
+                            span {
+                                class: "left-sidebar-tree-symlink-badge",
+                                class: if is_symlink_loop { "loop" },
+                                title: "{symlink_title}",
+                                Icon { name: IconName::Link, size: 12 }
+                            }
+                        }
                     }
                 } else {
                     // File: spacer + icon + label, click to open
@@ -562,9 +904,16 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
                         }
                         span {
                             class: "left-sidebar-tree-label",
-                            class: if !is_markdown { "disabled" },
+                            class: if !is_document { "disabled" },
                             "{name}"
                         }
+                        if is_symlink {
+                            span {
+                                class: "left-sidebar-tree-symlink-badge",
+                                title: "{symlink_title}",
+                                Icon { name: IconName::Link, size: 12 }
+                            }
+                        }
                     }
                 }
 
@@ -595,16 +944,9 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
 
             // Expanded directory children
             if is_dir && is_expanded {
-                {
-                    let children = read_sorted_entries(&path);
-                    rsx! {
-                        div {
-                            key: "{refresh_counter}",
-                            for child in children {
-                                FileTreeNode { path: child, depth: depth + 1, refresh_counter }
-                            }
-                        }
-                    }
+                div {
+                    key: "{refresh_counter}",
+                    DirectoryEntries { path: path.clone(), depth: depth + 1, refresh_counter }
                 }
             }
         }
@@ -617,6 +959,7 @@ fn FileTreeNode(path: PathBuf, depth: usize, mut refresh_counter: Signal<u32>) -
                 kind: if is_dir { SidebarItemKind::Directory } else { SidebarItemKind::File },
                 on_close: move |_| show_context_menu.set(false),
                 on_open: handle_open,
+                on_open_to_side: handle_open_to_side,
                 on_open_in_new_window: handle_open_in_new_window,
                 on_move_to_window: handle_open_in_window,
                 on_toggle_bookmark: handle_toggle_bookmark,
@@ -648,6 +991,9 @@ fn use_directory_watcher(directory: Option<PathBuf>, mut refresh_counter: Signal
             // Listen for changes and trigger refresh
             while watcher.recv().await.is_some() {
                 tracing::trace!(?dir, "Directory changed, triggering refresh");
+                crate::tags::invalidate_tag_cache(&dir);
+                crate::backlinks::invalidate_link_cache(&dir);
+                crate::partials::invalidate_partial_cache(&dir);
                 refresh_counter.set(refresh_counter() + 1);
             }
 
@@ -656,3 +1002,99 @@ fn use_directory_watcher(directory: Option<PathBuf>, mut refresh_counter: Signal
         });
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_sorted_entries_respects_gitignore() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join("node_modules")).unwrap();
+        fs::write(dir.join(".gitignore"), "node_modules/\ndraft.md\n").unwrap();
+        fs::write(dir.join("README.md"), "").unwrap();
+        fs::write(dir.join("draft.md"), "").unwrap();
+
+        let mut options = ListingOptions {
+            sort: ExplorerSort::Name,
+            show_all_files: false,
+            show_rst: false,
+            partials: PartialsConfig::default(),
+            ignore_patterns: Vec::new(),
+            respect_gitignore: true,
+            root: Some(dir.to_path_buf()),
+        };
+        let names = |options: &ListingOptions| -> Vec<String> {
+            read_sorted_entries(dir, options)
+                .iter()
+                .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(names(&options), ["README.md"]);
+        // Showing ignored files lists them again, still filtered to markdown
+        options.respect_gitignore = false;
+        assert_eq!(
+            names(&options),
+            [".git", "node_modules", "README.md", "draft.md"]
+        );
+    }
+
+    #[test]
+    fn test_search_files_ranks_and_filters() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join("docs/guides")).unwrap();
+        fs::create_dir_all(dir.join("build")).unwrap();
+        fs::write(dir.join(".gitignore"), "build/\n").unwrap();
+        fs::write(dir.join("docs/guides/setup.md"), "").unwrap();
+        fs::write(dir.join("docs/reset-update.md"), "").unwrap();
+        fs::write(dir.join("docs/setup.txt"), "").unwrap();
+        fs::write(dir.join("build/setup.md"), "").unwrap();
+
+        let options = ListingOptions {
+            sort: ExplorerSort::Name,
+            show_all_files: false,
+            show_rst: false,
+            partials: PartialsConfig::default(),
+            ignore_patterns: Vec::new(),
+            respect_gitignore: true,
+            root: Some(dir.to_path_buf()),
+        };
+        let results = search_files(dir, "setup", &options, 50);
+        assert!(!results.partial);
+        let results: Vec<String> = results
+            .matches
+            .into_iter()
+            .map(|result| result.relative)
+            .collect();
+
+        // Ignored and non-markdown files are left out, like in the tree
+        assert_eq!(results, ["docs/guides/setup.md", "docs/reset-update.md"]);
+        assert_eq!(search_files(dir, "setup", &options, 1).matches.len(), 1);
+        assert!(search_files(dir, "xyz", &options, 50).matches.is_empty());
+    }
+
+    #[test]
+    fn test_adjacent_entry() {
+        let entries: Vec<PathBuf> = ["a.md", "b.md", "c.md"].iter().map(PathBuf::from).collect();
+        let adjacent = |current: &str, forward, wrap| {
+            adjacent_entry(&entries, Path::new(current), forward, wrap)
+                .map(|entry| entry.to_str().unwrap())
+        };
+
+        assert_eq!(adjacent("a.md", true, false), Some("b.md"));
+        assert_eq!(adjacent("b.md", false, false), Some("a.md"));
+        assert_eq!(adjacent("c.md", true, false), None);
+        assert_eq!(adjacent("a.md", false, false), None);
+        assert_eq!(adjacent("c.md", true, true), Some("a.md"));
+        assert_eq!(adjacent("a.md", false, true), Some("c.md"));
+        assert_eq!(adjacent("missing.md", true, true), None);
+
+        let single = [PathBuf::from("a.md")];
+        assert_eq!(adjacent_entry(&single, Path::new("a.md"), true, true), None);
+    }
+}
@@ -0,0 +1,123 @@
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use super::file_explorer::{search_files, ListingOptions};
+use crate::components::icon::{Icon, IconName};
+use crate::state::AppState;
+
+/// Most results listed for a query
+const MAX_RESULTS: usize = 50;
+/// Wait for typing to pause before walking the tree
+const SEARCH_DEBOUNCE_MS: u64 = 150;
+
+/// Search box at the top of the file explorer
+///
+/// The input is left uncontrolled to preserve IME state: the query only seeds
+/// it when the explorer is re-rendered, and clearing resets it through the DOM.
+#[component]
+pub fn FileSearchInput(mut query: Signal<String>) -> Element {
+    let current = query.read().clone();
+    let has_query = !current.is_empty();
+
+    rsx! {
+        div {
+            class: "left-sidebar-search",
+            Icon { name: IconName::Search, size: 14, class: "left-sidebar-search-icon" }
+            input {
+                r#type: "text",
+                class: "left-sidebar-search-input",
+                placeholder: "Search files…",
+                initial_value: "{current}",
+                autocorrect: "off",
+                autocapitalize: "off",
+                spellcheck: "false",
+                oninput: move |evt| query.set(evt.value()),
+            }
+            if has_query {
+                button {
+                    class: "left-sidebar-search-clear",
+                    title: "Clear",
+                    onclick: move |_| {
+                        query.set(String::new());
+                        spawn(async move {
+                            let _ = document::eval(
+                                "document.querySelector('.left-sidebar-search-input').value = ''",
+                            )
+                            .await;
+                        });
+                    },
+                    Icon { name: IconName::Close, size: 12 }
+                }
+            }
+        }
+    }
+}
+
+/// Ranked files under `root` matching `query`, listed in place of the tree
+///
+/// The walk runs on a blocking thread once typing pauses; a newer query
+/// cancels the pending one.
+#[component]
+pub fn FileSearchResults(root: PathBuf, query: String) -> Element {
+    let mut state = use_context::<AppState>();
+
+    let results = use_resource(use_reactive!(|root, query| {
+        let options = ListingOptions::current(&state);
+        async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(SEARCH_DEBOUNCE_MS)).await;
+            tokio::task::spawn_blocking(move || search_files(&root, &query, &options, MAX_RESULTS))
+                .await
+                .unwrap_or_default()
+        }
+    }));
+
+    let current_file = state
+        .current_tab()
+        .and_then(|tab| tab.file().map(Path::to_path_buf));
+
+    let Some(results) = results.read().clone() else {
+        return rsx! {
+            div { class: "left-sidebar-search-status", "Searching…" }
+        };
+    };
+
+    rsx! {
+        div {
+            class: "left-sidebar-search-results",
+            if results.matches.is_empty() {
+                div { class: "left-sidebar-search-status", "No matching files" }
+            }
+            for result in results.matches {
+                div {
+                    key: "{result.relative}",
+                    class: "left-sidebar-search-result",
+                    class: if current_file.as_ref() == Some(&result.path) { "active" },
+                    title: "{result.path.display()}",
+                    onclick: {
+                        let path = result.path.clone();
+                        move |_| state.open_file(&path)
+                    },
+                    Icon {
+                        name: IconName::File,
+                        size: 16,
+                        class: "left-sidebar-tree-icon",
+                    }
+                    span {
+                        class: "left-sidebar-search-result-name",
+                        "{result.name()}"
+                    }
+                    span {
+                        class: "left-sidebar-search-result-dir",
+                        "{result.directory()}"
+                    }
+                }
+            }
+            if results.partial {
+                div {
+                    class: "left-sidebar-search-status",
+                    "Partial results: the directory is too large to search fully"
+                }
+            }
+        }
+    }
+}
@@ -102,7 +102,12 @@ pub fn QuickAccess() -> Element {
                             if is_directory {
                                 state.set_root_directory(&bookmark.path);
                             } else {
+                                // Open the file first so the root change can't
+                                // auto-open another file into an empty tab
                                 state.open_file(&bookmark.path);
+                                if let Some(root) = bookmark.jump_root() {
+                                    state.set_root_directory(root);
+                                }
                             }
                         },
                         on_drag_start: move |idx| {
@@ -4,4 +4,4 @@ mod floating_tab;
 mod tab_bar;
 mod tab_item;
 
-pub use tab_bar::{get_tab_bar_bounds, get_tab_count, TabBar};
+pub use tab_bar::{cancel_active_drag, get_tab_bar_bounds, get_tab_count, TabBar};
@@ -9,7 +9,7 @@ use super::floating_tab::FloatingTab;
 use super::tab_item::TabItem;
 use crate::components::icon::{Icon, IconName};
 use crate::drag::{self, GlobalActiveDrag};
-use crate::events::ACTIVE_DRAG_UPDATE;
+use crate::events::{ActiveDragUpdate, ACTIVE_DRAG_UPDATE};
 use crate::state::AppState;
 use crate::window::Offset;
 
@@ -80,17 +80,13 @@ fn unregister_tab_count(window_id: WindowId) {
     TAB_COUNTS.write().remove(&window_id);
 }
 
-/// Cancel active drag and restore tab to source window on Escape key
+/// Cancel active drag and restore tab to source window (Escape key)
 ///
 /// Operation order is important to ensure the tab is never lost:
 /// 1. Restore tab to source window (ensures tab exists before any cleanup)
 /// 2. Close preview window (cleanup visual state)
-/// 3. Clear drag state (cleanup global state)
-fn cancel_active_drag_on_escape(
-    state: &mut AppState,
-    current_window_id: WindowId,
-    active_drag_signal: &mut Signal<Option<GlobalActiveDrag>>,
-) {
+/// 3. Clear drag state (cleanup global state) and notify all tab bars
+pub fn cancel_active_drag(state: &mut AppState, current_window_id: WindowId) {
     use crate::drag::DetachState;
 
     // Step 1: Restore tab to source window first (ensures tab is never lost)
@@ -113,7 +109,7 @@ fn cancel_active_drag_on_escape(
     // Step 3: Clear drag state
     drag::end_active_drag();
     drag::end_drag();
-    active_drag_signal.set(None);
+    let _ = ACTIVE_DRAG_UPDATE.send(ActiveDragUpdate);
 }
 
 /// Start global active drag after threshold is exceeded
@@ -231,21 +227,13 @@ pub fn TabBar() -> Element {
     // Store tab bar element reference for bounds calculation on drag start
     let mut tab_bar_element: Signal<Option<std::rc::Rc<MountedData>>> = use_signal(|| None);
 
-    // Track escape key to cancel drag
+    // Track escape key to cancel a pending drag (an active drag is cancelled
+    // by the window-wide Escape handler, see components/escape.rs)
     let handle_keydown = move |evt: Event<KeyboardData>| {
-        if evt.key() == Key::Escape {
-            // Cancel local pending state
-            if matches!(*local_drag_state.read(), LocalDragState::Pending(_)) {
-                local_drag_state.set(LocalDragState::Idle);
-            }
-            // Cancel global active drag and restore tab
-            if drag::is_active_drag() {
-                cancel_active_drag_on_escape(
-                    &mut state,
-                    current_window_id,
-                    &mut active_drag_signal,
-                );
-            }
+        if evt.key() == Key::Escape
+            && matches!(*local_drag_state.read(), LocalDragState::Pending(_))
+        {
+            local_drag_state.set(LocalDragState::Idle);
         }
     };
 
@@ -0,0 +1,24 @@
+use dioxus::prelude::*;
+
+use crate::state::AppState;
+
+/// Transient notification displayed at the bottom of the window.
+///
+/// Shown via `AppState::show_toast`, which also clears it after a short delay.
+#[component]
+pub fn Toast() -> Element {
+    let state = use_context::<AppState>();
+    let toast = state.toast.read().clone();
+
+    rsx! {
+        if let Some(toast) = toast {
+            div {
+                // Re-key on each toast so the fade-in animation restarts
+                key: "{toast.id}",
+                class: "toast",
+                role: "status",
+                "{toast.message}"
+            }
+        }
+    }
+}
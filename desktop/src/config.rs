@@ -4,4 +4,4 @@ pub use app_config::*;
 
 // Configuration persistence (load/save methods + CONFIG global)
 mod persistence;
-pub use persistence::CONFIG;
+pub use persistence::{CONFIG, CONFIG_CHANGED};
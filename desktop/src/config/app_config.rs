@@ -1,26 +1,132 @@
 use serde::{Deserialize, Serialize};
 
+mod activation_config;
+mod alerts_config;
+mod anchors_config;
+mod annotations_config;
+mod auto_refresh_config;
 mod behavior;
+mod binary_files_config;
+mod cache_config;
+mod captions_config;
+mod clipboard_config;
+mod code_copy_config;
+mod code_lines_config;
+mod code_ruler_config;
+mod code_wrap_config;
+mod dark_images_config;
 mod directory_config;
+mod document_title_config;
+mod drop_config;
+mod editor_config;
+mod emoji_config;
+mod escape_config;
+mod export_config;
+mod fonts_config;
+mod frontmatter_config;
+mod geojson_config;
+mod header_config;
+mod heading_scale_config;
+mod home_document_config;
+mod image_cache_config;
+mod images_config;
+mod link_definitions_config;
+mod link_preview_config;
+mod lint_config;
+mod network_config;
+mod open_to_side_config;
+mod ordered_list_config;
+mod partial_writes_config;
+mod partials_config;
+mod quit_config;
+mod reader_profiles_config;
+mod reading_progress_config;
+mod reload_config;
 mod right_sidebar_config;
+mod rst_config;
+mod search_config;
+mod sibling_files_config;
 mod sidebar_config;
+mod snippets_config;
+mod spoilers_config;
+mod syntax_highlight_config;
+mod tables_config;
+mod task_lists_config;
 mod theme_config;
+mod typewriter_config;
 mod window_dimension;
 mod window_position_config;
 mod window_size_config;
 mod zoom_config;
 
-pub use behavior::{NewWindowBehavior, StartupBehavior};
+pub use activation_config::ActivationConfig;
+pub use alerts_config::{AlertTitleCase, AlertTypeConfig, AlertsConfig};
+pub use anchors_config::AnchorsConfig;
+pub use annotations_config::AnnotationsConfig;
+pub use auto_refresh_config::AutoRefreshConfig;
+pub use behavior::{
+    HomeDocumentBehavior, NewWindowBehavior, OpenBundleBehavior, OpenDirectoryBehavior,
+    ReopenBehavior, StartupBehavior,
+};
+pub use binary_files_config::BinaryFilesConfig;
+pub use cache_config::CacheConfig;
+pub use captions_config::CaptionsConfig;
+pub use clipboard_config::ClipboardConfig;
+pub use code_copy_config::CodeCopyConfig;
+pub use code_lines_config::CodeLinesConfig;
+pub use code_ruler_config::CodeRulerConfig;
+pub use code_wrap_config::{CodeWrapConfig, CodeWrapMode};
+pub use dark_images_config::{DarkImageMode, DarkImagesConfig};
 pub use directory_config::DirectoryConfig;
+pub use document_title_config::{DocumentTitleConfig, LeadingHeadingStyle};
+pub use drop_config::{DropConfig, DropOpenBehavior};
+pub use editor_config::EditorConfig;
+pub use emoji_config::EmojiConfig;
+pub use escape_config::EscapeConfig;
+pub use export_config::{ExportConfig, ExportMathMode, PdfPageSize};
+pub use fonts_config::FontsConfig;
+pub use frontmatter_config::{FrontmatterConfig, FrontmatterOpenMode};
+pub use geojson_config::GeoJsonConfig;
+pub use header_config::{CopyPathFormat, HeaderConfig, ModifiedTimeFormat};
+pub use heading_scale_config::{HeadingLevelStyle, HeadingScaleConfig};
+pub use home_document_config::HomeDocumentConfig;
+pub use image_cache_config::ImageCacheConfig;
+pub use images_config::{ImagesConfig, MissingImageMode, WideImageMode};
+pub use link_definitions_config::LinkDefinitionsConfig;
+pub use link_preview_config::LinkPreviewConfig;
+pub use lint_config::LintConfig;
+pub use network_config::NetworkConfig;
+pub use open_to_side_config::{OpenToSideConfig, OpenToSideModifier};
+pub use ordered_list_config::{OrderedListConfig, OrderedListNumbering};
+pub use partial_writes_config::{PartialWriteMode, PartialWritesConfig};
+pub use partials_config::PartialsConfig;
+pub use quit_config::QuitConfig;
+pub use reader_profiles_config::{ReaderProfile, ReaderProfilesConfig};
+pub use reading_progress_config::{ReadingProgressConfig, ReadingProgressPosition};
+pub use reload_config::{ReloadConfig, ReloadScrollMode};
 pub use right_sidebar_config::{RightSidebarConfig, DEFAULT_RIGHT_SIDEBAR_WIDTH};
+pub use rst_config::RstConfig;
+pub use search_config::SearchConfig;
+pub use sibling_files_config::SiblingFilesConfig;
 pub use sidebar_config::SidebarConfig;
+pub use snippets_config::SnippetsConfig;
+pub use spoilers_config::SpoilersConfig;
+pub use syntax_highlight_config::SyntaxHighlightConfig;
+pub use tables_config::TablesConfig;
+pub use task_lists_config::{TaskListsConfig, TaskProgressMode};
 pub use theme_config::ThemeConfig;
+pub use typewriter_config::TypewriterConfig;
 pub use window_dimension::{WindowDimension, WindowDimensionUnit};
 pub use window_position_config::{
     WindowPosition, WindowPositionConfig, WindowPositionMode, WindowPositionOffset,
 };
 pub use window_size_config::{WindowSize, WindowSizeConfig};
-pub use zoom_config::ZoomConfig;
+pub use zoom_config::{ZoomConfig, ZoomScrollAnchor};
+
+/// Serde default for settings that are on unless configured otherwise
+pub(super) fn default_true() -> bool {
+    true
+}
 
 /// Global application configuration
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -33,6 +139,55 @@ pub struct Config {
     pub window_position: WindowPositionConfig,
     pub window_size: WindowSizeConfig,
     pub zoom: ZoomConfig,
+    pub frontmatter: FrontmatterConfig,
+    pub network: NetworkConfig,
+    pub auto_refresh: AutoRefreshConfig,
+    pub clipboard: ClipboardConfig,
+    pub snippets: SnippetsConfig,
+    pub document_title: DocumentTitleConfig,
+    pub partials: PartialsConfig,
+    pub lint: LintConfig,
+    pub reading_progress: ReadingProgressConfig,
+    pub anchors: AnchorsConfig,
+    pub alerts: AlertsConfig,
+    pub typewriter: TypewriterConfig,
+    pub code_copy: CodeCopyConfig,
+    pub heading_scale: HeadingScaleConfig,
+    pub ordered_lists: OrderedListConfig,
+    pub reload: ReloadConfig,
+    pub geojson: GeoJsonConfig,
+    pub dark_images: DarkImagesConfig,
+    pub images: ImagesConfig,
+    pub activation: ActivationConfig,
+    pub link_preview: LinkPreviewConfig,
+    pub header: HeaderConfig,
+    pub search: SearchConfig,
+    pub partial_writes: PartialWritesConfig,
+    pub code_ruler: CodeRulerConfig,
+    pub code_wrap: CodeWrapConfig,
+    pub rst: RstConfig,
+    pub escape: EscapeConfig,
+    pub open_to_side: OpenToSideConfig,
+    pub image_cache: ImageCacheConfig,
+    pub reader_profiles: ReaderProfilesConfig,
+    pub drop: DropConfig,
+    pub captions: CaptionsConfig,
+    pub export: ExportConfig,
+    pub binary_files: BinaryFilesConfig,
+    pub fonts: FontsConfig,
+    pub editor: EditorConfig,
+    pub annotations: AnnotationsConfig,
+    pub spoilers: SpoilersConfig,
+    pub home_document: HomeDocumentConfig,
+    pub emoji: EmojiConfig,
+    pub syntax_highlight: SyntaxHighlightConfig,
+    pub tables: TablesConfig,
+    pub sibling_files: SiblingFilesConfig,
+    pub cache: CacheConfig,
+    pub code_lines: CodeLinesConfig,
+    pub quit: QuitConfig,
+    pub task_lists: TaskListsConfig,
+    pub link_definitions: LinkDefinitionsConfig,
 }
 
 #[cfg(test)]
@@ -55,17 +210,33 @@ mod tests {
         assert_eq!(config.directory.default_directory, None);
         assert_eq!(config.directory.on_startup, StartupBehavior::Default);
         assert_eq!(config.directory.on_new_window, NewWindowBehavior::Default);
+        assert_eq!(
+            config.directory.on_open_directory,
+            OpenDirectoryBehavior::ChangeRoot
+        );
+        assert!(!config.directory.remember_window_geometry);
+        assert!(!config.directory.open_single_markdown_file);
+        assert_eq!(config.directory.on_open_bundle, OpenBundleBehavior::Reveal);
 
         // Sidebar defaults
         assert!(!config.sidebar.default_open); // Default is false
         assert_eq!(config.sidebar.default_width, 280.0);
         assert!(!config.sidebar.default_show_all_files);
+        assert!(config.sidebar.follow_symlinks);
+        assert!(!config.sidebar.accordion_mode);
+        assert!(!config.sidebar.bookmark_root);
+        assert!(!config.sidebar.remember_view_per_directory);
+        assert!(!config.sidebar.reset_history_on_unrelated_root);
+        assert_eq!(config.sidebar.page_size, 200);
+        assert!(config.sidebar.ignore_patterns.is_empty());
+        assert!(!config.sidebar.respect_gitignore);
         assert_eq!(config.sidebar.on_startup, StartupBehavior::Default);
         assert_eq!(config.sidebar.on_new_window, NewWindowBehavior::Default);
 
         // Right sidebar defaults
         assert!(!config.right_sidebar.default_open);
         assert_eq!(config.right_sidebar.default_width, 220.0);
+        assert!(config.right_sidebar.scroll_spy);
         assert_eq!(config.right_sidebar.on_startup, StartupBehavior::Default);
         assert_eq!(
             config.right_sidebar.on_new_window,
@@ -90,6 +261,167 @@ mod tests {
         assert_eq!(config.zoom.default_zoom_level, 1.0);
         assert_eq!(config.zoom.on_startup, StartupBehavior::Default);
         assert_eq!(config.zoom.on_new_window, NewWindowBehavior::Default);
+        assert_eq!(config.zoom.scroll_anchor, ZoomScrollAnchor::Center);
+
+        // Frontmatter defaults
+        assert_eq!(config.frontmatter.tags_key, "tags");
+        assert_eq!(config.frontmatter.related_key, "related");
+        assert_eq!(config.frontmatter.max_depth, 4);
+        assert_eq!(config.frontmatter.max_list_items, 50);
+        assert!(!config.frontmatter.document_css);
+        assert_eq!(config.frontmatter.open, FrontmatterOpenMode::Closed);
+        assert_eq!(config.frontmatter.collapse_above, 5);
+
+        // Network defaults
+        assert_eq!(config.network.request_timeout_secs, 30);
+        assert_eq!(config.network.max_concurrent_downloads, 4);
+        assert!(config.network.user_agent.starts_with("Arto/"));
+        assert_eq!(config.network.proxy, None);
+        assert!(config.network.headers.is_empty());
+
+        // Auto-refresh defaults
+        assert!(!config.auto_refresh.enabled);
+        assert_eq!(config.auto_refresh.interval_secs, 5);
+
+        // Clipboard defaults
+        assert!(!config.clipboard.detect_on_focus);
+
+        // Snippets defaults
+        assert!(!config.snippets.enabled);
+        assert!(config.snippets.definitions.is_empty());
+
+        // Document title defaults
+        assert_eq!(
+            config.document_title.leading_heading,
+            LeadingHeadingStyle::Heading
+        );
+
+        // Partials defaults
+        assert!(!config.partials.enabled);
+        assert_eq!(config.partials.file_prefix, "_");
+        assert_eq!(config.partials.frontmatter_key, "partial");
+        assert!(!config.partials.hide_in_explorer);
+
+        // Lint defaults
+        assert!(!config.lint.enabled);
+        assert!(config.lint.missing_alt_text);
+        assert!(config.lint.heading_increment);
+        assert!(config.lint.trailing_whitespace);
+        assert!(config.lint.bare_urls);
+        assert!(config.lint.duplicate_headings);
+
+        // Reading progress defaults
+        assert!(!config.reading_progress.enabled);
+        assert_eq!(
+            config.reading_progress.position,
+            ReadingProgressPosition::Top
+        );
+        assert!(config.reading_progress.heading_markers);
+        assert!(!config.reading_progress.dim_read_sections);
+
+        // Anchor defaults
+        assert!(!config.anchors.github_user_content_prefix);
+        assert_eq!(config.anchors.empty_slug_fallback, "section");
+        assert!(config.anchors.heading_links);
+
+        // Alert defaults
+        assert_eq!(config.alerts.title_case, AlertTitleCase::Upper);
+        assert!(config.alerts.labels.is_empty());
+        assert!(!config.alerts.inline_title);
+        assert!(config.alerts.types.is_empty());
+
+        // Typewriter scrolling defaults
+        assert!(!config.typewriter.enabled);
+        assert_eq!(config.typewriter.focal_point, 0.4);
+
+        // Code copy defaults
+        assert!(!config.code_copy.strip_prompts);
+
+        // Heading scale defaults (GitHub)
+        assert_eq!(config.heading_scale.h1.size, 2.0);
+        assert_eq!(config.heading_scale.h6.size, 0.85);
+        assert_eq!(config.heading_scale.h2.margin_top, 24.0);
+        assert_eq!(config.heading_scale.h2.margin_bottom, 16.0);
+
+        // Ordered list defaults
+        assert_eq!(config.ordered_lists.numbering, OrderedListNumbering::Flat);
+        assert_eq!(
+            config.ordered_lists.outline_styles,
+            vec!["decimal", "lower-alpha", "lower-roman"]
+        );
+
+        // Reload defaults
+        assert_eq!(config.reload.scroll, ReloadScrollMode::Offset);
+
+        // GeoJSON defaults
+        assert!(!config.geojson.enabled);
+
+        // Dark image defaults
+        assert_eq!(config.dark_images.mode, DarkImageMode::Off);
+        assert_eq!(config.dark_images.filter, "invert(0.88) hue-rotate(180deg)");
+        assert_eq!(config.images.missing, MissingImageMode::Strict);
+        assert_eq!(config.images.inline_max_size, 256 * 1024);
+
+        // Header defaults
+        assert_eq!(config.header.copy_path, CopyPathFormat::Absolute);
+        assert!(!config.header.reload_all_tabs);
+        assert_eq!(config.header.modified_time, ModifiedTimeFormat::Hidden);
+
+        // Search defaults
+        assert_eq!(config.search.context_lines, 0);
+        assert!(!config.search.flash);
+
+        // Partial write defaults
+        assert_eq!(config.partial_writes.mode, PartialWriteMode::Off);
+        assert_eq!(config.partial_writes.settle_ms, 300);
+        assert_eq!(config.partial_writes.max_wait_ms, 5000);
+
+        // Code ruler defaults
+        assert!(!config.code_ruler.enabled);
+        assert_eq!(config.code_ruler.column, 80);
+        assert!(!config.code_ruler.highlight_long_lines);
+        assert_eq!(config.code_wrap.mode, CodeWrapMode::Scroll);
+        assert_eq!(config.code_wrap.narrow_width, 720);
+        assert!(!config.rst.enabled);
+        assert!(!config.escape.close_tab);
+        assert_eq!(config.open_to_side.link_modifier, OpenToSideModifier::Alt);
+        assert!(config.image_cache.enabled);
+        assert_eq!(config.image_cache.max_size_mb, 200);
+        assert_eq!(config.image_cache.ttl_hours, 24);
+        assert_eq!(config.reader_profiles.presets.len(), 3);
+        assert_eq!(config.reader_profiles.default_profile, None);
+        assert_eq!(config.drop.open_files, DropOpenBehavior::Current);
+        assert!(!config.captions.enabled);
+        assert_eq!(config.captions.listing_label, "Listing");
+        assert_eq!(config.export.math, ExportMathMode::Source);
+        assert!(!config.export.embed_fonts);
+        assert_eq!(config.export.pdf_page_size, PdfPageSize::A4);
+        assert_eq!(config.export.pdf_margin, 15.0);
+        assert_eq!(config.binary_files.open_externally, vec!["pdf"]);
+        assert_eq!(config.fonts.body_font_file, None);
+        assert_eq!(config.editor.command, None);
+        assert!(config.annotations.save_draft_on_close);
+        assert!(config.annotations.warn_on_save_failure);
+        assert!(!config.spoilers.enabled);
+        assert_eq!(config.home_document.path, None);
+        assert_eq!(
+            config.home_document.on_startup,
+            HomeDocumentBehavior::InsteadOfWelcome
+        );
+        assert!(config.emoji.shortcodes);
+        assert!(config.syntax_highlight.disabled_languages.is_empty());
+        assert!(!config.syntax_highlight.native);
+        assert_eq!(config.tables.max_rows, None);
+        assert!(!config.sibling_files.wrap_around);
+        assert!(!config.sibling_files.prompt_at_end);
+        assert_eq!(config.cache.memory_budget_mb, 64);
+        assert!(!config.cache.show_metrics);
+        assert!(!config.code_lines.line_numbers);
+        assert!(!config.quit.confirm);
+        assert_eq!(config.quit.window_threshold, 1);
+        assert_eq!(config.quit.tab_threshold, 10);
+        assert_eq!(config.task_lists.progress, TaskProgressMode::Off);
+        assert!(!config.link_definitions.show);
 
         // Window position defaults
         assert_eq!(
@@ -127,11 +459,23 @@ mod tests {
                 default_directory: Some(PathBuf::from("/home/user")),
                 on_startup: StartupBehavior::Default,
                 on_new_window: NewWindowBehavior::Default,
+                on_open_directory: OpenDirectoryBehavior::FocusExisting,
+                remember_window_geometry: true,
+                open_single_markdown_file: true,
+                on_open_bundle: OpenBundleBehavior::Refuse,
             },
             sidebar: SidebarConfig {
                 default_open: false,
                 default_width: 320.0,
                 default_show_all_files: true,
+                follow_symlinks: false,
+                accordion_mode: true,
+                bookmark_root: true,
+                remember_view_per_directory: true,
+                reset_history_on_unrelated_root: true,
+                page_size: 50,
+                ignore_patterns: vec!["node_modules".to_string()],
+                respect_gitignore: true,
                 on_startup: StartupBehavior::LastClosed,
                 on_new_window: NewWindowBehavior::LastFocused,
             },
@@ -139,6 +483,7 @@ mod tests {
                 default_open: true,
                 default_width: 250.0,
                 default_tab: Default::default(),
+                scroll_spy: false,
                 on_startup: StartupBehavior::LastClosed,
                 on_new_window: NewWindowBehavior::LastFocused,
             },
@@ -176,7 +521,221 @@ mod tests {
                 default_zoom_level: 1.5,
                 on_startup: StartupBehavior::LastClosed,
                 on_new_window: NewWindowBehavior::LastFocused,
+                scroll_anchor: ZoomScrollAnchor::Top,
+            },
+            frontmatter: FrontmatterConfig {
+                tags_key: "keywords".to_string(),
+                related_key: "see-also".to_string(),
+                max_depth: 2,
+                max_list_items: 10,
+                document_css: true,
+                open: FrontmatterOpenMode::Auto,
+                collapse_above: 3,
+            },
+            network: NetworkConfig {
+                request_timeout_secs: 10,
+                max_concurrent_downloads: 2,
+                user_agent: "Custom/1.0".to_string(),
+                proxy: Some("http://proxy.example.com:8080".to_string()),
+                headers: [(
+                    "api.example.com".to_string(),
+                    [("Authorization".to_string(), "Bearer token".to_string())].into(),
+                )]
+                .into(),
+            },
+            auto_refresh: AutoRefreshConfig {
+                enabled: true,
+                interval_secs: 10,
+            },
+            clipboard: ClipboardConfig {
+                detect_on_focus: true,
+            },
+            snippets: SnippetsConfig {
+                enabled: true,
+                definitions: [("warn-beta".to_string(), "**Beta**".to_string())].into(),
+            },
+            document_title: DocumentTitleConfig {
+                leading_heading: LeadingHeadingStyle::Title,
+            },
+            partials: PartialsConfig {
+                enabled: true,
+                file_prefix: "include-".to_string(),
+                frontmatter_key: "fragment".to_string(),
+                hide_in_explorer: true,
+            },
+            lint: LintConfig {
+                enabled: true,
+                trailing_whitespace: false,
+                ..LintConfig::default()
+            },
+            reading_progress: ReadingProgressConfig {
+                enabled: true,
+                position: ReadingProgressPosition::Right,
+                heading_markers: false,
+                dim_read_sections: true,
+            },
+            anchors: AnchorsConfig {
+                github_user_content_prefix: true,
+                empty_slug_fallback: String::new(),
+                heading_links: false,
+            },
+            alerts: AlertsConfig {
+                title_case: AlertTitleCase::Title,
+                labels: [("warning".to_string(), "Caution!".to_string())].into(),
+                inline_title: true,
+                types: [(
+                    "DANGER".to_string(),
+                    AlertTypeConfig {
+                        class: "danger".to_string(),
+                        icon: "stop".to_string(),
+                    },
+                )]
+                .into(),
+            },
+            typewriter: TypewriterConfig {
+                enabled: true,
+                focal_point: 0.5,
+            },
+            code_copy: CodeCopyConfig {
+                strip_prompts: true,
             },
+            heading_scale: HeadingScaleConfig {
+                h1: HeadingLevelStyle {
+                    size: 3.0,
+                    margin_top: 40.0,
+                    margin_bottom: 20.0,
+                },
+                ..HeadingScaleConfig::default()
+            },
+            ordered_lists: OrderedListConfig {
+                numbering: OrderedListNumbering::Legal,
+                outline_styles: vec!["upper-roman".to_string()],
+            },
+            reload: ReloadConfig {
+                scroll: ReloadScrollMode::SourceLine,
+            },
+            geojson: GeoJsonConfig { enabled: true },
+            dark_images: DarkImagesConfig {
+                mode: DarkImageMode::Diagrams,
+                filter: "brightness(0.8)".to_string(),
+            },
+            images: ImagesConfig {
+                wide: WideImageMode::Scroll,
+                missing: MissingImageMode::Search,
+                inline_max_size: 1024,
+            },
+            activation: ActivationConfig {
+                on_reopen: ReopenBehavior::LastFocused,
+            },
+            link_preview: LinkPreviewConfig {
+                enabled: true,
+                delay_ms: 250,
+                excerpt_length: 80,
+            },
+            header: HeaderConfig {
+                copy_path: CopyPathFormat::Relative,
+                reload_all_tabs: true,
+                modified_time: ModifiedTimeFormat::Relative,
+            },
+            search: SearchConfig {
+                context_lines: 3,
+                flash: true,
+            },
+            partial_writes: PartialWritesConfig {
+                mode: PartialWriteMode::Notice,
+                settle_ms: 500,
+                max_wait_ms: 10000,
+            },
+            code_ruler: CodeRulerConfig {
+                enabled: true,
+                column: 100,
+                highlight_long_lines: true,
+            },
+            code_wrap: CodeWrapConfig {
+                mode: CodeWrapMode::Narrow,
+                narrow_width: 600,
+            },
+            rst: RstConfig { enabled: true },
+            escape: EscapeConfig { close_tab: true },
+            open_to_side: OpenToSideConfig {
+                link_modifier: OpenToSideModifier::Shift,
+            },
+            image_cache: ImageCacheConfig {
+                enabled: false,
+                max_size_mb: 50,
+                ttl_hours: 6,
+            },
+            reader_profiles: ReaderProfilesConfig {
+                presets: vec![ReaderProfile {
+                    name: "Focus".to_string(),
+                    theme: Some(Theme::Dark),
+                    content_width: Some(720),
+                    zoom: Some(1.25),
+                    sidebar: Some(false),
+                    ..Default::default()
+                }],
+                default_profile: Some("Focus".to_string()),
+            },
+            drop: DropConfig {
+                open_files: DropOpenBehavior::Background,
+            },
+            captions: CaptionsConfig {
+                enabled: true,
+                figure_label: "Fig.".to_string(),
+                ..Default::default()
+            },
+            export: ExportConfig {
+                math: ExportMathMode::Image,
+                math_image_url: "https://example.com/math?{tex}".to_string(),
+                embed_fonts: true,
+                pdf_page_size: PdfPageSize::Letter,
+                pdf_margin: 20.0,
+            },
+            binary_files: BinaryFilesConfig {
+                open_externally: vec!["pdf".to_string(), "docx".to_string()],
+            },
+            fonts: FontsConfig {
+                body_font_file: Some(PathBuf::from("/fonts/Reading.woff2")),
+                code_font_file: None,
+            },
+            editor: EditorConfig {
+                command: Some("code -n".to_string()),
+            },
+            annotations: AnnotationsConfig {
+                save_draft_on_close: false,
+                warn_on_save_failure: true,
+            },
+            spoilers: SpoilersConfig { enabled: true },
+            home_document: HomeDocumentConfig {
+                path: Some(PathBuf::from("/notes/index.md")),
+                on_startup: HomeDocumentBehavior::WithWelcome,
+            },
+            emoji: EmojiConfig { shortcodes: false },
+            syntax_highlight: SyntaxHighlightConfig {
+                disabled_languages: vec!["text".to_string(), "pseudo".to_string()],
+                native: true,
+            },
+            tables: TablesConfig {
+                max_rows: Some(200),
+            },
+            sibling_files: SiblingFilesConfig {
+                wrap_around: true,
+                prompt_at_end: true,
+            },
+            cache: CacheConfig {
+                memory_budget_mb: 16,
+                show_metrics: true,
+            },
+            code_lines: CodeLinesConfig { line_numbers: true },
+            quit: QuitConfig {
+                confirm: true,
+                window_threshold: 2,
+                tab_threshold: 20,
+            },
+            task_lists: TaskListsConfig {
+                progress: TaskProgressMode::List,
+            },
+            link_definitions: LinkDefinitionsConfig { show: true },
         };
 
         let json = serde_json::to_string_pretty(&config).unwrap();
@@ -188,10 +747,25 @@ mod tests {
             parsed.directory.default_directory,
             Some(PathBuf::from("/home/user"))
         );
+        assert_eq!(
+            parsed.directory.on_open_directory,
+            OpenDirectoryBehavior::FocusExisting
+        );
+        assert_eq!(parsed.directory.on_open_bundle, OpenBundleBehavior::Refuse);
+        assert!(parsed.directory.remember_window_geometry);
+        assert!(parsed.directory.open_single_markdown_file);
         assert!(!parsed.sidebar.default_open);
         assert_eq!(parsed.sidebar.default_width, 320.0);
+        assert!(parsed.sidebar.accordion_mode);
+        assert!(parsed.sidebar.bookmark_root);
+        assert!(parsed.sidebar.remember_view_per_directory);
+        assert!(parsed.sidebar.reset_history_on_unrelated_root);
+        assert_eq!(parsed.sidebar.page_size, 50);
+        assert_eq!(parsed.sidebar.ignore_patterns, vec!["node_modules"]);
+        assert!(parsed.sidebar.respect_gitignore);
         assert!(parsed.right_sidebar.default_open);
         assert_eq!(parsed.right_sidebar.default_width, 250.0);
+        assert!(!parsed.right_sidebar.scroll_spy);
         assert_eq!(parsed.window_position.default_position.x.value, 10.0);
         assert_eq!(
             parsed.window_position.default_position.x.unit,
@@ -210,6 +784,138 @@ mod tests {
         assert_eq!(parsed.zoom.default_zoom_level, 1.5);
         assert_eq!(parsed.zoom.on_startup, StartupBehavior::LastClosed);
         assert_eq!(parsed.zoom.on_new_window, NewWindowBehavior::LastFocused);
+        assert_eq!(parsed.zoom.scroll_anchor, ZoomScrollAnchor::Top);
+        assert!(parsed.auto_refresh.enabled);
+        assert_eq!(parsed.auto_refresh.interval_secs, 10);
+        assert!(parsed.clipboard.detect_on_focus);
+        assert!(parsed.snippets.enabled);
+        assert_eq!(parsed.snippets.definitions["warn-beta"], "**Beta**");
+        assert_eq!(
+            parsed.document_title.leading_heading,
+            LeadingHeadingStyle::Title
+        );
+        assert!(parsed.partials.enabled);
+        assert_eq!(parsed.partials.file_prefix, "include-");
+        assert_eq!(parsed.partials.frontmatter_key, "fragment");
+        assert!(parsed.partials.hide_in_explorer);
+        assert!(parsed.lint.enabled);
+        assert!(!parsed.lint.trailing_whitespace);
+        assert!(parsed.lint.bare_urls);
+        assert!(parsed.reading_progress.enabled);
+        assert_eq!(
+            parsed.reading_progress.position,
+            ReadingProgressPosition::Right
+        );
+        assert!(!parsed.reading_progress.heading_markers);
+        assert!(parsed.reading_progress.dim_read_sections);
+        assert!(parsed.anchors.github_user_content_prefix);
+        assert_eq!(parsed.anchors.empty_slug_fallback, "");
+        assert!(!parsed.anchors.heading_links);
+        assert_eq!(parsed.alerts.title_case, AlertTitleCase::Title);
+        assert_eq!(parsed.alerts.labels["warning"], "Caution!");
+        assert!(parsed.alerts.inline_title);
+        assert_eq!(parsed.alerts.types["DANGER"].class, "danger");
+        assert_eq!(parsed.alerts.types["DANGER"].icon, "stop");
+        assert!(parsed.typewriter.enabled);
+        assert_eq!(parsed.typewriter.focal_point, 0.5);
+        assert!(parsed.code_copy.strip_prompts);
+        assert_eq!(parsed.heading_scale.h1.size, 3.0);
+        assert_eq!(parsed.heading_scale.h1.margin_top, 40.0);
+        assert_eq!(parsed.heading_scale.h2.size, 1.5);
+        assert_eq!(parsed.ordered_lists.numbering, OrderedListNumbering::Legal);
+        assert_eq!(parsed.ordered_lists.outline_styles, vec!["upper-roman"]);
+        assert_eq!(parsed.reload.scroll, ReloadScrollMode::SourceLine);
+        assert_eq!(parsed.frontmatter.related_key, "see-also");
+        assert_eq!(parsed.frontmatter.max_depth, 2);
+        assert_eq!(parsed.frontmatter.max_list_items, 10);
+        assert!(parsed.frontmatter.document_css);
+        assert_eq!(parsed.frontmatter.open, FrontmatterOpenMode::Auto);
+        assert_eq!(parsed.frontmatter.collapse_above, 3);
+        assert!(parsed.geojson.enabled);
+        assert_eq!(parsed.dark_images.mode, DarkImageMode::Diagrams);
+        assert_eq!(parsed.dark_images.filter, "brightness(0.8)");
+        assert_eq!(parsed.images.wide, WideImageMode::Scroll);
+        assert_eq!(parsed.images.missing, MissingImageMode::Search);
+        assert_eq!(parsed.images.inline_max_size, 1024);
+        assert_eq!(parsed.activation.on_reopen, ReopenBehavior::LastFocused);
+        assert!(parsed.link_preview.enabled);
+        assert_eq!(parsed.link_preview.delay_ms, 250);
+        assert_eq!(parsed.link_preview.excerpt_length, 80);
+        assert_eq!(parsed.header.copy_path, CopyPathFormat::Relative);
+        assert!(parsed.header.reload_all_tabs);
+        assert_eq!(parsed.header.modified_time, ModifiedTimeFormat::Relative);
+        assert_eq!(parsed.search.context_lines, 3);
+        assert!(parsed.search.flash);
+        assert_eq!(parsed.partial_writes.mode, PartialWriteMode::Notice);
+        assert_eq!(parsed.partial_writes.settle_ms, 500);
+        assert_eq!(parsed.partial_writes.max_wait_ms, 10000);
+        assert!(parsed.code_ruler.enabled);
+        assert_eq!(parsed.code_ruler.column, 100);
+        assert!(parsed.code_ruler.highlight_long_lines);
+        assert_eq!(parsed.code_wrap.mode, CodeWrapMode::Narrow);
+        assert_eq!(parsed.code_wrap.narrow_width, 600);
+        assert!(parsed.rst.enabled);
+        assert!(parsed.escape.close_tab);
+        assert_eq!(parsed.open_to_side.link_modifier, OpenToSideModifier::Shift);
+        assert!(!parsed.image_cache.enabled);
+        assert_eq!(parsed.image_cache.max_size_mb, 50);
+        assert_eq!(parsed.image_cache.ttl_hours, 6);
+        assert_eq!(parsed.reader_profiles.presets.len(), 1);
+        assert_eq!(parsed.reader_profiles.presets[0].name, "Focus");
+        assert_eq!(parsed.reader_profiles.presets[0].theme, Some(Theme::Dark));
+        assert_eq!(parsed.reader_profiles.presets[0].content_width, Some(720));
+        assert_eq!(parsed.reader_profiles.presets[0].right_sidebar, None);
+        assert_eq!(
+            parsed.reader_profiles.default_profile.as_deref(),
+            Some("Focus")
+        );
+        assert_eq!(parsed.drop.open_files, DropOpenBehavior::Background);
+        assert!(parsed.captions.enabled);
+        assert_eq!(parsed.captions.figure_label, "Fig.");
+        assert_eq!(parsed.captions.table_label, "Table");
+        assert_eq!(parsed.export.math, ExportMathMode::Image);
+        assert_eq!(
+            parsed.export.math_image_url,
+            "https://example.com/math?{tex}"
+        );
+        assert!(parsed.export.embed_fonts);
+        assert_eq!(parsed.export.pdf_page_size, PdfPageSize::Letter);
+        assert_eq!(parsed.export.pdf_margin, 20.0);
+        assert_eq!(parsed.binary_files.open_externally, vec!["pdf", "docx"]);
+        assert_eq!(
+            parsed.fonts.body_font_file,
+            Some(PathBuf::from("/fonts/Reading.woff2"))
+        );
+        assert_eq!(parsed.fonts.code_font_file, None);
+        assert_eq!(parsed.editor.command.as_deref(), Some("code -n"));
+        assert!(!parsed.annotations.save_draft_on_close);
+        assert!(parsed.annotations.warn_on_save_failure);
+        assert!(parsed.spoilers.enabled);
+        assert_eq!(
+            parsed.home_document.path,
+            Some(PathBuf::from("/notes/index.md"))
+        );
+        assert_eq!(
+            parsed.home_document.on_startup,
+            HomeDocumentBehavior::WithWelcome
+        );
+        assert!(!parsed.emoji.shortcodes);
+        assert_eq!(
+            parsed.syntax_highlight.disabled_languages,
+            vec!["text", "pseudo"]
+        );
+        assert!(parsed.syntax_highlight.native);
+        assert_eq!(parsed.tables.max_rows, Some(200));
+        assert!(parsed.sibling_files.wrap_around);
+        assert!(parsed.sibling_files.prompt_at_end);
+        assert_eq!(parsed.cache.memory_budget_mb, 16);
+        assert!(parsed.cache.show_metrics);
+        assert!(parsed.code_lines.line_numbers);
+        assert!(parsed.quit.confirm);
+        assert_eq!(parsed.quit.window_threshold, 2);
+        assert_eq!(parsed.quit.tab_threshold, 20);
+        assert_eq!(parsed.task_lists.progress, TaskProgressMode::List);
+        assert!(parsed.link_definitions.show);
     }
 
     #[test]
@@ -221,5 +927,6 @@ mod tests {
         assert_eq!(parsed.zoom.default_zoom_level, 1.0);
         assert_eq!(parsed.zoom.on_startup, StartupBehavior::Default);
         assert_eq!(parsed.zoom.on_new_window, NewWindowBehavior::Default);
+        assert_eq!(parsed.zoom.scroll_anchor, ZoomScrollAnchor::Center);
     }
 }
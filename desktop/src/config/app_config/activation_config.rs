@@ -0,0 +1,12 @@
+use super::behavior::ReopenBehavior;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for reactivating the app
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivationConfig {
+    /// Behavior on dock click while all windows are hidden: "main_window",
+    /// "last_focused", "all_windows" or "new_window"
+    #[serde(default)]
+    pub on_reopen: ReopenBehavior,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Letter case of the default alert title derived from the alert type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertTitleCase {
+    /// "NOTE", "WARNING" (as on GitHub)
+    #[default]
+    Upper,
+    /// "Note", "Warning"
+    Title,
+}
+
+/// An alert type added to the five GitHub ones
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertTypeConfig {
+    /// Class suffix of the alert (`markdown-alert-<class>`), also used for
+    /// the title
+    pub class: String,
+    /// Icon name given to the alert icon placeholder (the class when empty)
+    #[serde(default)]
+    pub icon: String,
+}
+
+/// Configuration for GitHub alerts (`> [!NOTE]`): their titles and types
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertsConfig {
+    /// Case of the default title: "upper" or "title"
+    #[serde(default)]
+    pub title_case: AlertTitleCase,
+    /// Custom titles by alert type (e.g. "warning" -> "Caution!"), used as-is
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Whether text after the marker (`> [!TIP] Shortcut`) becomes the title
+    /// instead of the first line of the alert body
+    #[serde(default)]
+    pub inline_title: bool,
+    /// Additional alert types by keyword (e.g. "DANGER" for `> [!DANGER]`)
+    #[serde(default)]
+    pub types: BTreeMap<String, AlertTypeConfig>,
+}
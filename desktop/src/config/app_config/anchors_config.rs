@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+fn default_empty_slug_fallback() -> String {
+    "section".to_string()
+}
+
+fn default_heading_links() -> bool {
+    true
+}
+
+/// Configuration for heading anchors in rendered documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorsConfig {
+    /// Whether headings also answer to GitHub's `user-content-` prefixed IDs,
+    /// so fragment links copied from GitHub (`#user-content-usage`) resolve
+    #[serde(default)]
+    pub github_user_content_prefix: bool,
+    /// ID of headings whose text leaves nothing to slug (only symbols or
+    /// emoji); repeats get `-1`, `-2`, ... like any other
+    /// slug. An empty string keeps GitHub's empty IDs
+    #[serde(default = "default_empty_slug_fallback")]
+    pub empty_slug_fallback: String,
+    /// Whether headings show a `#` link on hover that jumps to the heading and
+    /// copies its fragment (`#usage`)
+    #[serde(default = "default_heading_links")]
+    pub heading_links: bool,
+}
+
+impl Default for AnchorsConfig {
+    fn default() -> Self {
+        Self {
+            github_user_content_prefix: false,
+            empty_slug_fallback: default_empty_slug_fallback(),
+            heading_links: default_heading_links(),
+        }
+    }
+}
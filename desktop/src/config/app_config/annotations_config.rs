@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+/// Configuration for keeping document notes and read marks when closing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationsConfig {
+    /// Whether a note typed but not yet added is saved when its document,
+    /// the Notes tab or the window is closed
+    #[serde(default = "default_true")]
+    pub save_draft_on_close: bool,
+    /// Whether a dialog warns when notes or read marks couldn't be saved
+    /// while closing a window
+    #[serde(default = "default_true")]
+    pub warn_on_save_failure: bool,
+}
+
+impl Default for AnnotationsConfig {
+    fn default() -> Self {
+        Self {
+            save_draft_on_close: default_true(),
+            warn_on_save_failure: default_true(),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+fn default_interval_secs() -> u64 {
+    5
+}
+
+/// Configuration for polling the open file for changes
+///
+/// A fallback for network or virtual filesystems that don't deliver reliable
+/// change events to the file watcher.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoRefreshConfig {
+    /// Whether the active file is polled for changes
+    #[serde(default)]
+    pub enabled: bool,
+    /// Polling interval in seconds
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for AutoRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_interval_secs(),
+        }
+    }
+}
+
+impl AutoRefreshConfig {
+    /// Polling interval, or `None` when polling is disabled
+    pub fn poll_interval(&self) -> Option<Duration> {
+        self.enabled
+            .then(|| Duration::from_secs(self.interval_secs.max(1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_interval() {
+        let mut config = AutoRefreshConfig::default();
+        assert_eq!(config.poll_interval(), None);
+
+        config.enabled = true;
+        assert_eq!(config.poll_interval(), Some(Duration::from_secs(5)));
+
+        // A zero interval would spin; clamp to one second
+        config.interval_secs = 0;
+        assert_eq!(config.poll_interval(), Some(Duration::from_secs(1)));
+    }
+}
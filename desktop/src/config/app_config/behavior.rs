@@ -22,6 +22,58 @@ pub enum NewWindowBehavior {
     LastFocused,
 }
 
+/// Behavior when opening a directory that is already the root of another window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenDirectoryBehavior {
+    /// Change the current window's root directory
+    #[default]
+    ChangeRoot,
+    /// Focus the window already rooted at the directory
+    FocusExisting,
+}
+
+/// Behavior when a macOS bundle (`.app`, `.bundle`, ...) is opened as a directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenBundleBehavior {
+    /// Reveal the bundle in Finder
+    #[default]
+    Reveal,
+    /// Open the bundle with its default app (launching an application)
+    Open,
+    /// Ignore it and tell the user why
+    Refuse,
+    /// Browse its contents like any other directory
+    Browse,
+}
+
+/// Behavior when the app is reactivated (dock click) while its windows are hidden
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReopenBehavior {
+    /// Show the first window
+    #[default]
+    MainWindow,
+    /// Show the window that was focused last, if it still exists
+    LastFocused,
+    /// Show every hidden window
+    AllWindows,
+    /// Open a new window with the welcome page
+    NewWindow,
+}
+
+/// Behavior of the home document when the app starts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HomeDocumentBehavior {
+    /// Open the home document in place of the welcome screen
+    #[default]
+    InsteadOfWelcome,
+    /// Open the home document with the welcome screen in a second tab
+    WithWelcome,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +107,48 @@ mod tests {
         assert_eq!(default_json, r#""default""#);
         assert_eq!(last_focused_json, r#""last_focused""#);
     }
+
+    #[test]
+    fn test_open_directory_behavior_serialization() {
+        let change_root = OpenDirectoryBehavior::ChangeRoot;
+        let focus_existing = OpenDirectoryBehavior::FocusExisting;
+
+        let change_root_json = serde_json::to_string(&change_root).unwrap();
+        let focus_existing_json = serde_json::to_string(&focus_existing).unwrap();
+
+        assert_eq!(change_root_json, r#""change_root""#);
+        assert_eq!(focus_existing_json, r#""focus_existing""#);
+
+        let parsed: OpenDirectoryBehavior = serde_json::from_str(&focus_existing_json).unwrap();
+        assert_eq!(parsed, OpenDirectoryBehavior::FocusExisting);
+    }
+
+    #[test]
+    fn test_reopen_behavior_serialization() {
+        let last_focused_json = serde_json::to_string(&ReopenBehavior::LastFocused).unwrap();
+        assert_eq!(last_focused_json, r#""last_focused""#);
+        assert_eq!(
+            serde_json::to_string(&ReopenBehavior::AllWindows).unwrap(),
+            r#""all_windows""#
+        );
+
+        let parsed: ReopenBehavior = serde_json::from_str(r#""new_window""#).unwrap();
+        assert_eq!(parsed, ReopenBehavior::NewWindow);
+        assert_eq!(ReopenBehavior::default(), ReopenBehavior::MainWindow);
+    }
+
+    #[test]
+    fn test_home_document_behavior_serialization() {
+        assert_eq!(
+            serde_json::to_string(&HomeDocumentBehavior::InsteadOfWelcome).unwrap(),
+            r#""instead_of_welcome""#
+        );
+
+        let parsed: HomeDocumentBehavior = serde_json::from_str(r#""with_welcome""#).unwrap();
+        assert_eq!(parsed, HomeDocumentBehavior::WithWelcome);
+        assert_eq!(
+            HomeDocumentBehavior::default(),
+            HomeDocumentBehavior::InsteadOfWelcome
+        );
+    }
 }
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+fn default_open_externally() -> Vec<String> {
+    vec!["pdf".to_string()]
+}
+
+/// Configuration for opening files that aren't text
+///
+/// Images are shown in the viewer. Other binary files get a panel offering to
+/// reveal them in Finder or open them with the default application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryFilesConfig {
+    /// Extensions (without the dot, case-insensitive) of binary files handed
+    /// to the default application as soon as they are opened
+    #[serde(default = "default_open_externally")]
+    pub open_externally: Vec<String>,
+}
+
+impl Default for BinaryFilesConfig {
+    fn default() -> Self {
+        Self {
+            open_externally: default_open_externally(),
+        }
+    }
+}
+
+impl BinaryFilesConfig {
+    /// Whether a binary file should be opened with the default application
+    pub fn opens_externally(&self, path: &std::path::Path) -> bool {
+        let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+            return false;
+        };
+        self.open_externally
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_opens_externally() {
+        let config = BinaryFilesConfig::default();
+
+        assert!(config.opens_externally(Path::new("/docs/manual.PDF")));
+        assert!(!config.opens_externally(Path::new("/bin/tool")));
+        assert!(!config.opens_externally(Path::new("archive.zip")));
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+fn default_memory_budget_mb() -> u64 {
+    64
+}
+
+/// Configuration shared by the in-memory caches
+///
+/// Entries are evicted least recently used first once the caches together
+/// hold more than `memoryBudgetMb`; `0` turns in-memory caching off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheConfig {
+    /// Maximum memory held by the in-memory caches, in megabytes
+    #[serde(default = "default_memory_budget_mb")]
+    pub memory_budget_mb: u64,
+    /// Whether Preferences > About lists the size and hit rate of each cache
+    #[serde(default)]
+    pub show_metrics: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            memory_budget_mb: default_memory_budget_mb(),
+            show_metrics: false,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Memory budget in bytes
+    pub fn memory_budget_bytes(&self) -> u64 {
+        self.memory_budget_mb.saturating_mul(1024 * 1024)
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+fn default_figure_label() -> String {
+    "Figure".to_string()
+}
+
+fn default_listing_label() -> String {
+    "Listing".to_string()
+}
+
+fn default_table_label() -> String {
+    "Table".to_string()
+}
+
+/// Configuration for numbered captions of figures, code listings and tables
+///
+/// A paragraph starting with `Caption:` (or `: `) right after an image, code
+/// block or table becomes its numbered caption. A trailing `{#fig:name}`
+/// labels it, and `@fig:name` elsewhere in the document refers to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionsConfig {
+    /// Whether captions are recognized and numbered
+    #[serde(default)]
+    pub enabled: bool,
+    /// Label of image captions
+    #[serde(default = "default_figure_label")]
+    pub figure_label: String,
+    /// Label of code block captions
+    #[serde(default = "default_listing_label")]
+    pub listing_label: String,
+    /// Label of table captions
+    #[serde(default = "default_table_label")]
+    pub table_label: String,
+}
+
+impl Default for CaptionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            figure_label: default_figure_label(),
+            listing_label: default_listing_label(),
+            table_label: default_table_label(),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for clipboard integration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardConfig {
+    /// Whether to check the clipboard for markdown when a window gains focus
+    /// and offer to open it (never opened without confirmation)
+    #[serde(default)]
+    pub detect_on_focus: bool,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the copy buttons on code blocks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeCopyConfig {
+    /// Whether shell/console blocks get an extra button copying only the
+    /// commands, with prompts (`$ `, `>>> `) and output lines removed
+    #[serde(default)]
+    pub strip_prompts: bool,
+}
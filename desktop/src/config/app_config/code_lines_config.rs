@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for numbering the lines of code blocks
+///
+/// A `linenos` or `nolinenos` word after the language of a fenced code block
+/// (` ```rust linenos `) overrides `lineNumbers` for that block. Lines listed
+/// in braces (` ```rust {1,4-6} `) are highlighted either way.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeLinesConfig {
+    /// Whether code blocks show a line number gutter
+    #[serde(default)]
+    pub line_numbers: bool,
+}
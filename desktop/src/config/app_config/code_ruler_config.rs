@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+fn default_column() -> usize {
+    80
+}
+
+/// Configuration for the line length ruler in code blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeRulerConfig {
+    /// Whether code blocks show a vertical ruler at `column`
+    #[serde(default)]
+    pub enabled: bool,
+    /// Column the ruler is drawn after (line length limit)
+    #[serde(default = "default_column")]
+    pub column: usize,
+    /// Whether lines longer than `column` are highlighted
+    #[serde(default)]
+    pub highlight_long_lines: bool,
+}
+
+impl Default for CodeRulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            column: default_column(),
+            highlight_long_lines: false,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+fn default_narrow_width() -> u32 {
+    720
+}
+
+/// How long lines in code blocks are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeWrapMode {
+    /// Keep lines intact and scroll horizontally, like GitHub
+    #[default]
+    Scroll,
+    /// Wrap lines at the block's width
+    Wrap,
+    /// Wrap lines only while the window is narrower than `narrowWidth`
+    Narrow,
+}
+
+/// Configuration for wrapping long lines in code blocks
+///
+/// A `wrap` or `nowrap` word after the language of a fenced code block
+/// (` ```rust nowrap `) overrides the mode for that block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeWrapConfig {
+    /// Long lines: "scroll", "wrap" or "narrow"
+    #[serde(default)]
+    pub mode: CodeWrapMode,
+    /// Window width in pixels below which "narrow" wraps lines
+    #[serde(default = "default_narrow_width")]
+    pub narrow_width: u32,
+}
+
+impl Default for CodeWrapConfig {
+    fn default() -> Self {
+        Self {
+            mode: CodeWrapMode::default(),
+            narrow_width: default_narrow_width(),
+        }
+    }
+}
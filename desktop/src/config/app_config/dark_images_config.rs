@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Which images are dimmed/inverted in dark mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DarkImageMode {
+    /// Images are shown as-is
+    #[default]
+    Off,
+    /// Only images whose alt text contains `#invert`
+    OptIn,
+    /// Opted-in images plus SVG images (usually diagrams)
+    Diagrams,
+}
+
+/// Configuration for adapting light-background images to dark mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DarkImagesConfig {
+    /// Images affected: "off", "opt_in" or "diagrams". `#noinvert` in the alt
+    /// text always opts an image out
+    #[serde(default)]
+    pub mode: DarkImageMode,
+    /// CSS filter applied to affected images in dark mode
+    #[serde(default = "default_filter")]
+    pub filter: String,
+}
+
+fn default_filter() -> String {
+    "invert(0.88) hue-rotate(180deg)".to_string()
+}
+
+impl Default for DarkImagesConfig {
+    fn default() -> Self {
+        Self {
+            mode: DarkImageMode::default(),
+            filter: default_filter(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use super::behavior::{NewWindowBehavior, StartupBehavior};
+use super::behavior::{
+    NewWindowBehavior, OpenBundleBehavior, OpenDirectoryBehavior, StartupBehavior,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -12,4 +14,20 @@ pub struct DirectoryConfig {
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
     pub on_new_window: NewWindowBehavior,
+    /// Behavior when opening a directory already open as another window's root:
+    /// "change_root" or "focus_existing"
+    #[serde(default)]
+    pub on_open_directory: OpenDirectoryBehavior,
+    /// Whether windows opened for a directory reuse the size and position
+    /// last used for that directory
+    #[serde(default)]
+    pub remember_window_geometry: bool,
+    /// Whether setting a directory containing exactly one markdown file as
+    /// root also opens that file in an empty tab
+    #[serde(default)]
+    pub open_single_markdown_file: bool,
+    /// Behavior when a macOS bundle such as an `.app` is opened or dropped
+    /// as a directory: "reveal", "open", "refuse" or "browse"
+    #[serde(default)]
+    pub on_open_bundle: OpenBundleBehavior,
 }
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// How an H1 at the very start of a document is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeadingHeadingStyle {
+    /// Render it like any other heading
+    #[default]
+    Heading,
+    /// Render it as a distinct document title, with an emphasized paragraph
+    /// directly below it shown as the subtitle
+    Title,
+    /// Omit it when it repeats the file name shown in the window title
+    HideIfFileName,
+}
+
+/// Configuration for document title presentation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentTitleConfig {
+    /// Style of the leading H1: "heading", "title" or "hide_if_file_name"
+    #[serde(default)]
+    pub leading_heading: LeadingHeadingStyle,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_heading_style_serialization() {
+        assert_eq!(
+            serde_json::to_string(&LeadingHeadingStyle::HideIfFileName).unwrap(),
+            r#""hide_if_file_name""#
+        );
+        assert_eq!(
+            serde_json::from_str::<LeadingHeadingStyle>(r#""title""#).unwrap(),
+            LeadingHeadingStyle::Title
+        );
+    }
+}
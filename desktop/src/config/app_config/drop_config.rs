@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Where files dropped onto a window are opened
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropOpenBehavior {
+    /// Reuse an empty current tab, otherwise open a new tab
+    #[default]
+    Current,
+    /// Always open a new tab and switch to it
+    New,
+    /// Always open a new tab without leaving the current one
+    Background,
+}
+
+/// Configuration for dragging files onto a window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropConfig {
+    /// Dropped files: "current", "new" or "background"
+    /// (holding Shift while dropping always opens new tabs)
+    #[serde(default)]
+    pub open_files: DropOpenBehavior,
+}
+
+impl DropConfig {
+    /// Behavior for a drop, with Shift forcing new tabs
+    pub fn behavior(&self, shift_key: bool) -> DropOpenBehavior {
+        match self.open_files {
+            DropOpenBehavior::Current if shift_key => DropOpenBehavior::New,
+            behavior => behavior,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_forces_new_tab() {
+        let config = DropConfig::default();
+        assert_eq!(config.behavior(false), DropOpenBehavior::Current);
+        assert_eq!(config.behavior(true), DropOpenBehavior::New);
+
+        let background = DropConfig {
+            open_files: DropOpenBehavior::Background,
+        };
+        assert_eq!(background.behavior(true), DropOpenBehavior::Background);
+    }
+}
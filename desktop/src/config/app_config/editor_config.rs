@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for opening files in a text editor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditorConfig {
+    /// Editor command the file path is appended to (e.g. `code` or
+    /// `subl -n`); the system's default text editor is used when unset
+    #[serde(default)]
+    pub command: Option<String>,
+}
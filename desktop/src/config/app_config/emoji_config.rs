@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+fn default_shortcodes() -> bool {
+    true
+}
+
+/// Configuration for emoji in rendered documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiConfig {
+    /// Whether GitHub's `:name:` shortcodes (`:rocket:`) are shown as emoji;
+    /// unknown names are always left as written
+    #[serde(default = "default_shortcodes")]
+    pub shortcodes: bool,
+}
+
+impl Default for EmojiConfig {
+    fn default() -> Self {
+        Self {
+            shortcodes: default_shortcodes(),
+        }
+    }
+}
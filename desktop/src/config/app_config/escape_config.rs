@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the Escape key
+///
+/// Escape closes the innermost open UI first: context menu, then the search
+/// bar, then an active tab drag. Closing the current tab is the last step and
+/// only happens when enabled.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EscapeConfig {
+    /// Whether Escape closes the current tab when nothing else is open
+    /// (pinned tabs are never closed)
+    #[serde(default)]
+    pub close_tab: bool,
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+fn default_math_image_url() -> String {
+    "https://latex.codecogs.com/svg.image?{tex}".to_string()
+}
+
+fn default_pdf_margin() -> f64 {
+    15.0
+}
+
+/// PostScript points per millimeter
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+
+/// How math is written into exported documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportMathMode {
+    /// Keep the TeX source, rendered by the viewer's scripts
+    #[default]
+    Source,
+    /// Embed each expression as an image fetched from `mathImageUrl`
+    Image,
+}
+
+/// Paper size of PDF exports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PdfPageSize {
+    A3,
+    #[default]
+    A4,
+    A5,
+    Letter,
+    Legal,
+}
+
+impl PdfPageSize {
+    /// Width and height in PostScript points (portrait)
+    pub fn points(self) -> (f64, f64) {
+        match self {
+            Self::A3 => (842.0, 1191.0),
+            Self::A4 => (595.0, 842.0),
+            Self::A5 => (420.0, 595.0),
+            Self::Letter => (612.0, 792.0),
+            Self::Legal => (612.0, 1008.0),
+        }
+    }
+}
+
+/// Configuration for HTML and PDF export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportConfig {
+    /// How math expressions are exported
+    #[serde(default)]
+    pub math: ExportMathMode,
+    /// Image rendering service used when `math` is `image`; `{tex}` is
+    /// replaced by the URL-encoded expression
+    #[serde(default = "default_math_image_url")]
+    pub math_image_url: String,
+    /// Whether the font files from `fonts` are embedded into exported
+    /// documents so they look the same everywhere; fonts installed on the
+    /// system are never embedded
+    #[serde(default)]
+    pub embed_fonts: bool,
+    /// Paper size of PDF exports
+    #[serde(default)]
+    pub pdf_page_size: PdfPageSize,
+    /// Margin on every side of PDF pages, in millimeters
+    #[serde(default = "default_pdf_margin")]
+    pub pdf_margin: f64,
+}
+
+impl ExportConfig {
+    /// PDF page margin in PostScript points (negative values count as zero)
+    pub fn pdf_margin_points(&self) -> f64 {
+        self.pdf_margin.max(0.0) * POINTS_PER_MM
+    }
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            math: ExportMathMode::default(),
+            math_image_url: default_math_image_url(),
+            embed_fonts: false,
+            pdf_page_size: PdfPageSize::default(),
+            pdf_margin: default_pdf_margin(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_margin_points() {
+        let mut config = ExportConfig {
+            pdf_margin: 25.4,
+            ..Default::default()
+        };
+        assert!((config.pdf_margin_points() - 72.0).abs() < 1e-9);
+
+        config.pdf_margin = -5.0;
+        assert_eq!(config.pdf_margin_points(), 0.0);
+    }
+
+    #[test]
+    fn test_pdf_page_size_points() {
+        assert_eq!(PdfPageSize::default().points(), (595.0, 842.0));
+        assert_eq!(PdfPageSize::Letter.points(), (612.0, 792.0));
+    }
+}
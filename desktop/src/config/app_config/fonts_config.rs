@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Configuration for reading fonts loaded from font files
+///
+/// The files don't need to be installed: they are embedded into the window
+/// with `@font-face`. Supported formats are WOFF2, WOFF, TrueType and OpenType.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontsConfig {
+    /// Font file used for the document body text
+    #[serde(default)]
+    pub body_font_file: Option<PathBuf>,
+    /// Font file used for inline code and code blocks
+    #[serde(default)]
+    pub code_font_file: Option<PathBuf>,
+}
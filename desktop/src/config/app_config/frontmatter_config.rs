@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+fn default_tags_key() -> String {
+    "tags".to_string()
+}
+
+fn default_related_key() -> String {
+    "related".to_string()
+}
+
+fn default_max_depth() -> usize {
+    4
+}
+
+fn default_max_list_items() -> usize {
+    50
+}
+
+fn default_collapse_above() -> usize {
+    5
+}
+
+/// Whether the frontmatter table is initially expanded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrontmatterOpenMode {
+    /// Expanded unless it has more than `collapseAbove` keys
+    Auto,
+    /// Always expanded
+    Open,
+    /// Always collapsed
+    #[default]
+    Closed,
+}
+
+/// Configuration for frontmatter rendering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontmatterConfig {
+    /// Frontmatter key whose values are rendered as clickable tag chips
+    #[serde(default = "default_tags_key")]
+    pub tags_key: String,
+    /// Frontmatter key listing related documents, shown as a bar of links
+    /// above the content and counted as links for backlinks
+    #[serde(default = "default_related_key")]
+    pub related_key: String,
+    /// Nesting depth of mappings/lists rendered as tables; deeper values are
+    /// collapsed into an expandable summary
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    /// Number of list items shown before the rest are put behind "Show all"
+    #[serde(default = "default_max_list_items")]
+    pub max_list_items: usize,
+    /// Whether documents may style themselves with `arto-css` (stylesheets
+    /// next to the document) and `arto-style` (inline CSS) frontmatter keys
+    #[serde(default)]
+    pub document_css: bool,
+    /// Whether the table starts expanded; a manual toggle is remembered per
+    /// document for the rest of the session
+    #[serde(default)]
+    pub open: FrontmatterOpenMode,
+    /// Key count above which the table starts collapsed in `auto` mode
+    #[serde(default = "default_collapse_above")]
+    pub collapse_above: usize,
+}
+
+impl Default for FrontmatterConfig {
+    fn default() -> Self {
+        Self {
+            tags_key: default_tags_key(),
+            related_key: default_related_key(),
+            max_depth: default_max_depth(),
+            max_list_items: default_max_list_items(),
+            document_css: false,
+            open: FrontmatterOpenMode::default(),
+            collapse_above: default_collapse_above(),
+        }
+    }
+}
+
+impl FrontmatterConfig {
+    /// Whether a table with `key_count` rows starts expanded
+    pub fn starts_open(&self, key_count: usize) -> bool {
+        match self.open {
+            FrontmatterOpenMode::Auto => key_count <= self.collapse_above,
+            FrontmatterOpenMode::Open => true,
+            FrontmatterOpenMode::Closed => false,
+        }
+    }
+}
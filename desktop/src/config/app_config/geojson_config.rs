@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for rendering `geojson` code blocks as maps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonConfig {
+    /// Whether `geojson` blocks are drawn as interactive maps instead of code
+    #[serde(default)]
+    pub enabled: bool,
+}
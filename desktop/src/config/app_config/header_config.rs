@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Which path the Header copy button copies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyPathFormat {
+    /// The full path of the file
+    #[default]
+    Absolute,
+    /// The path relative to the sidebar root directory
+    Relative,
+}
+
+/// How the Header shows the current file's last-modified time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModifiedTimeFormat {
+    /// Not shown
+    #[default]
+    Hidden,
+    /// Time since the change ("5 minutes ago")
+    Relative,
+    /// Date and time of the change ("2025-01-31 14:05")
+    Absolute,
+}
+
+/// Configuration for the Header file action buttons
+///
+/// Shift-clicking a button performs the other action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderConfig {
+    /// Path copied by the copy button: "absolute" or "relative"
+    #[serde(default)]
+    pub copy_path: CopyPathFormat,
+    /// Whether the reload button reloads all tabs instead of the current one
+    #[serde(default)]
+    pub reload_all_tabs: bool,
+    /// Last-modified time shown after the file name: "hidden", "relative" or
+    /// "absolute"
+    #[serde(default)]
+    pub modified_time: ModifiedTimeFormat,
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+fn default_margin_top() -> f64 {
+    24.0
+}
+
+fn default_margin_bottom() -> f64 {
+    16.0
+}
+
+/// Size and spacing of one heading level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadingLevelStyle {
+    /// Font size relative to the body text (em)
+    pub size: f64,
+    /// Space above the heading in pixels
+    #[serde(default = "default_margin_top")]
+    pub margin_top: f64,
+    /// Space below the heading in pixels
+    #[serde(default = "default_margin_bottom")]
+    pub margin_bottom: f64,
+}
+
+impl HeadingLevelStyle {
+    const fn with_size(size: f64) -> Self {
+        Self {
+            size,
+            margin_top: 24.0,
+            margin_bottom: 16.0,
+        }
+    }
+}
+
+fn default_h1() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(2.0)
+}
+
+fn default_h2() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(1.5)
+}
+
+fn default_h3() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(1.25)
+}
+
+fn default_h4() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(1.0)
+}
+
+fn default_h5() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(0.875)
+}
+
+fn default_h6() -> HeadingLevelStyle {
+    HeadingLevelStyle::with_size(0.85)
+}
+
+/// Type scale of rendered headings (defaults match GitHub)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadingScaleConfig {
+    #[serde(default = "default_h1")]
+    pub h1: HeadingLevelStyle,
+    #[serde(default = "default_h2")]
+    pub h2: HeadingLevelStyle,
+    #[serde(default = "default_h3")]
+    pub h3: HeadingLevelStyle,
+    #[serde(default = "default_h4")]
+    pub h4: HeadingLevelStyle,
+    #[serde(default = "default_h5")]
+    pub h5: HeadingLevelStyle,
+    #[serde(default = "default_h6")]
+    pub h6: HeadingLevelStyle,
+}
+
+impl HeadingScaleConfig {
+    /// Styles of H1 through H6, in order
+    pub fn levels(&self) -> [HeadingLevelStyle; 6] {
+        [self.h1, self.h2, self.h3, self.h4, self.h5, self.h6]
+    }
+}
+
+impl Default for HeadingScaleConfig {
+    fn default() -> Self {
+        Self {
+            h1: default_h1(),
+            h2: default_h2(),
+            h3: default_h3(),
+            h4: default_h4(),
+            h5: default_h5(),
+            h6: default_h6(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_heading_scale_uses_level_defaults() {
+        let json = r#"{ "h3": { "size": 1.1 } }"#;
+        let parsed: HeadingScaleConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(parsed.h3.size, 1.1);
+        assert_eq!(parsed.h3.margin_top, 24.0);
+        assert_eq!(parsed.h3.margin_bottom, 16.0);
+        assert_eq!(parsed.h1, default_h1());
+    }
+}
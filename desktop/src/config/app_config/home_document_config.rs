@@ -0,0 +1,17 @@
+use super::behavior::HomeDocumentBehavior;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Configuration for the document opened when the app launches without a
+/// file or directory to open
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeDocumentConfig {
+    /// Document to open on launch; the welcome screen shows if it is unset
+    /// or missing
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Behavior on app startup: "instead_of_welcome" or "with_welcome"
+    #[serde(default)]
+    pub on_startup: HomeDocumentBehavior,
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_size_mb() -> u64 {
+    200
+}
+
+fn default_ttl_hours() -> u64 {
+    24
+}
+
+/// Configuration for the on-disk cache of downloaded remote images
+///
+/// Cached images are reused until they expire. The server's `Cache-Control`
+/// and `Expires` headers decide the lifetime when present; `ttlHours` is
+/// used otherwise. The least recently used images are evicted once the
+/// cache grows past `maxSizeMb`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCacheConfig {
+    /// Whether downloaded images are cached on disk
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Maximum total size of the cache in megabytes
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Lifetime of images whose response has no caching headers, in hours
+    #[serde(default = "default_ttl_hours")]
+    pub ttl_hours: u64,
+}
+
+impl Default for ImageCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            max_size_mb: default_max_size_mb(),
+            ttl_hours: default_ttl_hours(),
+        }
+    }
+}
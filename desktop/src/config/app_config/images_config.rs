@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+fn default_inline_max_size() -> u64 {
+    256 * 1024
+}
+
+/// How images wider than the content column are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WideImageMode {
+    /// Scale down to the content width
+    #[default]
+    Downscale,
+    /// Keep the natural size inside a horizontally scrolling frame
+    Scroll,
+    /// Keep the natural size, extending past the content column
+    Natural,
+}
+
+/// What happens when a local image path does not exist
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingImageMode {
+    /// Show the image as broken
+    #[default]
+    Strict,
+    /// Look for a file with the same name in the document's directory tree
+    /// and use it when there is exactly one
+    Search,
+}
+
+/// Configuration for images in rendered documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImagesConfig {
+    /// Wide images: "downscale", "scroll" or "natural"
+    #[serde(default)]
+    pub wide: WideImageMode,
+    /// Missing local images: "strict" or "search"
+    #[serde(default)]
+    pub missing: MissingImageMode,
+    /// Largest local image, in bytes, embedded into the document as a data
+    /// URL; bigger ones are loaded from disk by the webview
+    #[serde(default = "default_inline_max_size")]
+    pub inline_max_size: u64,
+}
+
+impl Default for ImagesConfig {
+    fn default() -> Self {
+        Self {
+            wide: WideImageMode::default(),
+            missing: MissingImageMode::default(),
+            inline_max_size: default_inline_max_size(),
+        }
+    }
+}
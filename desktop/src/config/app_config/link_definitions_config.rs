@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for auditing reference link definitions (`[ref]: url`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkDefinitionsConfig {
+    /// Whether a section at the end of the document lists every definition,
+    /// flagging unused and duplicate ones
+    #[serde(default)]
+    pub show: bool,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for previews of linked markdown documents shown on hover
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkPreviewConfig {
+    /// Whether hovering a link to a markdown file shows a preview
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hover time in milliseconds before the preview appears
+    #[serde(default = "default_delay_ms")]
+    pub delay_ms: u64,
+    /// Maximum length of the excerpt, in characters
+    #[serde(default = "default_excerpt_length")]
+    pub excerpt_length: usize,
+}
+
+fn default_delay_ms() -> u64 {
+    400
+}
+
+fn default_excerpt_length() -> usize {
+    200
+}
+
+impl Default for LinkPreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            delay_ms: default_delay_ms(),
+            excerpt_length: default_excerpt_length(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+/// Configuration for markdown linting shown in the Lint panel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintConfig {
+    /// Whether opened documents are linted at all
+    #[serde(default)]
+    pub enabled: bool,
+    /// Report images without alt text
+    #[serde(default = "default_true")]
+    pub missing_alt_text: bool,
+    /// Report headings skipping a level (e.g. H1 followed by H3)
+    #[serde(default = "default_true")]
+    pub heading_increment: bool,
+    /// Report lines ending in whitespace (two-space hard breaks excepted)
+    #[serde(default = "default_true")]
+    pub trailing_whitespace: bool,
+    /// Report URLs written as plain text instead of links
+    #[serde(default = "default_true")]
+    pub bare_urls: bool,
+    /// Report headings with the same text as an earlier heading
+    #[serde(default = "default_true")]
+    pub duplicate_headings: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            missing_alt_text: true,
+            heading_increment: true,
+            trailing_whitespace: true,
+            bare_urls: true,
+            duplicate_headings: true,
+        }
+    }
+}
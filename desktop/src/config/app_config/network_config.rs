@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ureq::http::{HeaderName, HeaderValue};
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_concurrent_downloads() -> usize {
+    4
+}
+
+fn default_user_agent() -> String {
+    format!("Arto/{}", env!("ARTO_BUILD_VERSION"))
+}
+
+/// Configuration for remote fetches (images, diagrams)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConfig {
+    /// Timeout for a whole HTTP request in seconds
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Maximum number of downloads running at the same time
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// User-Agent header sent with every request
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+    /// HTTP proxy URL (e.g. "http://proxy.example.com:8080").
+    /// When unset, the HTTP_PROXY/HTTPS_PROXY/ALL_PROXY env vars are used.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Extra headers per host, e.g. `{ "api.example.com": { "Authorization":
+    /// "Bearer …" } }`; only sent to a URL whose host matches exactly
+    #[serde(default)]
+    pub headers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout_secs: default_request_timeout_secs(),
+            max_concurrent_downloads: default_max_concurrent_downloads(),
+            user_agent: default_user_agent(),
+            proxy: None,
+            headers: BTreeMap::new(),
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Custom headers for a request to `host`
+    pub fn headers_for_host(&self, host: &str) -> Option<&BTreeMap<String, String>> {
+        self.headers
+            .iter()
+            .find(|(configured, _)| configured.eq_ignore_ascii_case(host))
+            .map(|(_, headers)| headers)
+    }
+
+    /// Drop headers with an invalid name or value, which would otherwise fail
+    /// every request to their host
+    pub fn remove_invalid_headers(&mut self) {
+        for (host, headers) in &mut self.headers {
+            headers.retain(|name, value| {
+                let valid = HeaderName::from_bytes(name.as_bytes()).is_ok()
+                    && HeaderValue::from_str(value).is_ok();
+                if !valid {
+                    tracing::warn!(%host, %name, "Ignoring invalid header in network config");
+                }
+                valid
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_headers(headers: &[(&str, &str, &str)]) -> NetworkConfig {
+        let mut config = NetworkConfig::default();
+        for (host, name, value) in headers {
+            config
+                .headers
+                .entry(host.to_string())
+                .or_default()
+                .insert(name.to_string(), value.to_string());
+        }
+        config
+    }
+
+    #[test]
+    fn test_headers_for_host() {
+        let config = config_with_headers(&[("api.example.com", "Authorization", "Bearer t")]);
+
+        let headers = config.headers_for_host("API.example.com").unwrap();
+        assert_eq!(headers["Authorization"], "Bearer t");
+        // Only an exact host match gets the headers
+        assert!(config.headers_for_host("example.com").is_none());
+        assert!(config.headers_for_host("evil.example.com").is_none());
+        assert!(config.headers_for_host("images.other.org").is_none());
+    }
+
+    #[test]
+    fn test_remove_invalid_headers() {
+        let mut config = config_with_headers(&[
+            ("example.com", "X-Token", "abc"),
+            ("example.com", "Bad Name", "abc"),
+            ("example.com", "X-Newline", "a\nb"),
+        ]);
+        config.remove_invalid_headers();
+
+        let names: Vec<&str> = config.headers["example.com"]
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(names, ["X-Token"]);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Modifier key that opens a clicked link in the side pane
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenToSideModifier {
+    /// Alt/Option-click
+    #[default]
+    Alt,
+    /// Shift-click
+    Shift,
+    /// Links only open to the side from the context menu
+    Off,
+}
+
+/// Configuration for "Open to the Side" (a second file next to the current one)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenToSideConfig {
+    /// Modifier for link clicks: "alt", "shift" or "off"
+    #[serde(default)]
+    pub link_modifier: OpenToSideModifier,
+}
+
+impl OpenToSideConfig {
+    /// Whether a link click with these modifiers opens to the side
+    pub fn matches(&self, alt_key: bool, shift_key: bool) -> bool {
+        match self.link_modifier {
+            OpenToSideModifier::Alt => alt_key,
+            OpenToSideModifier::Shift => shift_key,
+            OpenToSideModifier::Off => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_configured_modifier() {
+        let alt = OpenToSideConfig::default();
+        assert!(alt.matches(true, false));
+        assert!(!alt.matches(false, true));
+
+        let off = OpenToSideConfig {
+            link_modifier: OpenToSideModifier::Off,
+        };
+        assert!(!off.matches(true, true));
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+fn default_outline_styles() -> Vec<String> {
+    vec![
+        "decimal".to_string(),
+        "lower-alpha".to_string(),
+        "lower-roman".to_string(),
+    ]
+}
+
+/// How nested ordered lists are numbered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderedListNumbering {
+    /// Browser default (GitHub): `1.` at every level
+    #[default]
+    Flat,
+    /// Marker style cycles by depth through `outlineStyles` (`1.`, `a.`, `i.`)
+    Outline,
+    /// Numbers include every parent level (`1.`, `1.1.`, `1.1.1.`)
+    Legal,
+}
+
+/// Configuration for ordered list numbering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderedListConfig {
+    /// Numbering scheme: "flat", "outline" or "legal"
+    #[serde(default)]
+    pub numbering: OrderedListNumbering,
+    /// CSS `list-style-type` values cycled through by depth in outline mode
+    #[serde(default = "default_outline_styles")]
+    pub outline_styles: Vec<String>,
+}
+
+impl Default for OrderedListConfig {
+    fn default() -> Self {
+        Self {
+            numbering: OrderedListNumbering::Flat,
+            outline_styles: default_outline_styles(),
+        }
+    }
+}
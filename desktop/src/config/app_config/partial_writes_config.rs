@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// What to do when the opened file is still being written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialWriteMode {
+    /// Render whatever is on disk right away
+    #[default]
+    Off,
+    /// Keep the previous content until the file stops changing
+    Wait,
+    /// Like `wait`, with a notice that the file is being written
+    Notice,
+}
+
+fn default_settle_ms() -> u64 {
+    300
+}
+
+fn default_max_wait_ms() -> u64 {
+    5000
+}
+
+/// Configuration for opening files that are still being written
+///
+/// A file counts as written once its size and modification time stay the
+/// same for the settle window, so generators writing a document in chunks
+/// don't show up half-finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialWritesConfig {
+    /// Handling of files being written: "off", "wait" or "notice"
+    #[serde(default)]
+    pub mode: PartialWriteMode,
+    /// How long the file must stay unchanged, in milliseconds
+    #[serde(default = "default_settle_ms")]
+    pub settle_ms: u64,
+    /// Longest wait before rendering anyway, in milliseconds
+    #[serde(default = "default_max_wait_ms")]
+    pub max_wait_ms: u64,
+}
+
+impl Default for PartialWritesConfig {
+    fn default() -> Self {
+        Self {
+            mode: PartialWriteMode::default(),
+            settle_ms: default_settle_ms(),
+            max_wait_ms: default_max_wait_ms(),
+        }
+    }
+}
+
+impl PartialWritesConfig {
+    /// Settle window, or `None` when files are rendered right away
+    pub fn settle_window(&self) -> Option<Duration> {
+        (self.mode != PartialWriteMode::Off && self.settle_ms > 0)
+            .then(|| Duration::from_millis(self.settle_ms))
+    }
+
+    /// Longest wait for a file to settle
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_millis(self.max_wait_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_window() {
+        let mut config = PartialWritesConfig::default();
+        assert_eq!(config.settle_window(), None);
+
+        config.mode = PartialWriteMode::Notice;
+        assert_eq!(config.settle_window(), Some(Duration::from_millis(300)));
+
+        config.settle_ms = 0;
+        assert_eq!(config.settle_window(), None);
+    }
+
+    #[test]
+    fn test_partial_write_mode_serialization() {
+        assert_eq!(
+            serde_json::to_string(&PartialWriteMode::Notice).unwrap(),
+            r#""notice""#
+        );
+        let mode: PartialWriteMode = serde_json::from_str(r#""wait""#).unwrap();
+        assert_eq!(mode, PartialWriteMode::Wait);
+    }
+}
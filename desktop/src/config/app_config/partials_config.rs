@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+fn default_file_prefix() -> String {
+    "_".to_string()
+}
+
+fn default_frontmatter_key() -> String {
+    "partial".to_string()
+}
+
+/// Configuration for include-only "partial" files
+///
+/// A markdown file is a partial when its name starts with `file_prefix` or its
+/// frontmatter sets `frontmatter_key` to `true`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialsConfig {
+    /// Whether partials are detected at all
+    #[serde(default)]
+    pub enabled: bool,
+    /// File name prefix marking a partial (empty to disable)
+    #[serde(default = "default_file_prefix")]
+    pub file_prefix: String,
+    /// Frontmatter key marking a partial when set to `true` (empty to disable)
+    #[serde(default = "default_frontmatter_key")]
+    pub frontmatter_key: String,
+    /// Whether partials are hidden in the file explorer
+    #[serde(default)]
+    pub hide_in_explorer: bool,
+}
+
+impl Default for PartialsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file_prefix: default_file_prefix(),
+            frontmatter_key: default_frontmatter_key(),
+            hide_in_explorer: false,
+        }
+    }
+}
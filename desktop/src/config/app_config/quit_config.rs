@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+fn default_window_threshold() -> usize {
+    1
+}
+
+fn default_tab_threshold() -> usize {
+    10
+}
+
+/// Configuration for quitting the app (Arto > Quit, Cmd+Q)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuitConfig {
+    /// Whether quitting asks for confirmation when many windows or tabs are
+    /// open
+    #[serde(default)]
+    pub confirm: bool,
+    /// Confirm when more than this many windows are open
+    #[serde(default = "default_window_threshold")]
+    pub window_threshold: usize,
+    /// Confirm when more than this many tabs are open across all windows
+    #[serde(default = "default_tab_threshold")]
+    pub tab_threshold: usize,
+}
+
+impl Default for QuitConfig {
+    fn default() -> Self {
+        Self {
+            confirm: false,
+            window_threshold: default_window_threshold(),
+            tab_threshold: default_tab_threshold(),
+        }
+    }
+}
+
+impl QuitConfig {
+    /// Whether quitting with `windows` windows and `tabs` tabs open asks first
+    pub fn needs_confirmation(&self, windows: usize, tabs: usize) -> bool {
+        self.confirm && (windows > self.window_threshold || tabs > self.tab_threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_confirmation() {
+        let config = QuitConfig {
+            confirm: true,
+            ..Default::default()
+        };
+        assert!(!config.needs_confirmation(1, 10));
+        assert!(config.needs_confirmation(2, 2));
+        assert!(config.needs_confirmation(1, 11));
+
+        // Off by default
+        assert!(!QuitConfig::default().needs_confirmation(5, 50));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+
+/// A named bundle of appearance settings applied to a window at once
+/// (View > Reader Profile)
+///
+/// Unset fields leave the window's current value alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReaderProfile {
+    /// Name shown in the menu
+    pub name: String,
+    /// Theme: "auto", "light" or "dark"
+    #[serde(default)]
+    pub theme: Option<Theme>,
+    /// CSS font-family for the document body (e.g. "Georgia, serif")
+    #[serde(default)]
+    pub font_family: Option<String>,
+    /// Maximum width of the document column in pixels (0 for no limit)
+    #[serde(default)]
+    pub content_width: Option<u32>,
+    /// Zoom level (1.0 is actual size)
+    #[serde(default)]
+    pub zoom: Option<f64>,
+    /// Whether the left sidebar is shown
+    #[serde(default)]
+    pub sidebar: Option<bool>,
+    /// Whether the right sidebar is shown
+    #[serde(default)]
+    pub right_sidebar: Option<bool>,
+}
+
+/// Configuration for reader profiles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReaderProfilesConfig {
+    /// Profiles listed in the menu, in order
+    #[serde(default = "default_presets")]
+    pub presets: Vec<ReaderProfile>,
+    /// Name of the profile applied to new windows (e.g. saved with
+    /// `arto --profile NAME --save`)
+    #[serde(default)]
+    pub default_profile: Option<String>,
+}
+
+impl Default for ReaderProfilesConfig {
+    fn default() -> Self {
+        Self {
+            presets: default_presets(),
+            default_profile: None,
+        }
+    }
+}
+
+impl ReaderProfilesConfig {
+    /// The profile named `name`, ignoring case
+    pub fn find(&self, name: &str) -> Option<&ReaderProfile> {
+        self.presets
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The profile after the one named `current` (wrapping), or the first one
+    pub fn next_after(&self, current: Option<&str>) -> Option<&ReaderProfile> {
+        let next = current
+            .and_then(|name| self.presets.iter().position(|p| p.name == name))
+            .map_or(0, |index| (index + 1) % self.presets.len());
+        self.presets.get(next)
+    }
+}
+
+fn default_presets() -> Vec<ReaderProfile> {
+    vec![
+        ReaderProfile {
+            name: "Writing".to_string(),
+            theme: Some(Theme::Light),
+            font_family: Some("Georgia, \"Times New Roman\", serif".to_string()),
+            content_width: Some(680),
+            zoom: Some(1.1),
+            sidebar: None,
+            right_sidebar: Some(false),
+        },
+        ReaderProfile {
+            name: "Code Review".to_string(),
+            theme: Some(Theme::Dark),
+            font_family: None,
+            content_width: Some(0),
+            zoom: Some(1.0),
+            sidebar: Some(true),
+            right_sidebar: Some(true),
+        },
+        ReaderProfile {
+            name: "Presentation".to_string(),
+            theme: None,
+            font_family: None,
+            content_width: Some(1200),
+            zoom: Some(1.6),
+            sidebar: Some(false),
+            right_sidebar: Some(false),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_after_cycles_through_presets() {
+        let config = ReaderProfilesConfig::default();
+        let name = |profile: Option<&ReaderProfile>| profile.map(|p| p.name.clone());
+
+        assert_eq!(name(config.next_after(None)), Some("Writing".to_string()));
+        assert_eq!(
+            name(config.next_after(Some("Writing"))),
+            Some("Code Review".to_string())
+        );
+        assert_eq!(
+            name(config.next_after(Some("Presentation"))),
+            Some("Writing".to_string())
+        );
+        // A profile that was removed from the config starts over
+        assert_eq!(
+            name(config.next_after(Some("Gone"))),
+            Some("Writing".to_string())
+        );
+
+        let empty = ReaderProfilesConfig {
+            presets: vec![],
+            default_profile: None,
+        };
+        assert_eq!(empty.next_after(Some("Writing")), None);
+    }
+
+    #[test]
+    fn test_find_ignores_case() {
+        let config = ReaderProfilesConfig::default();
+        assert_eq!(
+            config.find("code review").map(|p| p.name.as_str()),
+            Some("Code Review")
+        );
+        assert!(config.find("missing").is_none());
+    }
+
+    #[test]
+    fn test_partial_profile_leaves_fields_unset() {
+        let profile: ReaderProfile =
+            serde_json::from_str(r#"{"name": "Night", "theme": "dark"}"#).unwrap();
+        assert_eq!(profile.theme, Some(Theme::Dark));
+        assert_eq!(profile.zoom, None);
+        assert_eq!(profile.font_family, None);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+/// Edge of the content area along which the reading progress bar is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingProgressPosition {
+    /// Horizontal bar along the top edge
+    #[default]
+    Top,
+    /// Vertical bar along the right edge, next to the scrollbar
+    Right,
+}
+
+/// Configuration for the reading progress indicator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingProgressConfig {
+    /// Whether the progress bar is shown
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where the bar is drawn: "top" or "right"
+    #[serde(default)]
+    pub position: ReadingProgressPosition,
+    /// Whether H1/H2 headings are marked on the bar as chapter boundaries
+    #[serde(default = "default_true")]
+    pub heading_markers: bool,
+    /// Whether content above the furthest position scrolled to is dimmed
+    ///
+    /// Independent of `enabled`; the furthest position of each document is
+    /// remembered across sessions.
+    #[serde(default)]
+    pub dim_read_sections: bool,
+}
+
+impl Default for ReadingProgressConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: ReadingProgressPosition::Top,
+            heading_markers: default_true(),
+            dim_read_sections: false,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// How the scroll position is restored when the open file reloads after a change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadScrollMode {
+    /// Keep the same pixel offset
+    #[default]
+    Offset,
+    /// Keep the same fraction of the scrollable height
+    Fraction,
+    /// Snap to the heading that was at the top of the viewport
+    Heading,
+    /// Scroll back to the source line that was at the top of the viewport
+    SourceLine,
+}
+
+/// Configuration for reloading the open file after it changes on disk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadConfig {
+    /// Scroll restoration: "offset", "fraction", "heading" or "source_line"
+    #[serde(default)]
+    pub scroll: ReloadScrollMode,
+}
@@ -9,6 +9,10 @@ fn default_right_sidebar_width() -> f64 {
     DEFAULT_RIGHT_SIDEBAR_WIDTH
 }
 
+fn default_scroll_spy() -> bool {
+    true
+}
+
 /// Configuration for right sidebar panel settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +25,9 @@ pub struct RightSidebarConfig {
     /// Default active tab
     #[serde(default)]
     pub default_tab: RightSidebarTab,
+    /// Whether the Contents tab highlights the heading at the scroll position
+    #[serde(default = "default_scroll_spy")]
+    pub scroll_spy: bool,
     /// Behavior on app startup: "default" or "last_closed"
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
@@ -33,6 +40,7 @@ impl Default for RightSidebarConfig {
             default_open: false,
             default_width: default_right_sidebar_width(),
             default_tab: RightSidebarTab::default(),
+            scroll_spy: default_scroll_spy(),
             on_startup: StartupBehavior::Default,
             on_new_window: NewWindowBehavior::Default,
         }
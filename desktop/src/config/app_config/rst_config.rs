@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for opening reStructuredText (`.rst`) files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RstConfig {
+    /// Whether `.rst` files are rendered (best-effort) instead of being
+    /// treated as unsupported files
+    #[serde(default)]
+    pub enabled: bool,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for navigating between in-document search matches
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchConfig {
+    /// Source lines of context kept above the block of the active match
+    /// (0 centers the match in the view)
+    #[serde(default)]
+    pub context_lines: usize,
+    /// Whether the block containing the active match briefly flashes
+    #[serde(default)]
+    pub flash: bool,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for stepping through the files of a directory in order
+/// (History > Next/Previous File in Directory)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiblingFilesConfig {
+    /// Whether stepping past the last file continues with the first one (and
+    /// the other way around)
+    #[serde(default)]
+    pub wrap_around: bool,
+    /// Whether the end of a document links to the previous and next files,
+    /// reading a folder of chapters like a book
+    #[serde(default)]
+    pub prompt_at_end: bool,
+}
@@ -8,6 +8,14 @@ fn default_sidebar_width() -> f64 {
     DEFAULT_SIDEBAR_WIDTH
 }
 
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+fn default_page_size() -> usize {
+    200
+}
+
 /// Configuration for sidebar-related settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +27,35 @@ pub struct SidebarConfig {
     pub default_width: f64,
     /// Whether to show all files (including non-markdown) by default
     pub default_show_all_files: bool,
+    /// Whether symlinks are resolved to their target when opened or set as root
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    /// Whether expanding a directory collapses its expanded siblings (accordion mode)
+    #[serde(default)]
+    pub accordion_mode: bool,
+    /// Whether bookmarking a file also records the current sidebar root
+    #[serde(default)]
+    pub bookmark_root: bool,
+    /// Whether the sort order and show-all-files filter are remembered per
+    /// root directory instead of being shared by all directories
+    #[serde(default)]
+    pub remember_view_per_directory: bool,
+    /// Whether setting a root unrelated to the current one (neither above nor
+    /// below it) clears the back/forward directory history
+    #[serde(default)]
+    pub reset_history_on_unrelated_root: bool,
+    /// Number of entries listed per directory before a "N more…" row
+    /// (0 lists every entry at once)
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+    /// File and directory names hidden from the explorer, with `*` and `?`
+    /// wildcards (e.g. "node_modules", "*.log")
+    #[serde(default)]
+    pub ignore_patterns: Vec<String>,
+    /// Whether entries ignored by `.gitignore` files are hidden from the
+    /// explorer (a toolbar button shows them for the session)
+    #[serde(default)]
+    pub respect_gitignore: bool,
     /// Behavior on app startup: "default" or "last_closed"
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
@@ -31,6 +68,14 @@ impl Default for SidebarConfig {
             default_open: false,
             default_width: default_sidebar_width(),
             default_show_all_files: false,
+            follow_symlinks: default_follow_symlinks(),
+            accordion_mode: false,
+            bookmark_root: false,
+            remember_view_per_directory: false,
+            reset_history_on_unrelated_root: false,
+            page_size: default_page_size(),
+            ignore_patterns: Vec::new(),
+            respect_gitignore: false,
             on_startup: StartupBehavior::Default,
             on_new_window: NewWindowBehavior::Default,
         }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Configuration for user-defined snippets expanded while rendering
+///
+/// A `{{name}}` token in a document is replaced by the markdown/HTML defined
+/// for `name` before the document is parsed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetsConfig {
+    /// Whether snippet tokens are expanded
+    #[serde(default)]
+    pub enabled: bool,
+    /// Snippet bodies by token name (e.g. "warn-beta" for `{{warn-beta}}`)
+    #[serde(default)]
+    pub definitions: BTreeMap<String, String>,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for `||spoiler||` text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpoilersConfig {
+    /// Whether text between `||` pairs is hidden until clicked (off by
+    /// default, since `||` is common in prose about code)
+    #[serde(default)]
+    pub enabled: bool,
+}
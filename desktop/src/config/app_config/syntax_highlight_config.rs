@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for syntax highlighting of code blocks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyntaxHighlightConfig {
+    /// Languages whose code blocks are shown without highlighting (matched
+    /// case-insensitively against the info string); a single block can also
+    /// opt out with a `nohl` flag, as in ` ```rust,nohl`
+    #[serde(default)]
+    pub disabled_languages: Vec<String>,
+    /// Whether code blocks are highlighted while the document is rendered
+    /// instead of by the viewer afterwards, which avoids a flash of plain
+    /// code in long documents; languages this doesn't know are still left to
+    /// the viewer
+    #[serde(default)]
+    pub native: bool,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for tables in rendered documents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TablesConfig {
+    /// Body rows shown before the rest of a longer table is collapsed behind
+    /// a "Show all rows" button, which keeps documents with large generated
+    /// tables responsive; every row is shown when unset
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Where completion of task list items (`- [x]`) is summarized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskProgressMode {
+    /// No summary
+    #[default]
+    Off,
+    /// Above each top-level list containing tasks (nested tasks included)
+    List,
+    /// Once, at the top of the document
+    Document,
+}
+
+/// Configuration for GFM task lists
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskListsConfig {
+    /// Progress summary ("7/10 tasks done"): "off", "list" or "document"
+    #[serde(default)]
+    pub progress: TaskProgressMode,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+fn default_focal_point() -> f64 {
+    0.4
+}
+
+/// Configuration for typewriter scrolling
+///
+/// When enabled, navigation targets (TOC headings, search matches, lint
+/// issues) are scrolled to a fixed focal line instead of the top or center
+/// of the viewport.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypewriterConfig {
+    /// Whether typewriter scrolling is enabled
+    #[serde(default)]
+    pub enabled: bool,
+    /// Vertical position of the focal line as a fraction of the viewport (0.0-1.0)
+    #[serde(default = "default_focal_point")]
+    pub focal_point: f64,
+}
+
+impl Default for TypewriterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            focal_point: default_focal_point(),
+        }
+    }
+}
@@ -6,6 +6,19 @@ fn default_zoom_level() -> f64 {
     1.0
 }
 
+/// Which part of the document stays in place when zooming in or out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoomScrollAnchor {
+    /// Keep the block at the center of the viewport where it was
+    #[default]
+    Center,
+    /// Keep the block at the top of the viewport where it was
+    Top,
+    /// Keep the raw scroll offset (the content reflows around it)
+    None,
+}
+
 /// Configuration for zoom-related settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +30,9 @@ pub struct ZoomConfig {
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
     pub on_new_window: NewWindowBehavior,
+    /// Position kept in place when zooming: "center", "top" or "none"
+    #[serde(default)]
+    pub scroll_anchor: ZoomScrollAnchor,
 }
 
 // Manual Default because f64's default is 0.0, but zoom default should be 1.0
@@ -26,6 +42,7 @@ impl Default for ZoomConfig {
             default_zoom_level: 1.0,
             on_startup: StartupBehavior::default(),
             on_new_window: NewWindowBehavior::default(),
+            scroll_anchor: ZoomScrollAnchor::default(),
         }
     }
 }
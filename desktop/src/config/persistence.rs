@@ -4,6 +4,7 @@ use parking_lot::RwLock;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::LazyLock;
+use tokio::sync::broadcast;
 
 impl Config {
     /// Get the configuration file path based on the platform
@@ -34,7 +35,8 @@ impl Config {
         }
 
         let content = fs::read_to_string(&path)?;
-        let config: Config = serde_json::from_str(&content)?;
+        let mut config: Config = serde_json::from_str(&content)?;
+        config.network.remove_invalid_headers();
 
         tracing::debug!(path = %path.display(), "Configuration loaded");
 
@@ -57,6 +59,16 @@ impl Config {
 
         Ok(())
     }
+
+    /// Get the configuration file path, writing the current configuration
+    /// there first if the file doesn't exist yet
+    pub fn ensure_file() -> Result<PathBuf> {
+        let path = Self::path();
+        if !path.exists() {
+            CONFIG.read().save()?;
+        }
+        Ok(path)
+    }
 }
 
 /// Global configuration instance
@@ -64,3 +76,10 @@ pub static CONFIG: LazyLock<RwLock<Config>> = LazyLock::new(|| {
     let config = Config::load().unwrap_or_default();
     RwLock::new(config)
 });
+
+/// Broadcast channel for configuration changes
+///
+/// Sent after preferences are saved so that open windows can apply settings
+/// that take effect live. Subscribers should read from CONFIG directly.
+pub static CONFIG_CHANGED: LazyLock<broadcast::Sender<()>> =
+    LazyLock::new(|| broadcast::channel(10).0);
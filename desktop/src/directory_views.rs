@@ -0,0 +1,197 @@
+//! Explorer view preferences remembered per directory.
+//!
+//! This module provides:
+//! - `ExplorerSort`: Order of entries in the file explorer
+//! - `DirectoryView`: Sort order and file filter chosen for one root directory
+//! - `DirectoryViews`: Collection of directory views with persistence
+//! - `DIRECTORY_VIEWS`: Global static for app-wide access
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Maximum number of directories whose view is remembered
+const MAX_DIRECTORY_VIEWS: usize = 100;
+
+/// Order of entries in the file explorer (directories always come first)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplorerSort {
+    /// Alphabetically by name
+    #[default]
+    Name,
+    /// Most recently modified first
+    Modified,
+}
+
+impl ExplorerSort {
+    /// The other sort order, for the explorer's toggle button
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Name => Self::Modified,
+            Self::Modified => Self::Name,
+        }
+    }
+}
+
+/// Explorer view chosen for a root directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryView {
+    /// Canonical path of the directory
+    pub path: PathBuf,
+    /// Whether non-markdown files are shown
+    pub show_all_files: bool,
+    /// Order of entries
+    #[serde(default)]
+    pub sort: ExplorerSort,
+}
+
+/// Directory views storage (saved to directory_views.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DirectoryViews {
+    /// Directory views, most recently updated first
+    pub items: Vec<DirectoryView>,
+}
+
+impl DirectoryViews {
+    /// Get the directory views file path
+    fn path() -> PathBuf {
+        const FILENAME: &str = "directory_views.json";
+        if let Some(mut path) = dirs::data_local_dir() {
+            path.push("arto");
+            path.push(FILENAME);
+            return path;
+        }
+
+        // Fallback to home directory
+        if let Some(mut path) = dirs::home_dir() {
+            path.push(".arto");
+            path.push(FILENAME);
+            return path;
+        }
+
+        PathBuf::from(FILENAME)
+    }
+
+    /// Load directory views from file or return empty
+    pub fn load() -> Self {
+        let path = Self::path();
+
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save directory views to file
+    pub fn save(&self) {
+        let path = Self::path();
+
+        tracing::debug!(path = %path.display(), count = self.items.len(), "Saving directory views");
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                tracing::error!(?e, "Failed to create directory views directory");
+                return;
+            }
+        }
+
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(e) = fs::write(&path, content) {
+                    tracing::error!(?e, "Failed to save directory views");
+                }
+            }
+            Err(e) => {
+                tracing::error!(?e, "Failed to serialize directory views");
+            }
+        }
+    }
+
+    /// Get the view remembered for a directory
+    pub fn get(&self, dir: &Path) -> Option<&DirectoryView> {
+        let dir = canonical(dir);
+        self.items.iter().find(|view| view.path == dir)
+    }
+
+    /// Remember the view of a directory at the front, dropping the least
+    /// recently updated entries beyond [`MAX_DIRECTORY_VIEWS`]
+    pub fn remember(&mut self, dir: &Path, show_all_files: bool, sort: ExplorerSort) {
+        let dir = canonical(dir);
+        self.items.retain(|view| view.path != dir);
+        self.items.insert(
+            0,
+            DirectoryView {
+                path: dir,
+                show_all_files,
+                sort,
+            },
+        );
+        self.items.truncate(MAX_DIRECTORY_VIEWS);
+    }
+}
+
+/// Key directories by canonical path, so links and `..` map to the same entry
+fn canonical(dir: &Path) -> PathBuf {
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Global directory views instance
+pub static DIRECTORY_VIEWS: LazyLock<RwLock<DirectoryViews>> =
+    LazyLock::new(|| RwLock::new(DirectoryViews::load()));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remember_replaces_and_moves_to_front() {
+        let mut views = DirectoryViews::default();
+        let docs = Path::new("/nonexistent/docs");
+        let notes = Path::new("/nonexistent/notes");
+
+        views.remember(docs, true, ExplorerSort::Name);
+        views.remember(notes, false, ExplorerSort::Modified);
+        views.remember(docs, false, ExplorerSort::Modified);
+
+        assert_eq!(views.items.len(), 2);
+        assert_eq!(views.items[0].path, docs);
+        let view = views.get(docs).unwrap();
+        assert!(!view.show_all_files);
+        assert_eq!(view.sort, ExplorerSort::Modified);
+        assert!(views.get(Path::new("/nonexistent/other")).is_none());
+    }
+
+    #[test]
+    fn test_remember_is_bounded() {
+        let mut views = DirectoryViews::default();
+        for i in 0..MAX_DIRECTORY_VIEWS + 5 {
+            let dir = PathBuf::from(format!("/nonexistent/{}", i));
+            views.remember(&dir, true, ExplorerSort::Name);
+        }
+
+        assert_eq!(views.items.len(), MAX_DIRECTORY_VIEWS);
+        // The oldest entries are dropped
+        assert!(views.get(Path::new("/nonexistent/0")).is_none());
+    }
+
+    #[test]
+    fn test_get_uses_canonical_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        fs::create_dir(&docs).unwrap();
+
+        let mut views = DirectoryViews::default();
+        views.remember(&docs, true, ExplorerSort::Modified);
+
+        assert!(views.get(&docs.join("..").join("docs")).is_some());
+    }
+}
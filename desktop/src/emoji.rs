@@ -0,0 +1,1276 @@
+//! Emoji shortcodes such as `:rocket:`.
+//!
+//! The names follow GitHub's gemoji set (including its aliases, like
+//! `:+1:` and `:thumbsup:`), covering the people, nature, food, travel,
+//! activity, object and symbol emoji that are single characters. Skin tone
+//! variants, ZWJ sequences and flags are left out.
+
+use std::borrow::Cow;
+
+/// Shortcode names and their emoji, sorted by name for binary search
+static EMOJI: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("1234", "🔢"),
+    ("1st_place_medal", "🥇"),
+    ("2nd_place_medal", "🥈"),
+    ("3rd_place_medal", "🥉"),
+    ("8ball", "🎱"),
+    ("a", "🅰\u{fe0f}"),
+    ("ab", "🆎"),
+    ("abacus", "🧮"),
+    ("abc", "🔤"),
+    ("abcd", "🔡"),
+    ("accept", "🉑"),
+    ("adhesive_bandage", "🩹"),
+    ("adult", "🧑"),
+    ("aerial_tramway", "🚡"),
+    ("airplane", "✈\u{fe0f}"),
+    ("alarm_clock", "⏰"),
+    ("alembic", "⚗\u{fe0f}"),
+    ("alien", "👽"),
+    ("ambulance", "🚑"),
+    ("amphora", "🏺"),
+    ("anchor", "⚓"),
+    ("angel", "👼"),
+    ("anger", "💢"),
+    ("angry", "😠"),
+    ("anguished", "😧"),
+    ("ant", "🐜"),
+    ("apple", "🍎"),
+    ("aquarius", "♒"),
+    ("aries", "♈"),
+    ("arrow_backward", "◀\u{fe0f}"),
+    ("arrow_double_down", "⏬"),
+    ("arrow_double_up", "⏫"),
+    ("arrow_down", "⬇\u{fe0f}"),
+    ("arrow_down_small", "🔽"),
+    ("arrow_forward", "▶\u{fe0f}"),
+    ("arrow_heading_down", "⤵\u{fe0f}"),
+    ("arrow_heading_up", "⤴\u{fe0f}"),
+    ("arrow_left", "⬅\u{fe0f}"),
+    ("arrow_lower_left", "↙\u{fe0f}"),
+    ("arrow_lower_right", "↘\u{fe0f}"),
+    ("arrow_right", "➡\u{fe0f}"),
+    ("arrow_right_hook", "↪\u{fe0f}"),
+    ("arrow_up", "⬆\u{fe0f}"),
+    ("arrow_up_down", "↕\u{fe0f}"),
+    ("arrow_up_small", "🔼"),
+    ("arrow_upper_left", "↖\u{fe0f}"),
+    ("arrow_upper_right", "↗\u{fe0f}"),
+    ("arrows_clockwise", "🔃"),
+    ("arrows_counterclockwise", "🔄"),
+    ("art", "🎨"),
+    ("articulated_lorry", "🚛"),
+    ("artificial_satellite", "🛰\u{fe0f}"),
+    ("astonished", "😲"),
+    ("athletic_shoe", "👟"),
+    ("atm", "🏧"),
+    ("atom_symbol", "⚛\u{fe0f}"),
+    ("avocado", "🥑"),
+    ("axe", "🪓"),
+    ("b", "🅱\u{fe0f}"),
+    ("baby", "👶"),
+    ("baby_bottle", "🍼"),
+    ("baby_chick", "🐤"),
+    ("baby_symbol", "🚼"),
+    ("back", "🔙"),
+    ("bacon", "🥓"),
+    ("badger", "🦡"),
+    ("badminton", "🏸"),
+    ("bagel", "🥯"),
+    ("baggage_claim", "🛄"),
+    ("baguette_bread", "🥖"),
+    ("balance_scale", "⚖\u{fe0f}"),
+    ("ballet_shoes", "🩰"),
+    ("balloon", "🎈"),
+    ("ballot_box", "🗳\u{fe0f}"),
+    ("ballot_box_with_check", "☑\u{fe0f}"),
+    ("bamboo", "🎍"),
+    ("banana", "🍌"),
+    ("bangbang", "‼\u{fe0f}"),
+    ("banjo", "🪕"),
+    ("bank", "🏦"),
+    ("bar_chart", "📊"),
+    ("barber", "💈"),
+    ("baseball", "⚾"),
+    ("basket", "🧺"),
+    ("basketball", "🏀"),
+    ("bat", "🦇"),
+    ("bathtub", "🛁"),
+    ("battery", "🔋"),
+    ("beach_umbrella", "🏖\u{fe0f}"),
+    ("bear", "🐻"),
+    ("bed", "🛏\u{fe0f}"),
+    ("bee", "🐝"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("beetle", "🪲"),
+    ("beginner", "🔰"),
+    ("bell", "🔔"),
+    ("bellhop_bell", "🛎\u{fe0f}"),
+    ("bento", "🍱"),
+    ("bike", "🚲"),
+    ("bikini", "👙"),
+    ("billed_cap", "🧢"),
+    ("biohazard", "☣\u{fe0f}"),
+    ("bird", "🐦"),
+    ("birthday", "🎂"),
+    ("black_circle", "⚫"),
+    ("black_flag", "🏴"),
+    ("black_heart", "🖤"),
+    ("black_joker", "🃏"),
+    ("black_large_square", "⬛"),
+    ("black_medium_small_square", "◾"),
+    ("black_medium_square", "◼\u{fe0f}"),
+    ("black_nib", "✒\u{fe0f}"),
+    ("black_small_square", "▪\u{fe0f}"),
+    ("black_square_button", "🔲"),
+    ("blossom", "🌼"),
+    ("blowfish", "🐡"),
+    ("blue_book", "📘"),
+    ("blue_car", "🚙"),
+    ("blue_heart", "💙"),
+    ("blue_square", "🟦"),
+    ("blush", "😊"),
+    ("boar", "🐗"),
+    ("boat", "⛵"),
+    ("book", "📖"),
+    ("bookmark", "🔖"),
+    ("bookmark_tabs", "📑"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("boot", "👢"),
+    ("bouquet", "💐"),
+    ("bow", "🙇"),
+    ("bow_and_arrow", "🏹"),
+    ("bowl_with_spoon", "🥣"),
+    ("bowling", "🎳"),
+    ("boxing_glove", "🥊"),
+    ("boy", "👦"),
+    ("brain", "🧠"),
+    ("bread", "🍞"),
+    ("bricks", "🧱"),
+    ("bridge_at_night", "🌉"),
+    ("briefcase", "💼"),
+    ("broccoli", "🥦"),
+    ("broken_heart", "💔"),
+    ("broom", "🧹"),
+    ("brown_circle", "🟤"),
+    ("brown_heart", "🤎"),
+    ("brown_square", "🟫"),
+    ("bug", "🐛"),
+    ("building_construction", "🏗\u{fe0f}"),
+    ("bulb", "💡"),
+    ("bullettrain_front", "🚅"),
+    ("bullettrain_side", "🚄"),
+    ("burrito", "🌯"),
+    ("bus", "🚌"),
+    ("busstop", "🚏"),
+    ("bust_in_silhouette", "👤"),
+    ("busts_in_silhouette", "👥"),
+    ("butter", "🧈"),
+    ("butterfly", "🦋"),
+    ("cactus", "🌵"),
+    ("cake", "🍰"),
+    ("calendar", "📆"),
+    ("call_me_hand", "🤙"),
+    ("calling", "📲"),
+    ("camel", "🐫"),
+    ("camera", "📷"),
+    ("camera_flash", "📸"),
+    ("camping", "🏕\u{fe0f}"),
+    ("cancer", "♋"),
+    ("candle", "🕯\u{fe0f}"),
+    ("candy", "🍬"),
+    ("canned_food", "🥫"),
+    ("canoe", "🛶"),
+    ("capital_abcd", "🔠"),
+    ("capricorn", "♑"),
+    ("car", "🚗"),
+    ("card_file_box", "🗃\u{fe0f}"),
+    ("card_index", "📇"),
+    ("card_index_dividers", "🗂\u{fe0f}"),
+    ("carousel_horse", "🎠"),
+    ("carrot", "🥕"),
+    ("cat", "🐱"),
+    ("cat2", "🐈"),
+    ("cd", "💿"),
+    ("chains", "⛓\u{fe0f}"),
+    ("chair", "🪑"),
+    ("champagne", "🍾"),
+    ("chart", "💹"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("checkered_flag", "🏁"),
+    ("cheese", "🧀"),
+    ("cherries", "🍒"),
+    ("cherry_blossom", "🌸"),
+    ("chess_pawn", "♟\u{fe0f}"),
+    ("chestnut", "🌰"),
+    ("chicken", "🐔"),
+    ("child", "🧒"),
+    ("children_crossing", "🚸"),
+    ("chipmunk", "🐿\u{fe0f}"),
+    ("chocolate_bar", "🍫"),
+    ("chopsticks", "🥢"),
+    ("christmas_tree", "🎄"),
+    ("church", "⛪"),
+    ("cinema", "🎦"),
+    ("circus_tent", "🎪"),
+    ("city_sunrise", "🌇"),
+    ("city_sunset", "🌆"),
+    ("cityscape", "🏙\u{fe0f}"),
+    ("cl", "🆑"),
+    ("clamp", "🗜\u{fe0f}"),
+    ("clap", "👏"),
+    ("clapper", "🎬"),
+    ("classical_building", "🏛\u{fe0f}"),
+    ("clinking_glasses", "🥂"),
+    ("clipboard", "📋"),
+    ("closed_book", "📕"),
+    ("closed_lock_with_key", "🔐"),
+    ("closed_umbrella", "🌂"),
+    ("cloud", "☁\u{fe0f}"),
+    ("cloud_with_lightning", "🌩\u{fe0f}"),
+    ("cloud_with_lightning_and_rain", "⛈\u{fe0f}"),
+    ("cloud_with_rain", "🌧\u{fe0f}"),
+    ("cloud_with_snow", "🌨\u{fe0f}"),
+    ("clown_face", "🤡"),
+    ("clubs", "♣\u{fe0f}"),
+    ("coat", "🧥"),
+    ("cocktail", "🍸"),
+    ("coconut", "🥥"),
+    ("coffee", "☕"),
+    ("coffin", "⚰\u{fe0f}"),
+    ("cold_face", "🥶"),
+    ("cold_sweat", "😰"),
+    ("collision", "💥"),
+    ("comet", "☄\u{fe0f}"),
+    ("compass", "🧭"),
+    ("computer", "💻"),
+    ("computer_mouse", "🖱\u{fe0f}"),
+    ("confetti_ball", "🎊"),
+    ("confounded", "😖"),
+    ("confused", "😕"),
+    ("congratulations", "㊗\u{fe0f}"),
+    ("construction", "🚧"),
+    ("construction_worker", "👷"),
+    ("control_knobs", "🎛\u{fe0f}"),
+    ("convenience_store", "🏪"),
+    ("cookie", "🍪"),
+    ("cool", "🆒"),
+    ("cop", "👮"),
+    ("copyright", "©\u{fe0f}"),
+    ("corn", "🌽"),
+    ("couch_and_lamp", "🛋\u{fe0f}"),
+    ("cow", "🐮"),
+    ("cow2", "🐄"),
+    ("cowboy_hat_face", "🤠"),
+    ("crab", "🦀"),
+    ("crayon", "🖍\u{fe0f}"),
+    ("credit_card", "💳"),
+    ("crescent_moon", "🌙"),
+    ("cricket", "🦗"),
+    ("cricket_game", "🏏"),
+    ("crocodile", "🐊"),
+    ("croissant", "🥐"),
+    ("crossed_fingers", "🤞"),
+    ("crossed_flags", "🎌"),
+    ("crossed_swords", "⚔\u{fe0f}"),
+    ("crown", "👑"),
+    ("cry", "😢"),
+    ("crying_cat_face", "😿"),
+    ("crystal_ball", "🔮"),
+    ("cucumber", "🥒"),
+    ("cup_with_straw", "🥤"),
+    ("cupcake", "🧁"),
+    ("cupid", "💘"),
+    ("curling_stone", "🥌"),
+    ("curly_loop", "➰"),
+    ("currency_exchange", "💱"),
+    ("curry", "🍛"),
+    ("cursing_face", "🤬"),
+    ("custard", "🍮"),
+    ("customs", "🛃"),
+    ("cut_of_meat", "🥩"),
+    ("cyclone", "🌀"),
+    ("dagger", "🗡\u{fe0f}"),
+    ("dancer", "💃"),
+    ("dango", "🍡"),
+    ("dark_sunglasses", "🕶\u{fe0f}"),
+    ("dart", "🎯"),
+    ("dash", "💨"),
+    ("date", "📅"),
+    ("deciduous_tree", "🌳"),
+    ("deer", "🦌"),
+    ("department_store", "🏬"),
+    ("derelict_house", "🏚\u{fe0f}"),
+    ("desert", "🏜\u{fe0f}"),
+    ("desert_island", "🏝\u{fe0f}"),
+    ("desktop_computer", "🖥\u{fe0f}"),
+    ("detective", "🕵\u{fe0f}"),
+    ("diamond_shape_with_a_dot_inside", "💠"),
+    ("diamonds", "♦\u{fe0f}"),
+    ("disappointed", "😞"),
+    ("disappointed_relieved", "😥"),
+    ("diving_mask", "🤿"),
+    ("dizzy", "💫"),
+    ("dizzy_face", "😵"),
+    ("dna", "🧬"),
+    ("do_not_litter", "🚯"),
+    ("dog", "🐶"),
+    ("dog2", "🐕"),
+    ("dollar", "💵"),
+    ("dolls", "🎎"),
+    ("dolphin", "🐬"),
+    ("door", "🚪"),
+    ("doughnut", "🍩"),
+    ("dove", "🕊\u{fe0f}"),
+    ("dragon", "🐉"),
+    ("dragon_face", "🐲"),
+    ("dress", "👗"),
+    ("dromedary_camel", "🐪"),
+    ("drooling_face", "🤤"),
+    ("drop_of_blood", "🩸"),
+    ("droplet", "💧"),
+    ("drum", "🥁"),
+    ("duck", "🦆"),
+    ("dumpling", "🥟"),
+    ("dvd", "📀"),
+    ("e-mail", "📧"),
+    ("eagle", "🦅"),
+    ("ear", "👂"),
+    ("ear_of_rice", "🌾"),
+    ("earth_africa", "🌍"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    ("egg", "🥚"),
+    ("eggplant", "🍆"),
+    ("eight_pointed_black_star", "✴\u{fe0f}"),
+    ("eight_spoked_asterisk", "✳\u{fe0f}"),
+    ("eject_button", "⏏\u{fe0f}"),
+    ("electric_plug", "🔌"),
+    ("elephant", "🐘"),
+    ("email", "✉\u{fe0f}"),
+    ("end", "🔚"),
+    ("envelope", "✉\u{fe0f}"),
+    ("envelope_with_arrow", "📩"),
+    ("euro", "💶"),
+    ("european_castle", "🏰"),
+    ("european_post_office", "🏤"),
+    ("evergreen_tree", "🌲"),
+    ("exclamation", "❗"),
+    ("exploding_head", "🤯"),
+    ("expressionless", "😑"),
+    ("eye", "👁\u{fe0f}"),
+    ("eyeglasses", "👓"),
+    ("eyes", "👀"),
+    ("face_with_head_bandage", "🤕"),
+    ("face_with_thermometer", "🤒"),
+    ("facepalm", "🤦"),
+    ("facepunch", "👊"),
+    ("factory", "🏭"),
+    ("fairy", "🧚"),
+    ("falafel", "🧆"),
+    ("fallen_leaf", "🍂"),
+    ("fast_forward", "⏩"),
+    ("fax", "📠"),
+    ("fearful", "😨"),
+    ("feet", "🐾"),
+    ("female_sign", "♀\u{fe0f}"),
+    ("ferris_wheel", "🎡"),
+    ("ferry", "⛴\u{fe0f}"),
+    ("field_hockey", "🏑"),
+    ("file_cabinet", "🗄\u{fe0f}"),
+    ("file_folder", "📁"),
+    ("film_projector", "📽\u{fe0f}"),
+    ("film_strip", "🎞\u{fe0f}"),
+    ("fire", "🔥"),
+    ("fire_engine", "🚒"),
+    ("fire_extinguisher", "🧯"),
+    ("firecracker", "🧨"),
+    ("fireworks", "🎆"),
+    ("first_quarter_moon", "🌓"),
+    ("first_quarter_moon_with_face", "🌛"),
+    ("fish", "🐟"),
+    ("fish_cake", "🍥"),
+    ("fishing_pole_and_fish", "🎣"),
+    ("fist", "✊"),
+    ("fist_left", "🤛"),
+    ("fist_oncoming", "👊"),
+    ("fist_raised", "✊"),
+    ("fist_right", "🤜"),
+    ("flags", "🎏"),
+    ("flamingo", "🦩"),
+    ("flashlight", "🔦"),
+    ("flat_shoe", "🥿"),
+    ("fleur_de_lis", "⚜\u{fe0f}"),
+    ("flight_arrival", "🛬"),
+    ("flight_departure", "🛫"),
+    ("flipper", "🐬"),
+    ("floppy_disk", "💾"),
+    ("flower_playing_cards", "🎴"),
+    ("flushed", "😳"),
+    ("flying_disc", "🥏"),
+    ("flying_saucer", "🛸"),
+    ("fog", "🌫\u{fe0f}"),
+    ("foggy", "🌁"),
+    ("football", "🏈"),
+    ("footprints", "👣"),
+    ("fork_and_knife", "🍴"),
+    ("fortune_cookie", "🥠"),
+    ("fountain", "⛲"),
+    ("fountain_pen", "🖋\u{fe0f}"),
+    ("four_leaf_clover", "🍀"),
+    ("fox_face", "🦊"),
+    ("framed_picture", "🖼\u{fe0f}"),
+    ("free", "🆓"),
+    ("fried_egg", "🍳"),
+    ("fried_shrimp", "🍤"),
+    ("fries", "🍟"),
+    ("frog", "🐸"),
+    ("frowning", "😦"),
+    ("frowning_face", "☹\u{fe0f}"),
+    ("fu", "🖕"),
+    ("fuelpump", "⛽"),
+    ("full_moon", "🌕"),
+    ("full_moon_with_face", "🌝"),
+    ("funeral_urn", "⚱\u{fe0f}"),
+    ("game_die", "🎲"),
+    ("garlic", "🧄"),
+    ("gear", "⚙\u{fe0f}"),
+    ("gem", "💎"),
+    ("gemini", "♊"),
+    ("genie", "🧞"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("gift_heart", "💝"),
+    ("giraffe", "🦒"),
+    ("girl", "👧"),
+    ("globe_with_meridians", "🌐"),
+    ("gloves", "🧤"),
+    ("goal_net", "🥅"),
+    ("goat", "🐐"),
+    ("goggles", "🥽"),
+    ("golf", "⛳"),
+    ("gorilla", "🦍"),
+    ("grapes", "🍇"),
+    ("green_apple", "🍏"),
+    ("green_book", "📗"),
+    ("green_circle", "🟢"),
+    ("green_heart", "💚"),
+    ("green_salad", "🥗"),
+    ("green_square", "🟩"),
+    ("grey_exclamation", "❕"),
+    ("grey_question", "❔"),
+    ("grimacing", "😬"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("guard", "💂"),
+    ("guitar", "🎸"),
+    ("gun", "🔫"),
+    ("hamburger", "🍔"),
+    ("hammer", "🔨"),
+    ("hammer_and_pick", "⚒\u{fe0f}"),
+    ("hammer_and_wrench", "🛠\u{fe0f}"),
+    ("hamster", "🐹"),
+    ("hand", "✋"),
+    ("hand_over_mouth", "🤭"),
+    ("handbag", "👜"),
+    ("handshake", "🤝"),
+    ("hankey", "💩"),
+    ("hatched_chick", "🐥"),
+    ("hatching_chick", "🐣"),
+    ("headphones", "🎧"),
+    ("hear_no_evil", "🙉"),
+    ("heart", "❤\u{fe0f}"),
+    ("heart_decoration", "💟"),
+    ("heart_eyes", "😍"),
+    ("heart_eyes_cat", "😻"),
+    ("heartbeat", "💓"),
+    ("heartpulse", "💗"),
+    ("hearts", "♥\u{fe0f}"),
+    ("heavy_check_mark", "✔\u{fe0f}"),
+    ("heavy_division_sign", "➗"),
+    ("heavy_dollar_sign", "💲"),
+    ("heavy_exclamation_mark", "❗"),
+    ("heavy_heart_exclamation", "❣\u{fe0f}"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_multiplication_x", "✖\u{fe0f}"),
+    ("heavy_plus_sign", "➕"),
+    ("hedgehog", "🦔"),
+    ("helicopter", "🚁"),
+    ("herb", "🌿"),
+    ("hibiscus", "🌺"),
+    ("high_brightness", "🔆"),
+    ("high_heel", "👠"),
+    ("hiking_boot", "🥾"),
+    ("hippopotamus", "🦛"),
+    ("hocho", "🔪"),
+    ("hole", "🕳\u{fe0f}"),
+    ("honey_pot", "🍯"),
+    ("honeybee", "🐝"),
+    ("horse", "🐴"),
+    ("hospital", "🏥"),
+    ("hot_face", "🥵"),
+    ("hot_pepper", "🌶\u{fe0f}"),
+    ("hotdog", "🌭"),
+    ("hotel", "🏨"),
+    ("hotsprings", "♨\u{fe0f}"),
+    ("hourglass", "⌛"),
+    ("hourglass_flowing_sand", "⏳"),
+    ("house", "🏠"),
+    ("house_with_garden", "🏡"),
+    ("houses", "🏘\u{fe0f}"),
+    ("hugs", "🤗"),
+    ("hushed", "😯"),
+    ("ice_cream", "🍨"),
+    ("ice_cube", "🧊"),
+    ("ice_hockey", "🏒"),
+    ("ice_skate", "⛸\u{fe0f}"),
+    ("icecream", "🍦"),
+    ("id", "🆔"),
+    ("ideograph_advantage", "🉐"),
+    ("imp", "👿"),
+    ("inbox_tray", "📥"),
+    ("incoming_envelope", "📨"),
+    ("infinity", "♾\u{fe0f}"),
+    ("information_desk_person", "💁"),
+    ("information_source", "ℹ\u{fe0f}"),
+    ("innocent", "😇"),
+    ("interrobang", "⁉\u{fe0f}"),
+    ("iphone", "📱"),
+    ("izakaya_lantern", "🏮"),
+    ("jack_o_lantern", "🎃"),
+    ("japan", "🗾"),
+    ("japanese_castle", "🏯"),
+    ("japanese_goblin", "👺"),
+    ("japanese_ogre", "👹"),
+    ("jeans", "👖"),
+    ("jigsaw", "🧩"),
+    ("joy", "😂"),
+    ("joy_cat", "😹"),
+    ("joystick", "🕹\u{fe0f}"),
+    ("kaaba", "🕋"),
+    ("kangaroo", "🦘"),
+    ("key", "🔑"),
+    ("keyboard", "⌨\u{fe0f}"),
+    ("kick_scooter", "🛴"),
+    ("kimono", "👘"),
+    ("kiss", "💋"),
+    ("kissing", "😗"),
+    ("kissing_cat", "😽"),
+    ("kissing_closed_eyes", "😚"),
+    ("kissing_heart", "😘"),
+    ("kissing_smiling_eyes", "😙"),
+    ("kite", "🪁"),
+    ("kiwi_fruit", "🥝"),
+    ("knife", "🔪"),
+    ("koala", "🐨"),
+    ("koko", "🈁"),
+    ("lab_coat", "🥼"),
+    ("label", "🏷\u{fe0f}"),
+    ("lacrosse", "🥍"),
+    ("lady_beetle", "🐞"),
+    ("lantern", "🏮"),
+    ("large_blue_circle", "🔵"),
+    ("large_blue_diamond", "🔷"),
+    ("large_orange_diamond", "🔶"),
+    ("last_quarter_moon", "🌗"),
+    ("last_quarter_moon_with_face", "🌜"),
+    ("latin_cross", "✝\u{fe0f}"),
+    ("laughing", "😆"),
+    ("leafy_green", "🥬"),
+    ("leaves", "🍃"),
+    ("ledger", "📒"),
+    ("left_luggage", "🛅"),
+    ("left_right_arrow", "↔\u{fe0f}"),
+    ("left_speech_bubble", "🗨\u{fe0f}"),
+    ("leftwards_arrow_with_hook", "↩\u{fe0f}"),
+    ("lemon", "🍋"),
+    ("leo", "♌"),
+    ("leopard", "🐆"),
+    ("level_slider", "🎚\u{fe0f}"),
+    ("libra", "♎"),
+    ("light_rail", "🚈"),
+    ("link", "🔗"),
+    ("lion", "🦁"),
+    ("lips", "👄"),
+    ("lipstick", "💄"),
+    ("lizard", "🦎"),
+    ("llama", "🦙"),
+    ("lobster", "🦞"),
+    ("lock", "🔒"),
+    ("lock_with_ink_pen", "🔏"),
+    ("lollipop", "🍭"),
+    ("loop", "➿"),
+    ("lotion_bottle", "🧴"),
+    ("loud_sound", "🔊"),
+    ("loudspeaker", "📢"),
+    ("love_hotel", "🏩"),
+    ("love_letter", "💌"),
+    ("love_you_gesture", "🤟"),
+    ("low_brightness", "🔅"),
+    ("luggage", "🧳"),
+    ("lying_face", "🤥"),
+    ("m", "Ⓜ\u{fe0f}"),
+    ("mag", "🔍"),
+    ("mag_right", "🔎"),
+    ("mage", "🧙"),
+    ("magnet", "🧲"),
+    ("mahjong", "🀄"),
+    ("mailbox", "📫"),
+    ("mailbox_closed", "📪"),
+    ("mailbox_with_mail", "📬"),
+    ("mailbox_with_no_mail", "📭"),
+    ("male_sign", "♂\u{fe0f}"),
+    ("man", "👨"),
+    ("mandarin", "🍊"),
+    ("mango", "🥭"),
+    ("mans_shoe", "👞"),
+    ("mantelpiece_clock", "🕰\u{fe0f}"),
+    ("maple_leaf", "🍁"),
+    ("martial_arts_uniform", "🥋"),
+    ("mask", "😷"),
+    ("meat_on_bone", "🍖"),
+    ("medal_military", "🎖\u{fe0f}"),
+    ("medal_sports", "🏅"),
+    ("medical_symbol", "⚕\u{fe0f}"),
+    ("mega", "📣"),
+    ("melon", "🍈"),
+    ("memo", "📝"),
+    ("menorah", "🕎"),
+    ("mens", "🚹"),
+    ("metal", "🤘"),
+    ("metro", "🚇"),
+    ("microbe", "🦠"),
+    ("microphone", "🎤"),
+    ("microscope", "🔬"),
+    ("middle_finger", "🖕"),
+    ("milk_glass", "🥛"),
+    ("milky_way", "🌌"),
+    ("minibus", "🚐"),
+    ("minidisc", "💽"),
+    ("mobile_phone_off", "📴"),
+    ("money_mouth_face", "🤑"),
+    ("money_with_wings", "💸"),
+    ("moneybag", "💰"),
+    ("monkey", "🐒"),
+    ("monkey_face", "🐵"),
+    ("monocle_face", "🧐"),
+    ("monorail", "🚝"),
+    ("moon", "🌔"),
+    ("mortar_board", "🎓"),
+    ("mosque", "🕌"),
+    ("mosquito", "🦟"),
+    ("motor_boat", "🛥\u{fe0f}"),
+    ("motor_scooter", "🛵"),
+    ("motorcycle", "🏍\u{fe0f}"),
+    ("motorway", "🛣\u{fe0f}"),
+    ("mount_fuji", "🗻"),
+    ("mountain", "⛰\u{fe0f}"),
+    ("mountain_cableway", "🚠"),
+    ("mountain_railway", "🚞"),
+    ("mountain_snow", "🏔\u{fe0f}"),
+    ("mouse", "🐭"),
+    ("mouse2", "🐁"),
+    ("movie_camera", "🎥"),
+    ("moyai", "🗿"),
+    ("muscle", "💪"),
+    ("mushroom", "🍄"),
+    ("musical_keyboard", "🎹"),
+    ("musical_note", "🎵"),
+    ("musical_score", "🎼"),
+    ("mute", "🔇"),
+    ("nail_care", "💅"),
+    ("name_badge", "📛"),
+    ("national_park", "🏞\u{fe0f}"),
+    ("nauseated_face", "🤢"),
+    ("nazar_amulet", "🧿"),
+    ("necktie", "👔"),
+    ("negative_squared_cross_mark", "❎"),
+    ("nerd_face", "🤓"),
+    ("neutral_face", "😐"),
+    ("new", "🆕"),
+    ("new_moon", "🌑"),
+    ("new_moon_with_face", "🌚"),
+    ("newspaper", "📰"),
+    ("newspaper_roll", "🗞\u{fe0f}"),
+    ("next_track_button", "⏭\u{fe0f}"),
+    ("ng", "🆖"),
+    ("night_with_stars", "🌃"),
+    ("ninja", "🥷"),
+    ("no_bell", "🔕"),
+    ("no_bicycles", "🚳"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("no_good", "🙅"),
+    ("no_mobile_phones", "📵"),
+    ("no_mouth", "😶"),
+    ("no_pedestrians", "🚷"),
+    ("no_smoking", "🚭"),
+    ("non-potable_water", "🚱"),
+    ("nose", "👃"),
+    ("notebook", "📓"),
+    ("notebook_with_decorative_cover", "📔"),
+    ("notes", "🎶"),
+    ("nut_and_bolt", "🔩"),
+    ("o", "⭕"),
+    ("o2", "🅾\u{fe0f}"),
+    ("ocean", "🌊"),
+    ("octopus", "🐙"),
+    ("oden", "🍢"),
+    ("office", "🏢"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("ok_person", "🙆"),
+    ("old_key", "🗝\u{fe0f}"),
+    ("older_man", "👴"),
+    ("older_woman", "👵"),
+    ("om", "🕉\u{fe0f}"),
+    ("on", "🔛"),
+    ("oncoming_automobile", "🚘"),
+    ("oncoming_bus", "🚍"),
+    ("oncoming_police_car", "🚔"),
+    ("oncoming_taxi", "🚖"),
+    ("onion", "🧅"),
+    ("open_book", "📖"),
+    ("open_file_folder", "📂"),
+    ("open_hands", "👐"),
+    ("open_mouth", "😮"),
+    ("open_umbrella", "☂\u{fe0f}"),
+    ("ophiuchus", "⛎"),
+    ("orange", "🍊"),
+    ("orange_book", "📙"),
+    ("orange_circle", "🟠"),
+    ("orange_heart", "🧡"),
+    ("orange_square", "🟧"),
+    ("orthodox_cross", "☦\u{fe0f}"),
+    ("otter", "🦦"),
+    ("outbox_tray", "📤"),
+    ("owl", "🦉"),
+    ("ox", "🐂"),
+    ("package", "📦"),
+    ("page_facing_up", "📄"),
+    ("page_with_curl", "📃"),
+    ("pager", "📟"),
+    ("paintbrush", "🖌\u{fe0f}"),
+    ("palm_tree", "🌴"),
+    ("palms_up_together", "🤲"),
+    ("pancakes", "🥞"),
+    ("panda_face", "🐼"),
+    ("paperclip", "📎"),
+    ("paperclips", "🖇\u{fe0f}"),
+    ("parachute", "🪂"),
+    ("parasol_on_ground", "⛱\u{fe0f}"),
+    ("parking", "🅿\u{fe0f}"),
+    ("parrot", "🦜"),
+    ("part_alternation_mark", "〽\u{fe0f}"),
+    ("partly_sunny", "⛅"),
+    ("partying_face", "🥳"),
+    ("passenger_ship", "🛳\u{fe0f}"),
+    ("passport_control", "🛂"),
+    ("pause_button", "⏸\u{fe0f}"),
+    ("paw_prints", "🐾"),
+    ("peace_symbol", "☮\u{fe0f}"),
+    ("peach", "🍑"),
+    ("peacock", "🦚"),
+    ("peanuts", "🥜"),
+    ("pear", "🍐"),
+    ("pen", "🖊\u{fe0f}"),
+    ("pencil", "📝"),
+    ("pencil2", "✏\u{fe0f}"),
+    ("penguin", "🐧"),
+    ("pensive", "😔"),
+    ("performing_arts", "🎭"),
+    ("persevere", "😣"),
+    ("petri_dish", "🧫"),
+    ("phone", "☎\u{fe0f}"),
+    ("pick", "⛏\u{fe0f}"),
+    ("pie", "🥧"),
+    ("pig", "🐷"),
+    ("pig2", "🐖"),
+    ("pig_nose", "🐽"),
+    ("pill", "💊"),
+    ("pinching_hand", "🤏"),
+    ("pineapple", "🍍"),
+    ("ping_pong", "🏓"),
+    ("pisces", "♓"),
+    ("pizza", "🍕"),
+    ("place_of_worship", "🛐"),
+    ("plate_with_cutlery", "🍽\u{fe0f}"),
+    ("play_or_pause_button", "⏯\u{fe0f}"),
+    ("pleading_face", "🥺"),
+    ("point_down", "👇"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up", "☝\u{fe0f}"),
+    ("point_up_2", "👆"),
+    ("police_car", "🚓"),
+    ("police_officer", "👮"),
+    ("poodle", "🐩"),
+    ("poop", "💩"),
+    ("popcorn", "🍿"),
+    ("post_office", "🏣"),
+    ("postal_horn", "📯"),
+    ("postbox", "📮"),
+    ("potable_water", "🚰"),
+    ("potato", "🥔"),
+    ("pouch", "👝"),
+    ("poultry_leg", "🍗"),
+    ("pound", "💷"),
+    ("pout", "😡"),
+    ("pouting_cat", "😾"),
+    ("pray", "🙏"),
+    ("prayer_beads", "📿"),
+    ("pretzel", "🥨"),
+    ("previous_track_button", "⏮\u{fe0f}"),
+    ("prince", "🤴"),
+    ("princess", "👸"),
+    ("printer", "🖨\u{fe0f}"),
+    ("probing_cane", "🦯"),
+    ("punch", "👊"),
+    ("purple_circle", "🟣"),
+    ("purple_heart", "💜"),
+    ("purple_square", "🟪"),
+    ("purse", "👛"),
+    ("pushpin", "📌"),
+    ("put_litter_in_its_place", "🚮"),
+    ("question", "❓"),
+    ("rabbit", "🐰"),
+    ("rabbit2", "🐇"),
+    ("raccoon", "🦝"),
+    ("racehorse", "🐎"),
+    ("racing_car", "🏎\u{fe0f}"),
+    ("radio", "📻"),
+    ("radio_button", "🔘"),
+    ("radioactive", "☢\u{fe0f}"),
+    ("rage", "😡"),
+    ("railway_car", "🚃"),
+    ("railway_track", "🛤\u{fe0f}"),
+    ("rainbow", "🌈"),
+    ("raised_back_of_hand", "🤚"),
+    ("raised_eyebrow", "🤨"),
+    ("raised_hand", "✋"),
+    ("raised_hand_with_fingers_splayed", "🖐\u{fe0f}"),
+    ("raised_hands", "🙌"),
+    ("raising_hand", "🙋"),
+    ("ram", "🐏"),
+    ("ramen", "🍜"),
+    ("rat", "🐀"),
+    ("razor", "🪒"),
+    ("receipt", "🧾"),
+    ("record_button", "⏺\u{fe0f}"),
+    ("recycle", "♻\u{fe0f}"),
+    ("red_car", "🚗"),
+    ("red_circle", "🔴"),
+    ("red_envelope", "🧧"),
+    ("red_square", "🟥"),
+    ("registered", "®\u{fe0f}"),
+    ("relaxed", "☺\u{fe0f}"),
+    ("relieved", "😌"),
+    ("reminder_ribbon", "🎗\u{fe0f}"),
+    ("repeat", "🔁"),
+    ("repeat_one", "🔂"),
+    ("rescue_worker_helmet", "⛑\u{fe0f}"),
+    ("restroom", "🚻"),
+    ("revolving_hearts", "💞"),
+    ("rewind", "⏪"),
+    ("rhinoceros", "🦏"),
+    ("ribbon", "🎀"),
+    ("rice", "🍚"),
+    ("rice_ball", "🍙"),
+    ("rice_cracker", "🍘"),
+    ("rice_scene", "🎑"),
+    ("right_anger_bubble", "🗯\u{fe0f}"),
+    ("ring", "💍"),
+    ("ringed_planet", "🪐"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("roll_eyes", "🙄"),
+    ("roll_of_paper", "🧻"),
+    ("roller_coaster", "🎢"),
+    ("rooster", "🐓"),
+    ("rose", "🌹"),
+    ("rosette", "🏵\u{fe0f}"),
+    ("rotating_light", "🚨"),
+    ("round_pushpin", "📍"),
+    ("rugby_football", "🏉"),
+    ("runner", "🏃"),
+    ("running", "🏃"),
+    ("running_shirt_with_sash", "🎽"),
+    ("sa", "🈂\u{fe0f}"),
+    ("safety_pin", "🧷"),
+    ("safety_vest", "🦺"),
+    ("sagittarius", "♐"),
+    ("sailboat", "⛵"),
+    ("sake", "🍶"),
+    ("salt", "🧂"),
+    ("sandal", "👡"),
+    ("sandwich", "🥪"),
+    ("santa", "🎅"),
+    ("sari", "🥻"),
+    ("satellite", "📡"),
+    ("satisfied", "😆"),
+    ("sauropod", "🦕"),
+    ("saxophone", "🎷"),
+    ("scarf", "🧣"),
+    ("school", "🏫"),
+    ("school_satchel", "🎒"),
+    ("scissors", "✂\u{fe0f}"),
+    ("scorpion", "🦂"),
+    ("scorpius", "♏"),
+    ("scream", "😱"),
+    ("scream_cat", "🙀"),
+    ("scroll", "📜"),
+    ("seat", "💺"),
+    ("secret", "㊙\u{fe0f}"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("selfie", "🤳"),
+    ("shallow_pan_of_food", "🥘"),
+    ("shamrock", "☘\u{fe0f}"),
+    ("shark", "🦈"),
+    ("shaved_ice", "🍧"),
+    ("sheep", "🐑"),
+    ("shell", "🐚"),
+    ("shield", "🛡\u{fe0f}"),
+    ("ship", "🚢"),
+    ("shirt", "👕"),
+    ("shit", "💩"),
+    ("shoe", "👞"),
+    ("shopping", "🛍\u{fe0f}"),
+    ("shopping_cart", "🛒"),
+    ("shorts", "🩳"),
+    ("shower", "🚿"),
+    ("shrimp", "🦐"),
+    ("shrug", "🤷"),
+    ("shushing_face", "🤫"),
+    ("signal_strength", "📶"),
+    ("six_pointed_star", "🔯"),
+    ("skateboard", "🛹"),
+    ("ski", "🎿"),
+    ("skull", "💀"),
+    ("skull_and_crossbones", "☠\u{fe0f}"),
+    ("skunk", "🦨"),
+    ("sled", "🛷"),
+    ("sleeping", "😴"),
+    ("sleepy", "😪"),
+    ("slightly_frowning_face", "🙁"),
+    ("slightly_smiling_face", "🙂"),
+    ("slot_machine", "🎰"),
+    ("sloth", "🦥"),
+    ("small_airplane", "🛩\u{fe0f}"),
+    ("small_blue_diamond", "🔹"),
+    ("small_orange_diamond", "🔸"),
+    ("small_red_triangle", "🔺"),
+    ("small_red_triangle_down", "🔻"),
+    ("smile", "😄"),
+    ("smile_cat", "😸"),
+    ("smiley", "😃"),
+    ("smiley_cat", "😺"),
+    ("smiling_face_with_three_hearts", "🥰"),
+    ("smiling_imp", "😈"),
+    ("smirk", "😏"),
+    ("smirk_cat", "😼"),
+    ("smoking", "🚬"),
+    ("snail", "🐌"),
+    ("snake", "🐍"),
+    ("sneezing_face", "🤧"),
+    ("snowflake", "❄\u{fe0f}"),
+    ("snowman", "⛄"),
+    ("snowman_with_snow", "☃\u{fe0f}"),
+    ("soap", "🧼"),
+    ("sob", "😭"),
+    ("soccer", "⚽"),
+    ("socks", "🧦"),
+    ("softball", "🥎"),
+    ("soon", "🔜"),
+    ("sos", "🆘"),
+    ("sound", "🔉"),
+    ("space_invader", "👾"),
+    ("spades", "♠\u{fe0f}"),
+    ("spaghetti", "🍝"),
+    ("sparkle", "❇\u{fe0f}"),
+    ("sparkler", "🎇"),
+    ("sparkles", "✨"),
+    ("sparkling_heart", "💖"),
+    ("speak_no_evil", "🙊"),
+    ("speaker", "🔈"),
+    ("speech_balloon", "💬"),
+    ("speedboat", "🚤"),
+    ("spider", "🕷\u{fe0f}"),
+    ("spider_web", "🕸\u{fe0f}"),
+    ("spiral_calendar", "🗓\u{fe0f}"),
+    ("spiral_notepad", "🗒\u{fe0f}"),
+    ("sponge", "🧽"),
+    ("spoon", "🥄"),
+    ("squid", "🦑"),
+    ("stadium", "🏟\u{fe0f}"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("star_and_crescent", "☪\u{fe0f}"),
+    ("star_of_david", "✡\u{fe0f}"),
+    ("star_struck", "🤩"),
+    ("stars", "🌠"),
+    ("station", "🚉"),
+    ("statue_of_liberty", "🗽"),
+    ("steam_locomotive", "🚂"),
+    ("stethoscope", "🩺"),
+    ("stew", "🍲"),
+    ("stop_button", "⏹\u{fe0f}"),
+    ("stop_sign", "🛑"),
+    ("stopwatch", "⏱\u{fe0f}"),
+    ("straight_ruler", "📏"),
+    ("strawberry", "🍓"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_closed_eyes", "😝"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("studio_microphone", "🎙\u{fe0f}"),
+    ("stuffed_flatbread", "🥙"),
+    ("sun_behind_large_cloud", "🌥\u{fe0f}"),
+    ("sun_behind_rain_cloud", "🌦\u{fe0f}"),
+    ("sun_behind_small_cloud", "🌤\u{fe0f}"),
+    ("sun_with_face", "🌞"),
+    ("sunflower", "🌻"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀\u{fe0f}"),
+    ("sunrise", "🌅"),
+    ("sunrise_over_mountains", "🌄"),
+    ("superhero", "🦸"),
+    ("supervillain", "🦹"),
+    ("sushi", "🍣"),
+    ("suspension_railway", "🚟"),
+    ("swan", "🦢"),
+    ("sweat", "😓"),
+    ("sweat_drops", "💦"),
+    ("sweat_smile", "😅"),
+    ("sweet_potato", "🍠"),
+    ("swim_brief", "🩲"),
+    ("symbols", "🔣"),
+    ("synagogue", "🕍"),
+    ("syringe", "💉"),
+    ("t-rex", "🦖"),
+    ("taco", "🌮"),
+    ("tada", "🎉"),
+    ("takeout_box", "🥡"),
+    ("tanabata_tree", "🎋"),
+    ("tangerine", "🍊"),
+    ("taurus", "♉"),
+    ("taxi", "🚕"),
+    ("tea", "🍵"),
+    ("teddy_bear", "🧸"),
+    ("telephone", "☎\u{fe0f}"),
+    ("telephone_receiver", "📞"),
+    ("telescope", "🔭"),
+    ("tennis", "🎾"),
+    ("tent", "⛺"),
+    ("test_tube", "🧪"),
+    ("thermometer", "🌡\u{fe0f}"),
+    ("thinking", "🤔"),
+    ("thought_balloon", "💭"),
+    ("thread", "🧵"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("ticket", "🎫"),
+    ("tickets", "🎟\u{fe0f}"),
+    ("tiger", "🐯"),
+    ("tiger2", "🐅"),
+    ("timer_clock", "⏲\u{fe0f}"),
+    ("tipping_hand_person", "💁"),
+    ("tired_face", "😫"),
+    ("tm", "™\u{fe0f}"),
+    ("toilet", "🚽"),
+    ("tokyo_tower", "🗼"),
+    ("tomato", "🍅"),
+    ("tongue", "👅"),
+    ("toolbox", "🧰"),
+    ("top", "🔝"),
+    ("tophat", "🎩"),
+    ("tornado", "🌪\u{fe0f}"),
+    ("trackball", "🖲\u{fe0f}"),
+    ("tractor", "🚜"),
+    ("traffic_light", "🚥"),
+    ("train", "🚋"),
+    ("train2", "🚆"),
+    ("tram", "🚊"),
+    ("triangular_flag_on_post", "🚩"),
+    ("triangular_ruler", "📐"),
+    ("trident", "🔱"),
+    ("triumph", "😤"),
+    ("trolleybus", "🚎"),
+    ("trophy", "🏆"),
+    ("tropical_drink", "🍹"),
+    ("tropical_fish", "🐠"),
+    ("truck", "🚚"),
+    ("trumpet", "🎺"),
+    ("tshirt", "👕"),
+    ("tulip", "🌷"),
+    ("tumbler_glass", "🥃"),
+    ("turkey", "🦃"),
+    ("turtle", "🐢"),
+    ("tv", "📺"),
+    ("twisted_rightwards_arrows", "🔀"),
+    ("two_hearts", "💕"),
+    ("umbrella", "☔"),
+    ("unamused", "😒"),
+    ("underage", "🔞"),
+    ("unicorn", "🦄"),
+    ("unlock", "🔓"),
+    ("up", "🆙"),
+    ("upside_down_face", "🙃"),
+    ("v", "✌\u{fe0f}"),
+    ("vampire", "🧛"),
+    ("vertical_traffic_light", "🚦"),
+    ("vhs", "📼"),
+    ("vibration_mode", "📳"),
+    ("video_camera", "📹"),
+    ("video_game", "🎮"),
+    ("violin", "🎻"),
+    ("virgo", "♍"),
+    ("volcano", "🌋"),
+    ("volleyball", "🏐"),
+    ("vomiting_face", "🤮"),
+    ("vs", "🆚"),
+    ("vulcan_salute", "🖖"),
+    ("waffle", "🧇"),
+    ("walking", "🚶"),
+    ("waning_crescent_moon", "🌘"),
+    ("waning_gibbous_moon", "🌖"),
+    ("warning", "⚠\u{fe0f}"),
+    ("wastebasket", "🗑\u{fe0f}"),
+    ("watch", "⌚"),
+    ("watermelon", "🍉"),
+    ("wave", "👋"),
+    ("wavy_dash", "〰\u{fe0f}"),
+    ("waxing_crescent_moon", "🌒"),
+    ("waxing_gibbous_moon", "🌔"),
+    ("wc", "🚾"),
+    ("weary", "😩"),
+    ("wedding", "💒"),
+    ("whale", "🐳"),
+    ("whale2", "🐋"),
+    ("wheel_of_dharma", "☸\u{fe0f}"),
+    ("wheelchair", "♿"),
+    ("white_check_mark", "✅"),
+    ("white_circle", "⚪"),
+    ("white_flag", "🏳\u{fe0f}"),
+    ("white_flower", "💮"),
+    ("white_heart", "🤍"),
+    ("white_large_square", "⬜"),
+    ("white_medium_small_square", "◽"),
+    ("white_medium_square", "◻\u{fe0f}"),
+    ("white_small_square", "▫\u{fe0f}"),
+    ("white_square_button", "🔳"),
+    ("wilted_flower", "🥀"),
+    ("wind_chime", "🎐"),
+    ("wind_face", "🌬\u{fe0f}"),
+    ("wine_glass", "🍷"),
+    ("wink", "😉"),
+    ("wolf", "🐺"),
+    ("woman", "👩"),
+    ("womans_clothes", "👚"),
+    ("womans_hat", "👒"),
+    ("womens", "🚺"),
+    ("woozy_face", "🥴"),
+    ("world_map", "🗺\u{fe0f}"),
+    ("worried", "😟"),
+    ("wrench", "🔧"),
+    ("writing_hand", "✍\u{fe0f}"),
+    ("x", "❌"),
+    ("yarn", "🧶"),
+    ("yawning_face", "🥱"),
+    ("yellow_circle", "🟡"),
+    ("yellow_heart", "💛"),
+    ("yellow_square", "🟨"),
+    ("yen", "💴"),
+    ("yin_yang", "☯\u{fe0f}"),
+    ("yo_yo", "🪀"),
+    ("yum", "😋"),
+    ("zany_face", "🤪"),
+    ("zap", "⚡"),
+    ("zebra", "🦓"),
+    ("zipper_mouth_face", "🤐"),
+    ("zombie", "🧟"),
+    ("zzz", "💤"),
+];
+
+/// Emoji for a shortcode name (without the colons)
+pub fn lookup(name: &str) -> Option<&'static str> {
+    EMOJI
+        .binary_search_by(|(code, _)| (*code).cmp(name))
+        .ok()
+        .map(|index| EMOJI[index].1)
+}
+
+/// Whether `c` can appear in a shortcode name
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '+' | '-')
+}
+
+/// Replace the known `:name:` shortcodes in `text` with their emoji
+///
+/// Unknown names are kept as written, so `10:30:00` or `:foo:` stay as is.
+pub fn replace_shortcodes(text: &str) -> Cow<'_, str> {
+    if !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !is_shortcode_char(c))
+            .unwrap_or(after.len());
+        let name = &after[..name_len];
+        match lookup(name).filter(|_| after[name_len..].starts_with(':')) {
+            Some(emoji) => {
+                output.push_str(&rest[..start]);
+                output.push_str(emoji);
+                rest = &after[name_len + 1..];
+            }
+            None => {
+                // The closing colon may open the next shortcode
+                output.push_str(&rest[..start + 1 + name_len]);
+                rest = &after[name_len..];
+            }
+        }
+    }
+    output.push_str(rest);
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emoji_table_is_sorted() {
+        assert!(EMOJI.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("rocket"), Some("🚀"));
+        assert_eq!(lookup("+1"), Some("👍"));
+        assert_eq!(lookup("thumbsup"), Some("👍"));
+        assert_eq!(lookup("heart"), Some("❤\u{fe0f}"));
+        assert_eq!(lookup("not_an_emoji"), None);
+        assert_eq!(lookup(""), None);
+    }
+
+    #[test]
+    fn test_replace_shortcodes() {
+        assert_eq!(replace_shortcodes("Launch :rocket:!"), "Launch 🚀!");
+        assert_eq!(replace_shortcodes(":smile::tada:"), "😄🎉");
+        assert_eq!(replace_shortcodes("Keep :foo: as is"), "Keep :foo: as is");
+        assert_eq!(replace_shortcodes("At 10:30:00"), "At 10:30:00");
+        assert_eq!(replace_shortcodes("key: :sparkles: value"), "key: ✨ value");
+        assert_eq!(replace_shortcodes("::+1:"), ":👍");
+        assert_eq!(replace_shortcodes("No colons"), "No colons");
+        assert_eq!(replace_shortcodes(":Rocket:"), ":Rocket:");
+    }
+}
@@ -0,0 +1,615 @@
+//! Exporting rendered documents as standalone HTML or PDF.
+//!
+//! Used by the "Export All Tabs" commands, which either write one HTML file
+//! per document into a directory or concatenate all documents into a single
+//! file with a combined table of contents. The application stylesheet is
+//! inlined so the output looks like the viewer without any other files, along
+//! with the configured font files when `export.embedFonts` is on.
+//!
+//! Mermaid diagrams and math are rendered by the viewer's scripts and are
+//! exported as their source. With `export.math` set to `image`, math is
+//! instead embedded as images from a rendering service so it survives
+//! copy-paste and viewing without scripts.
+//!
+//! PDF export prints the document already rendered in the window's webview
+//! (see `utils::pdf`), so diagrams and math appear as displayed; the print
+//! stylesheet hides everything around the document. Once the file is written,
+//! the document's headings are added to it as bookmarks.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use dioxus::desktop::wry::WebView;
+use lol_html::html_content::ContentType;
+use lol_html::{element, HtmlRewriter, Settings};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{ExportConfig, ExportMathMode, CONFIG};
+use crate::markdown::{inline_served_images, render_to_html_with_toc, HeadingInfo};
+use crate::utils::pdf::{add_outline, print_to_pdf, OutlineEntry, PdfPageSetup, PdfPrintJob};
+
+/// Deepest heading level listed in the combined table of contents
+const COMBINED_TOC_MAX_LEVEL: u8 = 2;
+
+/// Outcome of a batch export
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    /// Number of documents exported
+    pub exported: usize,
+    /// Documents that could not be exported, with the reason
+    pub failed: Vec<(PathBuf, String)>,
+    /// Problems that didn't stop the export, such as fonts that couldn't be
+    /// embedded
+    pub warnings: Vec<String>,
+}
+
+impl ExportReport {
+    /// One-line summary for the toast shown when the batch finishes
+    pub fn summary(&self) -> String {
+        let total = self.exported + self.failed.len();
+        let summary = if self.failed.is_empty() {
+            format!("Exported {} of {} tabs", self.exported, total)
+        } else {
+            format!(
+                "Exported {} of {} tabs ({} failed)",
+                self.exported,
+                total,
+                self.failed.len()
+            )
+        };
+        if self.warnings.is_empty() {
+            summary
+        } else {
+            format!("{}; {}", summary, self.warnings.join("; "))
+        }
+    }
+
+    fn fail(&mut self, path: &Path, error: anyhow::Error) {
+        tracing::warn!(path = %path.display(), ?error, "Failed to export document");
+        self.failed
+            .push((path.to_path_buf(), format!("{:#}", error)));
+    }
+}
+
+/// A rendered document ready to be written out
+struct RenderedDocument {
+    title: String,
+    body: String,
+    headings: Vec<HeadingInfo>,
+}
+
+/// Export each file to its own HTML file in `out_dir`
+///
+/// A failing file is recorded in the report and the batch continues.
+/// `progress` is called with (done, total) after each file.
+pub fn export_each(
+    files: &[PathBuf],
+    out_dir: &Path,
+    css: &str,
+    mut progress: impl FnMut(usize, usize),
+) -> ExportReport {
+    let mut report = ExportReport::default();
+    let mut used_names = HashSet::new();
+
+    for (index, file) in files.iter().enumerate() {
+        let output = out_dir.join(output_file_name(file, &mut used_names));
+        let result = render_document(file).and_then(|document| {
+            let html = standalone_html(&document.title, &document.body, css);
+            fs::write(&output, html).with_context(|| format!("writing {}", output.display()))
+        });
+        match result {
+            Ok(()) => report.exported += 1,
+            Err(error) => report.fail(file, error),
+        }
+        progress(index + 1, files.len());
+    }
+
+    report
+}
+
+/// Export all files into a single HTML file with a combined table of contents
+///
+/// Files that fail to render are left out and recorded in the report.
+pub fn export_combined(
+    files: &[PathBuf],
+    output: &Path,
+    css: &str,
+    mut progress: impl FnMut(usize, usize),
+) -> ExportReport {
+    let mut report = ExportReport::default();
+    let mut rendered_files = Vec::new();
+    let mut documents = Vec::new();
+
+    for (index, file) in files.iter().enumerate() {
+        match render_document(file) {
+            Ok(document) => {
+                rendered_files.push(file);
+                documents.push(document);
+            }
+            Err(error) => report.fail(file, error),
+        }
+        progress(index + 1, files.len());
+    }
+
+    let title = output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Export");
+    let html = standalone_html(title, &combined_body(&documents), css);
+    match fs::write(output, html).with_context(|| format!("writing {}", output.display())) {
+        Ok(()) => report.exported = documents.len(),
+        Err(error) => {
+            // Nothing was written, so none of the documents were exported
+            let message = format!("{:#}", error);
+            tracing::warn!(?error, "Failed to write combined export");
+            report.failed.extend(
+                rendered_files
+                    .into_iter()
+                    .map(|file| (file.clone(), message.clone())),
+            );
+        }
+    }
+
+    report
+}
+
+/// Export the document of `file` shown in `webview` as a paginated PDF
+///
+/// Page size and margins come from `export.pdfPageSize` and
+/// `export.pdfMargin`. The webview prints in the background, so this returns
+/// once the print job has started; follow up with [`add_pdf_outline`] when
+/// the job finishes.
+pub fn export_pdf(webview: &WebView, file: &Path, target: &Path) -> Result<PdfPrintJob> {
+    let setup = pdf_page_setup(&CONFIG.read().export);
+    print_to_pdf(webview, &document_title(file), target, &setup)
+}
+
+/// Add bookmarks for `headings` to the exported PDF at `target` (blocking)
+pub fn add_pdf_outline(target: &Path, headings: &[HeadingInfo]) -> Result<()> {
+    let outline = pdf_outline(headings);
+    if outline.is_empty() {
+        return Ok(());
+    }
+    add_outline(target, &outline)
+}
+
+/// Nest headings into outline entries by level, each under the closest
+/// preceding heading of a higher level
+fn pdf_outline(headings: &[HeadingInfo]) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    let mut rest = headings;
+    while let Some((first, tail)) = rest.split_first() {
+        let end = tail
+            .iter()
+            .position(|heading| heading.level <= first.level)
+            .unwrap_or(tail.len());
+        entries.push(OutlineEntry {
+            title: first.text.clone(),
+            children: pdf_outline(&tail[..end]),
+        });
+        rest = &tail[end..];
+    }
+    entries
+}
+
+/// Page geometry for PDF exports, keeping a printable area of at least half
+/// the shorter page side
+fn pdf_page_setup(config: &ExportConfig) -> PdfPageSetup {
+    let (width, height) = config.pdf_page_size.points();
+    PdfPageSetup {
+        width,
+        height,
+        margin: config.pdf_margin_points().min(width.min(height) / 4.0),
+    }
+}
+
+fn render_document(file: &Path) -> Result<RenderedDocument> {
+    let markdown =
+        fs::read_to_string(file).with_context(|| format!("reading {}", file.display()))?;
+    let (body, headings) = render_to_html_with_toc(&markdown, file)?;
+    // Exports must stand alone, so large images are embedded as well
+    let body = inline_served_images(&body)?;
+    let export_config = CONFIG.read().export.clone();
+    let body = match export_config.math {
+        ExportMathMode::Source => body,
+        ExportMathMode::Image => math_to_images(&body, &export_config)?,
+    };
+    Ok(RenderedDocument {
+        title: document_title(file),
+        body,
+        headings,
+    })
+}
+
+fn document_title(file: &Path) -> String {
+    file.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Untitled")
+        .to_string()
+}
+
+/// Pick a unique `<stem>.html` name for a file within one export batch
+fn output_file_name(file: &Path, used: &mut HashSet<String>) -> String {
+    let stem = document_title(file);
+    let mut name = format!("{}.html", stem);
+    let mut suffix = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{}-{}.html", stem, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+/// Wrap rendered markdown in a complete HTML page with the stylesheet inlined
+fn standalone_html(title: &str, body: &str, css: &str) -> String {
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n",
+            "<html>\n",
+            "<head>\n",
+            "<meta charset=\"utf-8\">\n",
+            "<title>{title}</title>\n",
+            "<style>\n{css}\n</style>\n",
+            "</head>\n",
+            "<body data-theme=\"light\">\n",
+            "<div class=\"markdown-viewer\">\n",
+            "<article class=\"markdown-body\">\n{body}\n</article>\n",
+            "</div>\n",
+            "</body>\n",
+            "</html>\n"
+        ),
+        title = html_escape::encode_text(title),
+        css = css,
+        body = body,
+    )
+}
+
+/// Concatenate documents behind a table of contents linking to each one
+///
+/// Ids are prefixed per document (`doc-N-`) so headings with the same name in
+/// different documents stay distinct.
+fn combined_body(documents: &[RenderedDocument]) -> String {
+    let mut toc = String::from("<nav class=\"export-toc\">\n<h1>Contents</h1>\n<ul>\n");
+    let mut sections = String::new();
+
+    for (index, document) in documents.iter().enumerate() {
+        let prefix = format!("doc-{}-", index + 1);
+        let section_id = format!("doc-{}", index + 1);
+        let title = html_escape::encode_text(&document.title);
+
+        toc.push_str(&format!("<li><a href=\"#{}\">{}</a>", section_id, title));
+        let entries: Vec<&HeadingInfo> = document
+            .headings
+            .iter()
+            .filter(|heading| heading.level <= COMBINED_TOC_MAX_LEVEL)
+            .collect();
+        if !entries.is_empty() {
+            toc.push_str("\n<ul>\n");
+            for heading in entries {
+                toc.push_str(&format!(
+                    "<li><a href=\"#{}{}\">{}</a></li>\n",
+                    prefix,
+                    heading.id,
+                    html_escape::encode_text(&heading.text)
+                ));
+            }
+            toc.push_str("</ul>\n");
+        }
+        toc.push_str("</li>\n");
+
+        let body = prefix_ids(&document.body, &prefix).unwrap_or_else(|error| {
+            tracing::warn!(?error, "Failed to prefix ids for combined export");
+            document.body.clone()
+        });
+        sections.push_str(&format!(
+            "<section id=\"{}\" class=\"export-document\">\n{}\n</section>\n",
+            section_id, body
+        ));
+    }
+
+    toc.push_str("</ul>\n</nav>\n");
+    toc + &sections
+}
+
+/// Prefix element ids and the in-document links pointing at them
+fn prefix_ids(html: &str, prefix: &str) -> Result<String> {
+    let mut output = Vec::new();
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                element!("[id]", |el| {
+                    if let Some(id) = el.get_attribute("id") {
+                        el.set_attribute("id", &format!("{}{}", prefix, id))?;
+                    }
+                    Ok(())
+                }),
+                element!("a[href^='#']", |el| {
+                    if let Some(href) = el.get_attribute("href") {
+                        el.set_attribute("href", &format!("#{}{}", prefix, &href[1..]))?;
+                    }
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| output.extend_from_slice(chunk),
+    );
+    rewriter.write(html.as_bytes())?;
+    rewriter.end()?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Replace math expressions with images from the configured rendering service
+///
+/// Repeated expressions are fetched once; an expression that fails to
+/// render keeps its source.
+fn math_to_images(html: &str, config: &ExportConfig) -> Result<String> {
+    let mut images: HashMap<(String, bool), Option<String>> = HashMap::new();
+    replace_math(html, |tex, display| {
+        images
+            .entry((tex.to_string(), display))
+            .or_insert_with(|| fetch_math_image(&config.math_image_url, tex, display))
+            .clone()
+    })
+}
+
+/// Rewrite each math element with the image `render` returns for its
+/// source (given as TeX and whether it is display math)
+fn replace_math(
+    html: &str,
+    mut render: impl FnMut(&str, bool) -> Option<String>,
+) -> Result<String> {
+    let mut output = Vec::new();
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![element!(
+                "span.preprocessed-math-inline, div.preprocessed-math-display, pre.preprocessed-math",
+                |el| {
+                    let Some(tex) = el.get_attribute("data-original-content") else {
+                        return Ok(());
+                    };
+                    let tex = html_escape::decode_html_entities(&tex).into_owned();
+                    let display = el.tag_name() != "span";
+                    if let Some(src) = render(&tex, display) {
+                        let img = format!(
+                            r#"<img class="math-image" src="{}" alt="{}">"#,
+                            src,
+                            html_escape::encode_double_quoted_attribute(&tex)
+                        );
+                        let replacement = if display {
+                            format!(r#"<div class="math-image-display">{}</div>"#, img)
+                        } else {
+                            img
+                        };
+                        el.replace(&replacement, ContentType::Html);
+                    }
+                    Ok(())
+                }
+            )],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| output.extend_from_slice(chunk),
+    );
+    rewriter.write(html.as_bytes())?;
+    rewriter.end()?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Fetch an expression as an image and return it as a data URL
+///
+/// Goes through the shared image download, so the 20 MiB size cap and the
+/// image cache apply.
+fn fetch_math_image(url_template: &str, tex: &str, display: bool) -> Option<String> {
+    let tex = if display {
+        format!("\\displaystyle {}", tex)
+    } else {
+        tex.to_string()
+    };
+    let url = url_template.replace(
+        "{tex}",
+        &utf8_percent_encode(&tex, NON_ALPHANUMERIC).to_string(),
+    );
+    match crate::utils::image::download_image(&url) {
+        Ok((bytes, content_type)) => Some(format!(
+            "data:{};base64,{}",
+            content_type.as_deref().unwrap_or("image/svg+xml"),
+            base64::prelude::BASE64_STANDARD.encode(bytes)
+        )),
+        Err(error) => {
+            tracing::warn!(%error, %url, "Failed to render math as an image");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_output_file_name_is_unique_within_batch() {
+        let mut used = HashSet::new();
+
+        assert_eq!(
+            output_file_name(Path::new("/a/README.md"), &mut used),
+            "README.html"
+        );
+        assert_eq!(
+            output_file_name(Path::new("/b/README.md"), &mut used),
+            "README-2.html"
+        );
+        assert_eq!(
+            output_file_name(Path::new("/c/README.md"), &mut used),
+            "README-3.html"
+        );
+        assert_eq!(
+            output_file_name(Path::new("/a/guide.md"), &mut used),
+            "guide.html"
+        );
+    }
+
+    #[test]
+    fn test_pdf_page_setup() {
+        let mut config = ExportConfig {
+            pdf_page_size: crate::config::PdfPageSize::Letter,
+            pdf_margin: 25.4,
+            ..Default::default()
+        };
+        let setup = pdf_page_setup(&config);
+        assert_eq!((setup.width, setup.height), (612.0, 792.0));
+        assert!((setup.margin - 72.0).abs() < 1e-9);
+
+        // Oversized margins leave half of the page width printable
+        config.pdf_margin = 500.0;
+        assert_eq!(pdf_page_setup(&config).margin, 153.0);
+    }
+
+    #[test]
+    fn test_pdf_outline_nests_by_level() {
+        let headings: Vec<HeadingInfo> = [
+            (1, "Guide"),
+            (2, "Install"),
+            (3, "macOS"),
+            (2, "Usage"),
+            (4, "Flags"),
+            (1, "FAQ"),
+        ]
+        .into_iter()
+        .map(|(level, text)| HeadingInfo {
+            level,
+            text: text.to_string(),
+            id: text.to_lowercase(),
+        })
+        .collect();
+        let entry = |title: &str, children| OutlineEntry {
+            title: title.to_string(),
+            children,
+        };
+
+        assert_eq!(
+            pdf_outline(&headings),
+            [
+                entry(
+                    "Guide",
+                    vec![
+                        entry("Install", vec![entry("macOS", vec![])]),
+                        // A skipped level still nests under the closest heading
+                        entry("Usage", vec![entry("Flags", vec![])]),
+                    ]
+                ),
+                entry("FAQ", vec![]),
+            ]
+        );
+        // Documents starting below the top level keep their headings
+        assert_eq!(pdf_outline(&headings[1..3]).len(), 1);
+        assert!(pdf_outline(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_ids() {
+        let html = r##"<h2 id="usage">Usage</h2><p><a href="#usage">here</a> <a href="other.md">other</a></p>"##;
+
+        let result = prefix_ids(html, "doc-1-").unwrap();
+
+        assert!(result.contains(r#"<h2 id="doc-1-usage">"#));
+        assert!(result.contains(r##"<a href="#doc-1-usage">"##));
+        assert!(result.contains(r#"<a href="other.md">"#));
+    }
+
+    #[test]
+    fn test_replace_math() {
+        let html = concat!(
+            r#"<p>Let <span class="preprocessed-math-inline" data-original-content="a &lt; b">a < b</span>.</p>"#,
+            r#"<div class="preprocessed-math-display" data-original-content="x^2">x^2</div>"#,
+            r#"<pre class="preprocessed-math" data-original-content="fail">fail</pre>"#,
+        );
+        let mut calls = Vec::new();
+
+        let result = replace_math(html, |tex, display| {
+            calls.push((tex.to_string(), display));
+            (tex != "fail").then(|| format!("data:image/svg+xml;base64,{}", tex.len()))
+        })
+        .unwrap();
+
+        assert_eq!(
+            calls,
+            vec![
+                ("a < b".to_string(), false),
+                ("x^2".to_string(), true),
+                ("fail".to_string(), true),
+            ]
+        );
+        assert!(result.contains(
+            r#"<p>Let <img class="math-image" src="data:image/svg+xml;base64,5" alt="a &lt; b">.</p>"#
+        ));
+        assert!(result.contains(
+            r#"<div class="math-image-display"><img class="math-image" src="data:image/svg+xml;base64,3" alt="x^2"></div>"#
+        ));
+        // Failed expressions keep their source
+        assert!(result.contains(r#"<pre class="preprocessed-math" data-original-content="fail">"#));
+    }
+
+    #[test]
+    fn test_export_each_continues_after_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let good = temp_dir.path().join("good.md");
+        fs::write(&good, "# Good\n\nText").unwrap();
+        let missing = temp_dir.path().join("missing.md");
+        let out_dir = temp_dir.path().join("out");
+        fs::create_dir(&out_dir).unwrap();
+
+        let mut calls = Vec::new();
+        let report = export_each(
+            &[missing.clone(), good],
+            &out_dir,
+            "body {}",
+            |done, total| calls.push((done, total)),
+        );
+
+        assert_eq!(report.exported, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, missing);
+        assert_eq!(calls, vec![(1, 2), (2, 2)]);
+        assert_eq!(report.summary(), "Exported 1 of 2 tabs (1 failed)");
+        let report = ExportReport {
+            warnings: vec!["couldn't embed Reading.woff2".to_string()],
+            ..report
+        };
+        assert_eq!(
+            report.summary(),
+            "Exported 1 of 2 tabs (1 failed); couldn't embed Reading.woff2"
+        );
+
+        let html = fs::read_to_string(out_dir.join("good.html")).unwrap();
+        assert!(html.contains("<title>good</title>"));
+        assert!(html.contains("<style>\nbody {}\n</style>"));
+        assert!(html.contains(r#"id="good""#));
+    }
+
+    #[test]
+    fn test_export_combined_builds_toc() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first.md");
+        fs::write(&first, "# Intro\n\n## Setup\n").unwrap();
+        let second = temp_dir.path().join("second.md");
+        fs::write(&second, "# Intro\n\n### Details\n").unwrap();
+        let output = temp_dir.path().join("book.html");
+
+        let report = export_combined(&[first, second], &output, "", |_, _| {});
+
+        assert_eq!(report.exported, 2);
+        assert!(report.failed.is_empty());
+        let html = fs::read_to_string(&output).unwrap();
+        assert!(html.contains("<title>book</title>"));
+        assert!(html.contains(r##"<a href="#doc-1">first</a>"##));
+        assert!(html.contains(r##"<a href="#doc-1-setup">Setup</a>"##));
+        assert!(html.contains(r##"<a href="#doc-2-intro">Intro</a>"##));
+        // H3 is below the combined TOC level
+        assert!(!html.contains(r##"<a href="#doc-2-details">Details</a>"##));
+        assert!(html.contains(r#"<section id="doc-2" class="export-document">"#));
+        assert!(html.contains(r#"id="doc-2-details""#));
+    }
+}
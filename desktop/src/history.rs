@@ -38,6 +38,12 @@ impl HistoryManager {
         }
     }
 
+    /// Forget every entry
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.current_index = 0;
+    }
+
     /// Push a new file to the history
     /// Clears forward history if not at the end
     pub fn push(&mut self, path: impl Into<PathBuf>) {
@@ -226,6 +232,21 @@ mod tests {
         assert_eq!(manager.len(), 1);
     }
 
+    #[test]
+    fn test_clear() {
+        let mut manager = HistoryManager::new();
+        manager.push("/test/file1.md");
+        manager.push("/test/file2.md");
+
+        manager.clear();
+        assert!(manager.is_empty());
+        assert!(!manager.can_go_back());
+
+        manager.push("/test/file3.md");
+        assert_eq!(manager.current_path(), Some(Path::new("/test/file3.md")));
+        assert!(!manager.can_go_back());
+    }
+
     #[test]
     fn test_scroll_position_saved() {
         let mut manager = HistoryManager::new();
@@ -29,16 +29,24 @@
 //! {"type":"directory","path":"/path/to/dir"}
 //! {"type":"reopen"}
 //! ```
+//!
+//! Paths are always absolute on the wire. Relative paths are resolved by the
+//! instance they were given to, against its own working directory, since the
+//! primary instance usually runs somewhere else (e.g. `/` when launched from
+//! Finder).
 
 use interprocess::local_socket::{prelude::*, GenericFilePath, ListenerOptions, Stream, ToFsName};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
+use crate::config::{ReopenBehavior, CONFIG};
+use crate::utils::file_operations::divert_bundle;
+
 // ============================================================================
 // OpenEvent definition
 // ============================================================================
@@ -131,9 +139,11 @@ fn handle_event_on_main_thread(
         }
         OpenEvent::Directory(dir) => {
             tracing::debug!(?dir, "Processing directory open event");
+            let params = crate::window::CreateMainWindowConfigParams::default()
+                .with_directory_geometry(&dir);
             let params = crate::window::CreateMainWindowConfigParams {
                 directory: Some(dir),
-                ..Default::default()
+                ..params
             };
             crate::window::create_main_window_sync(desktop, crate::state::Tab::default(), params);
         }
@@ -146,9 +156,29 @@ fn handle_event_on_main_thread(
                     crate::window::CreateMainWindowConfigParams::default(),
                 );
             } else {
+                reopen_hidden_windows(desktop);
+            }
+        }
+    }
+}
+
+/// Bring windows back on app activation while they are all hidden, as configured.
+fn reopen_hidden_windows(desktop: &std::rc::Rc<dioxus::desktop::DesktopService>) {
+    let behavior = CONFIG.read().activation.on_reopen;
+    tracing::debug!(?behavior, "Reopening hidden windows");
+    match behavior {
+        ReopenBehavior::MainWindow => crate::window::show_main_app_window(),
+        ReopenBehavior::LastFocused => {
+            if !crate::window::show_last_focused_main_window() {
                 crate::window::show_main_app_window();
             }
         }
+        ReopenBehavior::AllWindows => crate::window::show_all_main_windows(),
+        ReopenBehavior::NewWindow => crate::window::create_main_window_sync(
+            desktop,
+            crate::state::Tab::default(),
+            crate::window::CreateMainWindowConfigParams::default(),
+        ),
     }
 }
 
@@ -392,21 +422,48 @@ impl IpcMessage {
         }
     }
 
+    /// Convert a message received from another instance to an OpenEvent.
+    ///
+    /// The sender resolves relative paths in its own working directory, so a
+    /// relative path here can't be resolved correctly and is skipped.
+    fn into_received_event(self) -> Option<OpenEvent> {
+        match &self {
+            IpcMessage::File { path } | IpcMessage::Directory { path } if !path.is_absolute() => {
+                tracing::warn!(?path, "Skipping relative path received over IPC");
+                None
+            }
+            _ => Some(self.into_open_event()),
+        }
+    }
+
     /// Validate and categorize a path as File or Directory message.
     ///
-    /// This helper canonicalizes the path (resolving symlinks), checks if it's a file
-    /// or directory, and returns the appropriate IpcMessage variant.
+    /// This helper makes the path absolute against `cwd` (the working directory
+    /// of the process the path was given to), canonicalizes it (resolving
+    /// symlinks), checks if it's a file or directory, and returns the
+    /// appropriate IpcMessage variant.
     ///
     /// # Returns
     ///
     /// - `Some(IpcMessage::File)` if the path is a file
     /// - `Some(IpcMessage::Directory)` if the path is a directory
-    /// - `None` if the path is invalid (neither file nor directory)
-    fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+    /// - `None` if the path is invalid (neither file nor directory), relative
+    ///   without a known working directory, or a macOS bundle handled per
+    ///   `directory.onOpenBundle` instead
+    fn from_path(path: impl AsRef<Path>, cwd: Option<&Path>) -> Option<Self> {
         let path = path.as_ref();
-        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let Some(canonical) = resolve_path(path, cwd) else {
+            tracing::warn!(
+                ?path,
+                "Skipping relative path (working directory unavailable)"
+            );
+            return None;
+        };
         if canonical.is_dir() {
-            Some(IpcMessage::Directory { path: canonical })
+            let diverted = divert_bundle(&canonical, |notice| {
+                tracing::warn!(?path, "Skipping bundle: {notice}");
+            });
+            (!diverted).then_some(IpcMessage::Directory { path: canonical })
         } else if canonical.is_file() {
             Some(IpcMessage::File { path: canonical })
         } else {
@@ -416,27 +473,66 @@ impl IpcMessage {
     }
 }
 
-/// Validate and categorize a path as an OpenEvent.
-///
-/// This helper canonicalizes the path (resolving symlinks), checks if it's a file
-/// or directory, and returns the appropriate OpenEvent variant.
+/// Make `path` absolute against `cwd` and resolve symlinks where possible.
 ///
-/// # Returns
+/// Returns `None` for a relative path when the working directory is unknown
+/// (e.g. it was deleted).
+fn resolve_path(path: &Path, cwd: Option<&Path>) -> Option<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd?.join(path)
+    };
+    Some(absolute.canonicalize().unwrap_or(absolute))
+}
+
+/// Validate and categorize paths as OpenEvents, dropping repeated paths.
 ///
-/// - `Some(OpenEvent::File)` if the path is a file
-/// - `Some(OpenEvent::Directory)` if the path is a directory
-/// - `None` if the path is invalid (neither file nor directory)
+/// Relative paths are resolved against the current working directory.
+/// Paths are compared after canonicalization, so `a.md`, `./a.md` and a
+/// symlink to it open once, at the position of their first occurrence. A file
+/// passed together with its directory is kept: the directory becomes the
+/// sidebar root and the file still opens in a tab.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use std::path::Path;
-/// # use arto::ipc::{OpenEvent, validate_path};
+/// use std::path::PathBuf;
+/// # use arto::ipc::{OpenEvent, validate_paths};
 ///
-/// let event: Option<OpenEvent> = validate_path(Path::new("/path/to/file.md"));
+/// let events: Vec<OpenEvent> = validate_paths(&[PathBuf::from("/path/to/file.md")]);
 /// ```
-pub fn validate_path(path: impl AsRef<Path>) -> Option<OpenEvent> {
-    IpcMessage::from_path(path).map(|msg| msg.into_open_event())
+pub fn validate_paths(paths: &[PathBuf]) -> Vec<OpenEvent> {
+    messages_from_paths(paths, current_dir().as_deref())
+        .into_iter()
+        .map(IpcMessage::into_open_event)
+        .collect()
+}
+
+/// Working directory of this process, used to resolve relative paths.
+fn current_dir() -> Option<PathBuf> {
+    std::env::current_dir()
+        .inspect_err(|e| tracing::warn!(?e, "Failed to get the current working directory"))
+        .ok()
+}
+
+/// Build File/Directory messages for valid paths, skipping duplicates.
+fn messages_from_paths(paths: &[PathBuf], cwd: Option<&Path>) -> Vec<IpcMessage> {
+    let mut seen = HashSet::new();
+    paths
+        .iter()
+        .filter_map(|path| IpcMessage::from_path(path, cwd))
+        .filter(|message| match message {
+            IpcMessage::File { path } | IpcMessage::Directory { path } => {
+                let is_new = seen.insert(path.clone());
+                if !is_new {
+                    tracing::debug!(?path, "Skipping duplicate path");
+                }
+                is_new
+            }
+            IpcMessage::Reopen => true,
+        })
+        .collect()
 }
 
 /// Result of trying to send paths to an existing instance.
@@ -482,11 +578,12 @@ fn send_messages_to_primary(mut stream: Stream, paths: &[PathBuf]) -> std::io::R
     // Set write timeout to avoid hanging if primary is stuck
     set_socket_timeout(&stream, IPC_TIMEOUT);
 
-    // Build messages to send
+    // Build messages to send; relative paths are resolved here, in the
+    // directory this instance was launched from
     let mut messages: Vec<IpcMessage> = if paths.is_empty() {
         vec![IpcMessage::Reopen]
     } else {
-        paths.iter().filter_map(IpcMessage::from_path).collect()
+        messages_from_paths(paths, current_dir().as_deref())
     };
 
     // If all paths were invalid (filtered out), send Reopen to activate the app
@@ -791,9 +888,10 @@ fn handle_client_connection(stream: Stream) {
 
         tracing::debug!(?message, "Received IPC message");
 
-        let event = message.into_open_event();
-        push_event(event);
-        received_events = true;
+        if let Some(event) = message.into_received_event() {
+            push_event(event);
+            received_events = true;
+        }
     }
 
     // Wake main thread once after processing all messages from this client
@@ -944,6 +1042,100 @@ mod tests {
         assert!(!is_address_in_use(&err));
     }
 
+    #[test]
+    fn test_validate_paths_skips_duplicates() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().canonicalize().unwrap();
+        let file = dir.join("a.md");
+        let other = dir.join("b.md");
+        std::fs::write(&file, "# A").unwrap();
+        std::fs::write(&other, "# B").unwrap();
+
+        let events = validate_paths(&[
+            file.clone(),
+            other.clone(),
+            dir.join(".").join("a.md"),
+            dir.clone(),
+            file.clone(),
+            dir.join("missing.md"),
+        ]);
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], OpenEvent::File(p) if *p == file));
+        assert!(matches!(&events[1], OpenEvent::File(p) if *p == other));
+        assert!(matches!(&events[2], OpenEvent::Directory(p) if *p == dir));
+    }
+
+    #[test]
+    fn test_messages_from_paths_resolves_relative_paths_against_cwd() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().canonicalize().unwrap();
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        let file = dir.join("sub").join("doc.md");
+        std::fs::write(&file, "# Doc").unwrap();
+
+        let messages = messages_from_paths(
+            &[PathBuf::from("./sub/doc.md"), PathBuf::from("sub")],
+            Some(&dir),
+        );
+
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(&messages[0], IpcMessage::File { path } if *path == file));
+        assert!(matches!(&messages[1], IpcMessage::Directory { path } if *path == dir.join("sub")));
+
+        // Without a working directory relative paths can't be resolved
+        assert!(messages_from_paths(&[PathBuf::from("./sub/doc.md")], None).is_empty());
+        assert_eq!(messages_from_paths(&[file], None).len(), 1);
+    }
+
+    #[test]
+    fn test_relative_path_resolved_by_sender_across_ipc() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let sender_cwd = temp.path().canonicalize().unwrap();
+        std::fs::create_dir(sender_cwd.join("sub")).unwrap();
+        let file = sender_cwd.join("sub").join("doc.md");
+        std::fs::write(&file, "# Doc").unwrap();
+
+        // The secondary resolves the path in its own working directory...
+        let lines: Vec<String> =
+            messages_from_paths(&[PathBuf::from("./sub/doc.md")], Some(&sender_cwd))
+                .iter()
+                .map(|message| serde_json::to_string(message).unwrap())
+                .collect();
+
+        // ...so the primary opens the intended file wherever it runs
+        let events: Vec<OpenEvent> = lines
+            .iter()
+            .map(|line| serde_json::from_str::<IpcMessage>(line).unwrap())
+            .filter_map(IpcMessage::into_received_event)
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], OpenEvent::File(p) if *p == file));
+    }
+
+    #[test]
+    fn test_into_received_event_skips_relative_paths() {
+        let relative: IpcMessage =
+            serde_json::from_str(r#"{"type":"file","path":"sub/doc.md"}"#).unwrap();
+        assert!(relative.into_received_event().is_none());
+
+        let relative_dir: IpcMessage =
+            serde_json::from_str(r#"{"type":"directory","path":"sub"}"#).unwrap();
+        assert!(relative_dir.into_received_event().is_none());
+
+        let absolute: IpcMessage =
+            serde_json::from_str(r#"{"type":"file","path":"/path/to/file.md"}"#).unwrap();
+        assert!(matches!(
+            absolute.into_received_event(),
+            Some(OpenEvent::File(p)) if p == Path::new("/path/to/file.md")
+        ));
+
+        assert!(matches!(
+            IpcMessage::Reopen.into_received_event(),
+            Some(OpenEvent::Reopen)
+        ));
+    }
+
     #[test]
     fn test_event_queue_fifo_ordering() {
         // Drain any leftover events from other tests (global static is shared)
@@ -0,0 +1,253 @@
+//! Markdown linting for the Lint panel.
+//!
+//! A single pass over the parsed document reports common authoring problems
+//! with the 1-based source line they occur on. Each check can be turned off
+//! individually in [`LintConfig`].
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::collections::HashSet;
+
+use crate::config::LintConfig;
+
+/// How serious a lint issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSeverity {
+    Warning,
+    Info,
+}
+
+impl LintSeverity {
+    /// CSS class suffix and label for the panel
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// A single problem found in a document
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    /// 1-based source line
+    pub line: usize,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+/// Lint a markdown document, returning issues sorted by line
+pub fn lint_markdown(markdown: &str, config: &LintConfig) -> Vec<LintIssue> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(markdown.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset);
+
+    let mut issues = Vec::new();
+    if config.trailing_whitespace {
+        lint_trailing_whitespace(markdown, &mut issues);
+    }
+
+    // Frontmatter is parsed as a metadata block, so offsets match file lines
+    let parser = Parser::new_ext(markdown, Options::all()).into_offset_iter();
+
+    let mut previous_level: Option<usize> = None;
+    let mut heading: Option<(usize, String)> = None;
+    let mut seen_headings = HashSet::new();
+    let mut image: Option<(usize, bool)> = None;
+    let mut link_depth = 0;
+
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let level = heading_level(level);
+                if config.heading_increment {
+                    if let Some(previous) = previous_level.filter(|&p| level > p + 1) {
+                        issues.push(LintIssue {
+                            line: line_of(range.start),
+                            severity: LintSeverity::Warning,
+                            message: format!(
+                                "Heading level jumps from H{} to H{}",
+                                previous, level
+                            ),
+                        });
+                    }
+                }
+                previous_level = Some(level);
+                heading = Some((line_of(range.start), String::new()));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((line, text)) = heading.take() {
+                    let text = text.trim().to_string();
+                    if config.duplicate_headings
+                        && !text.is_empty()
+                        && !seen_headings.insert(text.to_lowercase())
+                    {
+                        issues.push(LintIssue {
+                            line,
+                            severity: LintSeverity::Info,
+                            message: format!("Duplicate heading \"{}\"", text),
+                        });
+                    }
+                }
+            }
+            Event::Start(Tag::Image { .. }) => {
+                image = Some((line_of(range.start), false));
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some((line, false)) = image.take() {
+                    if config.missing_alt_text {
+                        issues.push(LintIssue {
+                            line,
+                            severity: LintSeverity::Warning,
+                            message: "Image has no alt text".to_string(),
+                        });
+                    }
+                }
+            }
+            Event::Start(Tag::Link { .. }) => link_depth += 1,
+            Event::End(TagEnd::Link) => link_depth -= 1,
+            Event::Text(text) => {
+                if let Some((_, has_alt)) = image.as_mut() {
+                    *has_alt |= !text.trim().is_empty();
+                }
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&text);
+                }
+                if config.bare_urls && link_depth == 0 && image.is_none() {
+                    if let Some(position) = find_bare_url(&text) {
+                        issues.push(LintIssue {
+                            line: line_of(range.start + position),
+                            severity: LintSeverity::Warning,
+                            message: "Bare URL; wrap it in <...> or use a link".to_string(),
+                        });
+                    }
+                }
+            }
+            Event::Code(code) => {
+                if let Some((_, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(&code);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Report lines ending in whitespace; exactly two trailing spaces are a
+/// hard line break and therefore allowed
+fn lint_trailing_whitespace(markdown: &str, issues: &mut Vec<LintIssue>) {
+    for (index, line) in markdown.lines().enumerate() {
+        let trailing = &line[line.trim_end().len()..];
+        if !trailing.is_empty() && trailing != "  " && !line.trim().is_empty() {
+            issues.push(LintIssue {
+                line: index + 1,
+                severity: LintSeverity::Info,
+                message: "Trailing whitespace".to_string(),
+            });
+        }
+    }
+}
+
+/// Byte offset of the first plain-text URL in a text event
+fn find_bare_url(text: &str) -> Option<usize> {
+    ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| text.find(scheme))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn enabled() -> LintConfig {
+        LintConfig {
+            enabled: true,
+            ..LintConfig::default()
+        }
+    }
+
+    fn messages(issues: &[LintIssue]) -> Vec<(usize, &str)> {
+        issues
+            .iter()
+            .map(|issue| (issue.line, issue.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_markdown() {
+        let markdown = indoc! {"
+            ---
+            title: Doc
+            ---
+            # Title
+
+            ### Skipped level
+
+            ![](image.png) ![Alt](other.png)
+
+            See https://example.com or <https://example.org> or [link](https://example.net).
+
+            ## Title
+        "};
+
+        let issues = lint_markdown(markdown, &enabled());
+
+        assert_eq!(
+            messages(&issues),
+            vec![
+                (6, "Heading level jumps from H1 to H3"),
+                (8, "Image has no alt text"),
+                (10, "Bare URL; wrap it in <...> or use a link"),
+                (12, "Duplicate heading \"Title\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_trailing_whitespace() {
+        let markdown = "Hard break  \nTrailing space \nTab\t\nClean\n";
+        let issues = lint_markdown(markdown, &enabled());
+
+        assert_eq!(
+            messages(&issues),
+            vec![(2, "Trailing whitespace"), (3, "Trailing whitespace")]
+        );
+    }
+
+    #[test]
+    fn test_lint_checks_can_be_disabled() {
+        let markdown = "# A\n\n### B \n\n![](x.png)\n";
+        let config = LintConfig {
+            enabled: true,
+            missing_alt_text: false,
+            heading_increment: false,
+            trailing_whitespace: false,
+            bare_urls: false,
+            duplicate_headings: false,
+        };
+        assert!(lint_markdown(markdown, &config).is_empty());
+
+        // Nothing is reported while linting is disabled
+        assert!(lint_markdown(markdown, &LintConfig::default()).is_empty());
+    }
+}
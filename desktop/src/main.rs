@@ -1,23 +1,35 @@
+mod annotations;
 mod assets;
+mod backlinks;
 mod bookmarks;
 mod cache;
 mod components;
 mod config;
+mod directory_views;
 mod drag;
+mod emoji;
 mod events;
+mod export;
 mod history;
 mod ipc;
+mod lint;
 mod markdown;
 mod menu;
+mod partials;
 mod pinned_search;
+mod read_marks;
+mod rst;
+mod sanitize;
 mod state;
+mod syntax_highlight;
+mod tags;
 mod theme;
 mod utils;
 mod watcher;
 mod window;
 
-use clap::Parser;
-use dioxus::desktop::tao::event::{Event, WindowEvent};
+use clap::{CommandFactory, Parser};
+use dioxus::desktop::tao::event::{Event, StartCause, WindowEvent};
 use std::path::PathBuf;
 use tracing_subscriber::filter::EnvFilter;
 use tracing_subscriber::prelude::*;
@@ -43,12 +55,31 @@ const VERSION: &str = concat!(
         \x20 arto                     Launch Arto (shows welcome screen)\n\
         \x20 arto README.md           Open a specific file\n\
         \x20 arto docs/               Open a directory in the file explorer\n\
-        \x20 arto file1.md file2.md   Open multiple files in tabs"
+        \x20 arto file1.md file2.md   Open multiple files in tabs\n\
+        \x20 arto --theme dark --profile writing README.md\n\
+        \x20                          Open with a theme and reader profile"
 )]
+#[command(group(
+    clap::ArgGroup::new("launch_settings")
+        .args(["theme", "profile"])
+        .multiple(true)
+))]
 struct Cli {
     /// Files or directories to open
     #[arg()]
     paths: Vec<PathBuf>,
+
+    /// Theme for the window opened by this launch
+    #[arg(long, value_parser = ["auto", "light", "dark"])]
+    theme: Option<String>,
+
+    /// Reader profile (by name) for the window opened by this launch
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Save --theme and --profile as the defaults for new windows
+    #[arg(long, requires = "launch_settings")]
+    save: bool,
 }
 
 const DEFAULT_LOGLEVEL: &str = if cfg!(debug_assertions) {
@@ -64,6 +95,9 @@ fn main() {
     // Try to send paths to existing instance via IPC
     // If successful, exit immediately without initializing anything else
     if let ipc::SendResult::Sent = ipc::try_send_to_existing_instance(&cli.paths) {
+        if cli.theme.is_some() || cli.profile.is_some() {
+            eprintln!("Arto is already running; --theme and --profile only apply on launch");
+        }
         std::process::exit(0);
     }
 
@@ -73,18 +107,25 @@ fn main() {
     }
     init_tracing();
 
+    apply_launch_settings(&cli);
+
     // Clear stale WebView cache when build changes (app upgrade via Homebrew, etc.)
     cache::clear_stale_webview_cache_if_needed();
 
     // Start IPC server to accept connections from future instances
     ipc::start_ipc_server();
 
+    // Validate CLI paths (invalid and repeated paths are skipped, already logged by validate_paths)
+    let events = ipc::validate_paths(&cli.paths);
+
+    // The first window opens the first event; a directory may have remembered geometry
+    let initial_directory = match events.first() {
+        Some(ipc::OpenEvent::Directory(dir)) => Some(dir.clone()),
+        _ => None,
+    };
+
     // Push CLI paths to IPC event queue (MainApp will pop the first one)
-    for path in cli.paths {
-        let event = match ipc::validate_path(&path) {
-            Some(event) => event,
-            None => continue, // Invalid path, already logged by validate_path
-        };
+    for event in events {
         tracing::debug!(?event, "Pushing CLI path to IPC event queue");
         ipc::push_event(event);
     }
@@ -92,23 +133,28 @@ fn main() {
     let menu = menu::build_menu();
 
     // Get window parameters for first window from preferences
-    let params = window::CreateMainWindowConfigParams::from_preferences(true);
+    let mut params = window::CreateMainWindowConfigParams::from_preferences(true);
+    if let Some(dir) = &initial_directory {
+        params = params.with_directory_geometry(dir);
+    }
 
     let config = window::create_main_window_config(&params)
         .with_custom_event_handler(move |event, _target| {
             match event {
+                Event::NewEvents(StartCause::Init) => {
+                    // The app delegate exists once the event loop has started
+                    #[cfg(target_os = "macos")]
+                    menu::install_quit_confirmation();
+                }
                 Event::Opened { urls, .. } => {
                     // Handle file/directory open events from Finder
                     tracing::debug!(url_count = urls.len(), "Event::Opened received");
                     for url in urls {
                         match url.to_file_path() {
                             Ok(path) => {
-                                let event = if path.is_dir() {
-                                    ipc::OpenEvent::Directory(path)
-                                } else {
-                                    ipc::OpenEvent::File(path)
-                                };
-                                ipc::push_event(event);
+                                for event in ipc::validate_paths(&[path]) {
+                                    ipc::push_event(event);
+                                }
                             }
                             Err(_) => {
                                 tracing::info!(
@@ -165,6 +211,49 @@ fn main() {
     ipc::cleanup_socket();
 }
 
+/// Resolve `--theme` and `--profile` into overrides for the first window,
+/// saving them as defaults when `--save` is given
+fn apply_launch_settings(cli: &Cli) {
+    let theme = cli.theme.as_deref().map(theme::Theme::from);
+    let profile = cli.profile.as_deref().map(|name| {
+        config::CONFIG
+            .read()
+            .reader_profiles
+            .find(name)
+            .cloned()
+            .unwrap_or_else(|| {
+                Cli::command()
+                    .error(
+                        clap::error::ErrorKind::InvalidValue,
+                        format!("unknown reader profile '{name}'"),
+                    )
+                    .exit()
+            })
+    });
+
+    if cli.save {
+        let mut cfg = config::CONFIG.write();
+        if let Some(theme) = theme {
+            cfg.theme.default_theme = theme;
+        }
+        if let Some(profile) = &profile {
+            cfg.reader_profiles.default_profile = Some(profile.name.clone());
+        }
+        if let Err(e) = cfg.save() {
+            tracing::error!(%e, "Failed to save launch settings");
+        }
+    }
+
+    // An explicit --theme wins over the profile's theme
+    let profile = profile.map(|mut profile| {
+        if theme.is_some() {
+            profile.theme = theme;
+        }
+        profile
+    });
+    window::settings::set_launch_overrides(window::settings::LaunchOverrides { theme, profile });
+}
+
 fn init_tracing() {
     let silence_filter = tracing_subscriber::filter::filter_fn(|metadata| {
         // Filter out specific error from dioxus_core::properties:136
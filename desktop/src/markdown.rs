@@ -1,9 +1,59 @@
 use anyhow::Result;
 use base64::{engine::general_purpose, Engine as _};
+use lol_html::html_content::{ContentType, Element};
 use lol_html::{element, HtmlRewriter, Settings};
-use pulldown_cmark::{html, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use parking_lot::Mutex;
+use pulldown_cmark::{
+    html, CodeBlockKind, Event, HeadingLevel, LinkType, Options, Parser, Tag, TagEnd,
+};
 use serde_yaml::Value as YamlValue;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+use crate::config::{
+    AlertTitleCase, AlertsConfig, AnchorsConfig, CaptionsConfig, CodeLinesConfig,
+    FrontmatterConfig, FrontmatterOpenMode, LeadingHeadingStyle, MissingImageMode, SnippetsConfig,
+    SyntaxHighlightConfig, TaskProgressMode, WideImageMode, CONFIG,
+};
+use crate::emoji;
+use crate::partials::is_partial_document;
+use crate::sanitize::sanitize_inline_svg;
+use crate::syntax_highlight;
+use crate::utils::css::scope_css;
+use crate::utils::file::find_moved_file;
+use crate::utils::media::{image_type, issued_image_path, media_type, media_url};
+
+/// Prefix of the HTML comments used to carry source line numbers through rendering
+const SOURCE_LINE_MARKER: &str = "<!--source-line:";
+
+/// Banner shown above partials (include-only files) opened directly
+const PARTIAL_BANNER: &str = r#"<div class="partial-banner" role="note">This file is a partial, meant to be included in other documents.</div>"#;
+
+/// Frontmatter key listing stylesheets for the document, relative to it
+const DOCUMENT_CSS_KEY: &str = "arto-css";
+
+/// Frontmatter key with inline CSS for the document
+const DOCUMENT_STYLE_KEY: &str = "arto-style";
+
+/// Largest stylesheet a document may reference
+const MAX_DOCUMENT_CSS_BYTES: u64 = 256 * 1024;
+
+/// Frontmatter tables the user expanded or collapsed, by document (session only)
+static FRONTMATTER_TOGGLES: LazyLock<Mutex<HashMap<PathBuf, bool>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Maximum nesting of snippets referencing other snippets
+const MAX_SNIPPET_DEPTH: usize = 8;
+
+/// Alt text markers opting an image into or out of dark-mode inversion, with
+/// the class each one adds
+const DARK_IMAGE_MARKERS: [(&str, &str); 2] =
+    [("#noinvert", "dark-no-invert"), ("#invert", "dark-invert")];
 
 /// Information about a heading extracted from markdown
 #[derive(Debug, Clone, PartialEq)]
@@ -16,17 +66,20 @@ pub struct HeadingInfo {
     pub id: String,
 }
 
-/// Generate a URL-safe slug from heading text
+/// Generate a slug from heading text
+///
+/// Like GitHub, letters and digits of any script are kept (lowercased), so
+/// `日本語` stays `日本語` rather than becoming empty.
 fn generate_slug(text: &str) -> String {
     text.to_lowercase()
         .chars()
         .map(|c| {
-            if c.is_ascii_alphanumeric() {
+            if c.is_alphanumeric() {
                 c
             } else if c.is_whitespace() || c == '-' || c == '_' || c == '.' {
                 '-'
             } else {
-                // Skip other characters (including non-ASCII)
+                // Skip other characters (punctuation, symbols, emoji)
                 '\0'
             }
         })
@@ -38,6 +91,40 @@ fn generate_slug(text: &str) -> String {
         .join("-")
 }
 
+/// Unique heading IDs for one document, numbered like GitHub's
+/// (github-slugger): repeats of `overview` become `overview-1`,
+/// `overview-2`, ..., skipping numbers already taken by other headings
+struct Slugger {
+    occurrences: HashMap<String, usize>,
+    empty_fallback: String,
+}
+
+impl Slugger {
+    /// `empty_fallback` replaces slugs that come out empty (see
+    /// `AnchorsConfig::empty_slug_fallback`)
+    fn new(empty_fallback: &str) -> Self {
+        Self {
+            occurrences: HashMap::new(),
+            empty_fallback: generate_slug(empty_fallback),
+        }
+    }
+
+    fn slug(&mut self, text: &str) -> String {
+        let mut base = generate_slug(text);
+        if base.is_empty() {
+            base = self.empty_fallback.clone();
+        }
+        let mut slug = base.clone();
+        while self.occurrences.contains_key(&slug) {
+            let count = self.occurrences.entry(base.clone()).or_default();
+            *count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.occurrences.insert(slug.clone(), 0);
+        slug
+    }
+}
+
 /// Extract headings from markdown content
 pub fn extract_headings(markdown: &str) -> Vec<HeadingInfo> {
     let options = Options::all();
@@ -54,14 +141,17 @@ pub fn extract_headings(markdown: &str) -> Vec<HeadingInfo> {
     };
 
     // Process GitHub alerts (they contain their own parsing)
-    let processed = process_github_alerts(content);
+    let alerts = CONFIG.read().alerts.clone();
+    let processed = process_github_alerts(content, &alerts);
     let parser = Parser::new_ext(&processed, options);
 
     let mut headings = Vec::new();
     let mut current_level: Option<u8> = None;
     let mut current_text = String::new();
-    let mut slug_counts: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
+    // Like GitHub, IDs are made from shortcodes as written (`:rocket:` -> `rocket`)
+    let mut slug_text = String::new();
+    let mut slugger = Slugger::new(&CONFIG.read().anchors.empty_slug_fallback);
+    let emoji_shortcodes = CONFIG.read().emoji.shortcodes;
 
     for event in parser {
         match event {
@@ -75,29 +165,27 @@ pub fn extract_headings(markdown: &str) -> Vec<HeadingInfo> {
                     HeadingLevel::H6 => 6,
                 });
                 current_text.clear();
+                slug_text.clear();
             }
             Event::Text(text) if current_level.is_some() => {
-                current_text.push_str(&text);
+                if emoji_shortcodes {
+                    current_text.push_str(&emoji::replace_shortcodes(&text));
+                } else {
+                    current_text.push_str(&text);
+                }
+                slug_text.push_str(&text);
             }
             Event::Code(code) if current_level.is_some() => {
                 current_text.push_str(&code);
+                slug_text.push_str(&code);
             }
             Event::SoftBreak | Event::HardBreak if current_level.is_some() => {
                 current_text.push(' ');
+                slug_text.push(' ');
             }
             Event::End(TagEnd::Heading(_)) if current_level.is_some() => {
                 let level = current_level.take().unwrap();
-                let base_slug = generate_slug(&current_text);
-
-                // Handle duplicate slugs by appending a number
-                let id = if let Some(count) = slug_counts.get(&base_slug) {
-                    let new_count = count + 1;
-                    slug_counts.insert(base_slug.clone(), new_count);
-                    format!("{}-{}", base_slug, new_count)
-                } else {
-                    slug_counts.insert(base_slug.clone(), 0);
-                    base_slug
-                };
+                let id = slugger.slug(&slug_text);
 
                 headings.push(HeadingInfo {
                     level,
@@ -117,7 +205,8 @@ pub fn render_to_html(markdown: impl AsRef<str>, base_path: impl AsRef<Path>) ->
     let markdown = markdown.as_ref();
     let base_path = base_path.as_ref();
 
-    // Enable GitHub Flavored Markdown options
+    // Enable GitHub Flavored Markdown options, plus the parser's other
+    // extensions such as Pandoc-style definition lists (`: definition` lines)
     let options = Options::all();
 
     // Get base directory for resolving relative paths
@@ -127,20 +216,43 @@ pub fn render_to_html(markdown: impl AsRef<str>, base_path: impl AsRef<Path>) ->
         .unwrap_or_else(|| PathBuf::from("."));
 
     // Extract frontmatter if present
-    let (frontmatter_html, content) = extract_and_render_frontmatter(markdown);
+    let frontmatter_config = document_frontmatter_config(base_path);
+    let (frontmatter_html, content) =
+        extract_and_render_frontmatter(markdown, &frontmatter_config, &base_dir);
 
-    // Process GitHub alerts
-    let processed_markdown = process_github_alerts(&content);
+    // Expand snippets and wiki links, and process GitHub alerts
+    let snippets = CONFIG.read().snippets.clone();
+    let content = expand_snippets(&content, &snippets);
+    let content = process_wiki_links(&content, &base_dir);
+    let alerts = CONFIG.read().alerts.clone();
+    let processed_markdown = process_github_alerts(&content, &alerts);
 
     // Parse Markdown and process blocks
     let parser = Parser::new_ext(&processed_markdown, options);
     let parser = process_code_blocks(parser, "mermaid");
     let parser = process_code_blocks(parser, "math");
+    let parser = process_geojson_blocks(parser, CONFIG.read().geojson.enabled);
     let parser = process_math_expressions(parser);
+    let events = process_captions(parser.collect(), &CONFIG.read().captions);
+    let events = process_emoji_shortcodes(events, CONFIG.read().emoji.shortcodes);
+    let events = process_spoilers(events, CONFIG.read().spoilers.enabled);
+    let events = process_footnotes(events);
+    let events = process_task_progress(events, CONFIG.read().task_lists.progress);
+    let events = process_code_copy_buttons(events);
+    let syntax_highlight = CONFIG.read().syntax_highlight.clone();
+    let events = process_code_lines(events, &CONFIG.read().code_lines, &syntax_highlight);
+    let events = process_syntax_highlighting(events, &syntax_highlight);
 
     // Convert to HTML
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(
+        &mut html_output,
+        process_code_block_flags(events.into_iter(), syntax_highlight.disabled_languages),
+    );
+    if CONFIG.read().link_definitions.show {
+        html_output.extend(link_definitions_html(&processed_markdown));
+    }
+    let html_output = sanitize_inline_svg(&html_output);
 
     // Post-process HTML to handle all img and anchor tags (both from Markdown syntax and HTML tags)
     let html_output = post_process_html_tags(&html_output, base_dir.as_path());
@@ -155,35 +267,322 @@ pub fn render_to_html(markdown: impl AsRef<str>, base_path: impl AsRef<Path>) ->
     Ok(final_output)
 }
 
-/// Extract frontmatter from markdown and render it as an HTML table
-fn extract_and_render_frontmatter(markdown: &str) -> (String, String) {
+/// Title and opening text of a document, shown when hovering links to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentPreview {
+    /// Text of the first H1, if any
+    pub title: Option<String>,
+    /// Plain text of the first paragraph, cut at `max_chars`
+    pub excerpt: String,
+}
+
+/// Build the link preview of a document
+pub fn document_preview(markdown: &str, max_chars: usize) -> DocumentPreview {
+    let title = extract_headings(markdown)
+        .into_iter()
+        .find(|heading| heading.level == 1)
+        .map(|heading| heading.text);
+
+    let content = split_frontmatter(markdown).map_or(markdown, |(_, content)| content);
+    let mut excerpt = String::new();
+    let mut in_paragraph = false;
+    for event in Parser::new_ext(content, Options::all()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph) if in_paragraph => break,
+            Event::Text(text) | Event::Code(text) if in_paragraph => excerpt.push_str(&text),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => excerpt.push(' '),
+            _ => {}
+        }
+    }
+
+    DocumentPreview {
+        title,
+        excerpt: truncate_excerpt(excerpt.trim(), max_chars),
+    }
+}
+
+/// Cut text to at most `max_chars` characters, preferring a word boundary
+fn truncate_excerpt(text: &str, max_chars: usize) -> String {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text.to_string();
+    };
+    let head = &text[..cut];
+    let head = head
+        .rfind(char::is_whitespace)
+        .map_or(head, |space| &head[..space]);
+    format!("{}…", head.trim_end())
+}
+
+/// Split leading YAML frontmatter from markdown.
+///
+/// Returns the parsed YAML and the remaining content, or `None` if the
+/// document has no (valid) frontmatter block.
+fn split_frontmatter(markdown: &str) -> Option<(YamlValue, &str)> {
     // Check if markdown starts with frontmatter delimiter
     if !markdown.starts_with("---") {
-        return (String::new(), markdown.to_string());
+        return None;
     }
 
     // Find the closing delimiter
     let rest = &markdown[3..];
-    let Some(end_pos) = rest.find("\n---") else {
-        return (String::new(), markdown.to_string());
-    };
+    let end_pos = rest.find("\n---")?;
 
     let frontmatter_str = rest[..end_pos].trim();
     let content = rest[end_pos + 4..].trim_start();
 
     // Parse YAML
-    let Ok(yaml) = serde_yaml::from_str::<YamlValue>(frontmatter_str) else {
+    let yaml = serde_yaml::from_str::<YamlValue>(frontmatter_str).ok()?;
+    Some((yaml, content))
+}
+
+/// Remember that the user expanded (`open`) or collapsed the frontmatter of
+/// a document, so re-rendering it keeps that state
+pub fn remember_frontmatter_toggle(document: &Path, open: bool) {
+    FRONTMATTER_TOGGLES
+        .lock()
+        .insert(document.to_path_buf(), open);
+}
+
+/// Frontmatter settings for a document, with its remembered toggle applied
+fn document_frontmatter_config(document: &Path) -> FrontmatterConfig {
+    let mut config = CONFIG.read().frontmatter.clone();
+    if let Some(&open) = FRONTMATTER_TOGGLES.lock().get(document) {
+        config.open = if open {
+            FrontmatterOpenMode::Open
+        } else {
+            FrontmatterOpenMode::Closed
+        };
+    }
+    config
+}
+
+/// Extract frontmatter from markdown and render it as an HTML table
+///
+/// With `document_css` enabled, the document's own CSS (`arto-css`,
+/// `arto-style`) is rendered as a `<style>` scoped to the rendered content,
+/// ahead of the table.
+fn extract_and_render_frontmatter(
+    markdown: &str,
+    config: &FrontmatterConfig,
+    base_dir: &Path,
+) -> (String, String) {
+    let Some((yaml, content)) = split_frontmatter(markdown) else {
         return (String::new(), markdown.to_string());
     };
 
     // Render frontmatter as table
-    let html = render_frontmatter_table(&yaml);
+    let mut html = render_frontmatter_table(&yaml, config);
+
+    // Related documents stay visible while the table is collapsed
+    if let YamlValue::Mapping(mapping) = &yaml {
+        let related = mapping
+            .iter()
+            .find(|(key, _)| yaml_to_string(key) == config.related_key)
+            .map(|(_, value)| yaml_tags(value))
+            .unwrap_or_default();
+        if !related.is_empty() {
+            html.push_str(&render_related_links(&related));
+        }
+    }
+
+    if config.document_css {
+        if let YamlValue::Mapping(mapping) = &yaml {
+            html.insert_str(0, &render_document_css(mapping, base_dir));
+        }
+    }
 
     (html, content.to_string())
 }
 
+/// Render the CSS a document specifies in its frontmatter as a `<style>`
+///
+/// Stylesheets are read relative to `base_dir` and must stay inside it, and
+/// all rules are scoped to `.markdown-body` so the app UI is left alone.
+fn render_document_css(mapping: &serde_yaml::Mapping, base_dir: &Path) -> String {
+    let mut css = String::new();
+    for (key, value) in mapping {
+        let values = match value {
+            YamlValue::Sequence(seq) => seq.iter().map(yaml_to_string).collect(),
+            value => vec![yaml_to_string(value)],
+        };
+        match yaml_to_string(key).as_str() {
+            DOCUMENT_CSS_KEY => {
+                for href in values {
+                    if let Some(sheet) = read_document_stylesheet(href.trim(), base_dir) {
+                        css.push_str(&sheet);
+                        css.push('\n');
+                    }
+                }
+            }
+            DOCUMENT_STYLE_KEY => {
+                for inline in values {
+                    css.push_str(&inline);
+                    css.push('\n');
+                }
+            }
+            _ => {}
+        }
+    }
+    if css.trim().is_empty() {
+        return String::new();
+    }
+    // Keep the CSS from closing the style element early
+    let scoped = scope_css(&css, ".markdown-body").replace("</", "<\\/");
+    format!(r#"<style class="document-css">{}</style>"#, scoped)
+}
+
+/// Read a stylesheet referenced by a document, if it is a `.css` file inside `base_dir`
+fn read_document_stylesheet(href: &str, base_dir: &Path) -> Option<String> {
+    let root = base_dir.canonicalize().ok()?;
+    let path = root.join(href).canonicalize().ok()?;
+    let is_css = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("css"));
+    if !is_css || !path.starts_with(&root) {
+        tracing::warn!(
+            ?path,
+            "Ignoring document stylesheet outside the document directory"
+        );
+        return None;
+    }
+    if std::fs::metadata(&path).ok()?.len() > MAX_DOCUMENT_CSS_BYTES {
+        tracing::warn!(?path, "Ignoring document stylesheet that is too large");
+        return None;
+    }
+    std::fs::read_to_string(&path).ok()
+}
+
+/// Extract the tags listed under `tags_key` in the markdown's frontmatter
+pub fn extract_frontmatter_tags(markdown: &str, tags_key: &str) -> Vec<String> {
+    let Some((YamlValue::Mapping(mapping), _)) = split_frontmatter(markdown) else {
+        return Vec::new();
+    };
+    mapping
+        .iter()
+        .find(|(key, _)| yaml_to_string(key) == tags_key)
+        .map(|(_, value)| yaml_tags(value))
+        .unwrap_or_default()
+}
+
+/// Local link targets of a document, as written: markdown link destinations
+/// and the documents listed under `related_key` in its frontmatter
+///
+/// Fragments and queries are dropped and percent-escapes decoded; remote
+/// URLs and in-page links are skipped.
+pub fn extract_local_links(markdown: &str, related_key: &str) -> Vec<String> {
+    let mut links = extract_frontmatter_tags(markdown, related_key);
+    let content = split_frontmatter(markdown).map_or(markdown, |(_, content)| content);
+    for event in Parser::new_ext(content, Options::all()) {
+        if let Event::Start(Tag::Link { dest_url, .. }) = event {
+            links.push(dest_url.to_string());
+        }
+    }
+    links
+        .iter()
+        .filter_map(|link| local_link_path(link))
+        .collect()
+}
+
+/// The file path part of a local link, or `None` for remote and in-page links
+fn local_link_path(link: &str) -> Option<String> {
+    let path = link.split(['#', '?']).next()?.trim();
+    if path.is_empty() || is_remote_link(path) {
+        return None;
+    }
+    Some(
+        percent_encoding::percent_decode_str(path)
+            .decode_utf8_lossy()
+            .into_owned(),
+    )
+}
+
+fn is_remote_link(link: &str) -> bool {
+    link.contains("://") || link.starts_with("mailto:")
+}
+
+/// Check whether frontmatter sets `key` to `true` (e.g. `partial: true`)
+pub fn frontmatter_flag(markdown: &str, key: &str) -> bool {
+    let Some((YamlValue::Mapping(mapping), _)) = split_frontmatter(markdown) else {
+        return false;
+    };
+    mapping
+        .iter()
+        .any(|(k, value)| yaml_to_string(k) == key && value.as_bool() == Some(true))
+}
+
+/// Collect tag names from a YAML value.
+///
+/// Accepts both a list (`tags: [rust, async]`) and a comma-separated
+/// string (`tags: rust, async`).
+fn yaml_tags(value: &YamlValue) -> Vec<String> {
+    match value {
+        YamlValue::Sequence(seq) => seq
+            .iter()
+            .map(|v| yaml_to_string(v).trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        YamlValue::String(s) => s
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        YamlValue::Tagged(tagged) => yaml_tags(&tagged.value),
+        _ => Vec::new(),
+    }
+}
+
+/// Render tags as clickable chips (click handled by `window.handleFrontmatterTagClick`)
+fn render_tag_chips(tags: &[String]) -> String {
+    let chips: Vec<String> = tags
+        .iter()
+        .map(|tag| {
+            format!(
+                r#"<button type="button" class="frontmatter-tag" data-tag="{}" onclick="window.handleFrontmatterTagClick?.(this.dataset.tag)">{}</button>"#,
+                html_escape::encode_double_quoted_attribute(tag),
+                html_escape::encode_text(tag)
+            )
+        })
+        .collect();
+    format!(
+        r#"<span class="frontmatter-tags">{}</span>"#,
+        chips.join("")
+    )
+}
+
+/// Render related documents as a bar of links
+///
+/// Local documents are routed like markdown links in the content (see
+/// [`markdown_link_onmousedown`]); remote URLs are plain links.
+fn render_related_links(links: &[String]) -> String {
+    let chips: Vec<String> = links
+        .iter()
+        .map(|link| {
+            let href = html_escape::encode_double_quoted_attribute(link);
+            let text = html_escape::encode_text(link);
+            if is_remote_link(link) {
+                format!(
+                    r#"<a class="frontmatter-tag frontmatter-related-link" href="{}">{}</a>"#,
+                    href, text
+                )
+            } else {
+                format!(
+                    r#"<span class="md-link frontmatter-tag frontmatter-related-link" data-md-link="{}" onmousedown="{}">{}</span>"#,
+                    href,
+                    html_escape::encode_double_quoted_attribute(&markdown_link_onmousedown(link)),
+                    text
+                )
+            }
+        })
+        .collect();
+    format!(
+        r#"<nav class="frontmatter-related"><span class="frontmatter-related-label">Related</span>{}</nav>"#,
+        chips.join("")
+    )
+}
+
 /// Render YAML frontmatter as an HTML table
-fn render_frontmatter_table(yaml: &YamlValue) -> String {
+fn render_frontmatter_table(yaml: &YamlValue, config: &FrontmatterConfig) -> String {
     let YamlValue::Mapping(mapping) = yaml else {
         return String::new();
     };
@@ -193,25 +592,50 @@ fn render_frontmatter_table(yaml: &YamlValue) -> String {
     }
 
     let mut rows = String::new();
+    let mut row_count = 0;
     for (key, value) in mapping {
         let key_str = yaml_to_string(key);
-        let value_str = render_yaml_value(value);
+        // Styling directives are applied, not shown
+        if key_str == DOCUMENT_CSS_KEY || key_str == DOCUMENT_STYLE_KEY {
+            continue;
+        }
+        let tags = if key_str == config.tags_key {
+            yaml_tags(value)
+        } else {
+            Vec::new()
+        };
+        let value_str = if tags.is_empty() {
+            render_yaml_value(value, 1, config)
+        } else {
+            render_tag_chips(&tags)
+        };
         rows.push_str(&format!(
             "<tr><th>{}</th><td>{}</td></tr>\n",
             html_escape::encode_text(&key_str),
             value_str
         ));
+        row_count += 1;
+    }
+
+    if rows.is_empty() {
+        return String::new();
     }
 
+    // The click handler reports manual toggles so they can be remembered
     format!(
-        r#"<details class="frontmatter">
-<summary class="frontmatter-summary">Frontmatter</summary>
+        r#"<details class="frontmatter"{}>
+<summary class="frontmatter-summary" onclick="window.handleFrontmatterToggle?.(!this.parentElement.open, event)">Frontmatter</summary>
 <table class="frontmatter-table">
 <tbody>
 {}
 </tbody>
 </table>
 </details>"#,
+        if config.starts_open(row_count) {
+            " open"
+        } else {
+            ""
+        },
         rows
     )
 }
@@ -234,7 +658,13 @@ fn yaml_to_string(value: &YamlValue) -> String {
 }
 
 /// Render a YAML value as HTML (with special handling for arrays and objects)
-fn render_yaml_value(value: &YamlValue) -> String {
+///
+/// `depth` is the nesting level of `value` (1 for a top-level frontmatter
+/// value). Mappings and lists nested deeper than `max_depth` are collapsed into
+/// an expandable summary showing the raw YAML, and lists longer than
+/// `max_list_items` put the remaining items behind a "Show all" toggle, so
+/// heavy frontmatter can't blow up the rendered table.
+fn render_yaml_value(value: &YamlValue, depth: usize, config: &FrontmatterConfig) -> String {
     match value {
         YamlValue::Null => "<span class=\"yaml-null\">null</span>".to_string(),
         YamlValue::Bool(b) => format!("<span class=\"yaml-bool\">{}</span>", b),
@@ -244,23 +674,47 @@ fn render_yaml_value(value: &YamlValue) -> String {
             if seq.is_empty() {
                 return "<span class=\"yaml-empty\">[]</span>".to_string();
             }
-            let items: Vec<String> = seq
-                .iter()
-                .map(|v| format!("<li>{}</li>", render_yaml_value(v)))
-                .collect();
-            format!("<ul class=\"yaml-list\">{}</ul>", items.join(""))
+            if depth > config.max_depth {
+                return render_collapsed_yaml(
+                    value,
+                    &format!("[…] {}", count_label(seq.len(), "item")),
+                );
+            }
+            let render_items = |items: &[YamlValue]| -> String {
+                items
+                    .iter()
+                    .map(|v| format!("<li>{}</li>", render_yaml_value(v, depth + 1, config)))
+                    .collect()
+            };
+            let shown = seq.len().min(config.max_list_items.max(1));
+            let (head, rest) = seq.split_at(shown);
+            let mut html = format!("<ul class=\"yaml-list\">{}</ul>", render_items(head));
+            if !rest.is_empty() {
+                html.push_str(&format!(
+                    "<details class=\"yaml-more\"><summary>Show all ({} more)</summary><ul class=\"yaml-list\">{}</ul></details>",
+                    rest.len(),
+                    render_items(rest)
+                ));
+            }
+            html
         }
         YamlValue::Mapping(mapping) => {
             if mapping.is_empty() {
                 return "<span class=\"yaml-empty\">{{}}</span>".to_string();
             }
+            if depth > config.max_depth {
+                return render_collapsed_yaml(
+                    value,
+                    &format!("{{…}} {}", count_label(mapping.len(), "key")),
+                );
+            }
             let rows: Vec<String> = mapping
                 .iter()
                 .map(|(k, v)| {
                     format!(
                         "<tr><th>{}</th><td>{}</td></tr>",
                         html_escape::encode_text(&yaml_to_string(k)),
-                        render_yaml_value(v)
+                        render_yaml_value(v, depth + 1, config)
                     )
                 })
                 .collect();
@@ -269,20 +723,55 @@ fn render_yaml_value(value: &YamlValue) -> String {
                 rows.join("")
             )
         }
-        YamlValue::Tagged(tagged) => render_yaml_value(&tagged.value),
+        YamlValue::Tagged(tagged) => render_yaml_value(&tagged.value, depth, config),
     }
 }
 
+/// "1 key", "3 keys"
+fn count_label(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Render a nested value past the depth limit as a summary that expands to
+/// its raw YAML
+fn render_collapsed_yaml(value: &YamlValue, summary: &str) -> String {
+    let yaml = serde_yaml::to_string(value).unwrap_or_default();
+    format!(
+        "<details class=\"yaml-collapsed\"><summary>{}</summary><pre>{}</pre></details>",
+        html_escape::encode_text(summary),
+        html_escape::encode_text(yaml.trim_end())
+    )
+}
+
 /// Get SVG icon placeholder for alert type (actual SVG injected by JavaScript)
 fn get_alert_icon_placeholder(alert_type: &str) -> String {
     format!(
         r#"<span class="alert-icon" data-alert-type="{}"></span>"#,
-        alert_type
+        html_escape::encode_double_quoted_attribute(alert_type)
     )
 }
 
-/// Check if a line starts a GitHub alert and return alert info
-fn parse_alert_start(line: &str) -> Option<(&'static str, &'static str, &str)> {
+/// Keyword, class and icon of an alert type
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AlertKind<'a> {
+    name: &'a str,
+    class: &'a str,
+    icon: &'a str,
+}
+
+/// Check if a line starts a GitHub alert and return its indentation, type
+/// and the text after the marker
+///
+/// Types from `alerts.types` are recognized besides the GitHub ones (and may
+/// redefine them); other keywords are left to render as a blockquote.
+fn parse_alert_start<'a>(
+    line: &'a str,
+    config: &'a AlertsConfig,
+) -> Option<(usize, AlertKind<'a>, &'a str)> {
     const ALERT_TYPES: [(&str, &str); 5] = [
         ("NOTE", "note"),
         ("TIP", "tip"),
@@ -291,50 +780,139 @@ fn parse_alert_start(line: &str) -> Option<(&'static str, &'static str, &str)> {
         ("CAUTION", "caution"),
     ];
 
-    for &(alert_name, alert_class) in &ALERT_TYPES {
-        if let Some(rest) = line.strip_prefix(&format!("> [!{}]", alert_name)) {
-            return Some((alert_name, alert_class, rest));
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    let (name, rest) = trimmed.strip_prefix("> [!")?.split_once(']')?;
+    let kind = match config.types.get_key_value(name) {
+        Some((name, custom)) if !custom.class.is_empty() => AlertKind {
+            name,
+            class: &custom.class,
+            icon: if custom.icon.is_empty() {
+                &custom.class
+            } else {
+                &custom.icon
+            },
+        },
+        _ => {
+            let &(name, class) = ALERT_TYPES
+                .iter()
+                .find(|(alert_name, _)| *alert_name == name)?;
+            AlertKind {
+                name,
+                class,
+                icon: class,
+            }
+        }
+    };
+    Some((indent, kind, rest))
+}
+
+/// Get the column where the content of a list item starts, if the line
+/// starts one
+fn list_item_content_indent(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start_matches(' ');
+    let indent = line.len() - trimmed.len();
+    let marker_len = if trimmed.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = trimmed.bytes().take_while(u8::is_ascii_digit).count();
+        if !(1..=9).contains(&digits) || !trimmed[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        digits + 1
+    };
+
+    let after_marker = &trimmed[marker_len..];
+    let spaces = after_marker.len() - after_marker.trim_start_matches(' ').len();
+    if spaces == 0 && !after_marker.is_empty() {
+        return None;
+    }
+    // Content indented by 5+ spaces is an indented code block one column in
+    let spaces = if (1..=4).contains(&spaces) { spaces } else { 1 };
+    Some(indent + marker_len + spaces)
+}
+
+/// Build the title shown at the top of an alert
+///
+/// An inline title (text after the marker) wins over a configured label for
+/// the alert type, which wins over the type name in the configured case.
+fn alert_title(
+    alert_name: &str,
+    alert_class: &str,
+    inline_title: Option<&str>,
+    config: &AlertsConfig,
+) -> String {
+    if let Some(title) = inline_title {
+        return title.to_string();
+    }
+    if let Some((_, label)) = config
+        .labels
+        .iter()
+        .find(|(alert_type, _)| alert_type.eq_ignore_ascii_case(alert_class))
+    {
+        return label.clone();
+    }
+    match config.title_case {
+        AlertTitleCase::Upper => alert_name.to_string(),
+        AlertTitleCase::Title => {
+            let mut chars = alert_class.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
         }
     }
-    None
 }
 
 /// Process a single alert block and return HTML lines and next index
+///
+/// `indent` is the whitespace before the alert's `>` markers; generated lines
+/// are indented the same way so an alert inside a list item stays there.
 fn process_alert_block(
     lines: &[&str],
     start_index: usize,
-    alert_name: &str,
-    alert_class: &str,
+    indent: &str,
+    kind: AlertKind,
     first_line_content: &str,
+    config: &AlertsConfig,
 ) -> (Vec<String>, usize) {
     let mut html_lines = Vec::new();
 
     // Alert opening tag
     html_lines.push(format!(
         r#"<div class="markdown-alert markdown-alert-{}" dir="auto">"#,
-        alert_class
+        html_escape::encode_double_quoted_attribute(kind.class)
     ));
 
+    // Text after the marker is either the title or the first line of the body
+    let first_line_content = first_line_content.trim();
+    let inline_title =
+        (config.inline_title && !first_line_content.is_empty()).then_some(first_line_content);
+
     // Alert title with icon
-    let icon_placeholder = get_alert_icon_placeholder(alert_class);
+    let icon_placeholder = get_alert_icon_placeholder(kind.icon);
+    let title = alert_title(kind.name, kind.class, inline_title, config);
     html_lines.push(format!(
         r#"<p class="markdown-alert-title" dir="auto">{}{}</p>"#,
-        icon_placeholder, alert_name
+        icon_placeholder,
+        html_escape::encode_text(&title)
     ));
 
     // Collect alert content as markdown
     let mut content_lines = Vec::new();
-    if !first_line_content.trim().is_empty() {
-        content_lines.push(first_line_content.trim().to_string());
+    if inline_title.is_none() && !first_line_content.is_empty() {
+        content_lines.push(first_line_content.to_string());
     }
 
     // Collect following quoted lines
     let mut i = start_index + 1;
-    while i < lines.len() && lines[i].starts_with('>') {
-        if let Some(content) = lines[i].strip_prefix('>') {
-            // Preserve the structure by keeping leading space after '>'
-            content_lines.push(content.trim_start().to_string());
-        }
+    while let Some(content) = lines
+        .get(i)
+        .and_then(|line| line.strip_prefix(indent))
+        .and_then(|line| line.strip_prefix('>'))
+    {
+        // Preserve the structure by keeping leading space after '>'
+        content_lines.push(content.trim_start().to_string());
         i += 1;
     }
 
@@ -350,445 +928,3872 @@ fn process_alert_block(
 
     html_lines.push("</div>".to_string());
 
+    if !indent.is_empty() {
+        html_lines = html_lines
+            .iter()
+            .map(|chunk| {
+                chunk
+                    .trim_end_matches('\n')
+                    .split('\n')
+                    .map(|line| format!("{indent}{line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect();
+    }
+
     (html_lines, i)
 }
 
 /// Process GitHub alert format
-fn process_github_alerts(markdown: &str) -> String {
+fn process_github_alerts(markdown: &str, config: &AlertsConfig) -> String {
+    process_github_alerts_with_line_map(markdown, config).0
+}
+
+/// Process GitHub alert format, keeping track of where each output line came from
+///
+/// Returns the processed markdown and, for every line of it, the index of the
+/// input line it originates from. Lines generated for an alert all map to the
+/// alert's first line.
+///
+/// Alerts may be indented by up to three spaces, or further when they belong
+/// to the content of a list item.
+fn process_github_alerts_with_line_map(
+    markdown: &str,
+    config: &AlertsConfig,
+) -> (String, Vec<usize>) {
     let lines: Vec<&str> = markdown.lines().collect();
     let mut result = Vec::new();
+    let mut line_map = Vec::new();
+    // Content column of the list item the current line may belong to
+    let mut list_indent: Option<usize> = None;
     let mut i = 0;
 
     while i < lines.len() {
         let line = lines[i];
 
-        if let Some((alert_name, alert_class, rest)) = parse_alert_start(line) {
+        let alert = parse_alert_start(line, config).filter(|&(indent, ..)| {
+            // Deeper indentation outside a list item is an indented code block
+            indent < 4 || list_indent.is_some_and(|content| indent < content + 4)
+        });
+        if let Some((indent, kind, rest)) = alert {
             let (alert_html, next_index) =
-                process_alert_block(&lines, i, alert_name, alert_class, rest);
+                process_alert_block(&lines, i, &line[..indent], kind, rest, config);
+            for chunk in &alert_html {
+                line_map.extend(std::iter::repeat_n(i, chunk.split('\n').count()));
+            }
             result.extend(alert_html);
+            if indent == 0 {
+                list_indent = None;
+            }
             i = next_index;
         } else {
+            if let Some(content) = list_item_content_indent(line) {
+                list_indent = Some(content);
+            } else if !line.trim().is_empty() && !line.starts_with([' ', '\t']) {
+                list_indent = None;
+            }
             result.push(line.to_string());
+            line_map.push(i);
             i += 1;
         }
     }
 
-    result.join("\n")
+    (result.join("\n"), line_map)
 }
 
-/// Process Code blocks
-fn process_code_blocks<'a>(
-    parser: impl Iterator<Item = Event<'a>>,
-    target_lang: &'a str,
-) -> impl Iterator<Item = Event<'a>> {
-    let mut in_block = false;
-    let mut content = String::new();
-
-    parser.flat_map(move |event| match event {
-        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang)))
-            if lang.as_ref() == target_lang =>
-        {
-            in_block = true;
-            content.clear();
-            vec![]
-        }
-        Event::End(TagEnd::CodeBlock) if in_block => {
-            in_block = false;
-            // Store original content in data attribute for JavaScript processing
-            let html = format!(
-                r#"<pre class="preprocessed-{}" data-original-content="{}">{}</pre>"#,
-                target_lang,
-                html_escape::encode_double_quoted_attribute(&content),
-                &content,
-            );
-            vec![Event::Html(html.into())]
-        }
-        Event::Text(text) if in_block => {
-            content.push_str(&text);
-            vec![]
-        }
-        _ => vec![event],
-    })
+/// Expand user-defined `{{name}}` snippet tokens
+fn expand_snippets(markdown: &str, snippets: &SnippetsConfig) -> String {
+    expand_snippets_with_line_map(markdown, snippets).0
 }
 
-/// Process math expressions (inline and display)
-fn process_math_expressions<'a>(
-    parser: impl Iterator<Item = Event<'a>>,
-) -> impl Iterator<Item = Event<'a>> {
-    parser.map(|event| match event {
-        Event::InlineMath(content) => {
-            // Convert inline math to custom HTML structure
-            let html = format!(
-                r#"<span class="preprocessed-math-inline" data-original-content="{}">{}</span>"#,
-                html_escape::encode_text(&content),
-                &content,
-            );
-            Event::Html(html.into())
-        }
-        Event::DisplayMath(content) => {
-            // Convert display math to custom HTML structure
-            let html = format!(
-                r#"<div class="preprocessed-math-display" data-original-content="{}">{}</div>"#,
-                html_escape::encode_text(&content),
-                &content,
-            );
-            Event::Html(html.into())
-        }
-        other => other,
-    })
-}
+/// Expand user-defined `{{name}}` snippet tokens, keeping track of where each
+/// output line came from
+///
+/// Tokens inside fenced code blocks and code spans are left alone, as are
+/// tokens without a definition. Snippets may reference other snippets up to
+/// [`MAX_SNIPPET_DEPTH`] levels deep; deeper tokens are kept verbatim, which
+/// also stops self-referencing snippets. All lines produced by an expansion map
+/// to the line containing the token.
+fn expand_snippets_with_line_map(
+    markdown: &str,
+    snippets: &SnippetsConfig,
+) -> (String, Vec<usize>) {
+    if !snippets.enabled || snippets.definitions.is_empty() {
+        let line_count = markdown.lines().count();
+        return (markdown.to_string(), (0..line_count).collect());
+    }
 
-/// Infer MIME type from file extension
-fn get_mime_type(path: &Path) -> &'static str {
-    match path.extension().and_then(|e| e.to_str()) {
-        Some("png") => "image/png",
-        Some("jpg") | Some("jpeg") => "image/jpeg",
-        Some("gif") => "image/gif",
-        Some("svg") => "image/svg+xml",
-        Some("webp") => "image/webp",
-        Some("bmp") => "image/bmp",
-        Some("ico") => "image/x-icon",
-        _ => "image/png", // Default
+    let mut result = Vec::new();
+    let mut line_map = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (i, line) in markdown.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let expanded = match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                line.to_string()
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+                line.to_string()
+            }
+            None => expand_snippet_tokens(line, snippets, 0),
+        };
+        line_map.extend(std::iter::repeat_n(i, expanded.split('\n').count()));
+        result.push(expanded);
     }
+
+    (result.join("\n"), line_map)
 }
 
-/// Render Markdown to HTML with TOC information
-///
-/// Returns a tuple of (rendered HTML with heading IDs, extracted headings)
-pub fn render_to_html_with_toc(
-    markdown: impl AsRef<str>,
-    base_path: impl AsRef<Path>,
-) -> Result<(String, Vec<HeadingInfo>)> {
-    let markdown = markdown.as_ref();
-    let base_path = base_path.as_ref();
+/// Expand snippet tokens in a piece of text, skipping code spans
+fn expand_snippet_tokens(text: &str, snippets: &SnippetsConfig, depth: usize) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['`', '{']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('`') {
+            // Copy the code span (or an unmatched backtick run) verbatim
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let span_len = rest[ticks..]
+                .find(&rest[..ticks])
+                .map_or(ticks, |end| ticks + end + ticks);
+            output.push_str(&rest[..span_len]);
+            rest = &rest[span_len..];
+            continue;
+        }
 
-    // Extract headings first
-    let headings = extract_headings(markdown);
+        let token = rest
+            .strip_prefix("{{")
+            .and_then(|after| after.split_once("}}"))
+            .map(|(name, _)| name)
+            .filter(|name| {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            });
+        match token.and_then(|name| Some((name, snippets.definitions.get(name)?))) {
+            Some((name, body)) if depth < MAX_SNIPPET_DEPTH => {
+                output.push_str(&expand_snippet_tokens(body, snippets, depth + 1));
+                rest = &rest[name.len() + 4..];
+            }
+            Some((name, _)) => {
+                tracing::warn!(name, "Snippet nesting too deep; leaving token unexpanded");
+                output.push_str(&rest[..name.len() + 4]);
+                rest = &rest[name.len() + 4..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
 
-    // Enable GitHub Flavored Markdown options
-    let options = Options::all();
+    output.push_str(rest);
+    output
+}
 
-    // Get base directory for resolving relative paths
-    let base_dir = base_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."));
+/// Lines replaced by an inline table of contents
+const TOC_PLACEHOLDERS: [&str; 2] = ["[TOC]", "[[_TOC_]]"];
 
-    // Extract frontmatter if present
-    let (frontmatter_html, content) = extract_and_render_frontmatter(markdown);
+/// Stands in for the inline table of contents until the headings are known
+const INLINE_TOC_MARKER: &str = "<!--inline-toc-->";
 
-    // Process GitHub alerts
-    let processed_markdown = process_github_alerts(&content);
+/// Replace the first standalone `[TOC]` or `[[_TOC_]]` line with
+/// [`INLINE_TOC_MARKER`]
+///
+/// Later placeholders are escaped so they render as written (and aren't taken
+/// for wiki links). Placeholders in fenced code blocks are left alone, and no
+/// lines are added or removed.
+fn mark_inline_toc(markdown: &str) -> String {
+    if !TOC_PLACEHOLDERS.iter().any(|p| markdown.contains(p)) {
+        return markdown.to_string();
+    }
 
-    // Parse Markdown and process blocks
-    let parser = Parser::new_ext(&processed_markdown, options);
-    let parser = process_code_blocks(parser, "mermaid");
-    let parser = process_code_blocks(parser, "math");
-    let parser = process_math_expressions(parser);
+    let mut result = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut marked = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let converted = match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                line.to_string()
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+                line.to_string()
+            }
+            None if indent < 4 && TOC_PLACEHOLDERS.contains(&trimmed.trim_end()) => {
+                if marked {
+                    let mut escaped = line[..indent].to_string();
+                    for c in trimmed.trim_end().chars() {
+                        if matches!(c, '[' | ']' | '_') {
+                            escaped.push('\\');
+                        }
+                        escaped.push(c);
+                    }
+                    escaped
+                } else {
+                    marked = true;
+                    INLINE_TOC_MARKER.to_string()
+                }
+            }
+            None => line.to_string(),
+        };
+        result.push(converted);
+    }
 
-    // Convert to HTML
-    let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    result.join("\n")
+}
 
-    // Post-process HTML with heading IDs
-    let html_output = post_process_html_with_headings(&html_output, base_dir.as_path(), &headings);
+/// Nested lists of links to `headings`, following their levels
+fn inline_toc_html(headings: &[HeadingInfo]) -> String {
+    if headings.is_empty() {
+        return String::new();
+    }
 
-    // Prepend frontmatter table if present
-    let final_output = if frontmatter_html.is_empty() {
-        html_output
+    let mut html = String::from("<nav class=\"inline-toc\">\n");
+    // Levels of the lists currently open, outermost first
+    let mut levels: Vec<u8> = Vec::new();
+    for heading in headings {
+        while levels.last().is_some_and(|&level| level > heading.level) {
+            html.push_str("</li>\n</ul>\n");
+            levels.pop();
+        }
+        if levels.last() == Some(&heading.level) {
+            html.push_str("</li>\n");
+        } else {
+            html.push_str("<ul>\n");
+            levels.push(heading.level);
+        }
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            html_escape::encode_double_quoted_attribute(&heading.id),
+            html_escape::encode_text(&heading.text)
+        ));
+    }
+    for _ in levels {
+        html.push_str("</li>\n</ul>\n");
+    }
+    html.push_str("</nav>\n");
+    html
+}
+
+/// Extensions tried, in order, when resolving a `[[Page]]` wiki link
+const WIKI_LINK_EXTENSIONS: [&str; 2] = ["md", "markdown"];
+
+/// Convert `[[Page]]` and `[[Page|text]]` wiki links into Markdown links
+///
+/// The page is looked up in `base_dir` by its exact name, then with `.md` and
+/// `.markdown` appended; a page that doesn't exist becomes an invalid link.
+/// Links inside fenced code blocks and code spans, escaped ones (`\[[`) and
+/// brackets that are the text of a regular link (`[[1]](url)`) are left alone.
+/// Lines are never added or removed.
+fn process_wiki_links(markdown: &str, base_dir: &Path) -> String {
+    if !markdown.contains("[[") {
+        return markdown.to_string();
+    }
+
+    let mut result = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let converted = match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                line.to_string()
+            }
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+                line.to_string()
+            }
+            None => convert_wiki_links(line, base_dir),
+        };
+        result.push(converted);
+    }
+
+    result.join("\n")
+}
+
+/// Convert the wiki links in one line, skipping code spans
+fn convert_wiki_links(text: &str, base_dir: &Path) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['`', '\\', '[']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('`') {
+            // Copy the code span (or an unmatched backtick run) verbatim
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            let span_len = rest[ticks..]
+                .find(&rest[..ticks])
+                .map_or(ticks, |end| ticks + end + ticks);
+            output.push_str(&rest[..span_len]);
+            rest = &rest[span_len..];
+            continue;
+        }
+        if rest.starts_with('\\') {
+            // Keep an escaped character, so `\[[` doesn't start a link
+            let len = rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            output.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        let link = rest
+            .strip_prefix("[[")
+            .and_then(|after| after.split_once("]]"))
+            .filter(|(inner, after)| {
+                !inner.trim().is_empty()
+                    && !inner.contains(['[', ']'])
+                    && !after.starts_with(['(', '['])
+            });
+        let Some((inner, _)) = link else {
+            output.push('[');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let (page, text) = match inner.split_once('|') {
+            Some((page, text)) if !text.trim().is_empty() => (page.trim(), text.trim()),
+            Some((page, _)) => (page.trim(), page.trim()),
+            None => (inner.trim(), inner.trim()),
+        };
+        match resolve_wiki_link(page, base_dir) {
+            Some(target) => output.push_str(&format!("[{}](<{}>)", text, target)),
+            None => output.push_str(&format!(
+                r#"<span class="md-link md-link-invalid">{}</span>"#,
+                text
+            )),
+        }
+        rest = &rest[inner.len() + 4..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Find the file a wiki link points to, relative to `base_dir`
+fn resolve_wiki_link(page: &str, base_dir: &Path) -> Option<String> {
+    if page.contains(['<', '>']) {
+        return None;
+    }
+    std::iter::once(page.to_string())
+        .chain(
+            WIKI_LINK_EXTENSIONS
+                .iter()
+                .map(|extension| format!("{}.{}", page, extension)),
+        )
+        .find(|name| base_dir.join(name).is_file())
+}
+
+/// Insert source line markers in front of block-level elements
+///
+/// Each marker is an HTML comment carrying the 1-based line number of the block
+/// in the original document. `line_map` maps lines of `source` back to lines of
+/// the document body, which starts `line_offset` lines into the file (after the
+/// frontmatter). Markers are turned into attributes by [`apply_source_line_markers`].
+fn mark_source_lines<'a>(
+    parser: impl Iterator<Item = (Event<'a>, Range<usize>)>,
+    source: &str,
+    line_map: Vec<usize>,
+    line_offset: usize,
+) -> impl Iterator<Item = Event<'a>> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    parser.flat_map(move |(event, range)| {
+        let is_block = matches!(
+            event,
+            Event::Start(
+                Tag::Paragraph
+                    | Tag::Heading { .. }
+                    | Tag::BlockQuote(_)
+                    | Tag::CodeBlock(_)
+                    | Tag::HtmlBlock
+                    | Tag::List(_)
+                    | Tag::Item
+                    | Tag::Table(_)
+            ) | Event::Rule
+        );
+        if !is_block {
+            return vec![event];
+        }
+
+        let line = line_starts.partition_point(|&start| start <= range.start) - 1;
+        let line = line_map.get(line).copied().unwrap_or(line) + line_offset + 1;
+        let marker = format!("{}{}-->", SOURCE_LINE_MARKER, line);
+        vec![Event::Html(marker.into()), event]
+    })
+}
+
+/// Replace source line markers with `data-source-line` attributes
+///
+/// The line number is attached to the element that directly follows the marker.
+/// Markers that are not followed by an element (e.g. raw HTML blocks starting
+/// with text or a comment) are dropped.
+fn apply_source_line_markers(html_str: &str) -> String {
+    let mut output = String::with_capacity(html_str.len());
+    let mut rest = html_str;
+
+    while let Some(start) = rest.find(SOURCE_LINE_MARKER) {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + SOURCE_LINE_MARKER.len()..];
+        let line = after
+            .find("-->")
+            .and_then(|end| Some((after[..end].parse::<usize>().ok()?, end)));
+        let Some((line, end)) = line else {
+            // Not one of our markers; keep it verbatim
+            output.push_str(SOURCE_LINE_MARKER);
+            rest = after;
+            continue;
+        };
+        rest = &after[end + 3..];
+
+        let whitespace = rest.len() - rest.trim_start().len();
+        let name_len = rest[whitespace..]
+            .strip_prefix('<')
+            .filter(|tag| tag.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .map(|tag| {
+                tag.find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(tag.len())
+            });
+        if let Some(name_len) = name_len {
+            let split = whitespace + 1 + name_len;
+            output.push_str(&rest[..split]);
+            output.push_str(&format!(r#" data-source-line="{}""#, line));
+            rest = &rest[split..];
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Process Code blocks
+fn process_code_blocks<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+    target_lang: &'a str,
+) -> impl Iterator<Item = Event<'a>> {
+    let mut in_block = false;
+    let mut content = String::new();
+
+    parser.flat_map(move |event| match event {
+        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang)))
+            if lang.as_ref() == target_lang =>
+        {
+            in_block = true;
+            content.clear();
+            vec![]
+        }
+        Event::End(TagEnd::CodeBlock) if in_block => {
+            in_block = false;
+            // Store original content in data attribute for JavaScript processing
+            let html = format!(
+                r#"<pre class="preprocessed-{}" data-original-content="{}">{}</pre>"#,
+                target_lang,
+                html_escape::encode_double_quoted_attribute(&content),
+                &content,
+            );
+            vec![Event::Html(html.into())]
+        }
+        Event::Text(text) if in_block => {
+            content.push_str(&text);
+            vec![]
+        }
+        _ => vec![event],
+    })
+}
+
+/// Preprocess `geojson` code blocks for the map renderer when enabled
+///
+/// Disabled blocks are left as ordinary highlighted code.
+fn process_geojson_blocks<'a>(
+    parser: impl Iterator<Item = Event<'a>> + 'a,
+    enabled: bool,
+) -> Box<dyn Iterator<Item = Event<'a>> + 'a> {
+    if enabled {
+        Box::new(process_code_blocks(parser, "geojson"))
     } else {
-        format!("{}\n{}", frontmatter_html, html_output)
-    };
+        Box::new(parser)
+    }
+}
 
-    Ok((final_output, headings))
+/// Process math expressions (inline and display)
+fn process_math_expressions<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+) -> impl Iterator<Item = Event<'a>> {
+    parser.map(|event| match event {
+        Event::InlineMath(content) => {
+            // Convert inline math to custom HTML structure
+            let html = format!(
+                r#"<span class="preprocessed-math-inline" data-original-content="{}">{}</span>"#,
+                html_escape::encode_text(&content),
+                &content,
+            );
+            Event::Html(html.into())
+        }
+        Event::DisplayMath(content) => {
+            // Convert display math to custom HTML structure
+            let html = format!(
+                r#"<div class="preprocessed-math-display" data-original-content="{}">{}</div>"#,
+                html_escape::encode_text(&content),
+                &content,
+            );
+            Event::Html(html.into())
+        }
+        other => other,
+    })
 }
 
-/// Post-process HTML to handle img, anchor tags, and add heading IDs using lol_html
-fn post_process_html_with_headings(
-    html_str: &str,
-    base_dir: &Path,
-    headings: &[HeadingInfo],
-) -> String {
-    let base_dir = base_dir.to_path_buf();
-    let mut output = Vec::new();
-    let heading_index = std::cell::RefCell::new(0usize);
-    let headings = headings.to_vec();
+/// Wrap code blocks in `<div class="code-block">` along with a copy button
+/// holding the raw source in `data-code`
+///
+/// Runs before syntax highlighting so the source is taken from the text
+/// events rather than highlighted markup. Mermaid, math and GeoJSON blocks are
+/// HTML by now and are left to the viewer, which copies their
+/// `data-original-content`.
+fn process_code_copy_buttons(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut output = Vec::with_capacity(events.len());
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        if !matches!(event, Event::Start(Tag::CodeBlock(_))) {
+            output.push(event);
+            continue;
+        }
 
-    let mut rewriter = HtmlRewriter::new(
-        Settings {
-            element_content_handlers: vec![
-                // Process heading tags: add IDs for TOC navigation
-                element!("h1, h2, h3, h4, h5, h6", |el| {
-                    let mut idx = heading_index.borrow_mut();
-                    if let Some(heading) = headings.get(*idx) {
-                        el.set_attribute("id", &heading.id)?;
-                    }
-                    *idx += 1;
-                    Ok(())
-                }),
-                // Process img tags: convert relative paths to data URLs
-                element!("img[src]", move |el| {
-                    if let Some(src) = el.get_attribute("src") {
-                        if !src.starts_with("http://")
-                            && !src.starts_with("https://")
-                            && !src.starts_with("data:")
-                        {
-                            let absolute_path = base_dir.join(&src);
-                            if let Ok(canonical_path) = absolute_path.canonicalize() {
-                                if let Ok(image_data) = std::fs::read(&canonical_path) {
-                                    let mime_type = get_mime_type(&canonical_path);
-                                    let base64_data = general_purpose::STANDARD.encode(&image_data);
-                                    let data_url =
-                                        format!("data:{};base64,{}", mime_type, base64_data);
-                                    el.set_attribute("src", &data_url)?;
-                                }
-                            }
-                        }
-                    }
-                    Ok(())
-                }),
-                // Process anchor tags: convert markdown links to spans
-                element!("a[href]", |el| {
-                    if let Some(href) = el.get_attribute("href") {
-                        if !href.starts_with("http://") && !href.starts_with("https://") {
-                            if let Some(ext) = std::path::Path::new(&href)
-                                .extension()
-                                .and_then(|e| e.to_str())
-                            {
-                                // Replace with span element
-                                let escaped_href = href.replace('\'', "\\'");
-                                let onclick = indoc::formatdoc! {r#"
-                                        if (event.button === 0 || event.button === 1) {{
-                                            event.preventDefault();
-                                            window.handleMarkdownLinkClick('{escaped_href}', event.button);
-                                        }}"#
-                                };
-                                el.set_tag_name("span")?;
-                                el.remove_attribute("href");
-                                if ext != "md" && ext != "markdown" {
-                                    el.set_attribute("class", "md-link md-link-invalid")?;
-                                } else {
-                                    el.set_attribute("class", "md-link")?;
-                                }
-                                el.set_attribute("onmousedown", &onclick)?;
-                            }
-                        }
-                    }
-                    Ok(())
-                }),
-            ],
-            ..Settings::default()
-        },
-        |chunk: &[u8]| {
-            output.extend_from_slice(chunk);
-        },
-    );
+        let mut block = vec![event];
+        let mut code = String::new();
+        for event in events.by_ref() {
+            let end = matches!(event, Event::End(TagEnd::CodeBlock));
+            if let Event::Text(text) = &event {
+                code.push_str(text);
+            }
+            block.push(event);
+            if end {
+                break;
+            }
+        }
+        let open = format!(
+            r#"<div class="code-block"><button class="copy-button" data-code="{}" aria-label="Copy code to clipboard"></button>"#,
+            encode_code_attribute(&code),
+        );
+        // The source line stays on the <pre>, inside the wrapper
+        let marker = output
+            .last()
+            .is_some_and(is_source_line_marker)
+            .then(|| output.pop())
+            .flatten();
+        output.push(Event::Html(open.into()));
+        output.extend(marker);
+        output.extend(block);
+        output.push(Event::Html("</div>\n".into()));
+    }
+    output
+}
 
-    let _ = rewriter.write(html_str.as_bytes());
-    let _ = rewriter.end();
-    String::from_utf8(output).unwrap_or_else(|_| html_str.to_string())
+/// Escape source code for a double-quoted attribute, also covering quotes and
+/// backticks so the value survives any quoting
+fn encode_code_attribute(code: &str) -> String {
+    html_escape::encode_double_quoted_attribute(code)
+        .replace('\'', "&#39;")
+        .replace('`', "&#96;")
 }
 
-/// Post-process HTML to handle img and anchor tags using lol_html
-fn post_process_html_tags(html_str: &str, base_dir: &Path) -> String {
-    let base_dir = base_dir.to_path_buf();
-    let mut output = Vec::new();
+/// Info string words overriding `codeWrap.mode` for one code block, and the
+/// class they put on its `<pre>`
+const CODE_WRAP_FLAGS: [(&str, &str); 2] = [("wrap", "code-wrap"), ("nowrap", "code-nowrap")];
 
-    let mut rewriter = HtmlRewriter::new(
-        Settings {
-            element_content_handlers: vec![
-                // Process img tags: convert relative paths to data URLs
-                element!("img[src]", move |el| {
-                    if let Some(src) = el.get_attribute("src") {
-                        if !src.starts_with("http://")
-                            && !src.starts_with("https://")
-                            && !src.starts_with("data:")
-                        {
-                            let absolute_path = base_dir.join(&src);
-                            if let Ok(canonical_path) = absolute_path.canonicalize() {
-                                if let Ok(image_data) = std::fs::read(&canonical_path) {
-                                    let mime_type = get_mime_type(&canonical_path);
-                                    let base64_data = general_purpose::STANDARD.encode(&image_data);
-                                    let data_url =
-                                        format!("data:{};base64,{}", mime_type, base64_data);
-                                    el.set_attribute("src", &data_url)?;
-                                }
-                            }
-                        }
-                    }
-                    Ok(())
-                }),
-                // Process anchor tags: convert markdown links to spans
-                element!("a[href]", |el| {
-                    if let Some(href) = el.get_attribute("href") {
-                        if !href.starts_with("http://") && !href.starts_with("https://") {
-                            if let Some(ext) = std::path::Path::new(&href)
-                                .extension()
-                                .and_then(|e| e.to_str())
-                            {
-                                // Replace with span element
-                                let escaped_href = href.replace('\'', "\\'");
-                                let onclick = indoc::formatdoc! {r#"
-                                        if (event.button === 0 || event.button === 1) {{
-                                            event.preventDefault();
-                                            window.handleMarkdownLinkClick('{escaped_href}', event.button); 
-                                        }}"#
-                                };
-                                el.set_tag_name("span")?;
-                                el.remove_attribute("href");
-                                if ext != "md" && ext != "markdown" {
-                                    el.set_attribute("class", "md-link md-link-invalid")?;
-                                } else {
-                                    el.set_attribute("class", "md-link")?;
-                                }
-                                el.set_attribute("onmousedown", &onclick)?;
+/// Info string words turning off syntax highlighting for one code block
+const NO_HIGHLIGHT_FLAGS: [&str; 2] = ["nohl", "nohighlight"];
+
+/// Info string words overriding `codeLines.lineNumbers` for one code block
+const LINE_NUMBER_FLAGS: [(&str, bool); 2] = [("linenos", true), ("nolinenos", false)];
+
+/// Apply the words after the language of a fenced code block, separated by
+/// spaces or commas (` ```rust nowrap`, ` ```rust,nohl`)
+///
+/// `wrap`/`nowrap` become a class on the `<pre>`. `nohl`, like a language
+/// listed in `syntaxHighlight.disabledLanguages`, marks the `<code>` as
+/// `nohighlight` so the renderer leaves it plain. Runs right before the HTML
+/// is written, so the earlier passes still see the code block events.
+fn process_code_block_flags<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    disabled_languages: Vec<String>,
+) -> impl Iterator<Item = Event<'a>> {
+    events.map(move |event| {
+        let Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) = &event else {
+            return event;
+        };
+        let info = CodeBlockInfo::parse(info, &disabled_languages);
+        if info.wrap_class.is_none() && !info.no_highlight {
+            return event;
+        }
+
+        let code_class = class_attribute(&info.code_classes());
+        Event::Html(format!("<pre{}><code{code_class}>", info.pre_class()).into())
+    })
+}
+
+/// ` class="…"` attribute for a list of classes, empty without any
+fn class_attribute(classes: &[String]) -> String {
+    if classes.is_empty() {
+        String::new()
+    } else {
+        format!(r#" class="{}""#, classes.join(" "))
+    }
+}
+
+/// Language and flags of a fenced code block's info string
+struct CodeBlockInfo<'s> {
+    language: &'s str,
+    wrap_class: Option<&'static str>,
+    no_highlight: bool,
+    /// `linenos`/`nolinenos`, if given
+    line_numbers: Option<bool>,
+    /// Inclusive ranges of 1-based lines listed in braces (`{1,4-6}`)
+    highlighted_lines: Vec<(usize, usize)>,
+}
+
+impl<'s> CodeBlockInfo<'s> {
+    fn parse(info: &'s str, disabled_languages: &[String]) -> Self {
+        let words = info_words(info);
+        let (language, flags) = match words.split_first() {
+            Some((first, rest)) if !first.starts_with('{') => (*first, rest),
+            _ => ("", &words[..]),
+        };
+        let line_numbers = flags.iter().find_map(|word| {
+            LINE_NUMBER_FLAGS
+                .iter()
+                .find(|(flag, _)| flag == word)
+                .map(|(_, on)| *on)
+        });
+        let highlighted_lines = flags
+            .iter()
+            .filter_map(|word| parse_line_ranges(word))
+            .flatten()
+            .collect();
+        let wrap_class = flags.iter().find_map(|word| {
+            CODE_WRAP_FLAGS
+                .iter()
+                .find(|(flag, _)| flag == word)
+                .map(|(_, class)| *class)
+        });
+        let no_highlight = flags.iter().any(|word| NO_HIGHLIGHT_FLAGS.contains(word))
+            || (!language.is_empty()
+                && disabled_languages
+                    .iter()
+                    .any(|disabled| disabled.eq_ignore_ascii_case(language)));
+        Self {
+            language,
+            wrap_class,
+            no_highlight,
+            line_numbers,
+            highlighted_lines,
+        }
+    }
+
+    /// Classes of the `<code>`: the language and `nohighlight`
+    fn code_classes(&self) -> Vec<String> {
+        let mut classes = Vec::new();
+        if !self.language.is_empty() {
+            classes.push(format!(
+                "language-{}",
+                html_escape::encode_double_quoted_attribute(self.language)
+            ));
+        }
+        if self.no_highlight {
+            classes.push("nohighlight".to_string());
+        }
+        classes
+    }
+
+    fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&line))
+    }
+
+    /// Class attribute of the `<pre>`, if a wrap flag was given
+    fn pre_class(&self) -> String {
+        self.wrap_class
+            .map(|class| format!(r#" class="{class}""#))
+            .unwrap_or_default()
+    }
+}
+
+/// Split an info string into words at spaces and commas, keeping a `{…}` list
+/// of lines (which has commas of its own) in one word, also when it directly
+/// follows the language (` ```rust{1,3}`)
+fn info_words(info: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut in_braces = false;
+    for (index, c) in info.char_indices() {
+        if in_braces {
+            if c == '}' {
+                in_braces = false;
+                words.extend(start.take().map(|start| &info[start..=index]));
+            }
+        } else if c == '{' {
+            words.extend(start.take().map(|start| &info[start..index]));
+            start = Some(index);
+            in_braces = true;
+        } else if c == ',' || c.is_whitespace() {
+            words.extend(start.take().map(|start| &info[start..index]));
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    words.extend(start.map(|start| &info[start..]));
+    words
+}
+
+/// Parse a `{1,4-6}` list of lines into inclusive ranges, skipping entries
+/// that aren't line numbers
+fn parse_line_ranges(word: &str) -> Option<Vec<(usize, usize)>> {
+    let list = word.strip_prefix('{')?.strip_suffix('}')?;
+    let ranges = list
+        .split(',')
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let (start, end) = entry.split_once('-').unwrap_or((entry, entry));
+            let start: usize = start.trim().parse().ok()?;
+            let end: usize = end.trim().parse().ok()?;
+            (start >= 1 && start <= end).then_some((start, end))
+        })
+        .collect();
+    Some(ranges)
+}
+
+/// Number the lines of code blocks and highlight the requested ones
+///
+/// Applies to blocks listing lines in their info string (` ```rust {1,4-6}`)
+/// and, with `codeLines.lineNumbers` or a `linenos` flag, to every block.
+/// Each line becomes `<span class="code-line" data-line="N">`, counted from 1
+/// at the first line of code, so in a fenced block line N comes from source
+/// line `data-source-line` (the fence, set on the `<pre>`) + N. With native
+/// syntax highlighting the highlighted markup is split into the lines;
+/// otherwise the viewer's highlighter keeps the line wrappers.
+fn process_code_lines<'a>(
+    events: Vec<Event<'a>>,
+    config: &CodeLinesConfig,
+    syntax_highlight: &SyntaxHighlightConfig,
+) -> Vec<Event<'a>> {
+    let mut output = Vec::with_capacity(events.len());
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        let info_string = match &event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => info.to_string(),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Indented)) => String::new(),
+            _ => {
+                output.push(event);
+                continue;
+            }
+        };
+        let info = CodeBlockInfo::parse(&info_string, &syntax_highlight.disabled_languages);
+        let line_numbers = info.line_numbers.unwrap_or(config.line_numbers);
+        if !line_numbers && info.highlighted_lines.is_empty() {
+            output.push(event);
+            continue;
+        }
+
+        let mut code = String::new();
+        for event in events.by_ref() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => break,
+                _ => {}
+            }
+        }
+        let html = code_lines_html(&code, &info, line_numbers, syntax_highlight);
+        output.push(Event::Html(html.into()));
+    }
+    output
+}
+
+/// `<pre>` of a code block with each line wrapped in a `span.code-line`
+fn code_lines_html(
+    code: &str,
+    info: &CodeBlockInfo,
+    line_numbers: bool,
+    syntax_highlight: &SyntaxHighlightConfig,
+) -> String {
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let highlighted = (syntax_highlight.native && !info.no_highlight)
+        .then(|| syntax_highlight::highlight(code, info.language))
+        .flatten();
+    let lines: Vec<String> = match &highlighted {
+        Some(html) => split_highlighted_lines(html),
+        None => code
+            .split('\n')
+            .map(|line| html_escape::encode_text(line).into_owned())
+            .collect(),
+    };
+
+    let mut pre_classes = vec!["code-lines".to_string()];
+    pre_classes.extend(info.wrap_class.map(str::to_string));
+    if line_numbers {
+        pre_classes.push("line-numbers".to_string());
+    }
+    let mut code_classes = info.code_classes();
+    if highlighted.is_some() {
+        code_classes.push("hljs".to_string());
+    }
+    let mut html = format!(
+        r#"<pre{}><code{}{} style="--line-number-digits: {}">"#,
+        class_attribute(&pre_classes),
+        class_attribute(&code_classes),
+        if highlighted.is_some() {
+            r#" data-highlighted="yes""#
+        } else {
+            ""
+        },
+        lines.len().to_string().len(),
+    );
+    for (index, line) in lines.iter().enumerate() {
+        let number = index + 1;
+        let class = if info.is_highlighted(number) {
+            "code-line highlighted"
+        } else {
+            "code-line"
+        };
+        html.push_str(&format!(
+            r#"<span class="{class}" data-line="{number}">{line}</span>"#
+        ));
+        html.push('\n');
+    }
+    html.push_str("</code></pre>\n");
+    html
+}
+
+/// Split highlighted code into lines, closing the spans still open at each
+/// line break and reopening them on the next line so every line is balanced
+fn split_highlighted_lines(html: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut line = String::new();
+    let mut rest = html;
+    while let Some(index) = rest.find(['<', '\n']) {
+        line.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(after) = rest.strip_prefix('\n') {
+            line.push_str(&"</span>".repeat(open.len()));
+            lines.push(std::mem::take(&mut line));
+            line.extend(open.iter().copied());
+            rest = after;
+        } else {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = &rest[..end];
+            if tag.starts_with("</") {
+                open.pop();
+            } else {
+                open.push(tag);
+            }
+            line.push_str(tag);
+            rest = &rest[end..];
+        }
+    }
+    line.push_str(rest);
+    lines.push(line);
+    lines
+}
+
+/// Highlight fenced code blocks while rendering when `syntaxHighlight.native`
+/// is on
+///
+/// Highlighted blocks become HTML marked `data-highlighted` so the viewer's
+/// highlighter skips them. Blocks without a language, ones that opted out of
+/// highlighting and languages syntect doesn't know keep their events, so they
+/// are written as plain escaped code and left to the viewer. Mermaid and math
+/// blocks are already HTML by now.
+fn process_syntax_highlighting<'a>(
+    events: Vec<Event<'a>>,
+    config: &SyntaxHighlightConfig,
+) -> Vec<Event<'a>> {
+    if !config.native {
+        return events;
+    }
+    let mut output = Vec::with_capacity(events.len());
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        let Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) = &event else {
+            output.push(event);
+            continue;
+        };
+        let info = CodeBlockInfo::parse(info, &config.disabled_languages);
+        if info.no_highlight || !syntax_highlight::is_supported(info.language) {
+            output.push(event);
+            continue;
+        }
+
+        let mut block = vec![event.clone()];
+        let mut code = String::new();
+        for event in events.by_ref() {
+            let end = matches!(event, Event::End(TagEnd::CodeBlock));
+            if let Event::Text(text) = &event {
+                code.push_str(text);
+            }
+            block.push(event);
+            if end {
+                break;
+            }
+        }
+        match syntax_highlight::highlight(&code, info.language) {
+            Some(highlighted) => {
+                let code_open = format!(
+                    r#"<pre{}><code class="language-{} hljs" data-highlighted="yes">"#,
+                    info.pre_class(),
+                    html_escape::encode_double_quoted_attribute(info.language),
+                );
+                let html = format!("{code_open}{highlighted}</code></pre>\n");
+                output.push(Event::Html(html.into()));
+            }
+            None => output.extend(block),
+        }
+    }
+    output
+}
+
+/// Delimiter on both sides of spoiler text
+const SPOILER_DELIMITER: &str = "||";
+
+/// Opening tag of a spoiler, revealed (and hidden again) by click or keyboard
+const SPOILER_OPEN: &str = concat!(
+    r#"<span class="spoiler" role="button" tabindex="0" title="Show spoiler" "#,
+    r#"onclick="this.classList.toggle('revealed')" "#,
+    r#"onkeydown="if (event.key === 'Enter' || event.key === ' ') { event.preventDefault(); this.classList.toggle('revealed'); }">"#,
+);
+
+/// Hide `||spoiler||` text until it is clicked
+///
+/// Only text is searched, so code spans and code blocks are left alone, and
+/// table rows are already split into cells at their pipes (escaped `\|\|`
+/// inside a cell still forms a spoiler). Like emphasis, the hidden text may
+/// not start or end with whitespace, which keeps `a || b` as is, and it can't
+/// span inline markup.
+fn process_spoilers(events: Vec<Event<'_>>, enabled: bool) -> Vec<Event<'_>> {
+    if !enabled {
+        return events;
+    }
+    let mut output = Vec::with_capacity(events.len());
+    let mut in_code_block = false;
+    for event in merge_text_events(events) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => {
+                in_code_block = true;
+                output.push(event);
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                output.push(event);
+            }
+            Event::Text(text) if !in_code_block && text.contains(SPOILER_DELIMITER) => {
+                output.extend(split_spoilers(&text));
+            }
+            event => output.push(event),
+        }
+    }
+    output
+}
+
+/// Split text into plain text and spoiler elements (see [`process_spoilers`])
+fn split_spoilers(text: &str) -> Vec<Event<'static>> {
+    let mut events = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(SPOILER_DELIMITER) {
+        let after = &rest[start + SPOILER_DELIMITER.len()..];
+        let hidden = after
+            .find(SPOILER_DELIMITER)
+            .map(|end| &after[..end])
+            .filter(|hidden| {
+                !hidden.is_empty()
+                    && !hidden.starts_with(char::is_whitespace)
+                    && !hidden.ends_with(char::is_whitespace)
+            });
+        let Some(hidden) = hidden else {
+            // Not an opening delimiter; keep it as text
+            literal.push_str(&rest[..start + SPOILER_DELIMITER.len()]);
+            rest = after;
+            continue;
+        };
+        literal.push_str(&rest[..start]);
+        if !literal.is_empty() {
+            events.push(Event::Text(std::mem::take(&mut literal).into()));
+        }
+        events.push(Event::InlineHtml(SPOILER_OPEN.into()));
+        events.push(Event::Text(hidden.to_string().into()));
+        events.push(Event::InlineHtml("</span>".into()));
+        rest = &after[hidden.len() + SPOILER_DELIMITER.len()..];
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        events.push(Event::Text(literal.into()));
+    }
+    events
+}
+
+/// Show `:name:` shortcodes as emoji (see [`emoji::replace_shortcodes`])
+///
+/// Only text is searched, so code spans are left alone; text in code blocks
+/// and autolinks is skipped too.
+fn process_emoji_shortcodes(events: Vec<Event<'_>>, enabled: bool) -> Vec<Event<'_>> {
+    if !enabled {
+        return events;
+    }
+    let mut in_literal = false;
+    merge_text_events(events)
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::CodeBlock(_))
+            | Event::Start(Tag::Link {
+                link_type: LinkType::Autolink | LinkType::Email,
+                ..
+            }) => {
+                in_literal = true;
+                event
+            }
+            Event::End(TagEnd::CodeBlock | TagEnd::Link) => {
+                in_literal = false;
+                event
+            }
+            Event::Text(text) if !in_literal => {
+                let replaced = match emoji::replace_shortcodes(&text) {
+                    Cow::Owned(replaced) => Some(replaced),
+                    Cow::Borrowed(_) => None,
+                };
+                match replaced {
+                    Some(replaced) => Event::Text(replaced.into()),
+                    None => Event::Text(text),
+                }
+            }
+            event => event,
+        })
+        .collect()
+}
+
+/// Kinds of numbered captions, each with its own counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptionKind {
+    Figure,
+    Listing,
+    Table,
+}
+
+impl CaptionKind {
+    /// Label prefix (`@fig:name`), also used for the ids of unlabeled captions
+    fn prefix(self) -> &'static str {
+        match self {
+            CaptionKind::Figure => "fig",
+            CaptionKind::Listing => "lst",
+            CaptionKind::Table => "tbl",
+        }
+    }
+
+    fn label(self, config: &CaptionsConfig) -> &str {
+        match self {
+            CaptionKind::Figure => &config.figure_label,
+            CaptionKind::Listing => &config.listing_label,
+            CaptionKind::Table => &config.table_label,
+        }
+    }
+}
+
+/// Prefixes marking the paragraph after an image, code block or table as its caption
+const CAPTION_PREFIXES: &[&str] = &["Caption:", ": "];
+
+/// Number the captions of figures, code listings and tables, and resolve
+/// `@fig:name`-style references to them (see [`CaptionsConfig`])
+///
+/// A caption is a paragraph starting with one of [`CAPTION_PREFIXES`] right
+/// after a code block, a table or a paragraph holding only an image. For
+/// images it may also be the image paragraph's second line.
+fn process_captions<'a>(events: Vec<Event<'a>>, config: &CaptionsConfig) -> Vec<Event<'a>> {
+    if !config.enabled {
+        return events;
+    }
+
+    let events = merge_text_events(events);
+    let mut numbering = CaptionNumbering::default();
+    let mut output = Vec::with_capacity(events.len());
+    // Kind of the block that just ended, if its next paragraph may caption it
+    let mut captionable = None;
+    let mut i = 0;
+
+    while i < events.len() {
+        if matches!(&events[i], Event::Html(html) if html.starts_with(SOURCE_LINE_MARKER)) {
+            output.push(events[i].clone());
+            i += 1;
+            continue;
+        }
+        let previous = captionable.take();
+        match &events[i] {
+            Event::Start(Tag::Paragraph) => {
+                let end = i + events[i..]
+                    .iter()
+                    .position(|event| matches!(event, Event::End(TagEnd::Paragraph)))
+                    .unwrap_or(events.len() - i - 1);
+                let inner = &events[i + 1..end];
+                let image_end = inner
+                    .first()
+                    .filter(|event| matches!(event, Event::Start(Tag::Image { .. })))
+                    .and_then(|_| {
+                        inner
+                            .iter()
+                            .position(|event| matches!(event, Event::End(TagEnd::Image)))
+                    });
+
+                if let Some(caption) =
+                    previous.and_then(|kind| Some((kind, strip_caption_prefix(inner)?)))
+                {
+                    output.extend(numbering.render(caption.0, caption.1, config));
+                } else if let Some(image_end) = image_end {
+                    let rest = &inner[image_end + 1..];
+                    let caption = match rest.first() {
+                        Some(Event::SoftBreak) => strip_caption_prefix(&rest[1..]),
+                        _ => None,
+                    };
+                    output.extend_from_slice(&events[i..=i + 1 + image_end]);
+                    match caption {
+                        Some(caption) => {
+                            output.push(Event::Html("</p>\n".into()));
+                            output.extend(numbering.render(CaptionKind::Figure, caption, config));
+                        }
+                        None => {
+                            output.extend_from_slice(rest);
+                            output.push(Event::End(TagEnd::Paragraph));
+                            if rest.is_empty() {
+                                captionable = Some(CaptionKind::Figure);
                             }
                         }
                     }
-                    Ok(())
-                }),
-            ],
-            ..Settings::default()
-        },
-        |chunk: &[u8]| {
-            output.extend_from_slice(chunk);
-        },
-    );
+                } else {
+                    output.extend_from_slice(&events[i..=end]);
+                }
+                i = end + 1;
+                continue;
+            }
+            Event::Start(Tag::DefinitionList) => {
+                if let Some((end, image, caption)) = image_definition_caption(&events[i..]) {
+                    output.push(Event::Start(Tag::Paragraph));
+                    output.extend(image);
+                    output.push(Event::End(TagEnd::Paragraph));
+                    output.extend(numbering.render(CaptionKind::Figure, caption, config));
+                    i += end + 1;
+                    continue;
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => captionable = Some(CaptionKind::Listing),
+            Event::End(TagEnd::Table) => captionable = Some(CaptionKind::Table),
+            _ => {}
+        }
+        output.push(events[i].clone());
+        i += 1;
+    }
+
+    numbering.resolve_references(output)
+}
+
+/// Caption counters and the numbers assigned to labels
+#[derive(Default)]
+struct CaptionNumbering {
+    counts: [usize; 3],
+    labels: std::collections::HashMap<String, String>,
+}
+
+impl CaptionNumbering {
+    /// Number a caption and render it as a paragraph (`inline` without the prefix)
+    fn render<'a>(
+        &mut self,
+        kind: CaptionKind,
+        mut inline: Vec<Event<'a>>,
+        config: &CaptionsConfig,
+    ) -> Vec<Event<'a>> {
+        let count = &mut self.counts[kind as usize];
+        *count += 1;
+        let name = format!("{} {}", kind.label(config), count);
+        let id = match take_caption_label(&mut inline) {
+            Some(label) => {
+                self.labels.insert(label.clone(), name.clone());
+                label
+            }
+            None => format!("{}-{}", kind.prefix(), count),
+        };
+
+        let mut events = vec![Event::Html(
+            format!(
+                r#"<p class="caption caption-{}" id="{}"><span class="caption-label">{}:</span> "#,
+                kind.prefix(),
+                html_escape::encode_double_quoted_attribute(&id),
+                html_escape::encode_text(&name)
+            )
+            .into(),
+        )];
+        events.extend(inline);
+        events.push(Event::End(TagEnd::Paragraph));
+        events
+    }
+
+    /// Replace `@fig:name` references in text (outside code) with links to the caption
+    fn resolve_references<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        if self.labels.is_empty() {
+            return events;
+        }
+        let mut output = Vec::with_capacity(events.len());
+        let mut in_code_block = false;
+        for event in events {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Text(text) if !in_code_block => {
+                    output.extend(self.link_references(text));
+                    continue;
+                }
+                _ => {}
+            }
+            output.push(event);
+        }
+        output
+    }
+
+    fn link_references<'a>(&self, text: pulldown_cmark::CowStr<'a>) -> Vec<Event<'a>> {
+        let mut events = Vec::new();
+        let mut plain_start = 0;
+        let mut search_from = 0;
+        while let Some(offset) = text[search_from..].find('@') {
+            let at = search_from + offset;
+            search_from = at + 1;
+            // Skip e-mail addresses
+            if text[..at].ends_with(|c: char| c.is_alphanumeric()) {
+                continue;
+            }
+            let label_len = text[at + 1..]
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, ':' | '-' | '_')))
+                .unwrap_or(text.len() - at - 1);
+            // A sentence may end right after the reference
+            let label = text[at + 1..at + 1 + label_len].trim_end_matches(':');
+            let Some(name) = self.labels.get(label) else {
+                continue;
+            };
+            if plain_start < at {
+                events.push(Event::Text(text[plain_start..at].to_string().into()));
+            }
+            events.push(Event::InlineHtml(
+                format!(
+                    r##"<a class="caption-ref" href="#{}">{}</a>"##,
+                    html_escape::encode_double_quoted_attribute(label),
+                    html_escape::encode_text(name)
+                )
+                .into(),
+            ));
+            plain_start = at + 1 + label.len();
+            search_from = plain_start;
+        }
+        if plain_start == 0 {
+            return vec![Event::Text(text)];
+        }
+        if plain_start < text.len() {
+            events.push(Event::Text(text[plain_start..].to_string().into()));
+        }
+        events
+    }
+}
+
+/// The inline events of a caption paragraph without its prefix, or `None`
+/// if the paragraph isn't a caption
+fn strip_caption_prefix<'a>(inline: &[Event<'a>]) -> Option<Vec<Event<'a>>> {
+    let Some(Event::Text(first)) = inline.first() else {
+        return None;
+    };
+    let rest = CAPTION_PREFIXES
+        .iter()
+        .find_map(|prefix| first.strip_prefix(prefix))?;
+    let mut events = vec![Event::Text(rest.trim_start().to_string().into())];
+    events.extend_from_slice(&inline[1..]);
+    Some(events)
+}
+
+/// Split an image followed by a `: ` caption, which the parser reads as a
+/// definition list with the image as its term
+///
+/// Returns the index of the list's end in `events`, the image events and the
+/// caption's inline events.
+fn image_definition_caption<'a>(
+    events: &[Event<'a>],
+) -> Option<(usize, Vec<Event<'a>>, Vec<Event<'a>>)> {
+    let end = events
+        .iter()
+        .position(|event| matches!(event, Event::End(TagEnd::DefinitionList)))?;
+    let inner: Vec<&Event> = events[1..end]
+        .iter()
+        .filter(|event| !matches!(event, Event::Html(html) if html.starts_with(SOURCE_LINE_MARKER)))
+        .collect();
+
+    let title_end = inner
+        .iter()
+        .position(|event| matches!(event, Event::End(TagEnd::DefinitionListTitle)))?;
+    let image = &inner[1..title_end];
+    let is_image = matches!(inner.first(), Some(Event::Start(Tag::DefinitionListTitle)))
+        && matches!(image.first(), Some(Event::Start(Tag::Image { .. })))
+        && matches!(image.last(), Some(Event::End(TagEnd::Image)));
+    if !is_image {
+        return None;
+    }
+
+    // A single definition, either tight or wrapped in one paragraph
+    let mut definition = inner[title_end + 1..].iter().copied();
+    if !matches!(
+        definition.next(),
+        Some(Event::Start(Tag::DefinitionListDefinition))
+    ) || !matches!(
+        definition.next_back(),
+        Some(Event::End(TagEnd::DefinitionListDefinition))
+    ) {
+        return None;
+    }
+    let mut caption: Vec<Event> = definition.cloned().collect();
+    if matches!(caption.first(), Some(Event::Start(Tag::Paragraph)))
+        && matches!(caption.last(), Some(Event::End(TagEnd::Paragraph)))
+    {
+        caption.pop();
+        caption.remove(0);
+    }
+    let is_single_block = !caption.iter().any(|event| {
+        matches!(
+            event,
+            Event::Start(Tag::Paragraph | Tag::DefinitionListDefinition | Tag::DefinitionListTitle)
+        )
+    });
+    if caption.is_empty() || !is_single_block {
+        return None;
+    }
+
+    Some((end, image.iter().copied().cloned().collect(), caption))
+}
+
+/// Remove a trailing `{#label}` from a caption and return the label
+fn take_caption_label(inline: &mut [Event<'_>]) -> Option<String> {
+    let Some(Event::Text(last)) = inline.last_mut() else {
+        return None;
+    };
+    let trimmed = last.trim_end();
+    let open = trimmed.strip_suffix('}')?.rfind("{#")?;
+    let label = trimmed[open + 2..trimmed.len() - 1].trim().to_string();
+    if label.is_empty() || label.contains(char::is_whitespace) {
+        return None;
+    }
+    *last = trimmed[..open].trim_end().to_string().into();
+    Some(label)
+}
+
+/// Join adjacent text events, which the parser may split at special characters
+fn merge_text_events(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut merged: Vec<Event> = Vec::with_capacity(events.len());
+    for event in events {
+        match (merged.last_mut(), event) {
+            (Some(Event::Text(previous)), Event::Text(text)) => {
+                *previous = format!("{}{}", previous, text).into();
+            }
+            (_, event) => merged.push(event),
+        }
+    }
+    merged
+}
+
+/// Footnote definitions and the references numbered so far
+struct Footnotes<'a> {
+    /// Definition content by normalized label
+    definitions: HashMap<String, Vec<Event<'a>>>,
+    /// Normalized labels in the order they were first referenced
+    order: Vec<String>,
+    /// Number of references to each footnote
+    reference_counts: HashMap<String, usize>,
+}
+
+impl<'a> Footnotes<'a> {
+    /// Replace references with links to their footnotes
+    fn link_references(&mut self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::FootnoteReference(label) => self.link_reference(&label),
+                event => event,
+            })
+            .collect()
+    }
+
+    fn link_reference(&mut self, label: &str) -> Event<'a> {
+        let key = label.to_lowercase();
+        if !self.definitions.contains_key(&key) {
+            return Event::Text(format!("[^{}]", label).into());
+        }
+        if !self.order.contains(&key) {
+            self.order.push(key.clone());
+        }
+        let number = self.order.iter().position(|k| *k == key).unwrap_or(0) + 1;
+        let count = self.reference_counts.entry(key.clone()).or_default();
+        *count += 1;
+
+        let id = footnote_id(&key);
+        Event::InlineHtml(
+            format!(
+                r##"<sup><a href="#user-content-fn-{id}" id="{}" data-footnote-ref aria-describedby="footnote-label">{number}</a></sup>"##,
+                footnote_reference_id(&id, *count)
+            )
+            .into(),
+        )
+    }
+
+    /// Links back to every reference of a footnote
+    fn backlinks(&self, key: &str, number: usize) -> String {
+        let id = footnote_id(key);
+        let count = self.reference_counts.get(key).copied().unwrap_or(1);
+        (1..=count)
+            .map(|index| {
+                let (label, marker) = if index == 1 {
+                    (number.to_string(), String::new())
+                } else {
+                    (format!("{number}-{index}"), format!("<sup>{index}</sup>"))
+                };
+                format!(
+                    r##"<a href="#{}" data-footnote-backref aria-label="Back to reference {label}" class="data-footnote-backref">↩{marker}</a>"##,
+                    footnote_reference_id(&id, index)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Footnote label reduced to characters safe in an ID and a fragment link
+fn footnote_id(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// ID of the `index`th (1-based) reference to a footnote
+fn footnote_reference_id(id: &str, index: usize) -> String {
+    if index == 1 {
+        format!("user-content-fnref-{id}")
+    } else {
+        format!("user-content-fnref-{id}-{index}")
+    }
+}
+
+/// Summarize how many task list items are checked (`taskLists.progress`)
+///
+/// In [`TaskProgressMode::List`] a summary goes above every top-level list
+/// with tasks, counting the tasks of its nested lists too; in
+/// [`TaskProgressMode::Document`] one summary of all tasks opens the document.
+/// Documents are re-rendered when the file changes, so checking a box in the
+/// source updates the counts.
+fn process_task_progress(events: Vec<Event<'_>>, mode: TaskProgressMode) -> Vec<Event<'_>> {
+    let count_tasks = |events: &[Event]| {
+        events
+            .iter()
+            .fold((0, 0), |(done, total), event| match event {
+                Event::TaskListMarker(checked) => (done + usize::from(*checked), total + 1),
+                _ => (done, total),
+            })
+    };
+
+    match mode {
+        TaskProgressMode::Off => events,
+        TaskProgressMode::Document => {
+            let (done, total) = count_tasks(&events);
+            if total == 0 {
+                return events;
+            }
+            let mut output = Vec::with_capacity(events.len() + 1);
+            output.push(Event::Html(task_progress_html(done, total).into()));
+            output.extend(events);
+            output
+        }
+        TaskProgressMode::List => {
+            // Summaries of the top-level lists with tasks, by the index of their start
+            let mut summaries = HashMap::new();
+            let mut index = 0;
+            while index < events.len() {
+                if !matches!(events[index], Event::Start(Tag::List(_))) {
+                    index += 1;
+                    continue;
+                }
+                let end = list_end(&events, index);
+                let (done, total) = count_tasks(&events[index..end]);
+                if total > 0 {
+                    summaries.insert(index, task_progress_html(done, total));
+                }
+                index = end;
+            }
+
+            let mut output = Vec::with_capacity(events.len() + summaries.len());
+            for (index, event) in events.into_iter().enumerate() {
+                if let Some(summary) = summaries.remove(&index) {
+                    // Keep the list's source line marker next to it
+                    let marker = output
+                        .last()
+                        .is_some_and(is_source_line_marker)
+                        .then(|| output.pop())
+                        .flatten();
+                    output.push(Event::Html(summary.into()));
+                    output.extend(marker);
+                }
+                output.push(event);
+            }
+            output
+        }
+    }
+}
+
+/// Index just past the end of the list starting at `start`
+fn list_end(events: &[Event], start: usize) -> usize {
+    let mut depth = 0usize;
+    for (index, event) in events.iter().enumerate().skip(start) {
+        match event {
+            Event::Start(Tag::List(_)) => depth += 1,
+            Event::End(TagEnd::List(_)) => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    events.len()
+}
+
+/// Progress bar and "done/total tasks done" label
+fn task_progress_html(done: usize, total: usize) -> String {
+    let noun = if total == 1 { "task" } else { "tasks" };
+    format!(
+        concat!(
+            r#"<div class="task-progress"><progress value="{done}" max="{total}"></progress>"#,
+            r#"<span class="task-progress-label">{done}/{total} {noun} done</span></div>"#,
+            "\n",
+        ),
+        done = done,
+        total = total,
+        noun = noun,
+    )
+}
+
+/// A reference link definition (`[label]: url "title"`) and its use
+#[derive(Debug, Clone, PartialEq)]
+struct LinkDefinition {
+    label: String,
+    dest: String,
+    title: Option<String>,
+    /// Links and images referring to the definition
+    uses: usize,
+    /// Definitions of the label in the document; only the first one counts
+    definitions: usize,
+}
+
+/// Collect the document's reference link definitions in document order
+///
+/// Definitions aren't part of any block the parser reports, so lines outside
+/// the blocks that look like a definition are counted to find duplicates,
+/// which the parser drops silently.
+fn collect_link_definitions(markdown: &str) -> Vec<LinkDefinition> {
+    let parser = Parser::new_ext(markdown, Options::all()).into_offset_iter();
+    let mut definitions: Vec<(usize, LinkDefinition)> = parser
+        .reference_definitions()
+        .iter()
+        .map(|(label, definition)| {
+            let entry = LinkDefinition {
+                label: label.to_string(),
+                dest: definition.dest.to_string(),
+                title: definition.title.as_ref().map(|title| title.to_string()),
+                uses: 0,
+                definitions: 0,
+            };
+            (definition.span.start, entry)
+        })
+        .collect();
+    definitions.sort_by_key(|(start, _)| *start);
+    let mut definitions: Vec<LinkDefinition> = definitions.into_iter().map(|(_, d)| d).collect();
+    let index: HashMap<String, usize> = definitions
+        .iter()
+        .enumerate()
+        .map(|(i, definition)| (normalize_link_label(&definition.label), i))
+        .collect();
+
+    let mut blocks: Vec<Range<usize>> = Vec::new();
+    let mut depth = 0usize;
+    for (event, range) in parser {
+        match &event {
+            Event::Start(Tag::Link { link_type, id, .. } | Tag::Image { link_type, id, .. })
+                if matches!(
+                    link_type,
+                    LinkType::Reference | LinkType::Collapsed | LinkType::Shortcut
+                ) =>
+            {
+                if let Some(&i) = index.get(&normalize_link_label(id)) {
+                    definitions[i].uses += 1;
+                }
+            }
+            _ => {}
+        }
+        match event {
+            Event::Start(_) => {
+                if depth == 0 {
+                    blocks.push(range);
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            _ if depth == 0 => blocks.push(range),
+            _ => {}
+        }
+    }
+
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        let in_block = blocks.iter().any(|block| block.contains(&offset));
+        if let Some(label) = link_definition_label(line).filter(|_| !in_block) {
+            if let Some(&i) = index.get(&normalize_link_label(label)) {
+                definitions[i].definitions += 1;
+            }
+        }
+        offset += line.len();
+    }
+    definitions
+}
+
+/// Label of a line starting a reference definition (`[label]: …`)
+fn link_definition_label(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let (label, rest) = trimmed.strip_prefix('[')?.split_once(']')?;
+    rest.starts_with(':').then_some(label)
+}
+
+/// Link labels match case-insensitively with runs of whitespace collapsed
+fn normalize_link_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Section listing the document's reference link definitions
+/// (`linkDefinitions.show`), or `None` without any
+fn link_definitions_html(markdown: &str) -> Option<String> {
+    let definitions = collect_link_definitions(markdown);
+    if definitions.is_empty() {
+        return None;
+    }
+
+    let mut html = String::from(
+        "<section class=\"link-definitions\">\n\
+         <p class=\"link-definitions-title\">Link definitions</p>\n\
+         <table>\n<thead><tr><th>Label</th><th>Destination</th><th>Title</th><th>Uses</th></tr></thead>\n<tbody>\n",
+    );
+    for definition in &definitions {
+        let mut flags = Vec::new();
+        if definition.uses == 0 {
+            flags.push("unused".to_string());
+        }
+        if definition.definitions > 1 {
+            flags.push(format!("defined {} times", definition.definitions));
+        }
+        let (class, flags) = if flags.is_empty() {
+            (String::new(), String::new())
+        } else {
+            (
+                r#" class="link-definition-flagged""#.to_string(),
+                format!(
+                    r#" <span class="link-definition-flag">{}</span>"#,
+                    flags.join(", ")
+                ),
+            )
+        };
+        html.push_str(&format!(
+            r#"<tr{class}><td><code>[{label}]</code>{flags}</td><td><a href="{href}">{dest}</a></td><td>{title}</td><td>{uses}</td></tr>"#,
+            label = html_escape::encode_text(&definition.label),
+            href = html_escape::encode_double_quoted_attribute(&definition.dest),
+            dest = html_escape::encode_text(&definition.dest),
+            title = html_escape::encode_text(definition.title.as_deref().unwrap_or_default()),
+            uses = definition.uses,
+        ));
+        html.push('\n');
+    }
+    html.push_str("</tbody>\n</table>\n</section>\n");
+    Some(html)
+}
+
+/// Move footnote definitions into a GitHub-style section at the end of the
+/// document
+///
+/// Footnotes are numbered in the order they are first referenced, wherever
+/// they are defined, and every reference links to its definition and back.
+/// As on GitHub, labels match case-insensitively, definitions nobody refers
+/// to are dropped and references without a definition stay plain text.
+fn process_footnotes(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut footnotes = Footnotes {
+        definitions: HashMap::new(),
+        order: Vec::new(),
+        reference_counts: HashMap::new(),
+    };
+    let mut body = Vec::with_capacity(events.len());
+    let mut definition: Option<(String, Vec<Event>)> = None;
+    for event in events {
+        match event {
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                definition = Some((label.to_lowercase(), Vec::new()));
+            }
+            Event::End(TagEnd::FootnoteDefinition) => {
+                if let Some((key, content)) = definition.take() {
+                    // The first definition of a label wins
+                    footnotes.definitions.entry(key).or_insert(content);
+                }
+            }
+            event => match &mut definition {
+                Some((_, content)) => content.push(event),
+                None => body.push(event),
+            },
+        }
+    }
+
+    let mut output = footnotes.link_references(body);
+
+    // Definitions may refer to further footnotes, which are numbered after
+    // the ones referenced from the body
+    let mut items = Vec::new();
+    while let Some(key) = footnotes.order.get(items.len()).cloned() {
+        let content = footnotes.definitions.get(&key).cloned().unwrap_or_default();
+        let content = footnotes.link_references(content);
+        items.push((key, content));
+    }
+    if items.is_empty() {
+        return output;
+    }
+
+    output.push(Event::Html(
+        concat!(
+            "<section data-footnotes class=\"footnotes\">",
+            "<h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n"
+        )
+        .into(),
+    ));
+    for (number, (key, mut content)) in items.into_iter().enumerate() {
+        let backlinks = footnotes.backlinks(&key, number + 1);
+        output.push(Event::Html(
+            format!("<li id=\"user-content-fn-{}\">\n", footnote_id(&key)).into(),
+        ));
+        // Backlinks go at the end of the last paragraph, or after the content
+        let last_block = content
+            .iter()
+            .rposition(|event| !is_source_line_marker(event));
+        match last_block {
+            Some(index) if matches!(content[index], Event::End(TagEnd::Paragraph)) => {
+                content.insert(index, Event::InlineHtml(format!(" {backlinks}").into()));
+            }
+            _ => content.push(Event::Html(format!("<p>{backlinks}</p>\n").into())),
+        }
+        output.extend(content);
+        output.push(Event::Html("</li>\n".into()));
+    }
+    output.push(Event::Html("</ol>\n</section>\n".into()));
+    output
+}
+
+/// Handler routing a click on a local document link through
+/// `window.handleMarkdownLinkClick` (left and middle button)
+fn markdown_link_onmousedown(href: &str) -> String {
+    let escaped_href = href.replace('\'', "\\'");
+    indoc::formatdoc! {r#"
+            if (event.button === 0 || event.button === 1) {{
+                event.preventDefault();
+                window.handleMarkdownLinkClick('{escaped_href}', event.button, event);
+            }}"#
+    }
+}
+
+/// Replace a local image `src` with a data URL of the file's contents
+///
+/// Files larger than `inline_max_size` bytes get a [`media_url`] instead, so
+/// the webview loads them from disk. With [`MissingImageMode::Search`], a file
+/// that doesn't exist is looked up by name under the document's directory
+/// (or the directory the path climbs to with `..`); a unique match is used
+/// and marked as relocated.
+fn inline_local_image(
+    el: &mut Element,
+    src: &str,
+    base_dir: &Path,
+    missing: MissingImageMode,
+    inline_max_size: u64,
+) -> lol_html::HandlerResult {
+    let (path, relocated) = match base_dir.join(src).canonicalize() {
+        Ok(path) => (path, false),
+        Err(_) if missing == MissingImageMode::Search => match find_moved_image(src, base_dir) {
+            Some(path) => (path, true),
+            None => return Ok(()),
+        },
+        Err(_) => return Ok(()),
+    };
+    let Some(url) = local_image_url(&path, inline_max_size) else {
+        return Ok(());
+    };
+    el.set_attribute("src", &url)?;
+    if relocated {
+        el.set_attribute("data-relocated-from", src)?;
+        if !el.has_attribute("title") {
+            el.set_attribute(
+                "title",
+                &format!("Image not found at {}; showing {}", src, path.display()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// URL of a local image: a data URL, or the media handler for files larger
+/// than `inline_max_size`
+fn local_image_url(path: &Path, inline_max_size: u64) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > inline_max_size {
+        return Some(media_url(path));
+    }
+    let image_data = std::fs::read(path).ok()?;
+    Some(image_data_url(path, &image_data))
+}
+
+/// Resolve the local candidates of a `srcset` the way [`inline_local_image`]
+/// resolves `src`
+///
+/// Remote candidates, data URLs and files that don't exist are left as written.
+fn resolve_local_srcset(srcset: &str, base_dir: &Path, inline_max_size: u64) -> String {
+    map_srcset(srcset, |url| {
+        if url.starts_with("http://") || url.starts_with("https://") || url.starts_with("data:") {
+            return None;
+        }
+        let path = base_dir.join(url).canonicalize().ok()?;
+        local_image_url(&path, inline_max_size)
+    })
+}
+
+/// Replace the URLs of a `srcset` for which `map` returns a new one, keeping
+/// the width or density descriptors of the candidates
+fn map_srcset(srcset: &str, mut map: impl FnMut(&str) -> Option<String>) -> String {
+    let mut candidates = Vec::new();
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        // The URL runs to the next whitespace (data URLs contain commas)
+        let url_end = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let (url, after) = rest.split_at(url_end);
+        let (url, descriptor) = match url.strip_suffix(',') {
+            Some(url) => {
+                rest = after;
+                (url, "")
+            }
+            None => {
+                let end = after.find(',').unwrap_or(after.len());
+                rest = &after[end..];
+                (url, after[..end].trim())
+            }
+        };
+
+        let url = map(url).unwrap_or_else(|| url.to_string());
+        candidates.push(if descriptor.is_empty() {
+            url
+        } else {
+            format!("{} {}", url, descriptor)
+        });
+    }
+    candidates.join(", ")
+}
+
+/// Build a data URL of an image file's contents
+fn image_data_url(path: &Path, data: &[u8]) -> String {
+    format!(
+        "data:{};base64,{}",
+        get_mime_type(path),
+        general_purpose::STANDARD.encode(data)
+    )
+}
+
+/// Embed images that [`inline_local_image`] left to be loaded from disk
+///
+/// Used where the HTML must be self-contained, such as exports. Only image
+/// URLs the renderer issued are embedded; other `/arto-media/` URLs in the
+/// document's raw HTML are left as written.
+pub fn inline_served_images(html: &str) -> Result<String> {
+    let mut output = Vec::new();
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                element!("img[src^='/']", |el| {
+                    let path = el
+                        .get_attribute("src")
+                        .and_then(|src| issued_image_path(&src));
+                    if let Some(path) = path {
+                        if let Ok(data) = std::fs::read(&path) {
+                            el.set_attribute("src", &image_data_url(&path, &data))?;
+                        }
+                    }
+                    Ok(())
+                }),
+                element!("img[srcset], picture > source[srcset]", |el| {
+                    if let Some(srcset) = el.get_attribute("srcset") {
+                        let inlined = map_srcset(&srcset, |url| {
+                            let path = issued_image_path(url)?;
+                            let data = std::fs::read(&path).ok()?;
+                            Some(image_data_url(&path, &data))
+                        });
+                        el.set_attribute("srcset", &inlined)?;
+                    }
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| output.extend_from_slice(chunk),
+    );
+    rewriter.write(html.as_bytes())?;
+    rewriter.end()?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Look for a moved image by file name (see [`inline_local_image`])
+fn find_moved_image(src: &str, base_dir: &Path) -> Option<PathBuf> {
+    let src = Path::new(src);
+    let name = src.file_name()?;
+    let mut root = base_dir.to_path_buf();
+    for component in src.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                root.pop();
+            }
+            std::path::Component::CurDir => {}
+            _ => break,
+        }
+    }
+    find_moved_file(&root, name)
+}
+
+/// Resolve a local media `src` to the URL it is served under
+///
+/// Returns `None` for remote/data URLs, missing files and non-media files.
+fn resolve_media_src(src: &str, base_dir: &Path) -> Option<String> {
+    if src.starts_with("http://") || src.starts_with("https://") || src.starts_with("data:") {
+        return None;
+    }
+    let path = base_dir.join(src).canonicalize().ok()?;
+    media_type(&path)?;
+    Some(media_url(&path))
+}
+
+/// Build an inline player for an image-syntax embed of a local media file
+fn render_media_player(src: &str, base_dir: &Path, alt: &str) -> Option<String> {
+    let url = resolve_media_src(src, base_dir)?;
+    let (kind, _) = media_type(Path::new(src))?;
+    Some(format!(
+        r#"<{tag} class="media-embed" controls preload="metadata" src="{url}" title="{title}"></{tag}>"#,
+        tag = kind.tag_name(),
+        url = html_escape::encode_double_quoted_attribute(&url),
+        title = html_escape::encode_double_quoted_attribute(alt),
+    ))
+}
+
+/// Render an image file opened directly in a tab
+pub fn render_image_file(path: &Path, bytes: &[u8]) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    format!(
+        r#"<div class="image-file-viewer"><img src="{}" alt="{}"></div>"#,
+        image_data_url(path, bytes),
+        html_escape::encode_double_quoted_attribute(&name)
+    )
+}
+
+/// Infer MIME type from file extension
+fn get_mime_type(path: &Path) -> &'static str {
+    image_type(path).unwrap_or("image/png")
+}
+
+/// Render Markdown to HTML with TOC information
+///
+/// Returns a tuple of (rendered HTML with heading IDs, extracted headings).
+/// Block-level elements carry a `data-source-line` attribute with the line
+/// they start on, used to sync the split preview's source pane.
+pub fn render_to_html_with_toc(
+    markdown: impl AsRef<str>,
+    base_path: impl AsRef<Path>,
+) -> Result<(String, Vec<HeadingInfo>)> {
+    let markdown = markdown.as_ref();
+    let base_path = base_path.as_ref();
+
+    // Enable GitHub Flavored Markdown options, plus the parser's other
+    // extensions such as Pandoc-style definition lists (`: definition` lines)
+    let options = Options::all();
+
+    // Get base directory for resolving relative paths
+    let base_dir = base_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    // Extract frontmatter if present
+    let frontmatter_config = document_frontmatter_config(base_path);
+    let (frontmatter_html, content) =
+        extract_and_render_frontmatter(markdown, &frontmatter_config, &base_dir);
+
+    let frontmatter = &markdown[..markdown.len() - content.len()];
+    let line_offset = frontmatter.matches('\n').count();
+
+    // Expand snippets, the inline TOC placeholder and wiki links, and process
+    // GitHub alerts, remembering which source line each processed line came from
+    let snippets = CONFIG.read().snippets.clone();
+    let (expanded, snippet_line_map) = expand_snippets_with_line_map(&content, &snippets);
+    let expanded = mark_inline_toc(&expanded);
+    let expanded = process_wiki_links(&expanded, &base_dir);
+    let alerts = CONFIG.read().alerts.clone();
+    let (processed_markdown, alert_line_map) =
+        process_github_alerts_with_line_map(&expanded, &alerts);
+    let line_map = alert_line_map
+        .into_iter()
+        .map(|line| snippet_line_map.get(line).copied().unwrap_or(line))
+        .collect();
+
+    // Extract headings (including those from snippets) with the frontmatter
+    // kept in place, so the document is split the same way
+    let mut headings = extract_headings(&format!("{}{}", frontmatter, expanded));
+
+    // Parse Markdown and process blocks
+    let parser = Parser::new_ext(&processed_markdown, options).into_offset_iter();
+    let parser = mark_source_lines(parser, &processed_markdown, line_map, line_offset);
+    let parser = process_code_blocks(parser, "mermaid");
+    let parser = process_code_blocks(parser, "math");
+    let parser = process_geojson_blocks(parser, CONFIG.read().geojson.enabled);
+    let parser = process_math_expressions(parser);
+
+    // Present a leading H1 as configured; a hidden one is left out of the TOC
+    let leading_heading = CONFIG.read().document_title.leading_heading;
+    let (events, heading_hidden) =
+        apply_leading_heading_style(parser.collect(), leading_heading, &headings, base_path);
+    if heading_hidden {
+        headings.remove(0);
+    }
+    let events = process_captions(events, &CONFIG.read().captions);
+    let events = process_emoji_shortcodes(events, CONFIG.read().emoji.shortcodes);
+    let events = process_spoilers(events, CONFIG.read().spoilers.enabled);
+    let events = process_footnotes(events);
+    let events = process_task_progress(events, CONFIG.read().task_lists.progress);
+    let events = process_code_copy_buttons(events);
+    let syntax_highlight = CONFIG.read().syntax_highlight.clone();
+    let events = process_code_lines(events, &CONFIG.read().code_lines, &syntax_highlight);
+    let events = process_syntax_highlighting(events, &syntax_highlight);
+
+    // Convert to HTML
+    let mut html_output = String::new();
+    html::push_html(
+        &mut html_output,
+        process_code_block_flags(events.into_iter(), syntax_highlight.disabled_languages),
+    );
+    let mut html_output = apply_source_line_markers(&html_output);
+    if CONFIG.read().link_definitions.show {
+        html_output.extend(link_definitions_html(&processed_markdown));
+    }
+    let html_output = sanitize_inline_svg(&html_output);
+
+    // Post-process HTML with heading IDs
+    let anchors = CONFIG.read().anchors.clone();
+    let html_output =
+        post_process_html_with_headings(&html_output, base_dir.as_path(), &headings, &anchors);
+    let html_output = html_output.replacen(INLINE_TOC_MARKER, &inline_toc_html(&headings), 1);
+
+    // Prepend frontmatter table if present
+    let final_output = if frontmatter_html.is_empty() {
+        html_output
+    } else {
+        format!("{}\n{}", frontmatter_html, html_output)
+    };
+
+    // Flag partials opened directly, as they are meant to be read in context
+    let partials = CONFIG.read().partials.clone();
+    let final_output = if is_partial_document(base_path, markdown, &partials) {
+        format!("{}\n{}", PARTIAL_BANNER, final_output)
+    } else {
+        final_output
+    };
+
+    Ok((final_output, headings))
+}
+
+/// Check whether an event is a marker inserted by [`mark_source_lines`]
+fn is_source_line_marker(event: &Event) -> bool {
+    matches!(event, Event::Html(html) if html.starts_with(SOURCE_LINE_MARKER))
+}
+
+/// Normalize a title for comparison: lowercase alphanumerics only, so
+/// "Getting Started" matches "getting-started.md"
+fn normalize_title(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Apply the configured style to an H1 at the very start of the document
+///
+/// `headings` are the document's headings, the first of which is the leading
+/// H1 if there is one. In [`LeadingHeadingStyle::Title`] the heading gets the
+/// `document-title` class, and a paragraph directly below it that consists of
+/// a single emphasis becomes the `document-subtitle`. In
+/// [`LeadingHeadingStyle::HideIfFileName`] a heading repeating the file name is
+/// replaced by an empty anchor keeping its ID; the returned flag reports
+/// whether it was hidden.
+fn apply_leading_heading_style<'a>(
+    mut events: Vec<Event<'a>>,
+    style: LeadingHeadingStyle,
+    headings: &[HeadingInfo],
+    file: &Path,
+) -> (Vec<Event<'a>>, bool) {
+    let Some(heading) = headings.first() else {
+        return (events, false);
+    };
+    let Some(start) = events.iter().position(|e| !is_source_line_marker(e)) else {
+        return (events, false);
+    };
+    if !matches!(
+        events[start],
+        Event::Start(Tag::Heading {
+            level: HeadingLevel::H1,
+            ..
+        })
+    ) {
+        return (events, false);
+    }
+    let Some(end) = events[start..]
+        .iter()
+        .position(|e| matches!(e, Event::End(TagEnd::Heading(_))))
+        .map(|offset| start + offset)
+    else {
+        return (events, false);
+    };
+
+    match style {
+        LeadingHeadingStyle::Heading => (events, false),
+        LeadingHeadingStyle::Title => {
+            if let Event::Start(Tag::Heading { classes, .. }) = &mut events[start] {
+                classes.push("document-title".into());
+            }
+            if let Some(paragraph) =
+                (end + 1..events.len()).find(|&i| !is_source_line_marker(&events[i]))
+            {
+                if is_emphasis_paragraph(&events[paragraph..]) {
+                    events[paragraph] = Event::Html(r#"<p class="document-subtitle">"#.into());
+                }
+            }
+            (events, false)
+        }
+        LeadingHeadingStyle::HideIfFileName => {
+            let file_stem = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy())
+                .unwrap_or_default();
+            if normalize_title(&heading.text) != normalize_title(&file_stem) {
+                return (events, false);
+            }
+            let anchor = format!(
+                r#"<a id="{}" class="document-title-anchor"></a>"#,
+                html_escape::encode_double_quoted_attribute(&heading.id)
+            );
+            events.splice(start..=end, [Event::Html(anchor.into())]);
+            (events, true)
+        }
+    }
+}
+
+/// Check whether events start with a paragraph made of a single emphasis
+fn is_emphasis_paragraph(events: &[Event]) -> bool {
+    if !matches!(
+        events,
+        [
+            Event::Start(Tag::Paragraph),
+            Event::Start(Tag::Emphasis),
+            ..
+        ]
+    ) {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, event) in events.iter().enumerate().skip(1) {
+        match event {
+            Event::Start(Tag::Emphasis) => depth += 1,
+            Event::End(TagEnd::Emphasis) => {
+                depth -= 1;
+                if depth == 0 {
+                    return matches!(events.get(i + 1), Some(Event::End(TagEnd::Paragraph)));
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Prefix GitHub adds to heading IDs in rendered READMEs
+const USER_CONTENT_PREFIX: &str = "user-content-";
+
+/// `#` link to a heading, shown on hover
+///
+/// The sign comes from the stylesheet, so the link adds no text to the heading
+/// (search, copied text, scroll anchors). The renderer turns a click into a
+/// jump that stays in the document and copies the fragment.
+fn heading_link(heading: &HeadingInfo) -> String {
+    format!(
+        r##"<a class="heading-anchor" href="#{}" aria-label="Link to {}"></a>"##,
+        html_escape::encode_double_quoted_attribute(&heading.id),
+        html_escape::encode_double_quoted_attribute(&heading.text),
+    )
+}
+
+/// Post-process HTML to handle img, anchor tags, and add heading IDs using lol_html
+///
+/// With `anchors.githubUserContentPrefix`, every heading also gets a
+/// `user-content-` prefixed anchor and fragment links using that prefix are
+/// pointed at the plain ID, so links copied from GitHub resolve either way.
+/// With `anchors.headingLinks`, every heading starts with a `#` link to itself.
+fn post_process_html_with_headings(
+    html_str: &str,
+    base_dir: &Path,
+    headings: &[HeadingInfo],
+    anchors: &AnchorsConfig,
+) -> String {
+    let user_content_ids = anchors.github_user_content_prefix;
+    let heading_links = anchors.heading_links;
+    let base_dir = base_dir.to_path_buf();
+    let media_base_dir = base_dir.clone();
+    let srcset_base_dir = base_dir.clone();
+    let wide_images = CONFIG.read().images.wide;
+    let missing_images = CONFIG.read().images.missing;
+    let inline_max_size = CONFIG.read().images.inline_max_size;
+    let rst_links = CONFIG.read().rst.enabled;
+    let max_table_rows = CONFIG.read().tables.max_rows;
+    let mut output = Vec::new();
+    let heading_index = std::cell::RefCell::new(0usize);
+    let list_depth = Rc::new(Cell::new(0));
+    let table_rows = Rc::new(RefCell::new(Vec::new()));
+    let picture_depth = Rc::new(Cell::new(0));
+    let img_picture_depth = Rc::clone(&picture_depth);
+    let headings = headings.to_vec();
+
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                // Process heading tags: add IDs for TOC navigation
+                element!("h1, h2, h3, h4, h5, h6", |el| {
+                    let mut idx = heading_index.borrow_mut();
+                    if let Some(heading) = headings.get(*idx) {
+                        el.set_attribute("id", &heading.id)?;
+                        if heading_links {
+                            el.prepend(&heading_link(heading), ContentType::Html);
+                        }
+                        if user_content_ids {
+                            el.prepend(
+                                &format!(
+                                    r#"<a id="{}{}" class="user-content-anchor" aria-hidden="true"></a>"#,
+                                    USER_CONTENT_PREFIX,
+                                    html_escape::encode_double_quoted_attribute(&heading.id)
+                                ),
+                                ContentType::Html,
+                            );
+                        }
+                    }
+                    *idx += 1;
+                    Ok(())
+                }),
+                // Process ordered lists: add nesting depth classes for numbering styles
+                element!("ol", |el| mark_ordered_list(el, &list_depth)),
+                // Process long tables: collapse the rows beyond the configured count
+                element!("table", |el| collapse_long_table(
+                    el,
+                    &table_rows,
+                    max_table_rows
+                )),
+                element!("tbody > tr", |el| collapse_table_row(
+                    el,
+                    &table_rows,
+                    max_table_rows
+                )),
+                // Process fragment links: drop GitHub's `user-content-` prefix
+                element!("a[href^='#']", |el| {
+                    if !user_content_ids {
+                        return Ok(());
+                    }
+                    let href = el.get_attribute("href").unwrap_or_default();
+                    if let Some(id) = href[1..].strip_prefix(USER_CONTENT_PREFIX) {
+                        if headings.iter().any(|heading| heading.id == id) {
+                            el.set_attribute("href", &format!("#{}", id))?;
+                        }
+                    }
+                    Ok(())
+                }),
+                // Process media tags: serve local files through the media asset handler
+                element!("video[src], audio[src], source[src]", |el| {
+                    if let Some(url) = el
+                        .get_attribute("src")
+                        .and_then(|src| resolve_media_src(&src, &media_base_dir))
+                    {
+                        el.set_attribute("src", &url)?;
+                    }
+                    Ok(())
+                }),
+                // Process pictures: frame them in place of their img, and
+                // let the viewer pick light/dark sources by the theme
+                element!("picture", |el| frame_picture(
+                    el,
+                    &picture_depth,
+                    wide_images
+                )),
+                element!("picture > source[media]", mark_color_scheme_source),
+                // Process srcset: resolve local candidates like img src
+                element!("img[srcset], picture > source[srcset]", |el| {
+                    if let Some(srcset) = el.get_attribute("srcset") {
+                        let resolved =
+                            resolve_local_srcset(&srcset, &srcset_base_dir, inline_max_size);
+                        el.set_attribute("srcset", &resolved)?;
+                    }
+                    Ok(())
+                }),
+                // Process img tags: convert relative paths to data URLs
+                element!("img[src]", move |el| {
+                    if let Some(src) = el.get_attribute("src") {
+                        // Image syntax pointing at audio/video renders an inline player
+                        let alt = el.get_attribute("alt").unwrap_or_default();
+                        if let Some(player) = render_media_player(&src, &base_dir, &alt) {
+                            el.replace(&player, ContentType::Html);
+                            return Ok(());
+                        }
+                        mark_dark_image(el, &alt)?;
+                        if img_picture_depth.get() == 0 {
+                            frame_wide_image(el, wide_images);
+                        }
+                        if !src.starts_with("http://")
+                            && !src.starts_with("https://")
+                            && !src.starts_with("data:")
+                        {
+                            inline_local_image(
+                                el,
+                                &src,
+                                &base_dir,
+                                missing_images,
+                                inline_max_size,
+                            )?;
+                        }
+                    }
+                    Ok(())
+                }),
+                // Process anchor tags: convert markdown links to spans
+                element!("a[href]", |el| {
+                    if let Some(href) = el.get_attribute("href") {
+                        if !href.starts_with("http://") && !href.starts_with("https://") {
+                            // Spaces and non-ASCII names arrive percent-encoded
+                            let href = percent_encoding::percent_decode_str(&href)
+                                .decode_utf8_lossy()
+                                .into_owned();
+                            if let Some(ext) = std::path::Path::new(&href)
+                                .extension()
+                                .and_then(|e| e.to_str())
+                            {
+                                // Replace with span element
+                                let onclick = markdown_link_onmousedown(&href);
+                                el.set_tag_name("span")?;
+                                el.remove_attribute("href");
+                                if ext != "md" && ext != "markdown" && !(rst_links && ext == "rst")
+                                {
+                                    el.set_attribute("class", "md-link md-link-invalid")?;
+                                } else {
+                                    el.set_attribute("class", "md-link")?;
+                                    // Target for link previews on hover
+                                    el.set_attribute("data-md-link", &href)?;
+                                }
+                                el.set_attribute("onmousedown", &onclick)?;
+                            }
+                        }
+                    }
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| {
+            output.extend_from_slice(chunk);
+        },
+    );
+
+    let _ = rewriter.write(html_str.as_bytes());
+    let _ = rewriter.end();
+    String::from_utf8(output).unwrap_or_else(|_| html_str.to_string())
+}
+
+/// Add an `ol-depth-N` class (1-based nesting depth among ordered lists) to an
+/// ordered list, and expose its `start` number to CSS counters as `--ol-start`
+///
+/// `depth` tracks the number of currently open `<ol>` elements.
+fn mark_ordered_list(el: &mut Element, depth: &Rc<Cell<usize>>) -> lol_html::HandlerResult {
+    depth.set(depth.get() + 1);
+    let class = match el.get_attribute("class") {
+        Some(existing) => format!("{} ol-depth-{}", existing, depth.get()),
+        None => format!("ol-depth-{}", depth.get()),
+    };
+    el.set_attribute("class", &class)?;
+    if let Some(start) = el
+        .get_attribute("start")
+        .and_then(|start| start.parse::<i64>().ok())
+    {
+        el.set_attribute("style", &format!("--ol-start: {}", start - 1))?;
+    }
+
+    let depth = Rc::clone(depth);
+    if let Some(handlers) = el.end_tag_handlers() {
+        handlers.push(Box::new(move |_| {
+            depth.set(depth.get().saturating_sub(1));
+            Ok(())
+        }));
+    }
+    Ok(())
+}
+
+/// Class of the table body rows collapsed by `tables.maxRows`
+const COLLAPSED_ROW_CLASS: &str = "table-row-collapsed";
+
+/// Track the body rows of a table and, once it ends, add a button showing the
+/// rows collapsed beyond `max_rows`
+///
+/// `rows` holds the row counts of the currently open (possibly nested) tables.
+fn collapse_long_table(
+    el: &mut Element,
+    rows: &Rc<RefCell<Vec<usize>>>,
+    max_rows: Option<usize>,
+) -> lol_html::HandlerResult {
+    let Some(max_rows) = max_rows else {
+        return Ok(());
+    };
+    rows.borrow_mut().push(0);
+
+    let rows = Rc::clone(rows);
+    if let Some(handlers) = el.end_tag_handlers() {
+        handlers.push(Box::new(move |end| {
+            let count = rows.borrow_mut().pop().unwrap_or_default();
+            if count > max_rows {
+                end.after(
+                    &format!(
+                        r#"<div class="table-collapsed-toggle"><button type="button" class="table-show-all">Show all {} rows</button></div>"#,
+                        count
+                    ),
+                    ContentType::Html,
+                );
+            }
+            Ok(())
+        }));
+    }
+    Ok(())
+}
+
+/// Count a table body row, collapsing it when it comes after `max_rows`
+///
+/// Collapsed rows stay in the document, so they keep their `data-source-line`
+/// and search still finds their text.
+fn collapse_table_row(
+    el: &mut Element,
+    rows: &Rc<RefCell<Vec<usize>>>,
+    max_rows: Option<usize>,
+) -> lol_html::HandlerResult {
+    let Some(max_rows) = max_rows else {
+        return Ok(());
+    };
+    let mut rows = rows.borrow_mut();
+    let Some(count) = rows.last_mut() else {
+        return Ok(());
+    };
+    *count += 1;
+    if *count > max_rows {
+        let class = match el.get_attribute("class") {
+            Some(existing) => format!("{} {}", existing, COLLAPSED_ROW_CLASS),
+            None => COLLAPSED_ROW_CLASS.to_string(),
+        };
+        el.set_attribute("class", &class)?;
+    }
+    Ok(())
+}
+
+/// Turn a `#invert`/`#noinvert` marker in an image's alt text into a class
+///
+/// The marker is removed from the alt text; the dark-mode filter itself is a
+/// stylesheet rule so it follows the theme and configuration without a re-render.
+fn mark_dark_image(el: &mut Element, alt: &str) -> lol_html::HandlerResult {
+    let Some((marker, class)) = DARK_IMAGE_MARKERS
+        .iter()
+        .find(|(marker, _)| alt.contains(marker))
+    else {
+        return Ok(());
+    };
+    let alt = alt
+        .replace(marker, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    el.set_attribute("alt", &alt)?;
+    let class = match el.get_attribute("class") {
+        Some(existing) => format!("{} {}", existing, class),
+        None => class.to_string(),
+    };
+    el.set_attribute("class", &class)?;
+    Ok(())
+}
+
+/// `<picture>` source media queries on the color scheme, and the theme each
+/// one selects
+const COLOR_SCHEME_QUERIES: [(&str, &str); 2] = [
+    ("(prefers-color-scheme:dark)", "dark"),
+    ("(prefers-color-scheme:light)", "light"),
+];
+
+/// Mark a `<picture>` source for the dark or light theme
+///
+/// `prefers-color-scheme` follows the system rather than the theme chosen in
+/// Arto, so the viewer enables the sources marked with `data-color-scheme`
+/// by the current theme instead.
+fn mark_color_scheme_source(el: &mut Element) -> lol_html::HandlerResult {
+    let media: String = el
+        .get_attribute("media")
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if let Some((_, scheme)) = COLOR_SCHEME_QUERIES
+        .iter()
+        .find(|(query, _)| *query == media)
+    {
+        el.set_attribute("data-color-scheme", scheme)?;
+    }
+    Ok(())
+}
+
+/// Frame a `<picture>` like a lone image, counting the open pictures so their
+/// `<img>` isn't framed too (which would take it out of the picture)
+fn frame_picture(
+    el: &mut Element,
+    depth: &Rc<Cell<usize>>,
+    mode: WideImageMode,
+) -> lol_html::HandlerResult {
+    frame_wide_image(el, mode);
+    depth.set(depth.get() + 1);
+
+    let depth = Rc::clone(depth);
+    if let Some(handlers) = el.end_tag_handlers() {
+        handlers.push(Box::new(move |_| {
+            depth.set(depth.get().saturating_sub(1));
+            Ok(())
+        }));
+    }
+    Ok(())
+}
+
+/// Wrap an image in a frame controlling how it behaves when wider than the
+/// content column
+///
+/// Every image gets the frame since intrinsic sizes of remote images are not
+/// known here; the frame shrinks to fit images that are not wide.
+fn frame_wide_image(el: &mut Element, mode: WideImageMode) {
+    let class = match mode {
+        WideImageMode::Downscale => return,
+        WideImageMode::Scroll => "image-frame image-frame-scroll",
+        WideImageMode::Natural => "image-frame image-frame-natural",
+    };
+    el.before(&format!(r#"<span class="{}">"#, class), ContentType::Html);
+    el.after("</span>", ContentType::Html);
+}
+
+/// Post-process HTML to handle img and anchor tags using lol_html
+fn post_process_html_tags(html_str: &str, base_dir: &Path) -> String {
+    let base_dir = base_dir.to_path_buf();
+    let media_base_dir = base_dir.clone();
+    let srcset_base_dir = base_dir.clone();
+    let wide_images = CONFIG.read().images.wide;
+    let missing_images = CONFIG.read().images.missing;
+    let inline_max_size = CONFIG.read().images.inline_max_size;
+    let rst_links = CONFIG.read().rst.enabled;
+    let max_table_rows = CONFIG.read().tables.max_rows;
+    let mut output = Vec::new();
+    let list_depth = Rc::new(Cell::new(0));
+    let table_rows = Rc::new(RefCell::new(Vec::new()));
+    let picture_depth = Rc::new(Cell::new(0));
+    let img_picture_depth = Rc::clone(&picture_depth);
+
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                // Process ordered lists: add nesting depth classes for numbering styles
+                element!("ol", |el| mark_ordered_list(el, &list_depth)),
+                // Process long tables: collapse the rows beyond the configured count
+                element!("table", |el| collapse_long_table(
+                    el,
+                    &table_rows,
+                    max_table_rows
+                )),
+                element!("tbody > tr", |el| collapse_table_row(
+                    el,
+                    &table_rows,
+                    max_table_rows
+                )),
+                // Process media tags: serve local files through the media asset handler
+                element!("video[src], audio[src], source[src]", |el| {
+                    if let Some(url) = el
+                        .get_attribute("src")
+                        .and_then(|src| resolve_media_src(&src, &media_base_dir))
+                    {
+                        el.set_attribute("src", &url)?;
+                    }
+                    Ok(())
+                }),
+                // Process pictures: frame them in place of their img, and
+                // let the viewer pick light/dark sources by the theme
+                element!("picture", |el| frame_picture(
+                    el,
+                    &picture_depth,
+                    wide_images
+                )),
+                element!("picture > source[media]", mark_color_scheme_source),
+                // Process srcset: resolve local candidates like img src
+                element!("img[srcset], picture > source[srcset]", |el| {
+                    if let Some(srcset) = el.get_attribute("srcset") {
+                        let resolved =
+                            resolve_local_srcset(&srcset, &srcset_base_dir, inline_max_size);
+                        el.set_attribute("srcset", &resolved)?;
+                    }
+                    Ok(())
+                }),
+                // Process img tags: convert relative paths to data URLs
+                element!("img[src]", move |el| {
+                    if let Some(src) = el.get_attribute("src") {
+                        // Image syntax pointing at audio/video renders an inline player
+                        let alt = el.get_attribute("alt").unwrap_or_default();
+                        if let Some(player) = render_media_player(&src, &base_dir, &alt) {
+                            el.replace(&player, ContentType::Html);
+                            return Ok(());
+                        }
+                        mark_dark_image(el, &alt)?;
+                        if img_picture_depth.get() == 0 {
+                            frame_wide_image(el, wide_images);
+                        }
+                        if !src.starts_with("http://")
+                            && !src.starts_with("https://")
+                            && !src.starts_with("data:")
+                        {
+                            inline_local_image(
+                                el,
+                                &src,
+                                &base_dir,
+                                missing_images,
+                                inline_max_size,
+                            )?;
+                        }
+                    }
+                    Ok(())
+                }),
+                // Process anchor tags: convert markdown links to spans
+                element!("a[href]", |el| {
+                    if let Some(href) = el.get_attribute("href") {
+                        if !href.starts_with("http://") && !href.starts_with("https://") {
+                            // Spaces and non-ASCII names arrive percent-encoded
+                            let href = percent_encoding::percent_decode_str(&href)
+                                .decode_utf8_lossy()
+                                .into_owned();
+                            if let Some(ext) = std::path::Path::new(&href)
+                                .extension()
+                                .and_then(|e| e.to_str())
+                            {
+                                // Replace with span element
+                                let onclick = markdown_link_onmousedown(&href);
+                                el.set_tag_name("span")?;
+                                el.remove_attribute("href");
+                                if ext != "md" && ext != "markdown" && !(rst_links && ext == "rst")
+                                {
+                                    el.set_attribute("class", "md-link md-link-invalid")?;
+                                } else {
+                                    el.set_attribute("class", "md-link")?;
+                                    // Target for link previews on hover
+                                    el.set_attribute("data-md-link", &href)?;
+                                }
+                                el.set_attribute("onmousedown", &onclick)?;
+                            }
+                        }
+                    }
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| {
+            output.extend_from_slice(chunk);
+        },
+    );
+
+    let _ = rewriter.write(html_str.as_bytes());
+    let _ = rewriter.end();
+    String::from_utf8(output).unwrap_or_else(|_| html_str.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AlertTypeConfig;
+    use indoc::indoc;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_get_mime_type() {
+        assert_eq!(get_mime_type(Path::new("test.png")), "image/png");
+        assert_eq!(get_mime_type(Path::new("test.jpg")), "image/jpeg");
+        assert_eq!(get_mime_type(Path::new("test.jpeg")), "image/jpeg");
+        assert_eq!(get_mime_type(Path::new("test.gif")), "image/gif");
+        assert_eq!(get_mime_type(Path::new("test.svg")), "image/svg+xml");
+        assert_eq!(get_mime_type(Path::new("test.webp")), "image/webp");
+        assert_eq!(get_mime_type(Path::new("test.bmp")), "image/bmp");
+        assert_eq!(get_mime_type(Path::new("test.ico")), "image/x-icon");
+        assert_eq!(get_mime_type(Path::new("test.unknown")), "image/png");
+    }
+
+    #[test]
+    fn test_get_alert_icon_placeholder() {
+        let result = get_alert_icon_placeholder("note");
+        assert_eq!(
+            result,
+            r#"<span class="alert-icon" data-alert-type="note"></span>"#
+        );
+
+        let result = get_alert_icon_placeholder("warning");
+        assert_eq!(
+            result,
+            r#"<span class="alert-icon" data-alert-type="warning"></span>"#
+        );
+    }
+
+    #[test]
+    fn test_process_github_alerts_note() {
+        let input = indoc! {"
+            > [!NOTE]
+            > This is a note
+        "};
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert!(result.contains(r#"<div class="markdown-alert markdown-alert-note""#));
+        assert!(result.contains(r#"<p class="markdown-alert-title""#));
+        assert!(result.contains("NOTE"));
+        assert!(result.contains("This is a note"));
+        assert!(result.contains("</div>"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_warning() {
+        let input = indoc! {"
+            > [!WARNING]
+            > Be careful!
+        "};
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert!(result.contains(r#"markdown-alert-warning"#));
+        assert!(result.contains("WARNING"));
+        assert!(result.contains("Be careful!"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_with_multiline() {
+        let input = indoc! {"
+            > [!IMPORTANT]
+            > First line
+            > Second line
+            > Third line
+        "};
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert!(result.contains(r#"markdown-alert-important"#));
+        assert!(result.contains("First line"));
+        assert!(result.contains("Second line"));
+        assert!(result.contains("Third line"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_all_types() {
+        let alert_types = vec![
+            ("NOTE", "note"),
+            ("TIP", "tip"),
+            ("IMPORTANT", "important"),
+            ("WARNING", "warning"),
+            ("CAUTION", "caution"),
+        ];
+
+        for (alert_name, alert_class) in alert_types {
+            let input = format!("> [!{}]\n> Test content", alert_name);
+            let result = process_github_alerts(&input, &AlertsConfig::default());
+
+            assert!(
+                result.contains(&format!(r#"markdown-alert-{}"#, alert_class)),
+                "Should contain alert class for {}",
+                alert_name
+            );
+            assert!(
+                result.contains(alert_name),
+                "Should contain alert name {}",
+                alert_name
+            );
+        }
+    }
+
+    #[test]
+    fn test_process_github_alerts_no_match() {
+        let input = "Regular paragraph\n> Regular quote";
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert_eq!(result, input);
+        assert!(!result.contains("markdown-alert"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_title_case_and_labels() {
+        let input = "> [!NOTE]\n> Body\n\n> [!WARNING]\n> Body";
+        let config = AlertsConfig {
+            title_case: AlertTitleCase::Title,
+            labels: [("WARNING".to_string(), "Caution!".to_string())].into(),
+            ..AlertsConfig::default()
+        };
+        let result = process_github_alerts(input, &config);
+
+        assert!(result.contains(r#"data-alert-type="note"></span>Note</p>"#));
+        // Labels are matched regardless of the case of their key
+        assert!(result.contains(r#"data-alert-type="warning"></span>Caution!</p>"#));
+    }
+
+    #[test]
+    fn test_process_github_alerts_custom_types() {
+        let input = "> [!DANGER]\n> Do not\n\n> [!SUCCESS]\n> Done";
+        let config = AlertsConfig {
+            types: [
+                (
+                    "DANGER".to_string(),
+                    AlertTypeConfig {
+                        class: "danger".to_string(),
+                        icon: "caution".to_string(),
+                    },
+                ),
+                (
+                    "SUCCESS".to_string(),
+                    AlertTypeConfig {
+                        class: "success".to_string(),
+                        icon: String::new(),
+                    },
+                ),
+            ]
+            .into(),
+            ..AlertsConfig::default()
+        };
+        let result = process_github_alerts(input, &config);
+
+        assert!(result.contains(r#"<div class="markdown-alert markdown-alert-danger" dir="auto">"#));
+        assert!(result.contains(r#"data-alert-type="caution"></span>DANGER</p>"#));
+        assert!(result.contains("<p>Do not</p>"));
+        // The icon defaults to the class
+        assert!(result.contains(r#"data-alert-type="success"></span>SUCCESS</p>"#));
+
+        // The built-in types are still recognized
+        let result = process_github_alerts("> [!NOTE]\n> Body", &config);
+        assert!(result.contains("markdown-alert-note"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_unknown_type() {
+        let input = "> [!DANGER]\n> Do not";
+        let result = process_github_alerts(input, &AlertsConfig::default());
+        assert_eq!(result, input);
+
+        // The quote and its content are rendered, not swallowed
+        let html = render_to_html(input, Path::new("test.md")).unwrap();
+        assert!(html.contains("<blockquote>"));
+        assert!(html.contains("Do not"));
+        assert!(!html.contains("markdown-alert"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_inline_title() {
+        let input = "> [!TIP] Keyboard <shortcut>\n> Press Cmd+K";
+        let config = AlertsConfig {
+            inline_title: true,
+            ..AlertsConfig::default()
+        };
+        let result = process_github_alerts(input, &config);
+
+        assert!(result.contains(r#"</span>Keyboard &lt;shortcut&gt;</p>"#));
+        assert!(result.contains("<p>Press Cmd+K</p>"));
+
+        // Without the option the text stays part of the body
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert!(result.contains(r#"</span>TIP</p>"#));
+        assert!(result.contains("Keyboard <shortcut>"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_in_bullet_list() {
+        let input = indoc! {"
+            - First item
+              > [!NOTE]
+              > Nested note
+            - Second item
+        "};
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert!(result.contains("  <div class=\"markdown-alert markdown-alert-note\""));
+        assert!(result.contains("  <p>Nested note</p>\n  </div>"));
+
+        let html = render_to_html(input, Path::new("test.md")).unwrap();
+        let item_start = html.find("<li>").unwrap();
+        let item_end = html.find("</li>").unwrap();
+        let alert = html.find("markdown-alert-note").unwrap();
+        assert!(item_start < alert && alert < item_end);
+        assert_eq!(html.matches("<ul>").count(), 1);
+        assert!(html.contains("Second item"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_in_numbered_list() {
+        let input = indoc! {"
+            1. Install
+            10. Configure
+                > [!WARNING] Back up first
+                > Existing settings are replaced
+        "};
+        let config = AlertsConfig {
+            inline_title: true,
+            ..AlertsConfig::default()
+        };
+        let result = process_github_alerts(input, &config);
+
+        assert!(result.contains("    <div class=\"markdown-alert markdown-alert-warning\""));
+        assert!(result.contains("</span>Back up first</p>"));
+        assert!(result.contains("    <p>Existing settings are replaced</p>"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_indented_code_is_not_an_alert() {
+        let input = "Paragraph
+
+    > [!NOTE]
+    > Shown as code";
+        let result = process_github_alerts(input, &AlertsConfig::default());
+
+        assert_eq!(result, input);
+    }
+
+    fn snippets(definitions: &[(&str, &str)]) -> SnippetsConfig {
+        SnippetsConfig {
+            enabled: true,
+            definitions: definitions
+                .iter()
+                .map(|(name, body)| (name.to_string(), body.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_expand_snippets() {
+        let config = snippets(&[
+            ("warn-beta", "> [!WARNING]\n> This feature is in beta."),
+            ("badge", "<span class=\"badge\">{{status}}</span>"),
+            ("status", "stable"),
+        ]);
+
+        assert_eq!(
+            expand_snippets("Status: {{badge}} {{unknown}} {x}", &config),
+            "Status: <span class=\"badge\">stable</span> {{unknown}} {x}"
+        );
+
+        let (expanded, line_map) =
+            expand_snippets_with_line_map("# Title\n{{warn-beta}}\nAfter", &config);
+        assert_eq!(
+            expanded,
+            "# Title\n> [!WARNING]\n> This feature is in beta.\nAfter"
+        );
+        assert_eq!(line_map, vec![0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_expand_snippets_skips_code() {
+        let config = snippets(&[("name", "value")]);
+        let markdown = "`{{name}}` {{name}}\n```\n{{name}}\n```\n{{name}}";
+
+        assert_eq!(
+            expand_snippets(markdown, &config),
+            "`{{name}}` value\n```\n{{name}}\n```\nvalue"
+        );
+    }
+
+    #[test]
+    fn test_expand_snippets_stops_recursion() {
+        let config = snippets(&[("loop", "x{{loop}}")]);
+        let expanded = expand_snippets("{{loop}}", &config);
+
+        assert_eq!(
+            expanded,
+            format!("{}{{{{loop}}}}", "x".repeat(MAX_SNIPPET_DEPTH))
+        );
+    }
+
+    #[test]
+    fn test_mark_inline_toc() {
+        let markdown = "Intro\n\n[TOC]\n\n```\n[TOC]\n```\n  [[_TOC_]]  \n    [TOC]";
+        assert_eq!(
+            mark_inline_toc(markdown),
+            "Intro\n\n<!--inline-toc-->\n\n```\n[TOC]\n```\n  \\[\\[\\_TOC\\_\\]\\]\n    [TOC]"
+        );
+        assert_eq!(mark_inline_toc("See [TOC] here"), "See [TOC] here");
+    }
+
+    #[test]
+    fn test_inline_toc_html() {
+        let heading = |level, id: &str| HeadingInfo {
+            level,
+            text: id.to_uppercase(),
+            id: id.to_string(),
+        };
+        let headings = [
+            heading(1, "a"),
+            heading(2, "b"),
+            heading(3, "c"),
+            heading(2, "d"),
+            heading(1, "e"),
+        ];
+
+        assert_eq!(
+            inline_toc_html(&headings),
+            indoc! {r##"
+                <nav class="inline-toc">
+                <ul>
+                <li><a href="#a">A</a><ul>
+                <li><a href="#b">B</a><ul>
+                <li><a href="#c">C</a></li>
+                </ul>
+                </li>
+                <li><a href="#d">D</a></li>
+                </ul>
+                </li>
+                <li><a href="#e">E</a></li>
+                </ul>
+                </nav>
+            "##}
+        );
+        assert_eq!(inline_toc_html(&[]), "");
+    }
+
+    #[test]
+    fn test_render_to_html_with_toc_inline_toc() {
+        let markdown = "# Guide\n\n[[_TOC_]]\n\n## Install\n\n### From source\n\n[TOC]\n";
+        let (html, headings) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
+
+        assert_eq!(headings.len(), 3);
+        assert_eq!(html.matches(r#"<nav class="inline-toc">"#).count(), 1);
+        assert!(html.contains(r##"<li><a href="#guide">Guide</a><ul>"##));
+        assert!(html.contains(r##"<li><a href="#install">Install</a><ul>"##));
+        assert!(html.contains(r##"<li><a href="#from-source">From source</a></li>"##));
+        assert!(!html.contains("md-link-invalid"));
+        // Only the first placeholder expands
+        assert!(html.contains("[TOC]</p>"));
+    }
+
+    #[test]
+    fn test_process_wiki_links() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Other Note.md"), "# Other").unwrap();
+        fs::write(temp_dir.path().join("Guide.markdown"), "# Guide").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "plain").unwrap();
+        let markdown = indoc! {r"
+            See [[Other Note]] and [[Guide|the guide]], [[notes.txt]].
+            - Item with [[Other Note | alias]]
+            - Gone: [[Missing Page|text]]
+            Not links: \[[Other Note]] `[[Other Note]]` [[1]](https://example.com)
+            ```
+            [[Other Note]]
+            ```"};
+
+        assert_eq!(
+            process_wiki_links(markdown, temp_dir.path()),
+            indoc! {r#"
+                See [Other Note](<Other Note.md>) and [the guide](<Guide.markdown>), [notes.txt](<notes.txt>).
+                - Item with [alias](<Other Note.md>)
+                - Gone: <span class="md-link md-link-invalid">text</span>
+                Not links: \[[Other Note]] `[[Other Note]]` [[1]](https://example.com)
+                ```
+                [[Other Note]]
+                ```"#}
+        );
+    }
+
+    #[test]
+    fn test_render_to_html_wiki_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("index.md");
+        fs::write(temp_dir.path().join("Other Note.md"), "# Other").unwrap();
+        let markdown = "- [[Other Note|alias]]\n- [[Missing]]\n";
+
+        let html = render_to_html(markdown, &file).unwrap();
+
+        assert!(html.contains(r#"<li><span class="md-link" data-md-link="Other Note.md""#));
+        assert!(html.contains("window.handleMarkdownLinkClick('Other Note.md'"));
+        assert!(html.contains(">alias</span></li>"));
+        assert!(html.contains(r#"<li><span class="md-link md-link-invalid">Missing</span></li>"#));
+    }
+
+    #[test]
+    fn test_expand_snippets_disabled() {
+        let mut config = snippets(&[("name", "value")]);
+        config.enabled = false;
+
+        assert_eq!(expand_snippets("{{name}}", &config), "{{name}}");
+    }
+
+    #[test]
+    fn test_process_mermaid_blocks() {
+        let markdown = indoc! {"
+            ```mermaid
+            graph TD
+                A-->B
+            ```
+        "};
+
+        let options = Options::all();
+        let parser = Parser::new_ext(markdown, options);
+        let events: Vec<Event> = process_code_blocks(parser, "mermaid").collect();
+
+        // Verify that Mermaid block is converted to a single HTML event
+        let html_events: Vec<_> = events
+            .iter()
+            .filter_map(|e| {
+                if let Event::Html(html) = e {
+                    Some(html.as_ref())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        assert!(!html_events.is_empty(), "Should contain HTML event");
+        let html = html_events[0];
+        assert!(html.contains(r#"<pre class="preprocessed-mermaid""#));
+        assert!(html.contains(r#"data-original-content="#));
+        // Content is HTML-escaped in data attribute, so we just check the structure
+        assert!(html.contains("</pre>"));
+    }
+
+    #[test]
+    fn test_collect_link_definitions() {
+        let markdown = indoc! {r#"
+            See [the docs][Docs], [Home] and ![logo][].
+
+            [docs]: https://example.com/docs "Documentation"
+            [home]: ./README.md
+            [logo]: logo.png
+            [unused]: https://example.com/old
+            [Docs]: https://example.com/other
+
+            ```
+            [home]: not a definition
+            ```
+        "#};
+        let definitions = collect_link_definitions(markdown);
+        let summary: Vec<(&str, &str, usize, usize)> = definitions
+            .iter()
+            .map(|d| (d.label.as_str(), d.dest.as_str(), d.uses, d.definitions))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                // Labels match case-insensitively; the second [Docs] is a duplicate
+                ("docs", "https://example.com/docs", 1, 2),
+                ("home", "./README.md", 1, 1),
+                ("logo", "logo.png", 1, 1),
+                ("unused", "https://example.com/old", 0, 1),
+            ]
+        );
+        assert_eq!(definitions[0].title.as_deref(), Some("Documentation"));
+    }
+
+    #[test]
+    fn test_link_definitions_html() {
+        let markdown = "[a] <b>\n\n[a]: https://example.com/?a=1&b=2\n[b]: x.md\n";
+        let html = link_definitions_html(markdown).unwrap();
+
+        assert!(html.starts_with(r#"<section class="link-definitions">"#));
+        assert!(html.contains(concat!(
+            r#"<tr><td><code>[a]</code></td>"#,
+            r#"<td><a href="https://example.com/?a=1&amp;b=2">https://example.com/?a=1&amp;b=2</a></td>"#,
+            r#"<td></td><td>1</td></tr>"#,
+        )));
+        assert!(html.contains(concat!(
+            r#"<tr class="link-definition-flagged"><td><code>[b]</code> "#,
+            r#"<span class="link-definition-flag">unused</span></td>"#,
+        )));
+
+        assert_eq!(link_definitions_html("No [links] here\n"), None);
+    }
+
+    fn render_task_progress(markdown: &str, mode: TaskProgressMode) -> String {
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_task_progress(events, mode).into_iter(),
+        );
+        html_output
+    }
+
+    const TASKS: &str = indoc! {"
+        - [x] Plan
+        - [ ] Build
+          - [x] Backend
+          - [ ] Frontend
+
+        Notes
+
+        - Plain item
+
+        1. [x] Ship
+    "};
+
+    #[test]
+    fn test_process_task_progress_list() {
+        let html = render_task_progress(TASKS, TaskProgressMode::List);
+
+        // Nested tasks count towards their top-level list
+        assert!(html.starts_with(concat!(
+            r#"<div class="task-progress"><progress value="2" max="4"></progress>"#,
+            r#"<span class="task-progress-label">2/4 tasks done</span></div>"#,
+            "\n<ul>",
+        )));
+        assert!(html.contains("1/1 task done</span></div>\n<ol>"));
+        // Lists without tasks get no summary
+        assert_eq!(html.matches("task-progress-label").count(), 2);
+    }
+
+    #[test]
+    fn test_process_task_progress_document() {
+        let html = render_task_progress(TASKS, TaskProgressMode::Document);
+        assert!(html.starts_with(r#"<div class="task-progress"><progress value="3" max="5">"#));
+        assert_eq!(html.matches("task-progress-label").count(), 1);
+
+        let html = render_task_progress(TASKS, TaskProgressMode::Off);
+        assert!(!html.contains("task-progress"));
+        let html = render_task_progress("- Plain item\n", TaskProgressMode::Document);
+        assert!(!html.contains("task-progress"));
+    }
+
+    #[test]
+    fn test_process_task_progress_keeps_source_line() {
+        let markdown = "Intro\n\n- [x] Done\n- [ ] Open\n";
+        let events: Vec<_> = mark_source_lines(
+            Parser::new_ext(markdown, Options::all()).into_offset_iter(),
+            markdown,
+            (0..markdown.lines().count()).collect(),
+            0,
+        )
+        .collect();
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_task_progress(events, TaskProgressMode::List).into_iter(),
+        );
+        let html = apply_source_line_markers(&html_output);
+
+        // The summary is left unmarked; the list keeps its line
+        assert!(html.contains(r#"<div class="task-progress"><progress value="1" max="2">"#));
+        assert!(html.contains(r#"<ul data-source-line="3">"#));
+    }
+
+    #[test]
+    fn test_render_to_html_definition_list() {
+        let markdown = indoc! {"
+            Term
+            : The definition
+        "};
+        let html = render_to_html(markdown, Path::new("test.md")).unwrap();
+        assert!(html.contains("<dl>\n<dt>Term</dt>\n<dd>The definition</dd>\n</dl>"));
+    }
+
+    #[test]
+    fn test_render_to_html_definition_list_multiple_definitions() {
+        let markdown = indoc! {"
+            Term
+            : First
+
+            : Second
+
+            Other term
+            : Third
+        "};
+        let html = render_to_html(markdown, Path::new("test.md")).unwrap();
+
+        // Blank lines between items keep them in one list
+        assert_eq!(html.matches("<dl>").count(), 1);
+        assert!(
+            html.contains("<dt>Term</dt>\n<dd>\n<p>First</p>\n</dd>\n<dd>\n<p>Second</p>\n</dd>")
+        );
+        assert!(html.contains("<dt>Other term</dt>\n<dd>\n<p>Third</p>\n</dd>"));
+    }
+
+    #[test]
+    fn test_render_to_html_definition_list_inline_formatting() {
+        let markdown = indoc! {"
+            `Apple`
+            : A **red** fruit, see [the guide](https://example.com)
+        "};
+        let html = render_to_html(markdown, Path::new("test.md")).unwrap();
+        assert!(html.contains("<dt><code>Apple</code></dt>"));
+        assert!(html.contains(
+            r#"<dd>A <strong>red</strong> fruit, see <a href="https://example.com">the guide</a></dd>"#
+        ));
+    }
+
+    #[test]
+    fn test_render_to_html_code_copy_button() {
+        let markdown = indoc! {r#"
+            ```js
+            const s = `a "b" 'c'` && x < y;
+            ```
+        "#};
+
+        let html = render_to_html(markdown, "test.md").unwrap();
+
+        assert!(html.contains(concat!(
+            r#"<div class="code-block"><button class="copy-button" "#,
+            r#"data-code="const s = &#96;a &quot;b&quot; &#39;c&#39;&#96; &amp;&amp; x &lt; y;"#,
+            "\n\"",
+        )));
+        assert!(html.contains("</code></pre>\n</div>"));
+    }
+
+    #[test]
+    fn test_render_to_html_no_code_copy_button_for_mermaid() {
+        let markdown = indoc! {"
+            ```mermaid
+            graph TD
+                A-->B
+            ```
+        "};
+
+        let html = render_to_html(markdown, "test.md").unwrap();
+
+        assert!(html.contains(r#"<pre class="preprocessed-mermaid""#));
+        assert!(!html.contains("code-block"));
+        assert!(!html.contains("data-code"));
+    }
+
+    #[test]
+    fn test_process_geojson_blocks_only_when_enabled() {
+        let markdown = indoc! {r#"
+            ```geojson
+            {"type": "Point", "coordinates": [139.7, 35.6]}
+            ```
+        "#};
+
+        let render = |enabled: bool| {
+            let parser = Parser::new_ext(markdown, Options::all()).into_offset_iter();
+            let parser = mark_source_lines(parser, markdown, Vec::new(), 0);
+            let mut html = String::new();
+            html::push_html(&mut html, process_geojson_blocks(parser, enabled));
+            apply_source_line_markers(&html)
+        };
+
+        let enabled = render(true);
+        assert!(enabled.contains(r#"data-original-content="{&quot;type&quot;: &quot;Point&quot;"#));
+        assert!(enabled.contains(r#"<pre data-source-line="1" class="preprocessed-geojson""#));
+
+        let disabled = render(false);
+        assert!(!disabled.contains("preprocessed-geojson"));
+        assert!(disabled.contains(r#"class="language-geojson""#));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_img() {
+        let temp_dir = TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("test.png");
+        let png_data = vec![0x89, 0x50, 0x4E, 0x47];
+        fs::write(&image_path, png_data).unwrap();
+
+        let html = r#"<p><img src="test.png" alt="test" /></p>"#;
+        let result = post_process_html_tags(html, temp_dir.path());
+
+        assert!(
+            result.contains("data:image/png;base64,"),
+            "Should convert img src to data URL"
+        );
+        assert!(
+            !result.contains(r#"src="test.png""#),
+            "Should not contain original path"
+        );
+    }
+
+    #[test]
+    fn test_post_process_html_tags_serves_large_img() {
+        let temp_dir = TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("large.png");
+        let size = CONFIG.read().images.inline_max_size as usize + 1;
+        fs::write(&image_path, vec![0; size]).unwrap();
+
+        let html = r#"<p><img src="large.png" alt="large" /></p>"#;
+        let result = post_process_html_tags(html, temp_dir.path());
+        let url = media_url(&image_path.canonicalize().unwrap());
+
+        assert!(result.contains(&format!(r#"src="{url}""#)));
+
+        // Exports embed it after all
+        let inlined = inline_served_images(&result).unwrap();
+        assert!(inlined.contains("data:image/png;base64,"));
+        assert!(!inlined.contains("/arto-media/"));
+    }
+
+    #[test]
+    fn test_inline_served_images_skips_urls_not_issued() {
+        let temp_dir = TempDir::new().unwrap();
+        let secret = temp_dir.path().join("id_rsa");
+        let image = temp_dir.path().join("unlisted.png");
+        fs::write(&secret, "PRIVATE KEY").unwrap();
+        fs::write(&image, "png").unwrap();
+        let forged = |path: &Path| {
+            format!(
+                "/arto-media/{}",
+                percent_encoding::utf8_percent_encode(
+                    &path.to_string_lossy(),
+                    percent_encoding::NON_ALPHANUMERIC
+                )
+            )
+        };
+        // Issued, but not an image
+        let issued_secret = media_url(&secret);
+
+        let html = format!(
+            r#"<img src="{}"><img src="{}"><img src="{}"><img srcset="{} 2x">"#,
+            forged(&secret),
+            forged(&image),
+            issued_secret,
+            forged(&image),
+        );
+        let inlined = inline_served_images(&html).unwrap();
+
+        assert!(!inlined.contains("data:"));
+        assert_eq!(inlined, html);
+    }
+
+    #[test]
+    fn test_post_process_html_tags_dark_image_markers() {
+        let html = concat!(
+            r#"<p><img src="https://example.com/a.png" alt="Flow #invert chart" />"#,
+            r#"<img src="https://example.com/b.svg" alt="Logo #noinvert" class="logo" />"#,
+            r#"<img src="https://example.com/c.jpg" alt="Photo" /></p>"#,
+        );
+        let result = post_process_html_tags(html, Path::new("."));
+
+        assert!(result.contains(r#"alt="Flow chart""#));
+        assert!(result.contains(r#"class="dark-invert""#));
+        assert!(result.contains(r#"alt="Logo""#));
+        assert!(result.contains(r#"class="logo dark-no-invert""#));
+        assert!(result.contains(r#"<img src="https://example.com/c.jpg" alt="Photo" />"#));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_picture() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("dark.png"), [0x89, 0x50]).unwrap();
+        fs::write(temp_dir.path().join("light.png"), [0x89, 0x50]).unwrap();
+
+        let html = concat!(
+            r#"<picture><source media="(prefers-color-scheme: dark)" "#,
+            r#"srcset="dark.png 2x, https://example.com/dark.png 1x">"#,
+            r#"<source media="(min-width: 600px)" srcset="missing.png">"#,
+            r#"<img src="light.png" alt="Logo"></picture>"#,
+        );
+        let result = post_process_html_tags(html, temp_dir.path());
+
+        assert!(result.contains(r#"data-color-scheme="dark""#));
+        assert!(result.contains(
+            r#"srcset="data:image/png;base64,iVA= 2x, https://example.com/dark.png 1x""#
+        ));
+        assert!(result.contains(r#"<source media="(min-width: 600px)" srcset="missing.png">"#));
+        assert!(result.contains(r#"<img src="data:image/png;base64,iVA=" alt="Logo">"#));
+    }
+
+    #[test]
+    fn test_map_srcset() {
+        let upper = |url: &str| url.starts_with('a').then(|| url.to_uppercase());
+
+        assert_eq!(map_srcset("a.png", upper), "A.PNG");
+        assert_eq!(
+            map_srcset(" a.png 1x,b.png 2x ,\n a@3.png 3x", upper),
+            "A.PNG 1x, b.png 2x, A@3.PNG 3x"
+        );
+        assert_eq!(
+            map_srcset("a.png, data:image/png;base64,AA== 480w", upper),
+            "A.PNG, data:image/png;base64,AA== 480w"
+        );
+        assert_eq!(map_srcset("", upper), "");
+    }
+
+    #[test]
+    fn test_find_moved_image_searches_from_climbed_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let docs = temp.path().join("docs");
+        std::fs::create_dir_all(&docs).unwrap();
+        std::fs::create_dir_all(temp.path().join("assets/img")).unwrap();
+        std::fs::write(temp.path().join("assets/img/shot.png"), "").unwrap();
+
+        assert_eq!(
+            find_moved_image("../images/shot.png", &docs),
+            Some(temp.path().join("assets/img/shot.png"))
+        );
+        // Without `..` only the document's own directory tree is searched
+        assert_eq!(find_moved_image("images/shot.png", &docs), None);
+    }
+
+    #[test]
+    fn test_frame_wide_image() {
+        let frame = |mode: WideImageMode| {
+            let mut output = Vec::new();
+            let mut rewriter = HtmlRewriter::new(
+                Settings {
+                    element_content_handlers: vec![element!("img", move |el| {
+                        frame_wide_image(el, mode);
+                        Ok(())
+                    })],
+                    ..Settings::default()
+                },
+                |chunk: &[u8]| output.extend_from_slice(chunk),
+            );
+            rewriter.write(br#"<p><img src="a.png"></p>"#).unwrap();
+            rewriter.end().unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            frame(WideImageMode::Downscale),
+            r#"<p><img src="a.png"></p>"#
+        );
+        assert_eq!(
+            frame(WideImageMode::Scroll),
+            r#"<p><span class="image-frame image-frame-scroll"><img src="a.png"></span></p>"#
+        );
+        assert_eq!(
+            frame(WideImageMode::Natural),
+            r#"<p><span class="image-frame image-frame-natural"><img src="a.png"></span></p>"#
+        );
+    }
+
+    #[test]
+    fn test_document_preview() {
+        let markdown = indoc! {"
+            ---
+            title: ignored
+            ---
+            # Getting Started
+
+            This guide explains the `setup` of
+            the project in detail.
+
+            Second paragraph.
+        "};
+
+        let preview = document_preview(markdown, 200);
+        assert_eq!(preview.title.as_deref(), Some("Getting Started"));
+        assert_eq!(
+            preview.excerpt,
+            "This guide explains the setup of the project in detail."
+        );
 
-    let _ = rewriter.write(html_str.as_bytes());
-    let _ = rewriter.end();
-    String::from_utf8(output).unwrap_or_else(|_| html_str.to_string())
-}
+        let preview = document_preview(markdown, 20);
+        assert_eq!(preview.excerpt, "This guide explains…");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use std::fs;
-    use tempfile::TempDir;
+        let preview = document_preview("Just text", 200);
+        assert_eq!(preview.title, None);
+        assert_eq!(preview.excerpt, "Just text");
+    }
 
     #[test]
-    fn test_get_mime_type() {
-        assert_eq!(get_mime_type(Path::new("test.png")), "image/png");
-        assert_eq!(get_mime_type(Path::new("test.jpg")), "image/jpeg");
-        assert_eq!(get_mime_type(Path::new("test.jpeg")), "image/jpeg");
-        assert_eq!(get_mime_type(Path::new("test.gif")), "image/gif");
-        assert_eq!(get_mime_type(Path::new("test.svg")), "image/svg+xml");
-        assert_eq!(get_mime_type(Path::new("test.webp")), "image/webp");
-        assert_eq!(get_mime_type(Path::new("test.bmp")), "image/bmp");
-        assert_eq!(get_mime_type(Path::new("test.ico")), "image/x-icon");
-        assert_eq!(get_mime_type(Path::new("test.unknown")), "image/png");
+    fn test_post_process_html_tags_anchor() {
+        let html = r#"<a href="doc.md">Link</a>"#;
+        let result = post_process_html_tags(html, Path::new("."));
+
+        assert!(
+            result.contains(r#"<span class="md-link""#),
+            "Should convert to span"
+        );
+        assert!(
+            result.contains("handleMarkdownLinkClick"),
+            "Should add click handler"
+        );
+        assert!(result.contains(r#"data-md-link="doc.md""#));
+        assert!(!result.contains("<a "), "Should not contain anchor tag");
     }
 
     #[test]
-    fn test_get_alert_icon_placeholder() {
-        let result = get_alert_icon_placeholder("note");
-        assert_eq!(
-            result,
-            r#"<span class="alert-icon" data-alert-type="note"></span>"#
+    fn test_post_process_html_tags_http_urls() {
+        let html =
+            r#"<img src="https://example.com/image.png" /><a href="https://example.com">Link</a>"#;
+        let result = post_process_html_tags(html, Path::new("."));
+
+        assert!(
+            result.contains(r#"src="https://example.com/image.png""#),
+            "Should keep HTTP img"
+        );
+        assert!(
+            result.contains(r#"<a href="https://example.com""#),
+            "Should keep HTTP link"
         );
+    }
 
-        let result = get_alert_icon_placeholder("warning");
+    #[test]
+    fn test_post_process_html_tags_non_md_local_file() {
+        let html = r#"<a href="file.txt">Text File</a>"#;
+        let result = post_process_html_tags(html, Path::new("."));
+
+        assert!(
+            result.contains(r#"<span class="md-link md-link-invalid""#),
+            "Should convert to span with md-link and md-link-invalid class"
+        );
+        assert!(
+            result.contains("handleMarkdownLinkClick"),
+            "Should add click handler for local files"
+        );
+        assert!(!result.contains("<a "), "Should not contain anchor tag");
+    }
+
+    #[test]
+    fn test_post_process_html_tags_md_vs_other_files() {
+        let html = r#"<a href="doc.md">MD</a><a href="file.txt">TXT</a>"#;
+        let result = post_process_html_tags(html, Path::new("."));
+
+        // MD file should have only md-link class
+        assert!(
+            result.contains(r#"class="md-link""#),
+            "Should have md-link for .md file"
+        );
+
+        // TXT file should have both md-link and md-link-invalid classes
+        assert!(
+            result.contains(r#"class="md-link md-link-invalid""#),
+            "Should have md-link and md-link-invalid for .txt file"
+        );
+
+        // Both should have click handlers
+        let click_handler_count = result.matches("handleMarkdownLinkClick").count();
         assert_eq!(
-            result,
-            r#"<span class="alert-icon" data-alert-type="warning"></span>"#
+            click_handler_count, 2,
+            "Should have click handlers for both links"
         );
     }
 
     #[test]
-    fn test_process_github_alerts_note() {
-        let input = indoc! {"
-            > [!NOTE]
-            > This is a note
-        "};
-        let result = process_github_alerts(input);
+    fn test_render_to_html_basic() {
+        let markdown = "# Hello\n\nThis is a test.";
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
 
-        assert!(result.contains(r#"<div class="markdown-alert markdown-alert-note""#));
-        assert!(result.contains(r#"<p class="markdown-alert-title""#));
-        assert!(result.contains("NOTE"));
-        assert!(result.contains("This is a note"));
-        assert!(result.contains("</div>"));
+        let result = render_to_html(markdown, &md_path).unwrap();
+
+        assert!(result.contains("<h1>"));
+        assert!(result.contains("Hello"));
+        assert!(result.contains("<p>"));
+        assert!(result.contains("This is a test."));
     }
 
     #[test]
-    fn test_process_github_alerts_warning() {
-        let input = indoc! {"
-            > [!WARNING]
-            > Be careful!
+    fn test_code_block_language_classes() {
+        let markdown = indoc! {"
+            # Code Blocks Test
+
+            ```rust
+            fn main() {
+                println!(\"Hello\");
+            }
+            ```
+
+            ```python
+            def hello():
+                print(\"world\")
+            ```
+
+            ```
+            no language specified
+            ```
         "};
-        let result = process_github_alerts(input);
 
-        assert!(result.contains(r#"markdown-alert-warning"#));
-        assert!(result.contains("WARNING"));
-        assert!(result.contains("Be careful!"));
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let result = render_to_html(markdown, &md_path).unwrap();
+
+        // Print the output to inspect
+        println!("\n=== HTML OUTPUT ===\n{}\n===================\n", result);
+
+        // Check if language classes are present
+        let has_rust = result.contains("language-rust") || result.contains("class=\"rust\"");
+        let has_python = result.contains("language-python") || result.contains("class=\"python\"");
+
+        println!("Has rust class: {}", has_rust);
+        println!("Has python class: {}", has_python);
     }
 
     #[test]
-    fn test_process_github_alerts_with_multiline() {
-        let input = indoc! {"
-            > [!IMPORTANT]
-            > First line
-            > Second line
-            > Third line
+    fn test_render_to_html_sanitizes_inline_svg() {
+        let markdown = indoc! {r#"
+            # Diagram
+
+            <svg width="40" height="20" onload="alert(1)"><script>alert(2)</script><rect width="40" height="20"/></svg>
+        "#};
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        for result in [
+            render_to_html(markdown, &md_path).unwrap(),
+            render_to_html_with_toc(markdown, &md_path).unwrap().0,
+        ] {
+            assert!(!result.contains("alert"));
+            assert!(result.contains(r#"class="inline-svg""#));
+            assert!(result.contains("<rect"));
+        }
+    }
+
+    #[test]
+    fn test_render_to_html_with_alert() {
+        let markdown = indoc! {"
+            # Title
+
+            > [!NOTE]
+            > This is important
         "};
-        let result = process_github_alerts(input);
 
-        assert!(result.contains(r#"markdown-alert-important"#));
-        assert!(result.contains("First line"));
-        assert!(result.contains("Second line"));
-        assert!(result.contains("Third line"));
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let result = render_to_html(markdown, &md_path).unwrap();
+
+        assert!(result.contains("markdown-alert-note"));
+        assert!(result.contains("This is important"));
     }
 
     #[test]
-    fn test_process_github_alerts_all_types() {
-        let alert_types = vec![
-            ("NOTE", "note"),
-            ("TIP", "tip"),
-            ("IMPORTANT", "important"),
-            ("WARNING", "warning"),
-            ("CAUTION", "caution"),
-        ];
+    fn test_render_to_html_with_mermaid() {
+        let markdown = indoc! {"
+            ```mermaid
+            graph LR
+                A-->B
+            ```
+        "};
 
-        for (alert_name, alert_class) in alert_types {
-            let input = format!("> [!{}]\n> Test content", alert_name);
-            let result = process_github_alerts(&input);
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
 
-            assert!(
-                result.contains(&format!(r#"markdown-alert-{}"#, alert_class)),
-                "Should contain alert class for {}",
-                alert_name
-            );
-            assert!(
-                result.contains(alert_name),
-                "Should contain alert name {}",
-                alert_name
-            );
-        }
+        let result = render_to_html(markdown, &md_path).unwrap();
+
+        assert!(result.contains(r#"<pre class="preprocessed-mermaid""#));
+        assert!(result.contains("graph LR"));
     }
 
     #[test]
-    fn test_process_github_alerts_no_match() {
-        let input = "Regular paragraph\n> Regular quote";
-        let result = process_github_alerts(input);
+    fn test_process_math_expressions_inline() {
+        let markdown = "This is inline math: $x = y + z$";
+        let options = Options::all();
+        let parser = Parser::new_ext(markdown, options);
 
-        assert_eq!(result, input);
-        assert!(!result.contains("markdown-alert"));
+        let events: Vec<Event> = process_math_expressions(parser).collect();
+
+        // Verify that inline math is converted to custom HTML
+        let html_events: Vec<_> = events
+            .iter()
+            .filter_map(|e| {
+                if let Event::Html(html) = e {
+                    Some(html.as_ref())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        assert!(
+            html_events
+                .iter()
+                .any(|h| h.contains(r#"<span class="preprocessed-math-inline""#)),
+            "Should contain inline-math span"
+        );
+        assert!(
+            html_events
+                .iter()
+                .any(|h| h.contains("data-original-content")),
+            "Should contain data attribute"
+        );
+        assert!(
+            html_events.iter().any(|h| h.contains("x = y + z")),
+            "Should contain the math content"
+        );
     }
 
     #[test]
-    fn test_process_mermaid_blocks() {
+    fn test_process_math_expressions_display() {
         let markdown = indoc! {"
-            ```mermaid
-            graph TD
-                A-->B
-            ```
-        "};
+            Display math:
 
+            $$
+            x = \\frac{-b \\pm \\sqrt{b^2-4ac}}{2a}
+            $$
+        "};
         let options = Options::all();
         let parser = Parser::new_ext(markdown, options);
-        let events: Vec<Event> = process_code_blocks(parser, "mermaid").collect();
 
-        // Verify that Mermaid block is converted to a single HTML event
+        let events: Vec<Event> = process_math_expressions(parser).collect();
+
+        // Verify that display math is converted to custom HTML
         let html_events: Vec<_> = events
             .iter()
             .filter_map(|e| {
@@ -800,457 +4805,934 @@ mod tests {
             })
             .collect();
 
-        assert!(!html_events.is_empty(), "Should contain HTML event");
-        let html = html_events[0];
-        assert!(html.contains(r#"<pre class="preprocessed-mermaid""#));
-        assert!(html.contains(r#"data-original-content="#));
-        // Content is HTML-escaped in data attribute, so we just check the structure
-        assert!(html.contains("</pre>"));
+        assert!(
+            html_events
+                .iter()
+                .any(|h| h.contains(r#"<div class="preprocessed-math-display""#)),
+            "Should contain display-math div"
+        );
+        assert!(
+            html_events
+                .iter()
+                .any(|h| h.contains("data-original-content")),
+            "Should contain data attribute"
+        );
+        assert!(
+            html_events.iter().any(|h| h.contains("frac")),
+            "Should contain the math content"
+        );
     }
 
     #[test]
-    fn test_post_process_html_tags_img() {
-        let temp_dir = TempDir::new().unwrap();
-        let image_path = temp_dir.path().join("test.png");
-        let png_data = vec![0x89, 0x50, 0x4E, 0x47];
-        fs::write(&image_path, png_data).unwrap();
+    fn test_process_math_expressions_mixed() {
+        let markdown = "Inline $a + b$ and display $$c = d$$";
+        let options = Options::all();
+        let parser = Parser::new_ext(markdown, options);
 
-        let html = r#"<p><img src="test.png" alt="test" /></p>"#;
-        let result = post_process_html_tags(html, temp_dir.path());
+        let events: Vec<Event> = process_math_expressions(parser).collect();
+
+        let html_events: Vec<_> = events
+            .iter()
+            .filter_map(|e| {
+                if let Event::Html(html) = e {
+                    Some(html.as_ref())
+                } else {
+                    None
+                }
+            })
+            .collect();
 
+        // Should have both inline and display math
         assert!(
-            result.contains("data:image/png;base64,"),
-            "Should convert img src to data URL"
+            html_events
+                .iter()
+                .any(|h| h.contains(r#"class="preprocessed-math-inline""#)),
+            "Should contain inline math"
         );
         assert!(
-            !result.contains(r#"src="test.png""#),
-            "Should not contain original path"
+            html_events
+                .iter()
+                .any(|h| h.contains(r#"class="preprocessed-math-display""#)),
+            "Should contain display math"
         );
     }
 
     #[test]
-    fn test_post_process_html_tags_anchor() {
-        let html = r#"<a href="doc.md">Link</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+    fn test_render_to_html_with_math() {
+        let markdown = indoc! {"
+            # Math Test
+
+            Inline math: $E = mc^2$
+
+            Display math:
+            $$
+            \\int_0^\\infty e^{-x^2} dx = \\frac{\\sqrt{\\pi}}{2}
+            $$
+        "};
+
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let result = render_to_html(markdown, &md_path).unwrap();
 
         assert!(
-            result.contains(r#"<span class="md-link""#),
-            "Should convert to span"
+            result.contains(r#"class="preprocessed-math-inline""#),
+            "Should render inline math"
         );
         assert!(
-            result.contains("handleMarkdownLinkClick"),
-            "Should add click handler"
+            result.contains(r#"class="preprocessed-math-display""#),
+            "Should render display math"
+        );
+        assert!(
+            result.contains("data-original-content"),
+            "Should include data attributes"
         );
-        assert!(!result.contains("<a "), "Should not contain anchor tag");
     }
 
     #[test]
-    fn test_post_process_html_tags_http_urls() {
-        let html =
-            r#"<img src="https://example.com/image.png" /><a href="https://example.com">Link</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+    fn test_render_to_html_integrated() {
+        // Integration test: combining multiple features
+        let temp_dir = TempDir::new().unwrap();
+
+        // Create test image
+        let image_path = temp_dir.path().join("image.png");
+        let png_data = vec![0x89, 0x50, 0x4E, 0x47];
+        fs::write(&image_path, png_data).unwrap();
+
+        let markdown = indoc! {"
+            # Test Document
+
+            > [!WARNING]
+            > Be careful
+
+            ![Test Image](image.png)
+
+            [Link to other doc](other.md)
+
+            ```mermaid
+            graph TD
+                A-->B
+            ```
+        "};
+
+        let md_path = temp_dir.path().join("test.md");
+
+        let result = render_to_html(markdown, &md_path).unwrap();
 
+        // Verify that all features are correctly integrated
+        assert!(result.contains("<h1>"), "Should render heading");
         assert!(
-            result.contains(r#"src="https://example.com/image.png""#),
-            "Should keep HTTP img"
+            result.contains("markdown-alert-warning"),
+            "Should render alert"
         );
         assert!(
-            result.contains(r#"<a href="https://example.com""#),
-            "Should keep HTTP link"
+            result.contains("data:image/png"),
+            "Should convert image to data URL"
+        );
+        assert!(
+            result.contains(r#"class="md-link""#),
+            "Should convert md link"
+        );
+        assert!(
+            result.contains(r#"<pre class="preprocessed-mermaid""#),
+            "Should render mermaid"
         );
     }
 
     #[test]
-    fn test_post_process_html_tags_non_md_local_file() {
-        let html = r#"<a href="file.txt">Text File</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+    fn test_frontmatter_open_by_key_count() {
+        let markdown = "---\ntitle: Short\nauthor: Me\n---\n\nText";
+        let config = FrontmatterConfig {
+            open: FrontmatterOpenMode::Auto,
+            collapse_above: 2,
+            ..FrontmatterConfig::default()
+        };
+
+        let (html, _content) = extract_and_render_frontmatter(markdown, &config, Path::new("."));
+        assert!(html.contains(r#"<details class="frontmatter" open>"#));
+
+        let config = FrontmatterConfig {
+            collapse_above: 1,
+            ..config
+        };
+        let (html, _content) = extract_and_render_frontmatter(markdown, &config, Path::new("."));
+        assert!(html.contains(r#"<details class="frontmatter">"#));
+    }
 
-        assert!(
-            result.contains(r#"<span class="md-link md-link-invalid""#),
-            "Should convert to span with md-link and md-link-invalid class"
+    #[test]
+    fn test_document_frontmatter_config_uses_remembered_toggle() {
+        let document = Path::new("/docs/toggled-frontmatter.md");
+        assert_eq!(
+            document_frontmatter_config(document).open,
+            CONFIG.read().frontmatter.open
         );
-        assert!(
-            result.contains("handleMarkdownLinkClick"),
-            "Should add click handler for local files"
+
+        remember_frontmatter_toggle(document, true);
+        assert_eq!(
+            document_frontmatter_config(document).open,
+            FrontmatterOpenMode::Open
         );
-        assert!(!result.contains("<a "), "Should not contain anchor tag");
+
+        remember_frontmatter_toggle(document, false);
+        assert_eq!(
+            document_frontmatter_config(document).open,
+            FrontmatterOpenMode::Closed
+        );
+    }
+
+    #[test]
+    fn test_extract_and_render_frontmatter_basic() {
+        let markdown = indoc! {"
+            ---
+            title: Test Document
+            author: John Doe
+            ---
+
+            # Hello World
+        "};
+
+        let (html, content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), Path::new("."));
+
+        assert!(html.contains(r#"<details class="frontmatter">"#));
+        assert!(html.contains(r#"<table class="frontmatter-table""#));
+        assert!(html.contains("<th>title</th>"));
+        assert!(html.contains("<td>Test Document</td>"));
+        assert!(html.contains("<th>author</th>"));
+        assert!(html.contains("<td>John Doe</td>"));
+        assert!(content.starts_with("# Hello World"));
+    }
+
+    #[test]
+    fn test_extract_and_render_frontmatter_with_types() {
+        let markdown = indoc! {r#"
+            ---
+            enabled: true
+            count: 42
+            empty:
+            ---
+
+            Content
+        "#};
+
+        let (html, _content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), Path::new("."));
+
+        assert!(html.contains(r#"<span class="yaml-bool">true</span>"#));
+        assert!(html.contains(r#"<span class="yaml-number">42</span>"#));
+        assert!(html.contains(r#"<span class="yaml-null">null</span>"#));
+    }
+
+    #[test]
+    fn test_extract_and_render_frontmatter_with_list() {
+        let markdown = indoc! {"
+            ---
+            tags:
+              - rust
+              - markdown
+            ---
+
+            Content
+        "};
+
+        // Use a different tags key so `tags` renders as a plain list
+        let (html, _content) = extract_and_render_frontmatter(
+            markdown,
+            &FrontmatterConfig {
+                tags_key: "keywords".to_string(),
+                ..FrontmatterConfig::default()
+            },
+            Path::new("."),
+        );
+
+        assert!(html.contains(r#"<ul class="yaml-list">"#));
+        assert!(html.contains("<li>rust</li>"));
+        assert!(html.contains("<li>markdown</li>"));
+    }
+
+    #[test]
+    fn test_extract_and_render_frontmatter_collapses_deep_nesting() {
+        let markdown = indoc! {"
+            ---
+            a:
+              b:
+                c:
+                  d: <deep>
+            ---
+
+            Content
+        "};
+        let config = FrontmatterConfig {
+            max_depth: 2,
+            ..FrontmatterConfig::default()
+        };
+
+        let (html, _content) = extract_and_render_frontmatter(markdown, &config, Path::new("."));
+
+        // The values of `a` and `b` are tables; the mapping under `c` is past the limit
+        assert_eq!(
+            html.matches(r#"<table class="yaml-nested-table">"#).count(),
+            2
+        );
+        assert!(html.contains(
+            r#"<details class="yaml-collapsed"><summary>{…} 1 key</summary><pre>d: &lt;deep&gt;</pre></details>"#
+        ));
+    }
+
+    #[test]
+    fn test_extract_and_render_frontmatter_limits_long_lists() {
+        let markdown = indoc! {"
+            ---
+            items: [1, 2, 3, 4, 5]
+            ---
+
+            Content
+        "};
+        let config = FrontmatterConfig {
+            max_list_items: 2,
+            ..FrontmatterConfig::default()
+        };
+
+        let (html, _content) = extract_and_render_frontmatter(markdown, &config, Path::new("."));
+
+        assert!(html.contains(
+            r#"<ul class="yaml-list"><li><span class="yaml-number">1</span></li><li><span class="yaml-number">2</span></li></ul>"#
+        ));
+        assert!(html.contains(r#"<details class="yaml-more"><summary>Show all (3 more)</summary>"#));
+        assert!(html.contains(r#"<li><span class="yaml-number">5</span></li></ul></details>"#));
     }
 
     #[test]
-    fn test_post_process_html_tags_md_vs_other_files() {
-        let html = r#"<a href="doc.md">MD</a><a href="file.txt">TXT</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+    fn test_extract_and_render_frontmatter_renders_tag_chips() {
+        let markdown = indoc! {r#"
+            ---
+            tags: [rust, "a<b"]
+            ---
 
-        // MD file should have only md-link class
-        assert!(
-            result.contains(r#"class="md-link""#),
-            "Should have md-link for .md file"
-        );
+            Content
+        "#};
 
-        // TXT file should have both md-link and md-link-invalid classes
-        assert!(
-            result.contains(r#"class="md-link md-link-invalid""#),
-            "Should have md-link and md-link-invalid for .txt file"
-        );
+        let (html, _content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), Path::new("."));
 
-        // Both should have click handlers
-        let click_handler_count = result.matches("handleMarkdownLinkClick").count();
-        assert_eq!(
-            click_handler_count, 2,
-            "Should have click handlers for both links"
-        );
+        assert!(html.contains(r#"<span class="frontmatter-tags">"#));
+        assert!(html.contains(r#"data-tag="rust""#));
+        assert!(html.contains(r#"data-tag="a&lt;b""#));
+        assert!(html.contains(">a&lt;b</button>"));
+        assert!(!html.contains(r#"<ul class="yaml-list">"#));
     }
 
     #[test]
-    fn test_render_to_html_basic() {
-        let markdown = "# Hello\n\nThis is a test.";
-        let temp_dir = TempDir::new().unwrap();
-        let md_path = temp_dir.path().join("test.md");
+    fn test_extract_and_render_frontmatter_renders_related_links() {
+        let markdown = indoc! {r#"
+            ---
+            related: [notes/a.md, "it's.md", https://example.com]
+            ---
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+            Content
+        "#};
 
-        assert!(result.contains("<h1>"));
-        assert!(result.contains("Hello"));
-        assert!(result.contains("<p>"));
-        assert!(result.contains("This is a test."));
+        let (html, _content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), Path::new("."));
+
+        assert!(html.contains(r#"<nav class="frontmatter-related">"#));
+        assert!(html.contains(r#"data-md-link="notes/a.md""#));
+        assert!(html.contains(r#"handleMarkdownLinkClick('it\'s.md'"#));
+        assert!(html.contains(
+            r#"<a class="frontmatter-tag frontmatter-related-link" href="https://example.com">"#
+        ));
+    }
+
+    fn render_captions(markdown: &str, config: &CaptionsConfig) -> String {
+        let events = process_captions(Parser::new_ext(markdown, Options::all()).collect(), config);
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, events.into_iter());
+        html_output
+    }
+
+    fn captions_enabled() -> CaptionsConfig {
+        CaptionsConfig {
+            enabled: true,
+            ..Default::default()
+        }
     }
 
     #[test]
-    fn test_code_block_language_classes() {
+    fn test_process_captions_numbers_each_kind_separately() {
         let markdown = indoc! {"
-            # Code Blocks Test
-
             ```rust
-            fn main() {
-                println!(\"Hello\");
-            }
+            fn main() {}
             ```
 
-            ```python
-            def hello():
-                print(\"world\")
-            ```
+            Caption: Entry point
 
+            | a | b |
+            |---|---|
+            | 1 | 2 |
+
+            Caption: Values
+
+            ```sh
+            make
             ```
-            no language specified
-            ```
-        "};
 
-        let temp_dir = TempDir::new().unwrap();
-        let md_path = temp_dir.path().join("test.md");
+            Caption: Build *command*
+        "};
+        let html = render_captions(markdown, &captions_enabled());
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+        assert!(html.contains(
+            r#"<p class="caption caption-lst" id="lst-1"><span class="caption-label">Listing 1:</span> Entry point</p>"#
+        ));
+        assert!(html.contains(
+            r#"<p class="caption caption-tbl" id="tbl-1"><span class="caption-label">Table 1:</span> Values</p>"#
+        ));
+        assert!(html.contains(
+            r#"<span class="caption-label">Listing 2:</span> Build <em>command</em></p>"#
+        ));
+    }
 
-        // Print the output to inspect
-        println!("\n=== HTML OUTPUT ===\n{}\n===================\n", result);
+    #[test]
+    fn test_render_to_html_footnotes_with_multiple_references() {
+        let markdown = indoc! {"
+            [^second]: Defined first, referenced second.
 
-        // Check if language classes are present
-        let has_rust = result.contains("language-rust") || result.contains("class=\"rust\"");
-        let has_python = result.contains("language-python") || result.contains("class=\"python\"");
+            One[^first], two[^second] and one again[^FIRST].
 
-        println!("Has rust class: {}", has_rust);
-        println!("Has python class: {}", has_python);
+            [^first]: The first note.
+            [^unused]: Never referenced.
+        "};
+        let html = render_to_html(markdown, Path::new("test.md")).unwrap();
+
+        // Numbered by first reference, each reference with its own ID
+        assert!(html.contains(r##"<sup><a href="#user-content-fn-first" id="user-content-fnref-first" data-footnote-ref aria-describedby="footnote-label">1</a></sup>"##));
+        assert!(html.contains(r##"<a href="#user-content-fn-second" id="user-content-fnref-second" data-footnote-ref aria-describedby="footnote-label">2</a>"##));
+        assert!(html.contains(r##"<a href="#user-content-fn-first" id="user-content-fnref-first-2" data-footnote-ref aria-describedby="footnote-label">1</a>"##));
+
+        // Definitions follow at the end in reference order, linking back
+        let section = &html[html
+            .find(r#"<section data-footnotes class="footnotes">"#)
+            .unwrap()..];
+        let first = section.find(r#"<li id="user-content-fn-first">"#).unwrap();
+        let second = section.find(r#"<li id="user-content-fn-second">"#).unwrap();
+        assert!(first < second);
+        assert!(section.contains(r##"The first note. <a href="#user-content-fnref-first" data-footnote-backref aria-label="Back to reference 1" class="data-footnote-backref">↩</a> <a href="#user-content-fnref-first-2" data-footnote-backref aria-label="Back to reference 1-2" class="data-footnote-backref">↩<sup>2</sup></a></p>"##));
+        assert!(!html.contains("Never referenced"));
+        assert!(!html[..html.find("<section").unwrap()].contains("Defined first"));
     }
 
     #[test]
-    fn test_render_to_html_with_alert() {
+    fn test_render_to_html_footnote_with_inline_markdown() {
         let markdown = indoc! {"
-            # Title
+            See the note[^note] and a missing one[^missing].
 
-            > [!NOTE]
-            > This is important
+            [^note]: Some **bold** text, `code` and a [link](https://example.com).
         "};
+        let html = render_to_html(markdown, Path::new("test.md")).unwrap();
+
+        assert!(html.contains("[^missing]"));
+        let section = &html[html.find("<section").unwrap()..];
+        assert!(section.contains("<strong>bold</strong>"));
+        assert!(section.contains("<code>code</code>"));
+        assert!(section.contains(r#"<a href="https://example.com">link</a>"#));
+        assert!(section.contains(r#"aria-label="Back to reference 1""#));
+
+        // Source lines survive moving the definition
+        let (html, _) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
+        assert!(html.contains(r#"<li id="user-content-fn-note">"#));
+        assert!(html.contains("<strong>bold</strong>"));
+    }
 
-        let temp_dir = TempDir::new().unwrap();
-        let md_path = temp_dir.path().join("test.md");
+    #[test]
+    fn test_render_to_html_code_wrap_flags() {
+        let markdown = indoc! {"
+            ```rust nowrap
+            let a = 1;
+            ```
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+            ```text   wrap
+            long line
+            ```
 
-        assert!(result.contains("markdown-alert-note"));
-        assert!(result.contains("This is important"));
+            ```rust
+            plain
+            ```
+        "};
+        let (html, _) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
+
+        assert!(html.contains(
+            r#"<pre data-source-line="1" class="code-nowrap"><code class="language-rust">let a = 1;"#
+        ));
+        assert!(html.contains(
+            r#"<pre data-source-line="5" class="code-wrap"><code class="language-text">long line"#
+        ));
+        assert!(html.contains(r#"<pre data-source-line="9"><code class="language-rust">plain"#));
+        assert_eq!(html.matches("</code></pre>").count(), 3);
+    }
+
+    fn render_code_block_flags(markdown: &str, disabled_languages: &[&str]) -> String {
+        let events = Parser::new_ext(markdown, Options::all());
+        let disabled_languages = disabled_languages.iter().map(|s| s.to_string()).collect();
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_code_block_flags(events, disabled_languages),
+        );
+        html_output
     }
 
     #[test]
-    fn test_render_to_html_with_mermaid() {
+    fn test_process_code_block_flags_no_highlight() {
         let markdown = indoc! {"
-            ```mermaid
-            graph LR
-                A-->B
+            ```rust,nohl
+            let a = 1;
+            ```
+
+            ```rust nohighlight, wrap
+            let b = 2;
+            ```
+
+            ```Pseudo
+            step one
+            ```
+
+            ```rust,ignore
+            let c = 3;
+            ```
+
+            ```python
+            pass
             ```
         "};
+        let html = render_code_block_flags(markdown, &["pseudo"]);
 
-        let temp_dir = TempDir::new().unwrap();
-        let md_path = temp_dir.path().join("test.md");
+        assert!(html.contains(r#"<pre><code class="language-rust nohighlight">let a = 1;"#));
+        assert!(html.contains(
+            r#"<pre class="code-wrap"><code class="language-rust nohighlight">let b = 2;"#
+        ));
+        // Disabled languages match regardless of case
+        assert!(html.contains(r#"<pre><code class="language-Pseudo nohighlight">step one"#));
+        // Other words are left to the parser as before
+        assert!(html.contains(r#"<pre><code class="language-rust,ignore">let c = 3;"#));
+        assert!(html.contains(r#"<pre><code class="language-python">pass"#));
+    }
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+    #[test]
+    fn test_info_words_and_line_ranges() {
+        assert_eq!(
+            info_words("rust {1,4-6}, wrap"),
+            ["rust", "{1,4-6}", "wrap"]
+        );
+        assert_eq!(info_words("rust{3}"), ["rust", "{3}"]);
+        assert_eq!(info_words("{2} linenos"), ["{2}", "linenos"]);
 
-        assert!(result.contains(r#"<pre class="preprocessed-mermaid""#));
-        assert!(result.contains("graph LR"));
+        assert_eq!(parse_line_ranges("{1,4-6}"), Some(vec![(1, 1), (4, 6)]));
+        // Entries that aren't line numbers are skipped
+        assert_eq!(parse_line_ranges("{0,x,3-2,5}"), Some(vec![(5, 5)]));
+        assert_eq!(parse_line_ranges("wrap"), None);
+    }
+
+    fn render_code_lines(markdown: &str, config: &CodeLinesConfig) -> String {
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let syntax_highlight = SyntaxHighlightConfig::default();
+        let events = process_code_lines(events, config, &syntax_highlight);
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, events.into_iter());
+        html_output
     }
 
     #[test]
-    fn test_process_math_expressions_inline() {
-        let markdown = "This is inline math: $x = y + z$";
-        let options = Options::all();
-        let parser = Parser::new_ext(markdown, options);
+    fn test_process_code_lines_highlighted_lines() {
+        let markdown = indoc! {"
+            ```rust {1,3-4}
+            let a = 1;
+            let b = 2;
+            let c = a < b;
+            let d = 4;
+            ```
 
-        let events: Vec<Event> = process_math_expressions(parser).collect();
+            ```text
+            plain
+            ```
+        "};
+        let html = render_code_lines(markdown, &CodeLinesConfig::default());
+
+        assert!(html.contains(concat!(
+            r#"<pre class="code-lines"><code class="language-rust" style="--line-number-digits: 1">"#,
+            r#"<span class="code-line highlighted" data-line="1">let a = 1;</span>"#,
+            "\n",
+            r#"<span class="code-line" data-line="2">let b = 2;</span>"#,
+            "\n",
+            r#"<span class="code-line highlighted" data-line="3">let c = a &lt; b;</span>"#,
+            "\n",
+            r#"<span class="code-line highlighted" data-line="4">let d = 4;</span>"#,
+            "\n</code></pre>",
+        )));
+        // Blocks without ranges or line numbers are left alone
+        assert!(html.contains(r#"<pre><code class="language-text">plain"#));
+        assert_eq!(html.matches("code-lines").count(), 1);
+    }
 
-        // Verify that inline math is converted to custom HTML
-        let html_events: Vec<_> = events
-            .iter()
-            .filter_map(|e| {
-                if let Event::Html(html) = e {
-                    Some(html.as_ref())
-                } else {
-                    None
-                }
-            })
-            .collect();
+    #[test]
+    fn test_process_code_lines_line_numbers() {
+        let markdown = indoc! {"
+            ```rust linenos
+            one
+            ```
 
+            ```rust nolinenos
+            two
+            ```
+        "};
+        let html = render_code_lines(markdown, &CodeLinesConfig::default());
         assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains(r#"<span class="preprocessed-math-inline""#)),
-            "Should contain inline-math span"
+            html.contains(r#"<pre class="code-lines line-numbers"><code class="language-rust""#)
         );
-        assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains("data-original-content")),
-            "Should contain data attribute"
+        assert_eq!(html.matches("code-lines").count(), 1);
+
+        let config = CodeLinesConfig { line_numbers: true };
+        let html = render_code_lines(markdown, &config);
+        assert_eq!(html.matches("code-lines line-numbers").count(), 1);
+        assert!(html.contains(r#"<pre><code class="language-rust">two"#));
+
+        // Indented blocks are numbered too, with the gutter sized for the last line
+        let indented = (1..=10)
+            .map(|n| format!("    line {n}\n"))
+            .collect::<String>();
+        let html = render_code_lines(&indented, &config);
+        assert!(html.contains(
+            r#"<pre class="code-lines line-numbers"><code style="--line-number-digits: 2">"#
+        ));
+        assert!(html.contains(r#"<span class="code-line" data-line="10">line 10</span>"#));
+    }
+
+    #[test]
+    fn test_split_highlighted_lines() {
+        let html = concat!(
+            r#"<span class="hljs-comment">/* a"#,
+            "\n",
+            r#"b */</span> <span class="hljs-keyword">fn</span>"#,
+            "\n",
+            "x",
         );
-        assert!(
-            html_events.iter().any(|h| h.contains("x = y + z")),
-            "Should contain the math content"
+        assert_eq!(
+            split_highlighted_lines(html),
+            [
+                r#"<span class="hljs-comment">/* a</span>"#,
+                r#"<span class="hljs-comment">b */</span> <span class="hljs-keyword">fn</span>"#,
+                "x",
+            ]
         );
     }
 
     #[test]
-    fn test_process_math_expressions_display() {
+    fn test_render_to_html_code_lines_source_line() {
         let markdown = indoc! {"
-            Display math:
+            # Title
 
-            $$
-            x = \\frac{-b \\pm \\sqrt{b^2-4ac}}{2a}
-            $$
+            ```rust {2}
+            let a = 1;
+            let b = 2;
+            ```
         "};
-        let options = Options::all();
-        let parser = Parser::new_ext(markdown, options);
-
-        let events: Vec<Event> = process_math_expressions(parser).collect();
+        let (html, _) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
 
-        // Verify that display math is converted to custom HTML
-        let html_events: Vec<_> = events
-            .iter()
-            .filter_map(|e| {
-                if let Event::Html(html) = e {
-                    Some(html.as_ref())
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // The fence's source line stays on the <pre>, so line N is at 3 + N
+        assert!(html.contains(r#"<pre data-source-line="3" class="code-lines">"#));
+        assert!(html.contains(r#"<span class="code-line highlighted" data-line="2">"#));
+    }
 
-        assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains(r#"<div class="preprocessed-math-display""#)),
-            "Should contain display-math div"
-        );
-        assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains("data-original-content")),
-            "Should contain data attribute"
-        );
-        assert!(
-            html_events.iter().any(|h| h.contains("frac")),
-            "Should contain the math content"
+    fn render_syntax_highlighting(markdown: &str, config: &SyntaxHighlightConfig) -> String {
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let events = process_syntax_highlighting(events, config);
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_code_block_flags(events.into_iter(), config.disabled_languages.clone()),
         );
+        html_output
     }
 
     #[test]
-    fn test_process_math_expressions_mixed() {
-        let markdown = "Inline $a + b$ and display $$c = d$$";
-        let options = Options::all();
-        let parser = Parser::new_ext(markdown, options);
+    fn test_process_syntax_highlighting() {
+        let markdown = indoc! {r#"
+            ```rust wrap
+            fn main() {}
+            ```
 
-        let events: Vec<Event> = process_math_expressions(parser).collect();
+            ```python
+            print("<b>")
+            ```
 
-        let html_events: Vec<_> = events
-            .iter()
-            .filter_map(|e| {
-                if let Event::Html(html) = e {
-                    Some(html.as_ref())
-                } else {
-                    None
-                }
-            })
-            .collect();
+            ```no-such-language
+            a <- b
+            ```
 
-        // Should have both inline and display math
-        assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains(r#"class="preprocessed-math-inline""#)),
-            "Should contain inline math"
-        );
-        assert!(
-            html_events
-                .iter()
-                .any(|h| h.contains(r#"class="preprocessed-math-display""#)),
-            "Should contain display math"
+            ```rust,nohl
+            let a = 1;
+            ```
+        "#};
+        let config = SyntaxHighlightConfig {
+            native: true,
+            ..Default::default()
+        };
+        let html = render_syntax_highlighting(markdown, &config);
+
+        assert!(html.contains(concat!(
+            r#"<pre class="code-wrap"><code class="language-rust hljs" data-highlighted="yes">"#,
+            r#"<span class="hljs-keyword">fn</span>"#,
+        )));
+        assert!(html.contains(r#"<code class="language-python hljs" data-highlighted="yes">"#));
+        assert!(html.contains(r#"<span class="hljs-string">"&lt;b&gt;"</span>"#));
+        // Unknown languages and opted out blocks stay plain escaped code
+        assert!(html.contains(r#"<pre><code class="language-no-such-language">a &lt;- b"#));
+        assert!(html.contains(r#"<pre><code class="language-rust nohighlight">let a = 1;"#));
+        assert_eq!(html.matches("</code></pre>\n").count(), 4);
+    }
+
+    #[test]
+    fn test_process_syntax_highlighting_off() {
+        let markdown = "```rust\nfn main() {}\n```\n";
+        let html = render_syntax_highlighting(markdown, &SyntaxHighlightConfig::default());
+
+        assert_eq!(
+            html,
+            "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n"
         );
     }
 
+    fn render_spoilers(markdown: &str, enabled: bool) -> String {
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_spoilers(events, enabled).into_iter(),
+        );
+        html_output
+    }
+
     #[test]
-    fn test_render_to_html_with_math() {
+    fn test_process_spoilers() {
         let markdown = indoc! {"
-            # Math Test
-
-            Inline math: $E = mc^2$
+            The killers are ||the butler & maid|| and `a || b || c` stays, as does x || y || z.
 
-            Display math:
-            $$
-            \\int_0^\\infty e^{-x^2} dx = \\frac{\\sqrt{\\pi}}{2}
-            $$
+            ```
+            ||not hidden||
+            ```
         "};
+        let html = render_spoilers(markdown, true);
 
-        let temp_dir = TempDir::new().unwrap();
-        let md_path = temp_dir.path().join("test.md");
+        assert_eq!(html.matches(r#"<span class="spoiler""#).count(), 1);
+        assert!(html
+            .contains(r#"this.classList.toggle('revealed'); }">the butler &amp; maid</span> and"#));
+        assert!(html.contains("<code>a || b || c</code>"));
+        assert!(html.contains("x || y || z."));
+        assert!(html.contains("||not hidden||\n</code></pre>"));
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+        // Off by default
+        assert!(!render_spoilers(markdown, false).contains("spoiler"));
+    }
 
-        assert!(
-            result.contains(r#"class="preprocessed-math-inline""#),
-            "Should render inline math"
-        );
-        assert!(
-            result.contains(r#"class="preprocessed-math-display""#),
-            "Should render display math"
+    #[test]
+    fn test_process_spoilers_in_tables() {
+        let markdown = indoc! {r"
+            | Name | Secret |
+            | ---- | ------ |
+            | a    | \|\|hidden\|\| |
+            | b    ||c|| |
+        "};
+        let html = render_spoilers(markdown, true);
+
+        // Escaped pipes inside a cell form a spoiler
+        assert!(html.contains(r#"<td><span class="spoiler""#));
+        assert!(html.contains(">hidden</span></td>"));
+        assert_eq!(html.matches(r#"class="spoiler""#).count(), 1);
+        // Pipes next to cell pipes still separate cells
+        assert_eq!(html.matches("<tr>").count(), 3);
+        assert!(html.contains("<td>b</td>"));
+    }
+
+    fn render_emoji(markdown: &str, enabled: bool) -> String {
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let mut html_output = String::new();
+        html::push_html(
+            &mut html_output,
+            process_emoji_shortcodes(events, enabled).into_iter(),
         );
-        assert!(
-            result.contains("data-original-content"),
-            "Should include data attributes"
+        html_output
+    }
+
+    #[test]
+    fn test_process_emoji_shortcodes() {
+        let markdown = indoc! {"
+            Ship it :rocket:`:rocket:` :+1: and keep :not_an_emoji:, <https://example.com/:tada:>
+
+            ```
+            :rocket:
+            ```
+        "};
+        let html = render_emoji(markdown, true);
+
+        // Code spans, code blocks and autolinks keep their shortcodes
+        assert!(html.contains("Ship it 🚀<code>:rocket:</code> 👍 and keep :not_an_emoji:,"));
+        assert!(html.contains(">https://example.com/:tada:</a>"));
+        assert!(html.contains("<pre><code>:rocket:\n</code></pre>"));
+
+        let html = render_emoji(markdown, false);
+        assert!(html.contains("Ship it :rocket:<code>"));
+    }
+
+    #[test]
+    fn test_render_to_html_with_toc_emoji_heading() {
+        let markdown = "# :rocket: Launch\n\n## Setup :wrench:\n";
+        let (html, headings) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
+
+        // IDs are made from the shortcodes, like GitHub's
+        assert_eq!(headings[0].text, "🚀 Launch");
+        assert_eq!(headings[0].id, "rocket-launch");
+        assert_eq!(headings[1].text, "Setup 🔧");
+        assert_eq!(headings[1].id, "setup-wrench");
+        assert!(html.contains(r#"id="rocket-launch""#));
+        assert!(html.contains("🚀 Launch</h1>"));
+        assert!(html.contains("Setup 🔧"));
+    }
+
+    #[test]
+    fn test_process_captions_image() {
+        let html = render_captions(
+            "![Cat](cat.png)\nCaption: A cat\n\n![Dog](dog.png)\n\n: A dog",
+            &captions_enabled(),
         );
+
+        assert!(html.contains(
+            r#"<p><img src="cat.png" alt="Cat" /></p>
+<p class="caption caption-fig" id="fig-1"><span class="caption-label">Figure 1:</span> A cat</p>"#
+        ));
+        assert!(html.contains(r#"<span class="caption-label">Figure 2:</span> A dog</p>"#));
     }
 
     #[test]
-    fn test_render_to_html_integrated() {
-        // Integration test: combining multiple features
-        let temp_dir = TempDir::new().unwrap();
+    fn test_process_captions_requires_preceding_block() {
+        let html = render_captions("Some text\n\nCaption: Not a caption", &captions_enabled());
 
-        // Create test image
-        let image_path = temp_dir.path().join("image.png");
-        let png_data = vec![0x89, 0x50, 0x4E, 0x47];
-        fs::write(&image_path, png_data).unwrap();
+        assert!(!html.contains("caption-label"));
+        assert!(html.contains("<p>Caption: Not a caption</p>"));
+    }
 
+    #[test]
+    fn test_process_captions_resolves_references() {
         let markdown = indoc! {"
-            # Test Document
-
-            > [!WARNING]
-            > Be careful
+            As @fig:cat shows (mail me@fig:cat, see @fig:unknown):
 
-            ![Test Image](image.png)
+            ![Cat](cat.png)
 
-            [Link to other doc](other.md)
+            Caption: A cat {#fig:cat}
 
-            ```mermaid
-            graph TD
-                A-->B
-            ```
+            `@fig:cat` in code stays.
         "};
+        let html = render_captions(markdown, &captions_enabled());
 
-        let md_path = temp_dir.path().join("test.md");
+        assert!(html.contains(
+            r##"As <a class="caption-ref" href="#fig:cat">Figure 1</a> shows (mail me@fig:cat, see @fig:unknown):"##
+        ));
+        assert!(html.contains(
+            r#"<p class="caption caption-fig" id="fig:cat"><span class="caption-label">Figure 1:</span> A cat</p>"#
+        ));
+        assert!(html.contains("<code>@fig:cat</code> in code stays."));
+    }
 
-        let result = render_to_html(markdown, &md_path).unwrap();
+    #[test]
+    fn test_process_captions_disabled() {
+        let markdown = "```\ncode\n```\n\nCaption: Code";
+        let html = render_captions(markdown, &CaptionsConfig::default());
 
-        // Verify that all features are correctly integrated
-        assert!(result.contains("<h1>"), "Should render heading");
-        assert!(
-            result.contains("markdown-alert-warning"),
-            "Should render alert"
-        );
-        assert!(
-            result.contains("data:image/png"),
-            "Should convert image to data URL"
-        );
-        assert!(
-            result.contains(r#"class="md-link""#),
-            "Should convert md link"
-        );
-        assert!(
-            result.contains(r#"<pre class="preprocessed-mermaid""#),
-            "Should render mermaid"
-        );
+        assert!(html.contains("<p>Caption: Code</p>"));
+        assert!(!html.contains("caption-label"));
     }
 
     #[test]
-    fn test_extract_and_render_frontmatter_basic() {
+    fn test_extract_local_links() {
         let markdown = indoc! {"
             ---
-            title: Test Document
-            author: John Doe
+            related: [a.md, b.md]
             ---
 
-            # Hello World
+            [C](sub/c%20d.md#part), [web](https://example.com/e.md),
+            [top](#top) and [mail](mailto:x@example.com)
         "};
-
-        let (html, content) = extract_and_render_frontmatter(markdown);
-
-        assert!(html.contains(r#"<details class="frontmatter">"#));
-        assert!(html.contains(r#"<table class="frontmatter-table""#));
-        assert!(html.contains("<th>title</th>"));
-        assert!(html.contains("<td>Test Document</td>"));
-        assert!(html.contains("<th>author</th>"));
-        assert!(html.contains("<td>John Doe</td>"));
-        assert!(content.starts_with("# Hello World"));
+        assert_eq!(
+            extract_local_links(markdown, "related"),
+            vec!["a.md", "b.md", "sub/c d.md"]
+        );
     }
 
     #[test]
-    fn test_extract_and_render_frontmatter_with_types() {
-        let markdown = indoc! {r#"
+    fn test_extract_frontmatter_tags() {
+        let list = indoc! {"
             ---
-            enabled: true
-            count: 42
-            empty:
+            title: Notes
+            tags:
+              - rust
+              - async
             ---
+        "};
+        assert_eq!(
+            extract_frontmatter_tags(list, "tags"),
+            vec!["rust", "async"]
+        );
 
-            Content
-        "#};
-
-        let (html, _content) = extract_and_render_frontmatter(markdown);
+        let comma_separated = "---\ntags: rust, async\n---\nBody";
+        assert_eq!(
+            extract_frontmatter_tags(comma_separated, "tags"),
+            vec!["rust", "async"]
+        );
 
-        assert!(html.contains(r#"<span class="yaml-bool">true</span>"#));
-        assert!(html.contains(r#"<span class="yaml-number">42</span>"#));
-        assert!(html.contains(r#"<span class="yaml-null">null</span>"#));
+        assert!(extract_frontmatter_tags(list, "keywords").is_empty());
+        assert!(extract_frontmatter_tags("# No frontmatter", "tags").is_empty());
     }
 
     #[test]
-    fn test_extract_and_render_frontmatter_with_list() {
+    fn test_extract_and_render_frontmatter_document_css() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        std::fs::write(docs.join("style.css"), "h1 { color: red; }").unwrap();
+        std::fs::write(temp_dir.path().join("outside.css"), "p { color: blue; }").unwrap();
         let markdown = indoc! {"
             ---
-            tags:
-              - rust
-              - markdown
+            title: Report
+            arto-css: [style.css, ../outside.css]
+            arto-style: |
+              body { font-size: 20px; }
             ---
 
-            Content
+            # Report
         "};
 
-        let (html, _content) = extract_and_render_frontmatter(markdown);
+        let (html, _content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), &docs);
+        assert!(!html.contains("<style"));
+        assert!(!html.contains("arto-css"));
+        assert!(html.contains("<th>title</th>"));
 
-        assert!(html.contains(r#"<ul class="yaml-list">"#));
-        assert!(html.contains("<li>rust</li>"));
-        assert!(html.contains("<li>markdown</li>"));
+        let config = FrontmatterConfig {
+            document_css: true,
+            ..FrontmatterConfig::default()
+        };
+        let (html, _content) = extract_and_render_frontmatter(markdown, &config, &docs);
+        assert!(html.starts_with(r#"<style class="document-css">"#));
+        assert!(html.contains(".markdown-body h1 { color: red; }"));
+        assert!(html.contains(".markdown-body { font-size: 20px; }"));
+        // Stylesheets outside the document directory are ignored
+        assert!(!html.contains("color: blue"));
+        assert!(!html.contains("<th>arto-style</th>"));
     }
 
     #[test]
     fn test_extract_and_render_frontmatter_no_frontmatter() {
         let markdown = "# Just a heading\n\nSome content";
 
-        let (html, content) = extract_and_render_frontmatter(markdown);
+        let (html, content) =
+            extract_and_render_frontmatter(markdown, &FrontmatterConfig::default(), Path::new("."));
 
         assert!(html.is_empty());
         assert_eq!(content, markdown);
@@ -1300,11 +5782,35 @@ mod tests {
             generate_slug("Special: Characters! Here?"),
             "special-characters-here"
         );
-        assert_eq!(generate_slug("日本語"), ""); // Non-ASCII characters are stripped
         assert_eq!(generate_slug("Code `example`"), "code-example");
         assert_eq!(generate_slug("under_score"), "under-score");
     }
 
+    #[test]
+    fn test_generate_slug_keeps_unicode_letters() {
+        assert_eq!(generate_slug("日本語セクション"), "日本語セクション");
+        assert_eq!(generate_slug("はじめに: 概要"), "はじめに-概要");
+        assert_eq!(generate_slug("Привет Мир"), "привет-мир");
+        assert_eq!(generate_slug("Café Crème"), "café-crème");
+        assert_eq!(generate_slug("Ünïcödé «Quotes»"), "ünïcödé-quotes");
+        assert_eq!(generate_slug("🎉 Party"), "party");
+    }
+
+    #[test]
+    fn test_extract_headings_numbers_unicode_duplicates() {
+        let markdown = "# 概要\n\n# 概要\n\n# Обзор\n\n# Обзор\n\n# Résumé\n\n# Résumé\n";
+
+        let ids: Vec<String> = extract_headings(markdown)
+            .into_iter()
+            .map(|heading| heading.id)
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec!["概要", "概要-1", "обзор", "обзор-1", "résumé", "résumé-1"]
+        );
+    }
+
     #[test]
     fn test_extract_headings_basic() {
         let markdown = indoc! {"
@@ -1385,6 +5891,34 @@ mod tests {
         assert_eq!(headings[3].id, "overview-2");
     }
 
+    #[test]
+    fn test_extract_headings_numbers_duplicates_like_github() {
+        let markdown = "# Foo\n\n# Foo 1\n\n# Foo\n\n# Foo\n";
+
+        let ids: Vec<String> = extract_headings(markdown)
+            .into_iter()
+            .map(|heading| heading.id)
+            .collect();
+
+        // `foo-1` is taken by "Foo 1", so the first repeat of "Foo" skips it
+        assert_eq!(ids, vec!["foo", "foo-1", "foo-2", "foo-3"]);
+    }
+
+    #[test]
+    fn test_slugger_empty_slugs() {
+        let mut slugger = Slugger::new("section");
+        assert_eq!(slugger.slug("🎉"), "section");
+        assert_eq!(slugger.slug("!!!"), "section-1");
+        assert_eq!(slugger.slug("Section"), "section-2");
+        assert_eq!(slugger.slug("???"), "section-3");
+
+        // Without a fallback, empty slugs are numbered like GitHub's
+        let mut slugger = Slugger::new("");
+        assert_eq!(slugger.slug("🎉"), "");
+        assert_eq!(slugger.slug("***"), "-1");
+        assert_eq!(slugger.slug("→"), "-2");
+    }
+
     #[test]
     fn test_extract_headings_with_frontmatter() {
         let markdown = indoc! {"
@@ -1403,6 +5937,100 @@ mod tests {
         assert_eq!(headings[0].text, "Heading After Frontmatter");
     }
 
+    #[test]
+    fn test_render_to_html_with_media_embeds() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("clip.mp4"), b"video").unwrap();
+        fs::write(temp_dir.path().join("song.mp3"), b"audio").unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let markdown = indoc! {r#"
+            ![Demo](clip.mp4)
+
+            <audio controls src="song.mp3"></audio>
+
+            <video src="missing.mp4"></video>
+        "#};
+        let html = render_to_html(markdown, &md_path).unwrap();
+
+        let video_url = media_url(&temp_dir.path().join("clip.mp4").canonicalize().unwrap());
+        let audio_url = media_url(&temp_dir.path().join("song.mp3").canonicalize().unwrap());
+        assert!(html.contains(&format!(
+            r#"<video class="media-embed" controls preload="metadata" src="{}" title="Demo"></video>"#,
+            video_url
+        )));
+        assert!(html.contains(&format!(r#"<audio controls src="{}">"#, audio_url)));
+        // Missing files are left untouched
+        assert!(html.contains(r#"<video src="missing.mp4">"#));
+    }
+
+    fn render_leading_heading(
+        markdown: &str,
+        style: LeadingHeadingStyle,
+        file: &str,
+    ) -> (String, bool) {
+        let headings = extract_headings(markdown);
+        let events = Parser::new_ext(markdown, Options::all()).collect();
+        let (events, hidden) =
+            apply_leading_heading_style(events, style, &headings, Path::new(file));
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, events.into_iter());
+        (html_output, hidden)
+    }
+
+    #[test]
+    fn test_leading_heading_as_title() {
+        let markdown = "# Guide\n\n*A short subtitle*\n\nBody";
+        let (html, hidden) =
+            render_leading_heading(markdown, LeadingHeadingStyle::Title, "guide.md");
+
+        assert!(!hidden);
+        assert!(html.contains(r#"<h1 class="document-title">Guide</h1>"#));
+        assert!(html.contains(r#"<p class="document-subtitle"><em>A short subtitle</em></p>"#));
+        assert!(html.contains("<p>Body</p>"));
+
+        // A regular paragraph below the title is left alone
+        let (html, _) = render_leading_heading(
+            "# Guide\n\n*Not* a subtitle",
+            LeadingHeadingStyle::Title,
+            "guide.md",
+        );
+        assert!(!html.contains("document-subtitle"));
+    }
+
+    #[test]
+    fn test_leading_heading_hidden_when_matching_file_name() {
+        let markdown = "# Getting Started\n\nBody";
+        let (html, hidden) = render_leading_heading(
+            markdown,
+            LeadingHeadingStyle::HideIfFileName,
+            "/docs/getting-started.md",
+        );
+
+        assert!(hidden);
+        assert!(!html.contains("<h1"));
+        assert!(html.contains(r#"<a id="getting-started" class="document-title-anchor"></a>"#));
+
+        // Kept when the heading differs from the file name
+        let (html, hidden) = render_leading_heading(
+            markdown,
+            LeadingHeadingStyle::HideIfFileName,
+            "/docs/README.md",
+        );
+        assert!(!hidden);
+        assert!(html.contains("<h1>Getting Started</h1>"));
+    }
+
+    #[test]
+    fn test_leading_heading_requires_h1_first() {
+        let markdown = "Intro\n\n# Guide";
+        let (html, hidden) =
+            render_leading_heading(markdown, LeadingHeadingStyle::HideIfFileName, "guide.md");
+
+        assert!(!hidden);
+        assert!(html.contains("<h1>Guide</h1>"));
+    }
+
     #[test]
     fn test_render_to_html_with_toc() {
         let markdown = indoc! {"
@@ -1427,12 +6055,230 @@ mod tests {
 
         // Check IDs were added to HTML
         assert!(
-            html.contains(r#"<h1 id="title">"#),
+            html.contains(r#"<h1 data-source-line="1" id="title">"#),
             "H1 should have id attribute"
         );
         assert!(
-            html.contains(r#"<h2 id="section-1">"#),
+            html.contains(r#"<h2 data-source-line="5" id="section-1">"#),
             "H2 should have id attribute"
         );
     }
+
+    #[test]
+    fn test_post_process_html_marks_ordered_list_depth() {
+        let html = indoc! {r#"
+            <ol>
+            <li>one
+            <ul><li>bullet
+            <ol start="3"><li>nested</li></ol>
+            </li></ul>
+            </li>
+            </ol>
+            <ol><li>again</li></ol>
+        "#};
+
+        let processed =
+            post_process_html_with_headings(html, Path::new("."), &[], &AnchorsConfig::default());
+
+        assert_eq!(processed.matches(r#"<ol class="ol-depth-1">"#).count(), 2);
+        assert!(processed.contains(r#"<ol start="3" class="ol-depth-2" style="--ol-start: 2">"#));
+    }
+
+    fn collapse_tables(html: &str, max_rows: Option<usize>) -> String {
+        let table_rows = Rc::new(RefCell::new(Vec::new()));
+        let mut output = Vec::new();
+        let mut rewriter = HtmlRewriter::new(
+            Settings {
+                element_content_handlers: vec![
+                    element!("table", |el| collapse_long_table(el, &table_rows, max_rows)),
+                    element!("tbody > tr", |el| collapse_table_row(
+                        el,
+                        &table_rows,
+                        max_rows
+                    )),
+                ],
+                ..Settings::default()
+            },
+            |chunk: &[u8]| output.extend_from_slice(chunk),
+        );
+        rewriter.write(html.as_bytes()).unwrap();
+        rewriter.end().unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_collapse_long_tables() {
+        let markdown = indoc! {"
+            | Name | Count |
+            |:-----|------:|
+            | a    | 1     |
+            | b    | 2     |
+            | c    | 3     |
+
+            | Short |
+            |-------|
+            | x     |
+        "};
+        let (html, _) = render_to_html_with_toc(markdown, Path::new("test.md")).unwrap();
+        let html = collapse_tables(&html, Some(2));
+
+        assert!(html.contains(r#"<tr><td style="text-align: left">b</td>"#));
+        // Collapsed rows keep their alignment
+        assert!(html.contains(concat!(
+            r#"<tr class="table-row-collapsed">"#,
+            r#"<td style="text-align: left">c</td><td style="text-align: right">3</td></tr>"#
+        )));
+        assert_eq!(html.matches("table-row-collapsed").count(), 1);
+        assert!(html.contains(concat!(
+            r#"</table><div class="table-collapsed-toggle">"#,
+            r#"<button type="button" class="table-show-all">Show all 3 rows</button></div>"#
+        )));
+        // The short table is left alone
+        assert_eq!(html.matches("table-show-all").count(), 1);
+
+        assert_eq!(collapse_tables(&html, None), html);
+    }
+
+    #[test]
+    fn test_collapse_long_tables_nested() {
+        let html = concat!(
+            "<table><tbody>",
+            "<tr><td><table><tbody><tr><td>1</td></tr><tr><td>2</td></tr></tbody></table></td></tr>",
+            "<tr class=\"odd\"><td>outer</td></tr>",
+            "</tbody></table>",
+        );
+        let html = collapse_tables(html, Some(1));
+
+        // Each table counts its own rows
+        assert!(html.contains(r#"<tr class="table-row-collapsed"><td>2</td></tr>"#));
+        assert!(html.contains(r#"<tr class="odd table-row-collapsed"><td>outer</td></tr>"#));
+        assert_eq!(html.matches("Show all 2 rows").count(), 2);
+    }
+
+    #[test]
+    fn test_post_process_html_with_user_content_ids() {
+        let headings = vec![HeadingInfo {
+            level: 2,
+            text: "Usage".to_string(),
+            id: "usage".to_string(),
+        }];
+        let html = indoc! {r##"
+            <h2>Usage</h2>
+            <p><a href="#user-content-usage">a</a> <a href="#user-content-missing">b</a></p>
+        "##};
+
+        let mut anchors = AnchorsConfig {
+            github_user_content_prefix: true,
+            heading_links: false,
+            ..Default::default()
+        };
+        let processed = post_process_html_with_headings(html, Path::new("."), &headings, &anchors);
+
+        assert!(processed.contains(
+            r#"<h2 id="usage"><a id="user-content-usage" class="user-content-anchor" aria-hidden="true"></a>Usage</h2>"#
+        ));
+        assert!(processed.contains(r##"<a href="#usage">a</a>"##));
+        // Fragments that don't match a heading are left alone
+        assert!(processed.contains(r##"<a href="#user-content-missing">b</a>"##));
+
+        anchors.github_user_content_prefix = false;
+        let processed = post_process_html_with_headings(html, Path::new("."), &headings, &anchors);
+
+        assert!(processed.contains(r#"<h2 id="usage">Usage</h2>"#));
+        assert!(processed.contains(r##"<a href="#user-content-usage">a</a>"##));
+    }
+
+    #[test]
+    fn test_post_process_html_with_heading_links() {
+        let headings = vec![HeadingInfo {
+            level: 2,
+            text: "Usage \"quoted\"".to_string(),
+            id: "usage-quoted".to_string(),
+        }];
+        let html = "<h2>Usage &quot;quoted&quot;</h2>\n<p>Text</p>";
+
+        let processed = post_process_html_with_headings(
+            html,
+            Path::new("."),
+            &headings,
+            &AnchorsConfig::default(),
+        );
+        assert!(processed.contains(concat!(
+            r##"<h2 id="usage-quoted"><a class="heading-anchor" href="#usage-quoted" "##,
+            r#"aria-label="Link to Usage &quot;quoted&quot;"></a>Usage &quot;quoted&quot;</h2>"#
+        )));
+
+        let anchors = AnchorsConfig {
+            heading_links: false,
+            ..Default::default()
+        };
+        let processed = post_process_html_with_headings(html, Path::new("."), &headings, &anchors);
+        assert!(!processed.contains("heading-anchor"));
+    }
+
+    #[test]
+    fn test_render_to_html_with_toc_source_lines() {
+        let markdown = indoc! {"
+            # Title
+
+            Some content
+
+            - one
+            - two
+
+            ---
+        "};
+
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let (html, _) = render_to_html_with_toc(markdown, &md_path).unwrap();
+
+        assert!(html.contains(r#"<p data-source-line="3">Some content</p>"#));
+        assert!(html.contains(r#"<ul data-source-line="5">"#));
+        assert!(html.contains(r#"<li data-source-line="6">two</li>"#));
+        assert!(html.contains(r#"<hr data-source-line="8" />"#));
+        assert!(!html.contains(SOURCE_LINE_MARKER));
+    }
+
+    #[test]
+    fn test_render_to_html_with_toc_source_lines_after_frontmatter_and_alert() {
+        let markdown = indoc! {"
+            ---
+            title: Test
+            ---
+
+            > [!NOTE]
+            > First line
+            > Second line
+
+            After the alert
+        "};
+
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let (html, _) = render_to_html_with_toc(markdown, &md_path).unwrap();
+
+        assert!(html.contains(r#"<div data-source-line="5" class="markdown-alert"#));
+        assert!(html.contains(r#"<p data-source-line="9">After the alert</p>"#));
+    }
+
+    #[test]
+    fn test_apply_source_line_markers() {
+        assert_eq!(
+            apply_source_line_markers("<!--source-line:3-->\n<p>text</p>"),
+            "\n<p data-source-line=\"3\">text</p>"
+        );
+        // Markers not followed by an element are dropped
+        assert_eq!(
+            apply_source_line_markers("<!--source-line:3--><!-- note -->"),
+            "<!-- note -->"
+        );
+        // Comments that merely look like markers are kept as-is
+        assert_eq!(
+            apply_source_line_markers("<!--source-line:x--><p>"),
+            "<!--source-line:x--><p>"
+        );
+    }
 }
@@ -5,6 +5,9 @@ use dioxus_desktop::window;
 use std::path::PathBuf;
 
 use crate::components::content::set_preferences_tab_to_about;
+use crate::components::document_styles::export_fonts_css;
+use crate::components::right_sidebar::RightSidebarTab;
+use crate::config::Config;
 use crate::state::AppState;
 use crate::window::{self, settings::normalize_zoom_level, CreateMainWindowConfigParams};
 
@@ -18,19 +21,42 @@ enum MenuId {
     OpenDirectory,
     RevealInFinder,
     CopyFilePath,
+    ExportAllTabs,
+    ExportAllTabsCombined,
+    ExportPdf,
     CloseTab,
     CloseAllTabs,
     CloseWindow,
     CloseAllChildWindows,
     CloseAllWindows,
+    NextWindow,
+    PreviousWindow,
     Preferences,
+    OpenConfigFile,
+    OpenConfigFolder,
+    Quit,
     Find,
     ToggleSidebar,
+    ToggleShowAllFiles,
+    ToggleRightSidebar,
+    ShowContents,
+    ShowSearchInDocument,
+    ShowTags,
+    ShowBacklinks,
+    ShowLint,
+    ShowNotes,
+    NextRightSidebarTab,
+    PreviousRightSidebarTab,
+    CycleTheme,
+    NextReaderProfile,
+    ToggleSplitPreview,
     ActualSize,
     ZoomIn,
     ZoomOut,
     GoBack,
     GoForward,
+    NextFile,
+    PreviousFile,
     GoToHomepage,
 }
 
@@ -45,19 +71,42 @@ impl MenuId {
             "file.open_directory" => Some(Self::OpenDirectory),
             "file.reveal_in_finder" => Some(Self::RevealInFinder),
             "file.copy_file_path" => Some(Self::CopyFilePath),
+            "file.export_all_tabs" => Some(Self::ExportAllTabs),
+            "file.export_all_tabs_combined" => Some(Self::ExportAllTabsCombined),
+            "file.export_pdf" => Some(Self::ExportPdf),
             "file.close_tab" => Some(Self::CloseTab),
             "file.close_all_tabs" => Some(Self::CloseAllTabs),
             "file.close_window" => Some(Self::CloseWindow),
             "window.close_all_child_windows" => Some(Self::CloseAllChildWindows),
             "window.close_all_windows" => Some(Self::CloseAllWindows),
+            "window.next_window" => Some(Self::NextWindow),
+            "window.previous_window" => Some(Self::PreviousWindow),
             "app.preferences" => Some(Self::Preferences),
+            "app.open_config_file" => Some(Self::OpenConfigFile),
+            "app.open_config_folder" => Some(Self::OpenConfigFolder),
+            "app.quit" => Some(Self::Quit),
             "edit.find" => Some(Self::Find),
             "view.toggle_sidebar" => Some(Self::ToggleSidebar),
+            "view.toggle_show_all_files" => Some(Self::ToggleShowAllFiles),
+            "view.toggle_right_sidebar" => Some(Self::ToggleRightSidebar),
+            "view.show_contents" => Some(Self::ShowContents),
+            "view.show_search_in_document" => Some(Self::ShowSearchInDocument),
+            "view.show_tags" => Some(Self::ShowTags),
+            "view.show_backlinks" => Some(Self::ShowBacklinks),
+            "view.show_lint" => Some(Self::ShowLint),
+            "view.show_notes" => Some(Self::ShowNotes),
+            "view.next_right_sidebar_tab" => Some(Self::NextRightSidebarTab),
+            "view.previous_right_sidebar_tab" => Some(Self::PreviousRightSidebarTab),
+            "view.cycle_theme" => Some(Self::CycleTheme),
+            "view.next_reader_profile" => Some(Self::NextReaderProfile),
+            "view.toggle_split_preview" => Some(Self::ToggleSplitPreview),
             "view.actual_size" => Some(Self::ActualSize),
             "view.zoom_in" => Some(Self::ZoomIn),
             "view.zoom_out" => Some(Self::ZoomOut),
             "history.back" => Some(Self::GoBack),
             "history.forward" => Some(Self::GoForward),
+            "history.next_file" => Some(Self::NextFile),
+            "history.previous_file" => Some(Self::PreviousFile),
             "help.homepage" => Some(Self::GoToHomepage),
             _ => None,
         }
@@ -73,24 +122,50 @@ impl MenuId {
             Self::OpenDirectory => "file.open_directory",
             Self::RevealInFinder => "file.reveal_in_finder",
             Self::CopyFilePath => "file.copy_file_path",
+            Self::ExportAllTabs => "file.export_all_tabs",
+            Self::ExportAllTabsCombined => "file.export_all_tabs_combined",
+            Self::ExportPdf => "file.export_pdf",
             Self::CloseTab => "file.close_tab",
             Self::CloseAllTabs => "file.close_all_tabs",
             Self::CloseWindow => "file.close_window",
             Self::CloseAllChildWindows => "window.close_all_child_windows",
             Self::CloseAllWindows => "window.close_all_windows",
+            Self::NextWindow => "window.next_window",
+            Self::PreviousWindow => "window.previous_window",
             Self::Preferences => "app.preferences",
+            Self::OpenConfigFile => "app.open_config_file",
+            Self::OpenConfigFolder => "app.open_config_folder",
+            Self::Quit => "app.quit",
             Self::Find => "edit.find",
             Self::ToggleSidebar => "view.toggle_sidebar",
+            Self::ToggleShowAllFiles => "view.toggle_show_all_files",
+            Self::ToggleRightSidebar => "view.toggle_right_sidebar",
+            Self::ShowContents => "view.show_contents",
+            Self::ShowSearchInDocument => "view.show_search_in_document",
+            Self::ShowTags => "view.show_tags",
+            Self::ShowBacklinks => "view.show_backlinks",
+            Self::ShowLint => "view.show_lint",
+            Self::ShowNotes => "view.show_notes",
+            Self::NextRightSidebarTab => "view.next_right_sidebar_tab",
+            Self::PreviousRightSidebarTab => "view.previous_right_sidebar_tab",
+            Self::CycleTheme => "view.cycle_theme",
+            Self::NextReaderProfile => "view.next_reader_profile",
+            Self::ToggleSplitPreview => "view.toggle_split_preview",
             Self::ActualSize => "view.actual_size",
             Self::ZoomIn => "view.zoom_in",
             Self::ZoomOut => "view.zoom_out",
             Self::GoBack => "history.back",
             Self::GoForward => "history.forward",
+            Self::NextFile => "history.next_file",
+            Self::PreviousFile => "history.previous_file",
             Self::GoToHomepage => "help.homepage",
         }
     }
 }
 
+/// Prefix of the menu IDs applying a configured reader profile by index
+const READER_PROFILE_ID_PREFIX: &str = "view.reader_profile.";
+
 /// Helper to create a menu item with optional keyboard shortcut
 fn create_menu_item(
     id: MenuId,
@@ -132,8 +207,11 @@ fn add_app_menu(menu: &Menu) {
                 Some(Code::Comma),
                 None,
             ),
+            &create_menu_item(MenuId::OpenConfigFile, "Open Config File", None, None),
+            &create_menu_item(MenuId::OpenConfigFolder, "Open Config Folder", None, None),
             &PredefinedMenuItem::separator(),
-            &PredefinedMenuItem::quit(Some("Quit")),
+            // Not the predefined item, so quitting can ask first (quit.confirm)
+            &create_menu_item(MenuId::Quit, "Quit", Some(Code::KeyQ), None),
         ])
         .unwrap();
 
@@ -164,6 +242,15 @@ fn add_file_menu(menu: &Menu) {
                 Some(Modifiers::SHIFT),
             ),
             &PredefinedMenuItem::separator(),
+            &create_menu_item(MenuId::ExportAllTabs, "Export All Tabs...", None, None),
+            &create_menu_item(
+                MenuId::ExportAllTabsCombined,
+                "Export All Tabs as Single Document...",
+                None,
+                None,
+            ),
+            &create_menu_item(MenuId::ExportPdf, "Export as PDF...", None, None),
+            &PredefinedMenuItem::separator(),
             &create_menu_item(MenuId::CloseTab, "Close Tab", Some(Code::KeyW), None),
             &create_menu_item(MenuId::CloseAllTabs, "Close All Tabs", None, None),
             &create_menu_item(
@@ -207,6 +294,33 @@ fn add_view_menu(menu: &Menu) {
                 Some(Code::KeyB),
                 None,
             ),
+            &create_menu_item(
+                MenuId::ToggleShowAllFiles,
+                "Show All Files in Sidebar",
+                Some(Code::Period),
+                Some(Modifiers::SHIFT),
+            ),
+            &create_menu_item(
+                MenuId::ToggleRightSidebar,
+                "Toggle Table of Contents",
+                Some(Code::KeyB),
+                Some(Modifiers::SHIFT),
+            ),
+            &right_sidebar_menu(),
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::CycleTheme,
+                "Cycle Theme",
+                Some(Code::KeyL),
+                Some(Modifiers::SHIFT),
+            ),
+            &reader_profile_menu(),
+            &create_menu_item(
+                MenuId::ToggleSplitPreview,
+                "Toggle Split Preview",
+                Some(Code::KeyP),
+                Some(Modifiers::SHIFT),
+            ),
             &PredefinedMenuItem::separator(),
             &create_menu_item(MenuId::ActualSize, "Actual Size", Some(Code::Digit0), None),
             &create_menu_item(MenuId::ZoomIn, "Zoom In", Some(Code::Equal), None),
@@ -217,6 +331,102 @@ fn add_view_menu(menu: &Menu) {
     menu.append(&view_menu).unwrap();
 }
 
+/// "Reader Profile" submenu listing the configured profiles
+///
+/// The menu is built once at startup, so profiles added later appear after
+/// a restart (the "Next Profile" shortcut always uses the current config).
+fn reader_profile_menu() -> Submenu {
+    let submenu = Submenu::new("Reader Profile", true);
+    let presets = crate::config::CONFIG.read().reader_profiles.presets.clone();
+
+    for (index, profile) in presets.iter().enumerate() {
+        let id = format!("{READER_PROFILE_ID_PREFIX}{index}");
+        submenu
+            .append(&MenuItem::with_id(id, &profile.name, true, None))
+            .unwrap();
+    }
+    if presets.is_empty() {
+        submenu
+            .append(&MenuItem::new("No Profiles Configured", false, None))
+            .unwrap();
+    }
+
+    submenu
+        .append_items(&[
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::NextReaderProfile,
+                "Next Profile",
+                Some(Code::KeyP),
+                Some(Modifiers::ALT),
+            ),
+        ])
+        .unwrap();
+
+    submenu
+}
+
+/// "Right Sidebar" submenu selecting the right sidebar's tabs
+fn right_sidebar_menu() -> Submenu {
+    let submenu = Submenu::new("Right Sidebar", true);
+
+    submenu
+        .append_items(&[
+            &create_menu_item(
+                MenuId::ShowContents,
+                "Show Table of Contents",
+                Some(Code::Digit1),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::ShowSearchInDocument,
+                "Show Search in Document",
+                Some(Code::Digit2),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::ShowTags,
+                "Show Tags",
+                Some(Code::Digit3),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::ShowLint,
+                "Show Lint",
+                Some(Code::Digit4),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::ShowNotes,
+                "Show Notes",
+                Some(Code::Digit5),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::ShowBacklinks,
+                "Show Backlinks",
+                Some(Code::Digit6),
+                Some(Modifiers::ALT),
+            ),
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::NextRightSidebarTab,
+                "Next Tab",
+                Some(Code::BracketRight),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::PreviousRightSidebarTab,
+                "Previous Tab",
+                Some(Code::BracketLeft),
+                Some(Modifiers::ALT),
+            ),
+        ])
+        .unwrap();
+
+    submenu
+}
+
 fn add_history_menu(menu: &Menu) {
     let history_menu = Submenu::new("History", true);
 
@@ -229,6 +439,19 @@ fn add_history_menu(menu: &Menu) {
                 Some(Code::BracketRight),
                 None,
             ),
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::NextFile,
+                "Next File in Directory",
+                Some(Code::ArrowDown),
+                Some(Modifiers::ALT),
+            ),
+            &create_menu_item(
+                MenuId::PreviousFile,
+                "Previous File in Directory",
+                Some(Code::ArrowUp),
+                Some(Modifiers::ALT),
+            ),
         ])
         .unwrap();
 
@@ -240,6 +463,19 @@ fn add_window_menu(menu: &Menu) {
 
     window_menu
         .append_items(&[
+            &create_menu_item(
+                MenuId::NextWindow,
+                "Cycle Through Windows",
+                Some(Code::Backquote),
+                None,
+            ),
+            &create_menu_item(
+                MenuId::PreviousWindow,
+                "Cycle Through Windows Backward",
+                Some(Code::Backquote),
+                Some(Modifiers::SHIFT),
+            ),
+            &PredefinedMenuItem::separator(),
             &create_menu_item(
                 MenuId::CloseAllChildWindows,
                 "Close All Child Windows",
@@ -327,6 +563,23 @@ pub fn handle_menu_event_global(event: &MenuEvent) -> bool {
         MenuId::CloseAllWindows => {
             window::close_all_main_windows();
         }
+        MenuId::Quit => {
+            quit();
+        }
+        MenuId::NextWindow => {
+            window::cycle_main_window_focus(true);
+        }
+        MenuId::PreviousWindow => {
+            window::cycle_main_window_focus(false);
+        }
+        MenuId::OpenConfigFile => match Config::ensure_file() {
+            Ok(path) => crate::utils::file_operations::open_in_editor(path),
+            Err(e) => tracing::error!(%e, "Failed to write configuration file"),
+        },
+        MenuId::OpenConfigFolder => match Config::ensure_file() {
+            Ok(path) => crate::utils::file_operations::reveal_in_finder(path),
+            Err(e) => tracing::error!(%e, "Failed to write configuration file"),
+        },
         MenuId::GoToHomepage => {
             let _ = open::that("https://github.com/arto-app/Arto");
         }
@@ -344,9 +597,25 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
         return false;
     }
 
-    let menu_id = event.id().0.as_ref();
+    let menu_id: &str = event.id().0.as_ref();
     tracing::debug!("State menu event (focused window): {}", menu_id);
 
+    if let Some(index) = menu_id
+        .strip_prefix(READER_PROFILE_ID_PREFIX)
+        .and_then(|index| index.parse::<usize>().ok())
+    {
+        let profile = crate::config::CONFIG
+            .read()
+            .reader_profiles
+            .presets
+            .get(index)
+            .cloned();
+        if let Some(profile) = profile {
+            state.apply_reader_profile(profile);
+        }
+        return true;
+    }
+
     let id = match MenuId::from_str(menu_id) {
         Some(id) => id,
         None => return false,
@@ -391,18 +660,63 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
         MenuId::ToggleSidebar => {
             state.toggle_sidebar();
         }
+        MenuId::ToggleShowAllFiles => {
+            state.toggle_show_all_files();
+            if !state.sidebar.read().open {
+                let message = if state.sidebar.read().show_all_files {
+                    "Sidebar shows all files"
+                } else {
+                    "Sidebar shows markdown files only"
+                };
+                state.show_toast(message);
+            }
+        }
+        MenuId::ToggleRightSidebar => {
+            state.toggle_right_sidebar();
+        }
+        MenuId::ShowContents => {
+            state.show_right_sidebar_tab(RightSidebarTab::Contents);
+        }
+        MenuId::ShowSearchInDocument => {
+            state.show_right_sidebar_tab(RightSidebarTab::Search);
+        }
+        MenuId::ShowTags => {
+            state.show_right_sidebar_tab(RightSidebarTab::Tags);
+        }
+        MenuId::ShowBacklinks => {
+            state.show_right_sidebar_tab(RightSidebarTab::Backlinks);
+        }
+        MenuId::ShowLint => {
+            state.show_right_sidebar_tab(RightSidebarTab::Lint);
+        }
+        MenuId::ShowNotes => {
+            state.show_right_sidebar_tab(RightSidebarTab::Notes);
+        }
+        MenuId::NextRightSidebarTab => {
+            state.cycle_right_sidebar_tab(true);
+        }
+        MenuId::PreviousRightSidebarTab => {
+            state.cycle_right_sidebar_tab(false);
+        }
+        MenuId::CycleTheme => {
+            state.cycle_theme();
+        }
+        MenuId::NextReaderProfile => {
+            state.cycle_reader_profile();
+        }
+        MenuId::ToggleSplitPreview => {
+            state.toggle_split_preview();
+        }
         MenuId::ActualSize => {
-            state.zoom_level.set(1.0);
+            state.zoom_to(1.0);
         }
         MenuId::ZoomIn => {
             let current = normalize_zoom_level(*state.zoom_level.read());
-            let next = normalize_zoom_level(current + 0.1);
-            state.zoom_level.set(next);
+            state.zoom_to(current + 0.1);
         }
         MenuId::ZoomOut => {
             let current = normalize_zoom_level(*state.zoom_level.read());
-            let next = normalize_zoom_level(current - 0.1);
-            state.zoom_level.set(next);
+            state.zoom_to(current - 0.1);
         }
         MenuId::GoBack => {
             state.save_scroll_and_go_back();
@@ -410,6 +724,12 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
         MenuId::GoForward => {
             state.save_scroll_and_go_forward();
         }
+        MenuId::NextFile => {
+            state.open_sibling_file(true);
+        }
+        MenuId::PreviousFile => {
+            state.open_sibling_file(false);
+        }
         MenuId::RevealInFinder => {
             if let Some(file) = get_current_file(state) {
                 crate::utils::file_operations::reveal_in_finder(&file);
@@ -420,6 +740,15 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
                 crate::utils::clipboard::copy_text(file.to_string_lossy());
             }
         }
+        MenuId::ExportAllTabs => {
+            export_all_tabs(state, false);
+        }
+        MenuId::ExportAllTabsCombined => {
+            export_all_tabs(state, true);
+        }
+        MenuId::ExportPdf => {
+            export_pdf(state);
+        }
         MenuId::Find => {
             // None = get selected text from JavaScript
             state.open_search_with_text(None);
@@ -443,6 +772,154 @@ fn get_current_file(state: &AppState) -> Option<PathBuf> {
     })
 }
 
+/// Export every open file tab as HTML, either one file per tab into a chosen
+/// directory or combined into a single document
+///
+/// Runs on a background thread; progress and the final result are shown as toasts.
+fn export_all_tabs(state: &mut AppState, combined: bool) {
+    let files: Vec<PathBuf> = state
+        .tabs
+        .read()
+        .iter()
+        .filter_map(|tab| match &tab.content {
+            crate::state::TabContent::File(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    if files.is_empty() {
+        state.show_toast("No file tabs to export");
+        return;
+    }
+
+    let target = if combined {
+        pick_export_file()
+    } else {
+        pick_directory()
+    };
+    let Some(target) = target else {
+        return;
+    };
+
+    // Exports can't rely on the fonts installed here, so a reader profile font
+    // is only reported
+    let profile_font = state
+        .reader_profile
+        .read()
+        .as_ref()
+        .and_then(|profile| profile.font_family.clone());
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let mut css = crate::assets::main_style_css();
+        let mut warnings = Vec::new();
+        let (embed_fonts, fonts) = {
+            let config = crate::config::CONFIG.read();
+            (config.export.embed_fonts, config.fonts.clone())
+        };
+        if embed_fonts {
+            let (font_css, font_warnings) = export_fonts_css(&fonts, profile_font.as_deref());
+            css.push_str(&font_css);
+            warnings = font_warnings;
+        }
+        let progress = |done, total| {
+            progress_tx.send((done, total)).ok();
+        };
+        let mut report = if combined {
+            crate::export::export_combined(&files, &target, &css, progress)
+        } else {
+            crate::export::export_each(&files, &target, &css, progress)
+        };
+        report.warnings = warnings;
+        done_tx.send(report).ok();
+    });
+
+    let mut state = *state;
+    dioxus::prelude::spawn(async move {
+        while let Some((done, total)) = progress_rx.recv().await {
+            state.show_toast(format!("Exporting {}/{}...", done, total));
+        }
+        if let Ok(report) = done_rx.await {
+            state.show_toast(report.summary());
+        }
+    });
+}
+
+/// Export the current file tab as a PDF of the rendered document
+fn export_pdf(state: &mut AppState) {
+    let Some(file) = get_current_file(state) else {
+        state.show_toast("No file to export");
+        return;
+    };
+    let Some(target) = pick_pdf_file(&file) else {
+        return;
+    };
+
+    let job = match crate::export::export_pdf(&window().webview, &file, &target) {
+        Ok(job) => job,
+        Err(error) => {
+            tracing::warn!(?error, "Failed to export PDF");
+            state.show_toast(format!("PDF export failed: {}", error));
+            return;
+        }
+    };
+    let name = target
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    state.show_toast(format!("Exporting {}...", name));
+
+    let headings = state.toc_headings.read().clone();
+    let mut state = *state;
+    dioxus::prelude::spawn(async move {
+        if !job.finish().await {
+            tracing::warn!(?target, "PDF print job failed");
+            state.show_toast(format!("PDF export failed: could not write {}", name));
+            return;
+        }
+        let outline =
+            tokio::task::spawn_blocking(move || crate::export::add_pdf_outline(&target, &headings))
+                .await;
+        match outline {
+            Ok(Ok(())) => state.show_toast(format!("Exported {}", name)),
+            Ok(Err(error)) => {
+                tracing::warn!(?error, "Failed to add PDF bookmarks");
+                state.show_toast(format!("Exported {} without bookmarks: {}", name, error));
+            }
+            Err(error) => {
+                tracing::warn!(?error, "Failed to add PDF bookmarks");
+                state.show_toast(format!("Exported {} without bookmarks", name));
+            }
+        }
+    });
+}
+
+/// Show save dialog for a PDF export, suggesting the document's name
+fn pick_pdf_file(file: &std::path::Path) -> Option<PathBuf> {
+    use rfd::FileDialog;
+
+    let directory = file
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
+    FileDialog::new()
+        .add_filter("PDF", &["pdf"])
+        .set_file_name(file.with_extension("pdf").file_name()?.to_string_lossy())
+        .set_directory(directory)
+        .save_file()
+}
+
+/// Show save dialog for a combined HTML export
+fn pick_export_file() -> Option<PathBuf> {
+    use rfd::FileDialog;
+
+    FileDialog::new()
+        .add_filter("HTML", &["html"])
+        .set_file_name("export.html")
+        .set_directory(std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")))
+        .save_file()
+}
+
 /// Show file picker dialog and return selected file
 fn pick_markdown_file() -> Option<PathBuf> {
     use rfd::FileDialog;
@@ -460,6 +937,135 @@ fn pick_markdown_file() -> Option<PathBuf> {
     file
 }
 
+/// Quit the app, first asking when `quit.confirm` is on and more windows or
+/// tabs are open than configured
+///
+/// Nothing is closed before the answer, so declining leaves every window as
+/// it was. On macOS the question is asked by `applicationShouldTerminate:`
+/// (see [`install_quit_confirmation`]), which also covers the Dock's Quit
+/// and logout.
+#[cfg(target_os = "macos")]
+fn quit() {
+    terminate_app();
+}
+
+#[cfg(not(target_os = "macos"))]
+fn quit() {
+    if !should_quit() {
+        return;
+    }
+    terminate_app();
+}
+
+/// Whether quitting may go ahead, asking the user when it needs confirmation
+fn should_quit() -> bool {
+    let (windows, tabs) = window::count_open_windows_and_tabs();
+    let needs_confirmation = crate::config::CONFIG
+        .read()
+        .quit
+        .needs_confirmation(windows, tabs);
+    if needs_confirmation && !confirm_quit(windows, tabs) {
+        tracing::debug!("Quit cancelled");
+        return false;
+    }
+    true
+}
+
+/// Ask for confirmation whenever the app is about to terminate
+///
+/// tao's application delegate doesn't implement `applicationShouldTerminate:`,
+/// so it is added to the delegate's class. Every quit path (the menu item,
+/// the Dock, logout and shutdown) goes through it. Call once the event loop
+/// has started, as the delegate is set up then.
+#[cfg(target_os = "macos")]
+pub fn install_quit_confirmation() {
+    use objc2::runtime::{AnyObject, Imp, Sel};
+    use objc2::{ffi, sel, MainThreadMarker};
+    use objc2_app_kit::{NSApplication, NSApplicationTerminateReply};
+
+    extern "C-unwind" fn should_terminate(
+        _this: &AnyObject,
+        _cmd: Sel,
+        _sender: &AnyObject,
+    ) -> NSApplicationTerminateReply {
+        if should_quit() {
+            NSApplicationTerminateReply::TerminateNow
+        } else {
+            NSApplicationTerminateReply::TerminateCancel
+        }
+    }
+
+    let marker = MainThreadMarker::new().expect("Failed to get main thread marker");
+    let Some(delegate) = NSApplication::sharedApplication(marker).delegate() else {
+        tracing::warn!("No application delegate; quitting won't ask for confirmation");
+        return;
+    };
+    let delegate: &AnyObject = delegate.as_ref();
+    let should_terminate: extern "C-unwind" fn(&AnyObject, Sel, &AnyObject) -> _ = should_terminate;
+    // SAFETY: the function matches the selector's signature, which the type
+    // encoding ("NSUInteger, self, _cmd, id") describes.
+    let added = unsafe {
+        ffi::class_addMethod(
+            delegate.class() as *const _ as *mut _,
+            sel!(applicationShouldTerminate:),
+            std::mem::transmute::<_, Imp>(should_terminate),
+            c"Q@:@".as_ptr(),
+        )
+    };
+    if !added.as_bool() {
+        tracing::warn!("applicationShouldTerminate: is already implemented; not replacing it");
+    }
+}
+
+fn confirm_quit(windows: usize, tabs: usize) -> bool {
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Quit Arto?")
+        .set_description(quit_summary(windows, tabs))
+        .set_buttons(rfd::MessageButtons::OkCancelCustom(
+            "Quit".to_string(),
+            "Cancel".to_string(),
+        ))
+        .show();
+    match answer {
+        rfd::MessageDialogResult::Ok => true,
+        rfd::MessageDialogResult::Custom(label) => label == "Quit",
+        _ => false,
+    }
+}
+
+/// Description of what quitting closes, e.g. "2 windows with 5 tabs will be closed."
+fn quit_summary(windows: usize, tabs: usize) -> String {
+    let plural = |count: usize, noun: &str| {
+        if count == 1 {
+            format!("1 {noun}")
+        } else {
+            format!("{count} {noun}s")
+        }
+    };
+    format!(
+        "{} with {} will be closed.",
+        plural(windows, "window"),
+        plural(tabs, "tab")
+    )
+}
+
+/// Terminate the app the way the standard Quit item does, so the event loop
+/// ends normally and cleans up after itself
+#[cfg(target_os = "macos")]
+fn terminate_app() {
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::NSApplication;
+    let marker = MainThreadMarker::new().expect("Failed to get main thread marker");
+    NSApplication::sharedApplication(marker).terminate(None);
+}
+
+#[cfg(not(target_os = "macos"))]
+fn terminate_app() {
+    crate::ipc::cleanup_socket();
+    std::process::exit(0);
+}
+
 /// Show directory picker dialog and return selected directory
 fn pick_directory() -> Option<PathBuf> {
     use rfd::FileDialog;
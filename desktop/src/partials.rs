@@ -0,0 +1,155 @@
+//! Include-only "partial" documents.
+//!
+//! Partials are markdown files meant to be included in other documents rather
+//! than read on their own. They are recognized by a file name prefix or a
+//! frontmatter flag (see [`PartialsConfig`]). Frontmatter lookups are cached
+//! per file and revalidated by modification time.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+use crate::config::PartialsConfig;
+use crate::markdown::frontmatter_flag;
+use crate::utils::file::is_markdown_file;
+
+#[derive(Debug, Clone)]
+struct CachedFlag {
+    modified: SystemTime,
+    frontmatter_key: String,
+    partial: bool,
+}
+
+static PARTIAL_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedFlag>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Check whether a markdown file on disk is a partial
+pub fn is_partial_file(path: &Path, config: &PartialsConfig) -> bool {
+    if !config.enabled || !is_markdown_file(path) {
+        return false;
+    }
+    has_partial_prefix(path, &config.file_prefix)
+        || (!config.frontmatter_key.is_empty()
+            && frontmatter_marks_partial(path, &config.frontmatter_key))
+}
+
+/// Check whether a document is a partial, given its already loaded content
+pub fn is_partial_document(path: &Path, markdown: &str, config: &PartialsConfig) -> bool {
+    config.enabled
+        && (has_partial_prefix(path, &config.file_prefix)
+            || (!config.frontmatter_key.is_empty()
+                && frontmatter_flag(markdown, &config.frontmatter_key)))
+}
+
+/// Drop cached partial flags for every file under `dir`
+pub fn invalidate_partial_cache(dir: &Path) {
+    PARTIAL_CACHE
+        .lock()
+        .retain(|path, _| !path.starts_with(dir));
+}
+
+fn has_partial_prefix(path: &Path, prefix: &str) -> bool {
+    !prefix.is_empty()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(prefix))
+}
+
+/// Read the frontmatter flag of a file, using the cache when the file is unchanged
+fn frontmatter_marks_partial(path: &Path, frontmatter_key: &str) -> bool {
+    let Ok(modified) = fs::metadata(path).and_then(|meta| meta.modified()) else {
+        return false;
+    };
+
+    if let Some(cached) = PARTIAL_CACHE.lock().get(path) {
+        if cached.modified == modified && cached.frontmatter_key == frontmatter_key {
+            return cached.partial;
+        }
+    }
+
+    let partial = fs::read_to_string(path)
+        .map(|content| frontmatter_flag(&content, frontmatter_key))
+        .unwrap_or(false);
+    PARTIAL_CACHE.lock().insert(
+        path.to_path_buf(),
+        CachedFlag {
+            modified,
+            frontmatter_key: frontmatter_key.to_string(),
+            partial,
+        },
+    );
+    partial
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn enabled_config() -> PartialsConfig {
+        PartialsConfig {
+            enabled: true,
+            ..PartialsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_is_partial_file() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join("_header.md"), "# Header").unwrap();
+        fs::write(root.join("footer.md"), "---\npartial: true\n---\nFooter").unwrap();
+        fs::write(root.join("guide.md"), "---\npartial: false\n---\n# Guide").unwrap();
+        fs::write(root.join("_notes.txt"), "notes").unwrap();
+
+        let config = enabled_config();
+        assert!(is_partial_file(&root.join("_header.md"), &config));
+        assert!(is_partial_file(&root.join("footer.md"), &config));
+        assert!(!is_partial_file(&root.join("guide.md"), &config));
+        assert!(!is_partial_file(&root.join("_notes.txt"), &config));
+
+        // Nothing is a partial while detection is disabled
+        assert!(!is_partial_file(
+            &root.join("_header.md"),
+            &PartialsConfig::default()
+        ));
+    }
+
+    #[test]
+    fn test_is_partial_file_sees_updates_after_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let file = root.join("a.md");
+        let config = enabled_config();
+        fs::write(&file, "# A").unwrap();
+        assert!(!is_partial_file(&file, &config));
+
+        fs::write(&file, "---\npartial: true\n---\n# A").unwrap();
+        invalidate_partial_cache(root);
+        assert!(is_partial_file(&file, &config));
+    }
+
+    #[test]
+    fn test_is_partial_document() {
+        let config = enabled_config();
+        assert!(is_partial_document(
+            Path::new("/docs/_nav.md"),
+            "# Nav",
+            &config
+        ));
+        assert!(is_partial_document(
+            Path::new("/docs/nav.md"),
+            "---\npartial: true\n---\n# Nav",
+            &config
+        ));
+        assert!(!is_partial_document(
+            Path::new("/docs/nav.md"),
+            "# Nav",
+            &config
+        ));
+    }
+}
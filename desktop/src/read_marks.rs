@@ -0,0 +1,218 @@
+//! Read marks for the "dim already-read sections" reading aid.
+//!
+//! This module provides:
+//! - `ReadMark`: The furthest scroll position reached in one document
+//! - `ReadMarks`: Collection of read marks with persistence
+//! - `READ_MARKS`: Global static for app-wide access
+//! - `READ_MARKS_CHANGED`: Broadcast channel for cross-window sync
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+
+/// Maximum number of documents whose read mark is remembered
+const MAX_READ_MARKS: usize = 200;
+
+/// Scroll position recorded by "mark all read"; everything lies above it
+const FULLY_READ: f64 = f64::MAX;
+
+/// Furthest scroll position reached in a document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadMark {
+    /// Path to the document
+    pub path: PathBuf,
+    /// Furthest `scrollTop` of the content area, in pixels
+    pub furthest: f64,
+}
+
+/// Read marks storage (saved to read_marks.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReadMarks {
+    /// Read marks, most recently updated first
+    pub items: Vec<ReadMark>,
+}
+
+impl ReadMarks {
+    /// Get the read marks file path
+    fn path() -> PathBuf {
+        const FILENAME: &str = "read_marks.json";
+        if let Some(mut path) = dirs::data_local_dir() {
+            path.push("arto");
+            path.push(FILENAME);
+            return path;
+        }
+
+        // Fallback to home directory
+        if let Some(mut path) = dirs::home_dir() {
+            path.push(".arto");
+            path.push(FILENAME);
+            return path;
+        }
+
+        PathBuf::from(FILENAME)
+    }
+
+    /// Load read marks from file or return empty
+    pub fn load() -> Self {
+        let path = Self::path();
+
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save read marks to file
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+
+        tracing::debug!(path = %path.display(), count = self.items.len(), "Saving read marks");
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Get the furthest scroll position reached in a document (0 if unread)
+    pub fn furthest(&self, path: &Path) -> f64 {
+        self.items
+            .iter()
+            .find(|mark| mark.path == path)
+            .map_or(0.0, |mark| mark.furthest)
+    }
+
+    /// Record a scroll position, keeping only the furthest one
+    ///
+    /// Returns `true` if the read mark moved forward.
+    pub fn advance(&mut self, path: &Path, scroll: f64) -> bool {
+        if scroll <= self.furthest(path) {
+            return false;
+        }
+        self.set(path, scroll);
+        true
+    }
+
+    /// Mark the whole document as read
+    pub fn mark_read(&mut self, path: &Path) {
+        self.set(path, FULLY_READ);
+    }
+
+    /// Forget the read mark of a document
+    pub fn mark_unread(&mut self, path: &Path) {
+        self.items.retain(|mark| mark.path != path);
+    }
+
+    /// Store a read mark at the front, dropping the least recently updated
+    /// entries beyond [`MAX_READ_MARKS`]
+    fn set(&mut self, path: &Path, furthest: f64) {
+        self.items.retain(|mark| mark.path != path);
+        self.items.insert(
+            0,
+            ReadMark {
+                path: path.to_path_buf(),
+                furthest,
+            },
+        );
+        self.items.truncate(MAX_READ_MARKS);
+    }
+}
+
+/// Global read marks instance
+pub static READ_MARKS: LazyLock<RwLock<ReadMarks>> =
+    LazyLock::new(|| RwLock::new(ReadMarks::load()));
+
+/// Broadcast channel for read marks reset from the context menu
+///
+/// The payload is empty since subscribers should read from READ_MARKS directly.
+pub static READ_MARKS_CHANGED: LazyLock<broadcast::Sender<()>> =
+    LazyLock::new(|| broadcast::channel(10).0);
+
+/// Mark a document as read or unread, save, and broadcast the change
+pub fn set_document_read(path: &Path, read: bool) {
+    {
+        let mut marks = READ_MARKS.write();
+        if read {
+            marks.mark_read(path);
+        } else {
+            marks.mark_unread(path);
+        }
+        if let Err(e) = marks.save() {
+            tracing::error!(?e, "Failed to save read marks");
+        }
+    }
+    READ_MARKS_CHANGED.send(()).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_marks_advance_keeps_furthest() {
+        let mut marks = ReadMarks::default();
+        let path = Path::new("/docs/guide.md");
+
+        assert_eq!(marks.furthest(path), 0.0);
+        assert!(marks.advance(path, 300.0));
+        // Scrolling back up does not move the mark
+        assert!(!marks.advance(path, 100.0));
+        assert!(marks.advance(path, 500.0));
+
+        assert_eq!(marks.furthest(path), 500.0);
+        assert_eq!(marks.items.len(), 1);
+    }
+
+    #[test]
+    fn test_read_marks_mark_read_and_unread() {
+        let mut marks = ReadMarks::default();
+        let path = Path::new("/docs/guide.md");
+
+        marks.mark_read(path);
+        assert_eq!(marks.furthest(path), FULLY_READ);
+        assert!(!marks.advance(path, 10_000.0));
+
+        marks.mark_unread(path);
+        assert_eq!(marks.furthest(path), 0.0);
+        assert!(marks.items.is_empty());
+    }
+
+    #[test]
+    fn test_read_marks_are_bounded() {
+        let mut marks = ReadMarks::default();
+        for i in 0..MAX_READ_MARKS + 5 {
+            marks.advance(&PathBuf::from(format!("/docs/{}.md", i)), 100.0);
+        }
+
+        assert_eq!(marks.items.len(), MAX_READ_MARKS);
+        // Least recently updated documents are dropped first
+        assert_eq!(marks.furthest(Path::new("/docs/0.md")), 0.0);
+        assert_eq!(
+            marks.furthest(&PathBuf::from(format!("/docs/{}.md", MAX_READ_MARKS + 4))),
+            100.0
+        );
+    }
+
+    #[test]
+    fn test_read_marks_roundtrip_fully_read() {
+        let mut marks = ReadMarks::default();
+        marks.mark_read(Path::new("/docs/guide.md"));
+
+        let json = serde_json::to_string(&marks).unwrap();
+        let parsed: ReadMarks = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, marks);
+    }
+}
@@ -0,0 +1,1099 @@
+//! Best-effort rendering of reStructuredText documents.
+//!
+//! reStructuredText is converted to Markdown and rendered by the markdown
+//! pipeline, so relative links and images, alerts, the table of contents and
+//! the split preview behave as they do for `.md` files. The conversion keeps
+//! source lines in place where it can (a title's underline becomes a blank
+//! line, a directive line becomes a code fence), so `data-source-line` keeps
+//! pointing into the `.rst` file.
+//!
+//! Supported: section titles, paragraphs with inline markup and roles,
+//! bullet/enumerated/definition/field lists, literal and doctest blocks,
+//! code, image, figure, admonition and toctree directives, grid and simple
+//! tables, footnotes, hyperlink targets and transitions. Comments and `raw`
+//! directives are dropped; other directives show their content as text.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::markdown::{render_to_html_with_toc, HeadingInfo};
+
+/// Banner shown above rendered reStructuredText documents
+const RST_BANNER: &str = r#"<div class="rst-banner" role="note">reStructuredText is rendered on a best-effort basis; some constructs may be shown as plain text.</div>"#;
+
+/// Characters that can adorn section titles and transitions
+const ADORNMENT_CHARS: &str = "=-`:'\"~^_*+#<>.";
+
+/// Render reStructuredText to HTML with TOC heading extraction
+pub fn render_rst_to_html_with_toc(
+    rst: &str,
+    base_path: impl AsRef<Path>,
+) -> Result<(String, Vec<HeadingInfo>)> {
+    let markdown = rst_to_markdown(rst);
+    let (html, headings) = render_to_html_with_toc(&markdown, base_path)?;
+    Ok((format!("{}\n{}", RST_BANNER, html), headings))
+}
+
+/// Convert reStructuredText to Markdown
+pub fn rst_to_markdown(rst: &str) -> String {
+    let lines: Vec<&str> = rst.lines().collect();
+    let targets = collect_targets(&lines);
+    let mut out = convert(&lines, &targets);
+    // Keep a trailing closing fence on its own line
+    out.push(String::new());
+    out.join("\n")
+}
+
+/// Names of hyperlink targets (`.. _name: url`), lowercased
+fn collect_targets(lines: &[&str]) -> HashSet<String> {
+    lines
+        .iter()
+        .filter_map(|line| parse_target(line.trim_start()))
+        .map(|(name, _)| name.to_lowercase())
+        .collect()
+}
+
+/// Parse a hyperlink target (`.. _name: url`) into its name and URL
+fn parse_target(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(".. _")?;
+    let rest = rest.strip_prefix('`').unwrap_or(rest);
+    let colon = rest.find(':')?;
+    let name = rest[..colon].trim_end_matches('`');
+    (!name.is_empty()).then(|| (name, rest[colon + 1..].trim()))
+}
+
+/// Convert a block of lines, keeping one output line per input line where possible
+fn convert(lines: &[&str], targets: &HashSet<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut title_styles: Vec<(char, bool)> = Vec::new();
+    // Content column of the current list item, for continuation lines
+    let mut list_indent: Option<usize> = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        let indent = indentation(line);
+        let text = line.trim_start();
+        let after_blank = i == 0 || is_blank(lines[i - 1]);
+
+        if text.is_empty() {
+            out.push(String::new());
+            i += 1;
+            continue;
+        }
+
+        // Section title with overline
+        if indent == 0 && after_blank {
+            if let (Some(c), Some(title), Some(under)) =
+                (adornment_char(line), lines.get(i + 1), lines.get(i + 2))
+            {
+                if !is_blank(title) && adornment_char(under) == Some(c) {
+                    let level = heading_level(&mut title_styles, c, true);
+                    out.push(String::new());
+                    out.push(heading(level, title.trim(), targets));
+                    out.push(String::new());
+                    list_indent = None;
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+
+        // Section title with underline
+        if indent == 0 && after_blank && adornment_char(line).is_none() {
+            if let Some(c) = lines.get(i + 1).and_then(|next| adornment_char(next)) {
+                if lines[i + 1].trim_end().chars().count() >= text.chars().count() {
+                    let level = heading_level(&mut title_styles, c, false);
+                    out.push(heading(level, text, targets));
+                    out.push(String::new());
+                    list_indent = None;
+                    i += 2;
+                    continue;
+                }
+            }
+        }
+
+        // Transition
+        if indent == 0
+            && after_blank
+            && adornment_char(line).is_some()
+            && text.chars().count() >= 4
+            && lines.get(i + 1).is_none_or(|next| is_blank(next))
+        {
+            out.push("***".to_string());
+            i += 1;
+            continue;
+        }
+
+        // Explicit markup: directives, comments, targets and footnotes
+        if text == ".." || text.starts_with(".. ") {
+            i = convert_explicit_markup(lines, i, targets, &mut out);
+            continue;
+        }
+
+        // Tables
+        if indent == 0 && is_grid_border(text) {
+            i = convert_grid_table(lines, i, targets, &mut out);
+            continue;
+        }
+        if indent == 0 && after_blank && is_simple_table_border(text) {
+            i = convert_simple_table(lines, i, targets, &mut out);
+            continue;
+        }
+
+        // Doctest block
+        if after_blank && text.starts_with(">>>") {
+            let end = paragraph_end(lines, i);
+            i = push_fenced(&mut out, lines, i, i, end, "pycon", indent);
+            continue;
+        }
+
+        let item_indent = list_item_content_indent(text).map(|width| indent + width);
+        if let Some(content_indent) = item_indent {
+            list_indent = Some(content_indent);
+        } else if list_indent.is_some_and(|list| indent < list) {
+            list_indent = None;
+        }
+
+        // Block quote: indented text outside of a list item
+        if indent > 0 && item_indent.is_none() && list_indent.is_none() {
+            let end = block_end(lines, i, indent - 1);
+            let inner = dedent(&lines[i..end]);
+            push_quoted(&mut out, convert(&inner, targets));
+            i = end;
+            continue;
+        }
+
+        // Definition list: a term directly followed by an indented definition
+        if item_indent.is_none() && after_blank && !text.ends_with("::") {
+            if let Some(next) = lines.get(i + 1) {
+                if !is_blank(next) && indentation(next) > indent {
+                    let end = (i + 1..lines.len())
+                        .find(|&j| is_blank(lines[j]) || indentation(lines[j]) <= indent)
+                        .unwrap_or(lines.len());
+                    out.push(format!(
+                        "{}{}",
+                        " ".repeat(indent),
+                        convert_inline(text, targets)
+                    ));
+                    for (offset, definition) in lines[i + 1..end].iter().enumerate() {
+                        let marker = if offset == 0 { ": " } else { "  " };
+                        out.push(format!(
+                            "{}{}{}",
+                            " ".repeat(indent),
+                            marker,
+                            convert_inline(definition.trim(), targets)
+                        ));
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+        }
+
+        // Paragraph line, possibly introducing a literal block
+        let (text, literal) = match text.strip_suffix("::") {
+            Some("") => ("", true),
+            Some(rest) if rest.ends_with(' ') => (rest.trim_end(), true),
+            Some(rest) => (&text[..rest.len() + 1], true),
+            None => (text, false),
+        };
+        out.push(format!(
+            "{}{}",
+            " ".repeat(indent),
+            convert_line(text, targets)
+        ));
+        i += 1;
+
+        if literal {
+            let base = item_indent.or(list_indent).unwrap_or(indent);
+            let start = (i..lines.len()).find(|&j| !is_blank(lines[j]));
+            if let Some(start) = start.filter(|&start| indentation(lines[start]) > indent) {
+                let end = block_end(lines, start, indent);
+                i = push_fenced(&mut out, lines, i, start, end, "", base);
+            }
+        }
+    }
+
+    out
+}
+
+/// Convert a directive, comment, hyperlink target or footnote starting at `i`
+///
+/// Returns the index of the first line after it.
+fn convert_explicit_markup(
+    lines: &[&str],
+    i: usize,
+    targets: &HashSet<String>,
+    out: &mut Vec<String>,
+) -> usize {
+    let line = lines[i].trim_end();
+    let indent = indentation(line);
+    let text = line.trim_start();
+    let end = block_end(lines, i + 1, indent);
+
+    // Hyperlink target
+    if let Some((name, url)) = parse_target(text) {
+        out.push(if url.is_empty() {
+            String::new()
+        } else {
+            format!("[{}]: {}", name, url)
+        });
+        return i + 1;
+    }
+
+    // Footnote
+    let body = text.strip_prefix("..").unwrap_or("").trim_start();
+    if let Some(rest) = body.strip_prefix('[') {
+        if let Some(close) = rest.find(']') {
+            let label = rest[..close].trim_start_matches('#');
+            if !label.is_empty() && !label.contains(char::is_whitespace) {
+                out.push(format!(
+                    "[^{}]: {}",
+                    label,
+                    convert_inline(rest[close + 1..].trim(), targets)
+                ));
+                for continuation in &lines[i + 1..end] {
+                    out.push(format!(
+                        "    {}",
+                        convert_inline(continuation.trim(), targets)
+                    ));
+                }
+                return end;
+            }
+        }
+    }
+
+    let Some((name, argument)) = body.split_once("::") else {
+        // Comment
+        out.extend((i..end).map(|_| String::new()));
+        return end;
+    };
+    let name = name.trim().to_lowercase();
+    let argument = argument.trim();
+
+    // Options directly below the directive line
+    let mut options = Vec::new();
+    let mut content_start = i + 1;
+    while content_start < end {
+        let option = lines[content_start].trim();
+        let Some(rest) = option.strip_prefix(':') else {
+            break;
+        };
+        let (key, value) = rest.split_once(':').unwrap_or((rest, ""));
+        options.push((key.trim().to_lowercase(), value.trim().to_string()));
+        content_start += 1;
+    }
+    let option = |key: &str| {
+        options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    let content_start = (content_start..end)
+        .find(|&j| !is_blank(lines[j]))
+        .unwrap_or(end);
+
+    match name.as_str() {
+        "code" | "code-block" | "sourcecode" => {
+            if content_start == end {
+                out.extend((i..end).map(|_| String::new()));
+                return end;
+            }
+            push_fenced(out, lines, i, content_start, end, argument, indent)
+        }
+        "image" | "figure" => {
+            let alt = option("alt").unwrap_or("");
+            out.push(format!(
+                "{}![{}]({})",
+                " ".repeat(indent),
+                alt.replace(['[', ']'], ""),
+                argument.replace(' ', "%20")
+            ));
+            out.extend((i + 1..content_start).map(|_| String::new()));
+            // A figure's caption and legend are shown as paragraphs
+            let caption = dedent(&lines[content_start..end]);
+            out.extend(convert(&caption, targets));
+            end
+        }
+        "note" | "tip" | "hint" | "important" | "warning" | "attention" | "caution" | "danger"
+        | "error" | "seealso" | "admonition" => {
+            let kind = match name.as_str() {
+                "tip" | "hint" => "TIP",
+                "important" => "IMPORTANT",
+                "warning" | "attention" => "WARNING",
+                "caution" | "danger" | "error" => "CAUTION",
+                _ => "NOTE",
+            };
+            let mut alert = format!("> [!{}]", kind);
+            if !argument.is_empty() {
+                let argument = convert_inline(argument, targets);
+                if name == "admonition" {
+                    alert.push_str(&format!("\n> **{}**", argument));
+                } else {
+                    alert.push_str(&format!("\n> {}", argument));
+                }
+            }
+            out.push(alert);
+            out.extend((i + 1..content_start).map(|_| ">".to_string()));
+            let content = dedent(&lines[content_start..end]);
+            push_quoted(out, convert(&content, targets));
+            end
+        }
+        "toctree" => {
+            out.extend((i..content_start).map(|_| String::new()));
+            for entry in &lines[content_start..end] {
+                let entry = entry.trim();
+                if entry.is_empty() {
+                    out.push(String::new());
+                    continue;
+                }
+                let (title, document) = match entry.rsplit_once('<') {
+                    Some((title, document)) if entry.ends_with('>') => {
+                        (title.trim(), document.trim_end_matches('>'))
+                    }
+                    _ => (entry, entry),
+                };
+                out.push(format!("- [{}]({})", title, document_link(document)));
+            }
+            end
+        }
+        // Raw HTML and the like is never passed through
+        "raw" | "include" | "meta" | "contents" | "highlight" | "index" | "toc" => {
+            out.extend((i..end).map(|_| String::new()));
+            end
+        }
+        _ => {
+            out.extend((i..content_start).map(|_| String::new()));
+            let content = dedent(&lines[content_start..end]);
+            out.extend(convert(&content, targets));
+            end
+        }
+    }
+}
+
+/// Convert a grid table (`+---+---+`) to a Markdown table
+///
+/// Tables with spanning cells are shown as preformatted text instead.
+fn convert_grid_table(
+    lines: &[&str],
+    i: usize,
+    targets: &HashSet<String>,
+    out: &mut Vec<String>,
+) -> usize {
+    let end = (i..lines.len())
+        .find(|&j| {
+            let text = lines[j].trim();
+            !(text.starts_with('+') || text.starts_with('|'))
+        })
+        .unwrap_or(lines.len());
+    let border = lines[i].trim_end();
+    let columns: Vec<usize> = border
+        .char_indices()
+        .filter(|&(_, c)| c == '+')
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut header_rows = None;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut current: Option<Vec<String>> = None;
+    for line in &lines[i..end] {
+        let line = line.trim_end();
+        if line.starts_with('+') {
+            if let Some(row) = current.take() {
+                rows.push(row);
+            }
+            if line.contains('=') && header_rows.is_none() {
+                header_rows = Some(rows.len());
+            }
+            continue;
+        }
+        // Cells must line up with the border, or the table has spans
+        let aligned = columns
+            .iter()
+            .all(|&col| line.get(col..col + 1) == Some("|"));
+        if !aligned || line.len() != border.len() {
+            return push_fenced(out, lines, i, i, end, "text", 0);
+        }
+        let row = current.get_or_insert_with(|| vec![String::new(); columns.len() - 1]);
+        for (cell, bounds) in row.iter_mut().zip(columns.windows(2)) {
+            let part = line[bounds[0] + 1..bounds[1]].trim();
+            if !part.is_empty() {
+                if !cell.is_empty() {
+                    cell.push(' ');
+                }
+                cell.push_str(part);
+            }
+        }
+    }
+    if let Some(row) = current.take() {
+        rows.push(row);
+    }
+
+    push_table(out, rows, header_rows.unwrap_or(0), end - i, targets);
+    end
+}
+
+/// Convert a simple table (`=====  =====`) to a Markdown table
+fn convert_simple_table(
+    lines: &[&str],
+    i: usize,
+    targets: &HashSet<String>,
+    out: &mut Vec<String>,
+) -> usize {
+    let border = lines[i].trim_end();
+    let mut starts = Vec::new();
+    let mut previous = ' ';
+    for (index, c) in border.char_indices() {
+        if c == '=' && previous == ' ' {
+            starts.push(index);
+        }
+        previous = c;
+    }
+
+    let mut borders = 1;
+    let mut header_rows = None;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut end = lines.len();
+    for (j, line) in lines.iter().enumerate().skip(i + 1) {
+        let line = line.trim_end();
+        if is_simple_table_border(line.trim_start()) {
+            borders += 1;
+            let closes = lines.get(j + 1).is_none_or(|next| is_blank(next));
+            if closes || borders == 3 {
+                end = j + 1;
+                break;
+            }
+            header_rows = Some(rows.len());
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let cells: Vec<String> = starts
+            .iter()
+            .enumerate()
+            .map(|(column, &start)| {
+                let stop = starts.get(column + 1).copied().unwrap_or(line.len());
+                line.get(start.min(line.len())..stop.min(line.len()))
+                    .unwrap_or("")
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        // A blank first column continues the previous row
+        match rows.last_mut() {
+            Some(row) if cells[0].is_empty() => {
+                for (cell, part) in row.iter_mut().zip(cells) {
+                    if !part.is_empty() {
+                        cell.push(' ');
+                        cell.push_str(&part);
+                    }
+                }
+            }
+            _ => rows.push(cells),
+        }
+    }
+
+    push_table(out, rows, header_rows.unwrap_or(0), end - i, targets);
+    end
+}
+
+/// Push a Markdown table taking up `height` lines
+fn push_table(
+    out: &mut Vec<String>,
+    mut rows: Vec<Vec<String>>,
+    header_rows: usize,
+    height: usize,
+    targets: &HashSet<String>,
+) {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if width == 0 {
+        out.extend((0..height).map(|_| String::new()));
+        return;
+    }
+    let row_line = |row: &[String]| {
+        let cells: Vec<String> = (0..width)
+            .map(|col| {
+                let cell = row.get(col).map(String::as_str).unwrap_or("");
+                convert_inline(cell, targets).replace('|', "\\|")
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    // Markdown tables have a single header row
+    let header = if header_rows == 0 {
+        vec![String::new(); width]
+    } else {
+        let mut header = rows.remove(0);
+        for row in rows.drain(..header_rows - 1) {
+            for (cell, part) in header.iter_mut().zip(row) {
+                if !part.is_empty() {
+                    cell.push(' ');
+                    cell.push_str(&part);
+                }
+            }
+        }
+        header
+    };
+
+    let start = out.len();
+    out.push(row_line(&header));
+    out.push(format!("|{}", " --- |".repeat(width)));
+    out.extend(rows.iter().map(|row| row_line(row)));
+    // Pad to the table's height so later lines stay in place
+    while out.len() < start + height {
+        out.push(String::new());
+    }
+}
+
+/// Push `lines[content_start..content_end]` as a fenced code block
+///
+/// `lines[from..content_start]` (the directive and blank lines before the
+/// content) become blank lines, the last of them holding the opening fence.
+/// The closing fence goes on the blank line after the content. Returns the
+/// index of the first line after the block.
+fn push_fenced(
+    out: &mut Vec<String>,
+    lines: &[&str],
+    from: usize,
+    content_start: usize,
+    content_end: usize,
+    info: &str,
+    base_indent: usize,
+) -> usize {
+    let content = dedent(&lines[content_start..content_end]);
+    let content_end = content_start
+        + content
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |last| last + 1);
+    let longest_run = content
+        .iter()
+        .map(|line| longest_backtick_run(line))
+        .max()
+        .unwrap_or(0);
+    let prefix = " ".repeat(base_indent);
+    let fence = format!("{}{}", prefix, "`".repeat(longest_run.max(2) + 1));
+    let opening = format!("{}{}", fence, info.trim());
+
+    if from < content_start {
+        out.extend((from..content_start - 1).map(|_| String::new()));
+        out.push(opening);
+    } else {
+        push_on_previous_line(out, opening);
+    }
+    for line in &content[..content_end - content_start] {
+        out.push(if line.is_empty() {
+            String::new()
+        } else {
+            format!("{}{}", prefix, line)
+        });
+    }
+    if lines.get(content_end).is_some_and(|line| is_blank(line)) {
+        out.push(fence);
+        content_end + 1
+    } else {
+        push_on_previous_line(out, fence);
+        content_end
+    }
+}
+
+/// Push converted lines as a block quote
+fn push_quoted(out: &mut Vec<String>, converted: Vec<String>) {
+    for line in converted {
+        let quoted: Vec<String> = line
+            .split('\n')
+            .map(|part| {
+                if part.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {}", part)
+                }
+            })
+            .collect();
+        out.push(quoted.join("\n"));
+    }
+}
+
+/// Add a line to the previous output line (or replace it if blank)
+fn push_on_previous_line(out: &mut Vec<String>, line: String) {
+    match out.last_mut() {
+        Some(last) if last.is_empty() => *last = line,
+        Some(last) => {
+            last.push('\n');
+            last.push_str(&line);
+        }
+        None => out.push(line),
+    }
+}
+
+fn longest_backtick_run(line: &str) -> usize {
+    line.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// Heading level for a title adornment, in order of first appearance
+fn heading_level(styles: &mut Vec<(char, bool)>, c: char, overline: bool) -> usize {
+    let position = styles
+        .iter()
+        .position(|&style| style == (c, overline))
+        .unwrap_or_else(|| {
+            styles.push((c, overline));
+            styles.len() - 1
+        });
+    (position + 1).min(6)
+}
+
+fn heading(level: usize, title: &str, targets: &HashSet<String>) -> String {
+    format!("{} {}", "#".repeat(level), convert_inline(title, targets))
+}
+
+/// The character of a title adornment or transition line (`=====`)
+fn adornment_char(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let c = line.chars().next()?;
+    (ADORNMENT_CHARS.contains(c) && line.chars().count() >= 2 && line.chars().all(|ch| ch == c))
+        .then_some(c)
+}
+
+fn is_grid_border(text: &str) -> bool {
+    text.len() >= 3
+        && text.starts_with('+')
+        && text.ends_with('+')
+        && text.chars().all(|c| matches!(c, '+' | '-' | '='))
+}
+
+/// A simple table border has several runs of `=` separated by spaces
+fn is_simple_table_border(text: &str) -> bool {
+    text.chars().all(|c| c == '=' || c == ' ') && text.split_whitespace().count() >= 2
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Index of the first line from `start` on that is non-blank and indented at
+/// most `indent`, with trailing blank lines left out of the block
+fn block_end(lines: &[&str], start: usize, indent: usize) -> usize {
+    let mut end = (start..lines.len())
+        .find(|&j| !is_blank(lines[j]) && indentation(lines[j]) <= indent)
+        .unwrap_or(lines.len());
+    while end > start && is_blank(lines[end - 1]) {
+        end -= 1;
+    }
+    end
+}
+
+fn paragraph_end(lines: &[&str], start: usize) -> usize {
+    (start..lines.len())
+        .find(|&j| is_blank(lines[j]))
+        .unwrap_or(lines.len())
+}
+
+/// Remove the common indentation of non-blank lines
+fn dedent<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let common = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(common..).unwrap_or("").trim_end())
+        .collect()
+}
+
+/// Width of a list item marker including the space after it
+fn list_item_content_indent(text: &str) -> Option<usize> {
+    let marker_end = text.find(' ').unwrap_or(text.len());
+    let marker = &text[..marker_end];
+    let is_marker = matches!(marker, "-" | "*" | "+" | "•")
+        || marker == "#."
+        || marker
+            .strip_suffix('.')
+            .or_else(|| marker.strip_suffix(')'))
+            .map(|m| m.strip_prefix('(').unwrap_or(m))
+            .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    (is_marker && marker_end < text.len()).then(|| marker_end + 1)
+}
+
+/// Convert a paragraph line, keeping Markdown from reading it as other syntax
+fn convert_line(text: &str, targets: &HashSet<String>) -> String {
+    // Field list entry (`:Author: Name`)
+    if let Some(rest) = text.strip_prefix(':') {
+        if let Some((field, value)) = rest.split_once(": ").or_else(|| {
+            rest.strip_suffix(':')
+                .filter(|field| !field.contains(':'))
+                .map(|field| (field, ""))
+        }) {
+            if !field.is_empty() && !field.contains('`') {
+                return format!(
+                    "**{}:** {}\\",
+                    convert_inline(field, targets),
+                    convert_inline(value, targets)
+                );
+            }
+        }
+    }
+    // Line block (`| text`)
+    if let Some(rest) = text
+        .strip_prefix("| ")
+        .or(if text == "|" { Some("") } else { None })
+    {
+        return format!("{}\\", convert_inline(rest, targets));
+    }
+    // Auto-numbered and parenthesized enumerations
+    if let Some(rest) = text.strip_prefix("#. ") {
+        return format!("1. {}", convert_inline(rest, targets));
+    }
+    if let Some((number, rest)) = text
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once(") "))
+        .filter(|(number, _)| number.chars().all(|c| c.is_ascii_digit()))
+    {
+        return format!("{}. {}", number, convert_inline(rest, targets));
+    }
+
+    let converted = convert_inline(text, targets);
+    if converted.starts_with(['#', '>']) {
+        format!("\\{}", converted)
+    } else {
+        converted
+    }
+}
+
+/// Convert inline markup: literals, roles, interpreted text, hyperlink and
+/// footnote references. Text outside code is HTML-escaped.
+fn convert_inline(text: &str, targets: &HashSet<String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        // Inline literal
+        if let Some(literal) = rest.strip_prefix("``") {
+            if let Some(close) = literal.find("``") {
+                out.push_str(&code_span(&literal[..close]));
+                rest = &literal[close + 2..];
+                continue;
+            }
+        }
+
+        // Role (`:role:`text``)
+        if let Some((role, content, after)) = parse_role(rest) {
+            out.push_str(&convert_role(role, content));
+            rest = after;
+            continue;
+        }
+
+        // Interpreted text and hyperlink references
+        if let Some(quoted) = rest.strip_prefix('`') {
+            if let Some(close) = quoted.find('`') {
+                let content = &quoted[..close];
+                let after = &quoted[close + 1..];
+                let (is_reference, after) = match after.strip_prefix("__") {
+                    Some(after) => (true, after),
+                    None => match after.strip_prefix('_') {
+                        Some(after) => (true, after),
+                        None => (false, after),
+                    },
+                };
+                out.push_str(&convert_interpreted(content, is_reference, targets));
+                rest = after;
+                continue;
+            }
+        }
+
+        // Plain text up to the next markup character
+        let next = rest[1..]
+            .find(['`', ':'])
+            .map_or(rest.len(), |index| index + 1);
+        out.push_str(&convert_plain(&rest[..next], targets));
+        rest = &rest[next..];
+    }
+
+    out
+}
+
+/// Parse a role at the start of `text` into its name, content and the rest
+fn parse_role(text: &str) -> Option<(&str, &str, &str)> {
+    let rest = text.strip_prefix(':')?;
+    let name_end = rest.find(":`")?;
+    let name = &rest[..name_end];
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+' | ':'))
+    {
+        return None;
+    }
+    let content = &rest[name_end + 2..];
+    let close = content.find('`')?;
+    Some((name, &content[..close], &content[close + 1..]))
+}
+
+fn convert_role(role: &str, content: &str) -> String {
+    let (title, target) = split_link(content);
+    match role {
+        "doc" => format!("[{}]({})", escape_text(title), document_link(target)),
+        "ref" | "term" | "abbr" | "sub" | "sup" | "subscript" | "superscript" | "title"
+        | "title-reference" => escape_text(title),
+        "emphasis" => format!("*{}*", escape_text(content)),
+        "strong" => format!("**{}**", escape_text(content)),
+        "math" => format!("${}$", content),
+        "download" => format!("[{}]({})", escape_text(title), target.replace(' ', "%20")),
+        // Literal roles and references to code objects (`:py:func:`, `:class:`)
+        _ => code_span(title),
+    }
+}
+
+/// Convert interpreted text (`` `text` ``) or a hyperlink reference (`` `text`_ ``)
+fn convert_interpreted(content: &str, is_reference: bool, targets: &HashSet<String>) -> String {
+    if !is_reference {
+        // The default role renders as a title reference (italics)
+        return format!("*{}*", escape_text(content));
+    }
+    let (title, target) = split_link(content);
+    if title != target || content.ends_with('>') {
+        if let Some(name) = target.strip_suffix('_') {
+            return format!("[{}][{}]", escape_text(title), name);
+        }
+        return format!("[{}]({})", escape_text(title), target.replace(' ', "%20"));
+    }
+    if targets.contains(&content.to_lowercase()) {
+        format!("[{}]", escape_text(content))
+    } else {
+        escape_text(content)
+    }
+}
+
+/// Split `title <target>` into its title and target
+fn split_link(content: &str) -> (&str, &str) {
+    match content.rsplit_once('<') {
+        Some((title, target)) if content.ends_with('>') && !title.trim().is_empty() => {
+            (title.trim(), target.trim_end_matches('>'))
+        }
+        Some(("", target)) if content.ends_with('>') => {
+            let target = target.trim_end_matches('>');
+            (target, target)
+        }
+        _ => (content, content),
+    }
+}
+
+/// Link to a Sphinx document name (`guide/intro` → `guide/intro.rst`)
+fn document_link(document: &str) -> String {
+    let document = document.trim().replace(' ', "%20");
+    if Path::new(&document).extension().is_some() {
+        document
+    } else {
+        format!("{}.rst", document)
+    }
+}
+
+/// Convert plain text: footnote and simple hyperlink references
+fn convert_plain(text: &str, targets: &HashSet<String>) -> String {
+    let mut out = String::new();
+    for (index, word) in text.split(' ').enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        let core = word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+        let trailing = &word[core.len()..];
+
+        // Footnote reference (`[1]_`, `[#note]_`)
+        if let Some(label) = core
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix("]_"))
+            .map(|label| label.trim_start_matches('#'))
+            .filter(|label| !label.is_empty())
+        {
+            out.push_str(&format!("[^{}]{}", label, escape_text(trailing)));
+            continue;
+        }
+
+        // Hyperlink reference (`name_`)
+        if let Some(name) = core.strip_suffix('_').filter(|name| !name.ends_with('_')) {
+            if targets.contains(&name.to_lowercase()) {
+                out.push_str(&format!("[{}]{}", escape_text(name), escape_text(trailing)));
+                continue;
+            }
+        }
+
+        out.push_str(&escape_text(word));
+    }
+    out
+}
+
+fn code_span(code: &str) -> String {
+    let ticks = "`".repeat(longest_backtick_run(code) + 1);
+    if code.starts_with('`') || code.ends_with('`') {
+        format!("{ticks} {code} {ticks}")
+    } else {
+        format!("{ticks}{code}{ticks}")
+    }
+}
+
+/// Escape text so Markdown shows it literally (no raw HTML)
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_rst_to_markdown_titles_keep_lines() {
+        let rst = indoc! {"
+            =====
+            Guide
+            =====
+
+            Intro
+            -----
+
+            Text with ``code`` and *emphasis*.
+
+            Details
+            -------
+        "};
+
+        assert_eq!(
+            rst_to_markdown(rst),
+            indoc! {"
+
+                # Guide
+
+
+                ## Intro
+
+
+                Text with `code` and *emphasis*.
+
+                ## Details
+
+            "}
+        );
+    }
+
+    #[test]
+    fn test_rst_to_markdown_literal_and_code_blocks() {
+        let rst = indoc! {"
+            Run this::
+
+                cargo build
+
+            .. code-block:: rust
+               :linenos:
+
+               fn main() {}
+
+            Done.
+        "};
+
+        assert_eq!(
+            rst_to_markdown(rst),
+            indoc! {"
+                Run this:
+                ```
+                cargo build
+                ```
+
+
+                ```rust
+                fn main() {}
+                ```
+                Done.
+            "}
+        );
+    }
+
+    #[test]
+    fn test_rst_to_markdown_links_and_roles() {
+        let rst = indoc! {"
+            See `the docs <https://example.com>`_, Arto_ and :doc:`intro`.
+            Call :func:`render` or :doc:`Setup <guide/setup>` [1]_.
+
+            .. _Arto: https://github.com/arto-app/Arto
+            .. [1] A footnote.
+        "};
+
+        let markdown = rst_to_markdown(rst);
+
+        assert!(markdown.contains("[the docs](https://example.com)"));
+        assert!(markdown.contains("[Arto] and [intro](intro.rst)."));
+        assert!(markdown.contains("Call `render` or [Setup](guide/setup.rst) [^1]."));
+        assert!(markdown.contains("[Arto]: https://github.com/arto-app/Arto"));
+        assert!(markdown.contains("[^1]: A footnote."));
+    }
+
+    #[test]
+    fn test_rst_to_markdown_admonitions_and_images() {
+        let rst = indoc! {"
+            .. note::
+
+               Keep ``this`` in mind.
+
+            .. image:: images/logo.png
+               :alt: Logo
+        "};
+
+        assert_eq!(
+            rst_to_markdown(rst),
+            indoc! {"
+                > [!NOTE]
+                >
+                > Keep `this` in mind.
+
+                ![Logo](images/logo.png)
+
+            "}
+        );
+    }
+
+    #[test]
+    fn test_rst_to_markdown_tables() {
+        let rst = indoc! {"
+            +------+-------+
+            | Name | Value |
+            +======+=======+
+            | a    | 1     |
+            +------+-------+
+
+            =====  =====
+            Name   Value
+            =====  =====
+            b      2
+            =====  =====
+        "};
+
+        let markdown = rst_to_markdown(rst);
+
+        assert!(markdown.contains("| Name | Value |\n| --- | --- |\n| a | 1 |"));
+        assert!(markdown.contains("| Name | Value |\n| --- | --- |\n| b | 2 |"));
+    }
+
+    #[test]
+    fn test_rst_to_markdown_drops_raw_html() {
+        let rst = indoc! {"
+            .. raw:: html
+
+               <script>alert(1)</script>
+
+            Text <b>not bold</b>.
+        "};
+
+        let markdown = rst_to_markdown(rst);
+
+        assert!(!markdown.contains("<script>"));
+        assert!(markdown.contains("Text &lt;b>not bold&lt;/b>."));
+    }
+}
@@ -0,0 +1,331 @@
+//! Sanitization of raw HTML embedded in documents.
+//!
+//! Inline `<svg>` is passed through by the markdown parser verbatim, and SVG
+//! can carry scripts (`<script>`, event handler attributes, `javascript:`
+//! links, HTML inside `<foreignObject>`). [`sanitize_inline_svg`] keeps only
+//! drawing elements and safe attributes inside `<svg>`, and makes the outer
+//! `<svg>` scale down to the content width.
+
+use lol_html::html_content::Element;
+use lol_html::{element, HtmlRewriter, Settings};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Class added to top-level inline SVGs so they can be scaled by CSS
+const INLINE_SVG_CLASS: &str = "inline-svg";
+
+/// Elements kept inside inline SVG (lowercase); everything else is removed
+/// together with its content
+const ALLOWED_SVG_ELEMENTS: &[&str] = &[
+    "svg",
+    "a",
+    "g",
+    "defs",
+    "desc",
+    "title",
+    "metadata",
+    "symbol",
+    "use",
+    "image",
+    "switch",
+    "path",
+    "rect",
+    "circle",
+    "ellipse",
+    "line",
+    "polyline",
+    "polygon",
+    "text",
+    "tspan",
+    "textpath",
+    "lineargradient",
+    "radialgradient",
+    "stop",
+    "pattern",
+    "clippath",
+    "mask",
+    "marker",
+    "filter",
+    "feblend",
+    "fecolormatrix",
+    "fecomponenttransfer",
+    "fecomposite",
+    "feconvolvematrix",
+    "fediffuselighting",
+    "fedisplacementmap",
+    "fedistantlight",
+    "fedropshadow",
+    "feflood",
+    "fefunca",
+    "fefuncb",
+    "fefuncg",
+    "fefuncr",
+    "fegaussianblur",
+    "feimage",
+    "femerge",
+    "femergenode",
+    "femorphology",
+    "feoffset",
+    "fepointlight",
+    "fespecularlighting",
+    "fespotlight",
+    "fetile",
+    "feturbulence",
+    "animate",
+    "animatemotion",
+    "animatetransform",
+    "set",
+    "mpath",
+];
+
+/// Raster image types allowed as `data:` URLs in SVG links
+const ALLOWED_DATA_IMAGE_PREFIXES: &[&str] = &[
+    "data:image/png",
+    "data:image/jpeg",
+    "data:image/gif",
+    "data:image/webp",
+];
+
+/// Remove scripting from inline SVG and mark top-level SVGs for scaling
+///
+/// HTML outside `<svg>` is returned unchanged.
+pub fn sanitize_inline_svg(html: &str) -> String {
+    if !contains_svg(html) {
+        return html.to_string();
+    }
+
+    let mut output = Vec::new();
+    let svg_depth = Rc::new(Cell::new(0usize));
+
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                element!("svg", |el| mark_svg_root(el, &svg_depth)),
+                element!("svg *", |el| {
+                    sanitize_svg_element(el);
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| {
+            output.extend_from_slice(chunk);
+        },
+    );
+
+    let _ = rewriter.write(html.as_bytes());
+    let _ = rewriter.end();
+    String::from_utf8(output).unwrap_or_default()
+}
+
+fn contains_svg(html: &str) -> bool {
+    html.as_bytes()
+        .windows(4)
+        .any(|window| window.eq_ignore_ascii_case(b"<svg"))
+}
+
+/// Sanitize an `<svg>` element's own attributes; the outermost one also gets
+/// the scaling class and a `viewBox` derived from its size when it has none
+///
+/// `depth` tracks the number of currently open `<svg>` elements.
+fn mark_svg_root(el: &mut Element, depth: &Rc<Cell<usize>>) -> lol_html::HandlerResult {
+    sanitize_attributes(el);
+
+    depth.set(depth.get() + 1);
+    if depth.get() == 1 {
+        let class = match el.get_attribute("class") {
+            Some(existing) => format!("{} {}", existing, INLINE_SVG_CLASS),
+            None => INLINE_SVG_CLASS.to_string(),
+        };
+        el.set_attribute("class", &class)?;
+
+        // Without a viewBox the drawing would be cropped instead of scaled
+        if !el.has_attribute("viewBox") {
+            let size = |name| {
+                el.get_attribute(name)
+                    .and_then(|value| value.trim().trim_end_matches("px").parse::<f64>().ok())
+            };
+            if let (Some(width), Some(height)) = (size("width"), size("height")) {
+                el.set_attribute("viewBox", &format!("0 0 {} {}", width, height))?;
+            }
+        }
+    }
+
+    let depth = Rc::clone(depth);
+    if let Some(handlers) = el.end_tag_handlers() {
+        handlers.push(Box::new(move |_| {
+            depth.set(depth.get().saturating_sub(1));
+            Ok(())
+        }));
+    }
+    Ok(())
+}
+
+fn sanitize_svg_element(el: &mut Element) {
+    let tag = el.tag_name().to_ascii_lowercase();
+    if !ALLOWED_SVG_ELEMENTS.contains(&tag.as_str()) || animates_unsafe_attribute(el, &tag) {
+        el.remove();
+        return;
+    }
+    sanitize_attributes(el);
+
+    // `<use>` may only reference shapes in the same document
+    if tag == "use" {
+        for name in ["href", "xlink:href"] {
+            if el
+                .get_attribute(name)
+                .is_some_and(|href| !href.trim_start().starts_with('#'))
+            {
+                el.remove_attribute(name);
+            }
+        }
+    }
+}
+
+/// Whether an animation element targets an event handler or a link, which
+/// could inject script after sanitization
+fn animates_unsafe_attribute(el: &Element, tag: &str) -> bool {
+    matches!(tag, "animate" | "set")
+        && el.get_attribute("attributeName").is_some_and(|name| {
+            let name = name.trim().to_ascii_lowercase();
+            name.starts_with("on") || name == "href" || name == "xlink:href"
+        })
+}
+
+/// Remove event handlers and script-bearing URLs from an element's attributes
+fn sanitize_attributes(el: &mut Element) {
+    let unsafe_attributes: Vec<String> = el
+        .attributes()
+        .iter()
+        .filter(|attr| !is_safe_attribute(&attr.name(), &attr.value()))
+        .map(|attr| attr.name())
+        .collect();
+    for name in unsafe_attributes {
+        el.remove_attribute(&name);
+    }
+}
+
+fn is_safe_attribute(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if name.starts_with("on") {
+        return false;
+    }
+    if name == "style" {
+        let value = value.to_ascii_lowercase();
+        return !value.contains("javascript:") && !value.contains("expression(");
+    }
+    if matches!(
+        name.as_str(),
+        "href" | "xlink:href" | "src" | "from" | "to" | "values"
+    ) {
+        return is_safe_url(value);
+    }
+    true
+}
+
+/// Reject `javascript:`-like schemes and `data:` URLs other than raster images
+fn is_safe_url(value: &str) -> bool {
+    // Browsers decode entities and ignore whitespace and control characters
+    // inside the scheme
+    let normalized: String = html_escape::decode_html_entities(value)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if normalized.starts_with("javascript:") || normalized.starts_with("vbscript:") {
+        return false;
+    }
+    if normalized.starts_with("data:") {
+        return ALLOWED_DATA_IMAGE_PREFIXES
+            .iter()
+            .any(|prefix| normalized.starts_with(prefix));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_without_svg_is_unchanged() {
+        let html = r#"<p onclick="x()">Hello <script>alert(1)</script></p>"#;
+        assert_eq!(sanitize_inline_svg(html), html);
+    }
+
+    #[test]
+    fn test_safe_svg_is_kept() {
+        let html = r##"<svg viewBox="0 0 10 10"><defs><linearGradient id="g"><stop offset="0" stop-color="red"/></linearGradient></defs><rect width="10" height="10" fill="url(#g)"/><use href="#g"/></svg>"##;
+        let result = sanitize_inline_svg(html);
+        assert!(result.contains(r#"<svg viewBox="0 0 10 10" class="inline-svg">"#));
+        assert!(result.contains(r#"<stop offset="0" stop-color="red"/>"#));
+        assert!(result.contains(r#"fill="url(#g)""#));
+        assert!(result.contains(r##"<use href="#g"/>"##));
+    }
+
+    #[test]
+    fn test_hostile_svg_scripts_are_removed() {
+        let html = indoc::indoc! {r#"
+            <svg width="100" height="50" onload="alert(1)">
+              <script>alert(2)</script>
+              <SCRIPT type="text/javascript">alert(3)</SCRIPT>
+              <foreignObject><body onload="alert(4)"><script>alert(5)</script></body></foreignObject>
+              <circle r="5" onmouseover="alert(6)" ONCLICK="alert(7)"/>
+              <iframe src="https://example.com"></iframe>
+              <style>body { display: none }</style>
+            </svg>
+        "#};
+        let result = sanitize_inline_svg(html);
+        assert!(!result.contains("alert"), "{result}");
+        assert!(!result.to_ascii_lowercase().contains("foreignobject"));
+        assert!(!result.contains("iframe"));
+        assert!(!result.contains("<style"));
+        assert!(result.contains(r#"<circle r="5" />"#), "{result}");
+    }
+
+    #[test]
+    fn test_hostile_svg_links_are_neutralized() {
+        let html = indoc::indoc! {r#"
+            <svg>
+              <a href="javascript:alert(1)"><text>one</text></a>
+              <a xlink:href=" java&#x09;script:alert(2)"><text>two</text></a>
+              <a href="data:text/html;base64,PHNjcmlwdD4="><text>three</text></a>
+              <image href="data:image/png;base64,iVBORw0KGgo="/>
+              <use href="https://evil.example/sprite.svg#icon"/>
+              <a href="https://example.com"><text>four</text></a>
+            </svg>
+        "#};
+        let result = sanitize_inline_svg(html);
+        assert!(!result.contains("javascript"), "{result}");
+        assert!(!result.contains("data:text/html"));
+        assert!(!result.contains("evil.example"));
+        assert!(result.contains(r#"<image href="data:image/png;base64,iVBORw0KGgo="/>"#));
+        assert!(result.contains(r#"<a href="https://example.com">"#));
+    }
+
+    #[test]
+    fn test_hostile_svg_animations_are_removed() {
+        let html = indoc::indoc! {r#"
+            <svg>
+              <a><set attributeName="href" to="javascript:alert(1)"/><text>x</text></a>
+              <animate attributeName="onbegin" to="alert(2)"/>
+              <circle r="5"><animate attributeName="r" from="5" to="10" dur="1s"/></circle>
+            </svg>
+        "#};
+        let result = sanitize_inline_svg(html);
+        assert!(!result.contains("alert"), "{result}");
+        assert!(result.contains(r#"<animate attributeName="r" from="5" to="10" dur="1s"/>"#));
+    }
+
+    #[test]
+    fn test_outer_svg_gets_scaling_class_and_view_box() {
+        let html = r#"<svg width="200px" height="100" class="logo"><svg width="10" height="10"></svg></svg>"#;
+        let result = sanitize_inline_svg(html);
+        // Attribute names are written in lowercase; the HTML parser restores
+        // the case of SVG attributes such as viewBox
+        assert_eq!(
+            result,
+            r#"<svg width="200px" height="100" class="logo inline-svg" viewbox="0 0 200 100"><svg width="10" height="10"></svg></svg>"#
+        );
+    }
+}
@@ -1,13 +1,22 @@
 use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
+use dioxus::desktop::window;
 use dioxus::prelude::*;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use crate::components::right_sidebar::RightSidebarTab;
-use crate::config::DEFAULT_RIGHT_SIDEBAR_WIDTH;
+use crate::config::{
+    OpenDirectoryBehavior, ReaderProfile, ZoomScrollAnchor, CONFIG, DEFAULT_RIGHT_SIDEBAR_WIDTH,
+};
+use crate::lint::LintIssue;
 use crate::markdown::HeadingInfo;
 use crate::pinned_search::PinnedSearchId;
 use crate::theme::Theme;
+use crate::utils::clipboard_detect::ClipboardMarkdown;
+use crate::utils::file::{resolve_symlink, single_markdown_file};
+use crate::window::main::{find_window_with_root_directory, focus_window};
+use crate::window::settings::normalize_zoom_level;
 
 mod sidebar;
 mod tabs;
@@ -15,6 +24,17 @@ mod tabs;
 pub use sidebar::Sidebar;
 pub use tabs::{Tab, TabContent};
 
+/// Transient notification shown over the content area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// Monotonic id so a newer toast isn't cleared by an older toast's timer
+    pub id: u64,
+    pub message: String,
+}
+
+/// How long a toast stays visible
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_millis(1500);
+
 /// Information about a single search match for display in the Search tab.
 #[derive(Debug, Clone, PartialEq)]
 pub struct SearchMatch {
@@ -57,11 +77,19 @@ pub struct AppState {
     pub active_tab: Signal<usize>,
     pub current_theme: Signal<Theme>,
     pub zoom_level: Signal<f64>,
+    /// Focal block captured before a zoom change, restored once it renders
+    pub pending_zoom_anchor: Signal<Option<String>>,
     pub sidebar: Signal<Sidebar>,
     pub right_sidebar_open: Signal<bool>,
     pub right_sidebar_width: Signal<f64>,
     pub right_sidebar_tab: Signal<RightSidebarTab>,
     pub toc_headings: Signal<Vec<HeadingInfo>>,
+    /// Id of the heading the reader is currently in (TOC scroll-spy)
+    pub active_heading: Signal<Option<String>>,
+    /// Lint issues of the current document (for the Lint tab)
+    pub lint_issues: Signal<Vec<LintIssue>>,
+    /// Modification time of the current file when it was last loaded (for the Header)
+    pub file_modified: Signal<Option<SystemTime>>,
     pub position: Signal<LogicalPosition<i32>>,
     pub size: Signal<LogicalSize<u32>>,
     // Search state (not persisted, managed via JavaScript for IME compatibility)
@@ -82,6 +110,15 @@ pub struct AppState {
     /// Current scroll position of the content area.
     /// Updated by scroll events, used to save position before back/forward navigation.
     pub current_scroll_position: Signal<f64>,
+    /// Currently visible toast notification (cleared automatically)
+    pub toast: Signal<Option<Toast>>,
+    /// Markdown detected on the clipboard, offered for opening until dismissed
+    pub clipboard_offer: Signal<Option<ClipboardMarkdown>>,
+    /// Frontmatter tag selected for cross-file filtering (shown in the Tags tab)
+    pub tag_filter: Signal<Option<String>>,
+    /// Reader profile last applied to this window (its font and content
+    /// width stay in effect until another profile is applied)
+    pub reader_profile: Signal<Option<ReaderProfile>>,
 }
 
 impl AppState {
@@ -93,11 +130,15 @@ impl AppState {
             active_tab: Signal::new(0),
             current_theme: Signal::new(theme),
             zoom_level: Signal::new(1.0),
+            pending_zoom_anchor: Signal::new(None),
             sidebar: Signal::new(Sidebar::default()),
             right_sidebar_open: Signal::new(false),
             right_sidebar_width: Signal::new(DEFAULT_RIGHT_SIDEBAR_WIDTH),
             right_sidebar_tab: Signal::new(RightSidebarTab::default()),
             toc_headings: Signal::new(Vec::new()),
+            active_heading: Signal::new(None),
+            lint_issues: Signal::new(Vec::new()),
+            file_modified: Signal::new(None),
             position: Signal::new(Default::default()),
             size: Signal::new(Default::default()),
             // Search state
@@ -110,6 +151,10 @@ impl AppState {
             pinned_matches: Signal::new(HashMap::new()),
             pending_scroll_position: Signal::new(None),
             current_scroll_position: Signal::new(0.0),
+            toast: Signal::new(None),
+            clipboard_offer: Signal::new(None),
+            tag_filter: Signal::new(None),
+            reader_profile: Signal::new(None),
         }
     }
 }
@@ -123,12 +168,40 @@ impl Default for AppState {
 impl AppState {
     /// Set the root directory and add to history
     /// Note: The directory is persisted to state file when window closes
+    ///
+    /// When configured to focus existing windows, a directory that is already
+    /// another window's root focuses that window instead.
     pub fn set_root_directory(&mut self, path: impl Into<PathBuf>) {
-        let path = path.into();
-        let mut sidebar = self.sidebar.write();
-        sidebar.root_directory = Some(path.clone());
-        sidebar.expanded_dirs.clear();
-        sidebar.push_to_history(path);
+        let (follow_symlinks, on_open_directory, open_single_file) = {
+            let config = CONFIG.read();
+            (
+                config.sidebar.follow_symlinks,
+                config.directory.on_open_directory,
+                config.directory.open_single_markdown_file,
+            )
+        };
+        let path = match resolve_symlink(path.into(), follow_symlinks) {
+            Ok(path) => path,
+            Err(reason) => {
+                self.show_toast(reason);
+                return;
+            }
+        };
+        if on_open_directory == OpenDirectoryBehavior::FocusExisting {
+            let current_window = window().id();
+            if let Some(window_id) = find_window_with_root_directory(&path, current_window) {
+                tracing::info!(?path, "Directory already open as root, focusing its window");
+                focus_window(window_id);
+                return;
+            }
+        }
+        self.sidebar.write().change_root_directory(path.clone());
+        // A single-document folder opens straight to its document
+        if open_single_file && self.is_current_tab_no_file() {
+            if let Some(file) = single_markdown_file(&path) {
+                self.open_file(file);
+            }
+        }
     }
 
     /// Set the root directory without adding to history (used for history navigation)
@@ -136,6 +209,7 @@ impl AppState {
         let mut sidebar = self.sidebar.write();
         sidebar.root_directory = Some(path);
         sidebar.expanded_dirs.clear();
+        sidebar.restore_view();
     }
 
     /// Go back in directory history
@@ -184,6 +258,30 @@ impl AppState {
         self.right_sidebar_tab.set(tab);
     }
 
+    /// Open the right sidebar on the given tab
+    pub fn show_right_sidebar_tab(&mut self, tab: RightSidebarTab) {
+        self.right_sidebar_tab.set(tab);
+        self.right_sidebar_open.set(true);
+    }
+
+    /// Switch the right sidebar to the next (or previous) tab, opening it if closed
+    pub fn cycle_right_sidebar_tab(&mut self, forward: bool) {
+        let current = *self.right_sidebar_tab.read();
+        let tab = if forward {
+            current.next()
+        } else {
+            current.previous()
+        };
+        self.show_right_sidebar_tab(tab);
+    }
+
+    /// Show files tagged with `tag` in the right sidebar's Tags tab
+    pub fn filter_by_tag(&mut self, tag: impl Into<String>) {
+        self.tag_filter.set(Some(tag.into()));
+        self.right_sidebar_tab.set(RightSidebarTab::Tags);
+        self.right_sidebar_open.set(true);
+    }
+
     /// Toggle search bar visibility
     ///
     /// Note: Does NOT clear search state when closing. Search highlights and
@@ -222,6 +320,99 @@ impl AppState {
         self.search_open.set(true);
     }
 
+    /// Advance this window's theme to the next variant.
+    ///
+    /// Only affects the current window; other windows keep their own theme.
+    pub fn cycle_theme(&mut self) {
+        let next = self.current_theme.read().next();
+        self.current_theme.set(next);
+        self.show_toast(format!("Theme: {}", next.label()));
+    }
+
+    /// Change this window's zoom level
+    ///
+    /// Unless `zoom.scrollAnchor` is "none", the block at the focal line is
+    /// captured first so the content view can scroll it back into place.
+    pub fn zoom_to(&mut self, level: f64) {
+        let level = normalize_zoom_level(level);
+        let anchor = CONFIG.read().zoom.scroll_anchor;
+        if anchor == ZoomScrollAnchor::None || level == *self.zoom_level.peek() {
+            self.zoom_level.set(level);
+            return;
+        }
+
+        let anchor = serde_json::to_string(&anchor).unwrap_or_default();
+        let mut state = *self;
+        spawn(async move {
+            let captured = document::eval(&format!(
+                "return window.Arto.zoomAnchor.capture({});",
+                anchor
+            ))
+            .await;
+            state.pending_zoom_anchor.set(
+                captured
+                    .ok()
+                    .filter(|captured| !captured.is_null())
+                    .map(|captured| captured.to_string()),
+            );
+            state.zoom_level.set(level);
+        });
+    }
+
+    /// Apply a reader profile to this window and announce it
+    pub fn apply_reader_profile(&mut self, profile: ReaderProfile) {
+        self.show_toast(format!("Profile: {}", profile.name));
+        self.set_reader_profile(profile);
+    }
+
+    /// Change every setting a reader profile defines at once
+    pub fn set_reader_profile(&mut self, profile: ReaderProfile) {
+        if let Some(theme) = profile.theme {
+            self.current_theme.set(theme);
+        }
+        if let Some(zoom) = profile.zoom {
+            self.zoom_level.set(normalize_zoom_level(zoom));
+        }
+        if let Some(open) = profile.sidebar {
+            self.sidebar.write().open = open;
+        }
+        if let Some(open) = profile.right_sidebar {
+            self.right_sidebar_open.set(open);
+        }
+        self.reader_profile.set(Some(profile));
+    }
+
+    /// Apply the configured reader profile after the current one
+    pub fn cycle_reader_profile(&mut self) {
+        let current = self.reader_profile.read().as_ref().map(|p| p.name.clone());
+        let next = CONFIG
+            .read()
+            .reader_profiles
+            .next_after(current.as_deref())
+            .cloned();
+        match next {
+            Some(profile) => self.apply_reader_profile(profile),
+            None => self.show_toast("No reader profiles configured"),
+        }
+    }
+
+    /// Show a short-lived toast notification
+    pub fn show_toast(&mut self, message: impl Into<String>) {
+        let id = self.toast.peek().as_ref().map_or(0, |toast| toast.id + 1);
+        self.toast.set(Some(Toast {
+            id,
+            message: message.into(),
+        }));
+
+        let mut toast = self.toast;
+        spawn(async move {
+            tokio::time::sleep(TOAST_DURATION).await;
+            if toast.peek().as_ref().is_some_and(|toast| toast.id == id) {
+                toast.set(None);
+            }
+        });
+    }
+
     /// Update pinned search matches from JavaScript callback
     pub fn update_pinned_matches(&mut self, matches: HashMap<PinnedSearchId, Vec<SearchMatch>>) {
         self.pinned_matches.set(matches);
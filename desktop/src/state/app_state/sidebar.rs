@@ -1,4 +1,6 @@
 use super::AppState;
+use crate::config::CONFIG;
+use crate::directory_views::{ExplorerSort, DIRECTORY_VIEWS};
 use crate::history::HistoryManager;
 use dioxus::prelude::*;
 use std::collections::HashSet;
@@ -12,6 +14,11 @@ pub struct Sidebar {
     pub expanded_dirs: HashSet<PathBuf>,
     pub width: f64,
     pub show_all_files: bool,
+    /// Order of entries in the file explorer
+    pub sort: ExplorerSort,
+    /// Whether entries ignored by `.gitignore` are listed for now despite
+    /// `sidebar.respectGitignore` (not remembered)
+    pub show_ignored_files: bool,
     /// History of root directory navigation.
     ///
     /// This history is intentionally kept in-memory only and is not persisted
@@ -29,6 +36,8 @@ impl Default for Sidebar {
             expanded_dirs: HashSet::new(),
             width: 280.0,
             show_all_files: false,
+            sort: ExplorerSort::default(),
+            show_ignored_files: false,
             dir_history: HistoryManager::new(),
         }
     }
@@ -45,6 +54,22 @@ impl Sidebar {
         }
     }
 
+    /// Toggle directory expansion state, collapsing expanded siblings
+    /// (and everything below them) when the directory gets expanded
+    pub fn toggle_expansion_exclusive(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if self.expanded_dirs.contains(path) {
+            self.expanded_dirs.remove(path);
+            return;
+        }
+
+        if let Some(parent) = path.parent() {
+            self.expanded_dirs
+                .retain(|dir| dir == parent || !dir.starts_with(parent) || dir.starts_with(path));
+        }
+        self.expanded_dirs.insert(path.to_owned());
+    }
+
     /// Check if we can go back in directory history
     pub fn can_go_back(&self) -> bool {
         self.dir_history.can_go_back()
@@ -60,6 +85,23 @@ impl Sidebar {
         self.dir_history.push(path);
     }
 
+    /// Set a new root directory and add it to history
+    ///
+    /// With `sidebar.resetHistoryOnUnrelatedRoot`, switching to a root that is
+    /// neither an ancestor nor a descendant of the current one (another
+    /// project) starts the history over.
+    pub fn change_root_directory(&mut self, path: PathBuf) {
+        let reset_history = CONFIG.read().sidebar.reset_history_on_unrelated_root;
+        let previous = self.root_directory.replace(path.clone());
+        if reset_history && previous.is_some_and(|previous| !is_related_directory(&previous, &path))
+        {
+            self.dir_history.clear();
+        }
+        self.expanded_dirs.clear();
+        self.restore_view();
+        self.push_to_history(path);
+    }
+
     /// Go back in directory history
     pub fn go_back(&mut self) -> Option<PathBuf> {
         self.dir_history.go_back().map(|e| e.path.clone())
@@ -69,6 +111,61 @@ impl Sidebar {
     pub fn go_forward(&mut self) -> Option<PathBuf> {
         self.dir_history.go_forward().map(|e| e.path.clone())
     }
+
+    /// Show or hide non-markdown files
+    pub fn set_show_all_files(&mut self, show_all_files: bool) {
+        self.show_all_files = show_all_files;
+        self.remember_view();
+    }
+
+    /// Change the order of entries
+    pub fn set_sort(&mut self, sort: ExplorerSort) {
+        self.sort = sort;
+        self.remember_view();
+    }
+
+    /// Remember the current view for the root directory, if configured
+    fn remember_view(&self) {
+        if !CONFIG.read().sidebar.remember_view_per_directory {
+            return;
+        }
+        let Some(root) = &self.root_directory else {
+            return;
+        };
+        let mut views = DIRECTORY_VIEWS.write();
+        views.remember(root, self.show_all_files, self.sort);
+        views.save();
+    }
+
+    /// Apply the view remembered for the root directory, if configured
+    ///
+    /// Directories without a remembered view get the configured defaults.
+    pub fn restore_view(&mut self) {
+        let (remember, default_show_all_files) = {
+            let config = CONFIG.read();
+            (
+                config.sidebar.remember_view_per_directory,
+                config.sidebar.default_show_all_files,
+            )
+        };
+        if !remember {
+            return;
+        }
+        let Some(root) = &self.root_directory else {
+            return;
+        };
+        let view = DIRECTORY_VIEWS.read().get(root).cloned();
+        match view {
+            Some(view) => {
+                self.show_all_files = view.show_all_files;
+                self.sort = view.sort;
+            }
+            None => {
+                self.show_all_files = default_show_all_files;
+                self.sort = ExplorerSort::default();
+            }
+        }
+    }
 }
 
 impl AppState {
@@ -78,17 +175,48 @@ impl AppState {
         sidebar.open = !sidebar.open;
     }
 
+    /// Toggle between markdown-only and all files in the explorer
+    pub fn toggle_show_all_files(&mut self) {
+        let mut sidebar = self.sidebar.write();
+        let show_all_files = !sidebar.show_all_files;
+        sidebar.set_show_all_files(show_all_files);
+    }
+
     /// Toggle directory expansion state
     pub fn toggle_directory_expansion(&mut self, path: impl AsRef<Path>) {
+        let accordion_mode = CONFIG.read().sidebar.accordion_mode;
         let mut sidebar = self.sidebar.write();
-        sidebar.toggle_expansion(path);
+        if accordion_mode {
+            sidebar.toggle_expansion_exclusive(path);
+        } else {
+            sidebar.toggle_expansion(path);
+        }
     }
 }
 
+/// Whether one directory contains the other (or they are the same)
+fn is_related_directory(a: &Path, b: &Path) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_related_directory() {
+        let project = Path::new("/work/project");
+        assert!(is_related_directory(project, project));
+        assert!(is_related_directory(
+            project,
+            Path::new("/work/project/docs")
+        ));
+        assert!(is_related_directory(project, Path::new("/work")));
+        // Prefixes are matched by component, not by string
+        assert!(!is_related_directory(project, Path::new("/work/project-b")));
+        assert!(!is_related_directory(project, Path::new("/other")));
+    }
+
     #[test]
     fn test_sidebar_default() {
         let sidebar = Sidebar::default();
@@ -134,6 +262,46 @@ mod tests {
         assert!(sidebar.expanded_dirs.contains(&path2));
     }
 
+    #[test]
+    fn test_sidebar_toggle_expansion_exclusive_collapses_siblings() {
+        let mut sidebar = Sidebar::default();
+        let root = PathBuf::from("/test");
+        let dir1 = PathBuf::from("/test/dir1");
+        let dir1_child = PathBuf::from("/test/dir1/child");
+        let dir2 = PathBuf::from("/test/dir2");
+
+        sidebar.toggle_expansion(root.clone());
+        sidebar.toggle_expansion(dir1.clone());
+        sidebar.toggle_expansion(dir1_child.clone());
+
+        sidebar.toggle_expansion_exclusive(dir2.clone());
+
+        assert!(sidebar.expanded_dirs.contains(&root));
+        assert!(sidebar.expanded_dirs.contains(&dir2));
+        assert!(!sidebar.expanded_dirs.contains(&dir1));
+        assert!(!sidebar.expanded_dirs.contains(&dir1_child));
+    }
+
+    #[test]
+    fn test_sidebar_toggle_expansion_exclusive_keeps_own_subtree() {
+        let mut sidebar = Sidebar::default();
+        let dir1 = PathBuf::from("/test/dir1");
+        let dir1_child = PathBuf::from("/test/dir1/child");
+        let other = PathBuf::from("/other/dir");
+
+        sidebar.toggle_expansion(dir1_child.clone());
+        sidebar.toggle_expansion(other.clone());
+
+        sidebar.toggle_expansion_exclusive(dir1.clone());
+        assert!(sidebar.expanded_dirs.contains(&dir1));
+        assert!(sidebar.expanded_dirs.contains(&dir1_child));
+        assert!(sidebar.expanded_dirs.contains(&other));
+
+        // Collapsing works like a regular toggle
+        sidebar.toggle_expansion_exclusive(dir1.clone());
+        assert!(!sidebar.expanded_dirs.contains(&dir1));
+    }
+
     #[test]
     fn test_sidebar_history_initial_state() {
         let sidebar = Sidebar::default();
@@ -16,8 +16,11 @@
 
 use super::content::TabContent;
 use super::tab::Tab;
+use crate::components::sidebar::file_explorer::{sibling_file, ListingOptions};
+use crate::config::CONFIG;
 use crate::history::HistoryManager;
 use crate::state::AppState;
+use crate::utils::file::resolve_symlink;
 use dioxus::prelude::*;
 use std::path::{Path, PathBuf};
 
@@ -194,7 +197,15 @@ impl AppState {
     /// Open a file, reusing NoFile tab or existing tab with the same file if possible
     /// Used when opening from sidebar or external sources
     pub fn open_file(&mut self, file: impl AsRef<Path>) {
-        let file = file.as_ref();
+        let follow_symlinks = CONFIG.read().sidebar.follow_symlinks;
+        let file = match resolve_symlink(file, follow_symlinks) {
+            Ok(file) => file,
+            Err(reason) => {
+                self.show_toast(reason);
+                return;
+            }
+        };
+        let file = file.as_path();
         // Check if the file is already open in another tab
         if let Some(tab_index) = self.find_tab_with_file(file) {
             // Switch to the existing tab instead of creating a new one
@@ -218,6 +229,31 @@ impl AppState {
         });
     }
 
+    /// Navigate to the document before or after the current file in its
+    /// directory, in the order the file explorer lists them
+    ///
+    /// Reads the directory on the calling thread; shows a toast at either end
+    /// unless `siblingFiles.wrapAround` is set.
+    pub fn open_sibling_file(&mut self, forward: bool) {
+        let Some(file) = self
+            .current_tab()
+            .and_then(|tab| tab.file().map(Path::to_path_buf))
+        else {
+            return;
+        };
+        let options = ListingOptions::current(self);
+        let wrap = CONFIG.read().sibling_files.wrap_around;
+        match sibling_file(&file, &options, forward, wrap) {
+            Some(sibling) => {
+                let position = *self.current_scroll_position.read();
+                self.save_current_scroll_position(position);
+                self.navigate_to_file(sibling);
+            }
+            None if forward => self.show_toast("No next file in this directory"),
+            None => self.show_toast("No previous file in this directory"),
+        }
+    }
+
     /// Open preferences in a tab. Reuses existing preferences tab if found.
     pub fn open_preferences(&mut self) {
         // Check if preferences tab already exists
@@ -244,6 +280,8 @@ impl AppState {
                 content: TabContent::Preferences,
                 history: HistoryManager::new(),
                 pinned: false,
+                split_preview: false,
+                side_file: None,
             });
             let new_index = tabs.len() - 1;
             drop(tabs);
@@ -500,6 +538,43 @@ impl AppState {
         }
     }
 
+    /// Toggle the split source/rendered preview for the current tab
+    pub fn toggle_split_preview(&mut self) {
+        self.update_current_tab(|tab| {
+            tab.split_preview = !tab.split_preview;
+        });
+    }
+
+    /// Show a file in the side pane next to the current tab's file.
+    /// Opens the file normally when the current tab has no file to compare with.
+    pub fn open_file_to_side(&mut self, file: impl AsRef<Path>) {
+        let follow_symlinks = CONFIG.read().sidebar.follow_symlinks;
+        let file = match resolve_symlink(file, follow_symlinks) {
+            Ok(file) => file,
+            Err(reason) => {
+                self.show_toast(reason);
+                return;
+            }
+        };
+        let has_file = self
+            .current_tab()
+            .is_some_and(|tab| matches!(tab.content, TabContent::File(_)));
+        if !has_file {
+            self.open_file(file);
+            return;
+        }
+        self.update_current_tab(|tab| {
+            tab.side_file = Some(file);
+        });
+    }
+
+    /// Close the side pane of the current tab
+    pub fn close_side_pane(&mut self) {
+        self.update_current_tab(|tab| {
+            tab.side_file = None;
+        });
+    }
+
     /// Reload the current tab.
     /// For file tabs, this re-reads the file from disk.
     /// For other tab types, this forces a re-render.
@@ -526,4 +601,16 @@ impl AppState {
             }
         }
     }
+
+    /// Reload every file tab by re-reading its file from disk.
+    /// Unlike `reload_current_tab`, this keeps each tab's history as is.
+    pub fn reload_all_tabs(&mut self) {
+        let mut tabs = self.tabs.write();
+        for tab in tabs.iter_mut() {
+            if let Some(path) = tab.file().map(|p| p.to_path_buf()) {
+                // Reassigning the same file path makes the viewer re-read it
+                tab.content = crate::state::TabContent::File(path);
+            }
+        }
+    }
 }
@@ -8,6 +8,10 @@ pub struct Tab {
     pub content: TabContent,
     pub history: HistoryManager,
     pub pinned: bool,
+    /// Show the raw markdown source side by side with the rendered view
+    pub split_preview: bool,
+    /// File rendered in the side pane next to this tab's file ("Open to the Side")
+    pub side_file: Option<PathBuf>,
 }
 
 impl Tab {
@@ -20,6 +24,8 @@ impl Tab {
             content,
             history,
             pinned: false,
+            split_preview: false,
+            side_file: None,
         }
     }
 
@@ -29,6 +35,8 @@ impl Tab {
             content: TabContent::Inline(content),
             history: HistoryManager::new(),
             pinned: false,
+            split_preview: false,
+            side_file: None,
         }
     }
 
@@ -157,6 +165,16 @@ mod tests {
         assert_eq!(tab.file(), None);
     }
 
+    #[test]
+    fn test_navigate_to_keeps_split_preview() {
+        let mut tab = Tab::new("/test/first.md");
+        tab.split_preview = true;
+
+        tab.navigate_to("/test/second.md");
+
+        assert!(tab.split_preview);
+    }
+
     // === display_name() tests ===
 
     #[test]
@@ -2,7 +2,7 @@ use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::components::right_sidebar::RightSidebarTab;
 use crate::config::DEFAULT_RIGHT_SIDEBAR_WIDTH;
@@ -41,6 +41,19 @@ impl From<LogicalSize<u32>> for Size {
     }
 }
 
+/// Maximum number of directories whose window geometry is remembered
+const MAX_DIRECTORY_GEOMETRIES: usize = 50;
+
+/// Window geometry last used for a root directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryGeometry {
+    /// Canonical root directory
+    pub directory: PathBuf,
+    pub position: Position,
+    pub size: Size,
+}
+
 /// Persisted state from the last closed window
 ///
 /// This is a subset of AppState that gets saved to session.json
@@ -60,6 +73,8 @@ pub struct PersistedState {
     pub window_size: Size,
     #[serde(default = "default_zoom_level")]
     pub zoom_level: f64,
+    /// Window geometry per root directory, most recently used first
+    pub directory_geometries: Vec<DirectoryGeometry>,
 }
 
 fn default_zoom_level() -> f64 {
@@ -80,6 +95,7 @@ impl Default for PersistedState {
             window_position: Position::default(),
             window_size: Size::default(),
             zoom_level: 1.0,
+            directory_geometries: Vec::new(),
         }
     }
 }
@@ -99,6 +115,7 @@ impl From<&AppState> for PersistedState {
             window_position: (*state.position.read()).into(),
             window_size: (*state.size.read()).into(),
             zoom_level: *state.zoom_level.read(),
+            directory_geometries: Vec::new(),
         }
     }
 }
@@ -137,6 +154,38 @@ impl PersistedState {
         }
     }
 
+    /// Get the window geometry remembered for a root directory
+    pub fn directory_geometry(&self, directory: &Path) -> Option<&DirectoryGeometry> {
+        let directory = canonical_directory(directory);
+        self.directory_geometries
+            .iter()
+            .find(|geometry| geometry.directory == directory)
+    }
+
+    /// Remember the window geometry for a root directory
+    ///
+    /// The directory moves to the front of the list; the least recently used
+    /// entries are dropped beyond [`MAX_DIRECTORY_GEOMETRIES`].
+    pub fn remember_directory_geometry(
+        &mut self,
+        directory: &Path,
+        position: Position,
+        size: Size,
+    ) {
+        let directory = canonical_directory(directory);
+        self.directory_geometries
+            .retain(|geometry| geometry.directory != directory);
+        self.directory_geometries.insert(
+            0,
+            DirectoryGeometry {
+                directory,
+                position,
+                size,
+            },
+        );
+        self.directory_geometries.truncate(MAX_DIRECTORY_GEOMETRIES);
+    }
+
     /// Save persisted state to file
     ///
     /// This function should be called when a window is closing to persist its state.
@@ -176,3 +225,56 @@ impl PersistedState {
         }
     }
 }
+
+/// Key directories by their canonical path so different spellings of the same
+/// directory share one entry
+fn canonical_directory(directory: &Path) -> PathBuf {
+    directory
+        .canonicalize()
+        .unwrap_or_else(|_| directory.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remember_directory_geometry() {
+        let mut state = PersistedState::default();
+        let size = Size {
+            width: 800,
+            height: 600,
+        };
+        state.remember_directory_geometry(Path::new("/project-a"), Position { x: 10, y: 20 }, size);
+        state.remember_directory_geometry(Path::new("/project-b"), Position::default(), size);
+        state.remember_directory_geometry(Path::new("/project-a"), Position { x: 30, y: 40 }, size);
+
+        assert_eq!(state.directory_geometries.len(), 2);
+        assert_eq!(
+            state.directory_geometries[0].directory,
+            PathBuf::from("/project-a")
+        );
+        let geometry = state.directory_geometry(Path::new("/project-a")).unwrap();
+        assert_eq!((geometry.position.x, geometry.position.y), (30, 40));
+        assert!(state.directory_geometry(Path::new("/project-c")).is_none());
+    }
+
+    #[test]
+    fn test_remember_directory_geometry_is_bounded() {
+        let mut state = PersistedState::default();
+        for i in 0..MAX_DIRECTORY_GEOMETRIES + 5 {
+            let directory = PathBuf::from(format!("/project-{}", i));
+            state.remember_directory_geometry(&directory, Position::default(), Size::default());
+        }
+
+        assert_eq!(state.directory_geometries.len(), MAX_DIRECTORY_GEOMETRIES);
+        // Oldest entries are dropped first
+        assert!(state.directory_geometry(Path::new("/project-0")).is_none());
+        assert!(state
+            .directory_geometry(Path::new(&format!(
+                "/project-{}",
+                MAX_DIRECTORY_GEOMETRIES + 4
+            )))
+            .is_some());
+    }
+}
@@ -0,0 +1,183 @@
+//! Syntax highlighting of code blocks while the document is rendered
+//!
+//! Tokens are wrapped in the same `hljs-*` classes the viewer's highlighter
+//! produces, so the light and dark code themes color them without knowing
+//! which side did the work.
+
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+use syntect::easy::ScopeRangeIterator;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// Scopes and the class of their tokens; the first match wins, so more
+/// specific scopes come before the ones they refine
+const SCOPE_CLASSES: &[(&str, &str)] = &[
+    ("comment", "hljs-comment"),
+    ("string.regexp", "hljs-regexp"),
+    ("string", "hljs-string"),
+    ("constant.numeric", "hljs-number"),
+    ("constant.character.escape", "hljs-char escape_"),
+    ("constant.language", "hljs-literal"),
+    ("constant.other.symbol", "hljs-symbol"),
+    ("constant", "hljs-variable constant_"),
+    ("keyword.operator", "hljs-operator"),
+    ("keyword", "hljs-keyword"),
+    ("storage", "hljs-keyword"),
+    ("entity.name.function", "hljs-title function_"),
+    ("entity.name.class", "hljs-title class_"),
+    ("entity.name.struct", "hljs-title class_"),
+    ("entity.name.enum", "hljs-title class_"),
+    ("entity.name.trait", "hljs-title class_"),
+    ("entity.name.type", "hljs-title class_"),
+    ("entity.other.inherited-class", "hljs-title class_"),
+    ("entity.name.tag", "hljs-name"),
+    ("entity.name.section", "hljs-section"),
+    ("entity.other.attribute-name", "hljs-attr"),
+    ("entity.name", "hljs-title"),
+    ("support.function", "hljs-built_in"),
+    ("support.type", "hljs-type"),
+    ("support.class", "hljs-type"),
+    ("variable.language", "hljs-variable language_"),
+    ("variable.parameter", "hljs-params"),
+    ("variable.function", "hljs-title function_"),
+    ("meta.annotation", "hljs-meta"),
+    ("meta.attribute", "hljs-meta"),
+    ("meta.preprocessor", "hljs-meta"),
+    ("markup.heading", "hljs-section"),
+    ("markup.bold", "hljs-strong"),
+    ("markup.italic", "hljs-emphasis"),
+    ("markup.inserted", "hljs-addition"),
+    ("markup.deleted", "hljs-deletion"),
+    ("markup.underline.link", "hljs-link"),
+    ("markup.quote", "hljs-quote"),
+    ("markup.raw", "hljs-code"),
+    ("markup.list", "hljs-bullet"),
+];
+
+/// Syntaxes bundled with syntect, loaded on first use
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn scope_classes() -> &'static [(Scope, &'static str)] {
+    static SCOPES: OnceLock<Vec<(Scope, &'static str)>> = OnceLock::new();
+    SCOPES.get_or_init(|| {
+        SCOPE_CLASSES
+            .iter()
+            .filter_map(|(scope, class)| Some((Scope::new(scope).ok()?, *class)))
+            .collect()
+    })
+}
+
+/// Find the syntax for a code block language, by name or file extension
+/// (`rust`, `rs`, `Python`)
+fn find_syntax(language: &str) -> Option<&'static SyntaxReference> {
+    if language.is_empty() {
+        return None;
+    }
+    syntax_set().find_syntax_by_token(language)
+}
+
+/// Whether `language` can be highlighted here
+pub fn is_supported(language: &str) -> bool {
+    find_syntax(language).is_some()
+}
+
+/// Class of a token, from its innermost scope that has one
+fn token_class(stack: &ScopeStack) -> Option<&'static str> {
+    stack.as_slice().iter().rev().find_map(|scope| {
+        scope_classes()
+            .iter()
+            .find(|(prefix, _)| prefix.is_prefix_of(*scope))
+            .map(|(_, class)| *class)
+    })
+}
+
+/// Highlight `code` as `language`, returning the escaped code with its tokens
+/// wrapped in `<span class="hljs-…">`
+///
+/// Returns `None` for languages syntect doesn't know, or if the code can't be
+/// parsed, leaving the block to the viewer.
+pub fn highlight(code: &str, language: &str) -> Option<String> {
+    let syntax = find_syntax(language)?;
+    let mut state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+    let mut output = String::with_capacity(code.len() * 2);
+    // Adjacent tokens of the same class share one span
+    let mut open_class: Option<&str> = None;
+
+    for line in LinesWithEndings::from(code) {
+        let ops = state.parse_line(line, syntax_set()).ok()?;
+        for (range, op) in ScopeRangeIterator::new(&ops, line) {
+            stack.apply(op).ok()?;
+            if range.is_empty() {
+                continue;
+            }
+            let class = token_class(&stack);
+            if class != open_class {
+                if open_class.is_some() {
+                    output.push_str("</span>");
+                }
+                if let Some(class) = class {
+                    let _ = write!(output, r#"<span class="{class}">"#);
+                }
+                open_class = class;
+            }
+            output.push_str(&html_escape::encode_text(&line[range]));
+        }
+    }
+    if open_class.is_some() {
+        output.push_str("</span>");
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_rust() {
+        let html = highlight("fn main() {\n    let s = \"<hi>\"; // done\n}\n", "rust").unwrap();
+
+        assert!(html.contains(r#"<span class="hljs-keyword">fn</span>"#));
+        assert!(html.contains(r#"<span class="hljs-title function_">main</span>"#));
+        assert!(html.contains(r#"<span class="hljs-string">"&lt;hi&gt;"</span>"#));
+        assert!(html.contains(r#"<span class="hljs-comment">// done"#));
+    }
+
+    #[test]
+    fn test_highlight_python() {
+        let html = highlight("def greet(name):\n    return 42\n", "python").unwrap();
+
+        assert!(html.contains(r#"<span class="hljs-keyword">def</span>"#));
+        assert!(html.contains(r#"<span class="hljs-title function_">greet</span>"#));
+        assert!(html.contains(r#"<span class="hljs-number">42</span>"#));
+    }
+
+    #[test]
+    fn test_highlight_keeps_text() {
+        let code = "x = [1, 2]\n# a < b\n";
+        let html = highlight(code, "py").unwrap();
+        let mut text = String::new();
+        let mut rest = html.as_str();
+        // Strip the spans, then the text is the escaped code
+        while let Some(start) = rest.find('<') {
+            text.push_str(&rest[..start]);
+            rest = &rest[rest[start..].find('>').unwrap() + start + 1..];
+        }
+        text.push_str(rest);
+
+        assert_eq!(text, html_escape::encode_text(code));
+    }
+
+    #[test]
+    fn test_highlight_unknown_language() {
+        assert_eq!(highlight("a <- b", "no-such-language"), None);
+        assert_eq!(highlight("a <- b", ""), None);
+        assert!(!is_supported("no-such-language"));
+        assert!(is_supported("Rust"));
+    }
+}
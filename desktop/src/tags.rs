@@ -0,0 +1,162 @@
+//! Frontmatter tag index for cross-file tag filtering.
+//!
+//! Scans markdown files under a root directory and caches their frontmatter
+//! tags per file. Scans skip what the file explorer hides and stop early in
+//! very large trees. Cached entries are revalidated by modification time and
+//! dropped explicitly when the directory watcher reports changes.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+use crate::markdown::extract_frontmatter_tags;
+use crate::utils::file::{is_markdown_file, walk_files, ScanOptions, MAX_SCANNED_ENTRIES};
+
+#[derive(Debug, Clone)]
+struct CachedTags {
+    modified: SystemTime,
+    tags_key: String,
+    tags: Vec<String>,
+}
+
+static TAG_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedTags>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Files with a tag
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaggedFiles {
+    pub files: Vec<PathBuf>,
+    /// The scan stopped early, so some tagged files may be missing
+    pub partial: bool,
+}
+
+/// Find all markdown files under `root` whose frontmatter contains `tag`.
+///
+/// Tags are compared case-insensitively. Results are sorted by path.
+pub fn find_files_with_tag(
+    root: &Path,
+    tag: &str,
+    tags_key: &str,
+    options: &ScanOptions,
+) -> TaggedFiles {
+    let tag = tag.to_lowercase();
+    let mut files = Vec::new();
+    let partial = walk_files(root, options, MAX_SCANNED_ENTRIES, |file| {
+        let tagged = is_markdown_file(&file)
+            && tags_for_file(&file, tags_key)
+                .iter()
+                .any(|t| t.to_lowercase() == tag);
+        if tagged {
+            files.push(file);
+        }
+        ControlFlow::Continue(())
+    });
+    files.sort();
+    TaggedFiles { files, partial }
+}
+
+/// Drop cached tags for every file under `dir`
+pub fn invalidate_tag_cache(dir: &Path) {
+    TAG_CACHE.lock().retain(|path, _| !path.starts_with(dir));
+}
+
+/// Get tags for a single file, using the cache when the file is unchanged
+fn tags_for_file(path: &Path, tags_key: &str) -> Vec<String> {
+    let Ok(modified) = fs::metadata(path).and_then(|meta| meta.modified()) else {
+        return Vec::new();
+    };
+
+    if let Some(cached) = TAG_CACHE.lock().get(path) {
+        if cached.modified == modified && cached.tags_key == tags_key {
+            return cached.tags.clone();
+        }
+    }
+
+    let tags = fs::read_to_string(path)
+        .map(|content| extract_frontmatter_tags(&content, tags_key))
+        .unwrap_or_default();
+    TAG_CACHE.lock().insert(
+        path.to_path_buf(),
+        CachedTags {
+            modified,
+            tags_key: tags_key.to_string(),
+            tags: tags.clone(),
+        },
+    );
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_files_with_tag() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::create_dir(root.join(".hidden")).unwrap();
+        fs::write(root.join("a.md"), "---\ntags: [rust, async]\n---\n# A").unwrap();
+        fs::write(root.join("sub/b.md"), "---\ntags: [Rust]\n---\n# B").unwrap();
+        fs::write(root.join("c.md"), "---\ntags: [go]\n---\n# C").unwrap();
+        fs::write(root.join(".hidden/d.md"), "---\ntags: [rust]\n---\n# D").unwrap();
+
+        let matches = find_files_with_tag(root, "rust", "tags", &ScanOptions::default());
+
+        assert_eq!(
+            matches.files,
+            vec![root.join("a.md"), root.join("sub/b.md")]
+        );
+        assert!(!matches.partial);
+    }
+
+    #[test]
+    fn test_find_files_with_tag_skips_ignored_directories() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("node_modules")).unwrap();
+        fs::create_dir(root.join("target")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        for file in ["a.md", "node_modules/b.md", "target/c.md"] {
+            fs::write(root.join(file), "---\ntags: [rust]\n---\n").unwrap();
+        }
+
+        let options = ScanOptions {
+            ignore_patterns: vec!["node_modules".to_string()],
+            respect_gitignore: true,
+            ..ScanOptions::default()
+        };
+        let matches = find_files_with_tag(root, "rust", "tags", &options);
+
+        assert_eq!(matches.files, vec![root.join("a.md")]);
+    }
+
+    #[test]
+    fn test_find_files_with_tag_sees_updates_after_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let file = root.join("a.md");
+        fs::write(&file, "---\ntags: [draft]\n---\n").unwrap();
+        let options = ScanOptions::default();
+        assert_eq!(
+            find_files_with_tag(root, "draft", "tags", &options).files,
+            vec![file.clone()]
+        );
+
+        fs::write(&file, "---\ntags: [published]\n---\n").unwrap();
+        invalidate_tag_cache(root);
+
+        assert!(find_files_with_tag(root, "draft", "tags", &options)
+            .files
+            .is_empty());
+        assert_eq!(
+            find_files_with_tag(root, "published", "tags", &options).files,
+            vec![file]
+        );
+    }
+}
@@ -19,6 +19,26 @@ impl From<&str> for Theme {
     }
 }
 
+impl Theme {
+    /// Next theme in the cycle order: Auto → Light → Dark → Auto
+    pub fn next(self) -> Self {
+        match self {
+            Theme::Auto => Theme::Light,
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Auto,
+        }
+    }
+
+    /// Human-readable name for display
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Auto => "Auto",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+}
+
 pub fn resolve_theme(theme: Theme) -> DioxusTheme {
     match theme {
         // NOTE:
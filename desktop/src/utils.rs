@@ -1,6 +1,14 @@
 pub mod clipboard;
+pub mod clipboard_detect;
+pub mod css;
 pub mod file;
 pub mod file_operations;
+pub mod fuzzy;
+pub mod gitignore;
+pub mod http;
 pub mod image;
+pub mod media;
+pub mod pdf;
+pub mod proxy_icon;
 pub mod screen;
 pub mod window_title;
@@ -10,6 +10,7 @@ use std::sync::LazyLock;
 use std::sync::Mutex;
 
 use super::image::extract_base64_from_data_url;
+use super::media::issued_image_path;
 
 /// Global clipboard instance held for the application lifetime.
 ///
@@ -32,9 +33,26 @@ pub fn copy_text(text: impl AsRef<str>) {
     }
 }
 
+/// Read text from the system clipboard.
+///
+/// Returns `None` when the clipboard is empty or holds non-text content.
+pub fn read_text() -> Option<String> {
+    let mut clipboard = CLIPBOARD.lock().unwrap();
+    match clipboard.get_text() {
+        Ok(text) => Some(text),
+        Err(arboard::Error::ContentNotAvailable) => None,
+        Err(e) => {
+            tracing::debug!(%e, "Failed to read text from clipboard");
+            None
+        }
+    }
+}
+
 /// Copy an image from a data URL to the system clipboard.
 ///
-/// The data URL should be in the format: `data:image/png;base64,<base64-encoded-data>`
+/// The data URL should be in the format: `data:image/png;base64,<base64-encoded-data>`.
+/// Local images loaded from disk (`/arto-media/<encoded path>`) are read from
+/// their file instead.
 ///
 /// # Examples
 ///
@@ -45,21 +63,32 @@ pub fn copy_text(text: impl AsRef<str>) {
 pub fn copy_image_from_data_url(data_url: impl AsRef<str>) {
     let data_url = data_url.as_ref();
 
-    // Extract base64 data from data URL
-    let base64_data = match extract_base64_from_data_url(data_url) {
-        Ok(data) => data,
-        Err(e) => {
-            tracing::error!(%e, "Failed to extract base64 data from data URL");
-            return;
+    let image_bytes = if let Some(path) = issued_image_path(data_url) {
+        // Large local image loaded from disk by the webview
+        match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!(%e, ?path, "Failed to read image file");
+                return;
+            }
         }
-    };
+    } else {
+        // Extract base64 data from data URL
+        let base64_data = match extract_base64_from_data_url(data_url) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!(%e, "Failed to extract base64 data from data URL");
+                return;
+            }
+        };
 
-    // Decode base64 to bytes
-    let image_bytes = match base64::prelude::BASE64_STANDARD.decode(base64_data) {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            tracing::error!(%e, "Failed to decode base64 image data");
-            return;
+        // Decode base64 to bytes
+        match base64::prelude::BASE64_STANDARD.decode(base64_data) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!(%e, "Failed to decode base64 image data");
+                return;
+            }
         }
     };
 
@@ -0,0 +1,159 @@
+//! Detecting markdown on the clipboard.
+//!
+//! Used to offer opening the clipboard contents when a window gains focus.
+//! Detection is deliberately conservative: a prompt for ordinary copied text
+//! would be more annoying than a missed markdown snippet.
+
+use percent_encoding::percent_decode_str;
+use std::path::PathBuf;
+
+use super::file::is_markdown_file;
+
+/// Minimum number of markdown-looking lines for text to count as markdown
+const MIN_MARKDOWN_LINES: usize = 2;
+
+/// Markdown found on the clipboard
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardMarkdown {
+    /// Path to an existing markdown file
+    File(PathBuf),
+    /// Markdown source text
+    Text(String),
+}
+
+/// Detect whether clipboard text is a markdown file path or markdown source
+pub fn detect_clipboard_markdown(text: &str) -> Option<ClipboardMarkdown> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if let Some(path) = parse_markdown_path(text) {
+        return Some(ClipboardMarkdown::File(path));
+    }
+    looks_like_markdown(text).then(|| ClipboardMarkdown::Text(text.to_string()))
+}
+
+/// Parse a single-line path (plain, quoted, `~/`-relative or `file://` URL)
+/// pointing at an existing markdown file
+fn parse_markdown_path(text: &str) -> Option<PathBuf> {
+    if text.contains('\n') {
+        return None;
+    }
+    let text = text.trim_matches(|c| c == '"' || c == '\'');
+    let path = if let Some(url_path) = text.strip_prefix("file://") {
+        PathBuf::from(percent_decode_str(url_path).decode_utf8().ok()?.as_ref())
+    } else if let Some(relative) = text.strip_prefix("~/") {
+        dirs::home_dir()?.join(relative)
+    } else {
+        PathBuf::from(text)
+    };
+    (path.is_absolute() && is_markdown_file(&path) && path.is_file()).then_some(path)
+}
+
+/// Heuristic check for markdown source text.
+///
+/// Requires several markdown-looking lines, at least one of which is a heading
+/// or code fence, so plain prose and bare lists are not picked up.
+fn looks_like_markdown(text: &str) -> bool {
+    let lines: Vec<&str> = text.lines().map(str::trim_start).collect();
+    let markdown_lines = lines.iter().filter(|line| is_markdown_line(line)).count();
+    let has_structure = lines
+        .iter()
+        .any(|line| is_heading(line) || is_code_fence(line));
+    markdown_lines >= MIN_MARKDOWN_LINES && has_structure
+}
+
+fn is_heading(line: &str) -> bool {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&level) && line[level..].starts_with(' ')
+}
+
+fn is_code_fence(line: &str) -> bool {
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn is_markdown_line(line: &str) -> bool {
+    let is_list_item = ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+        || line.split_once(". ").is_some_and(|(number, _)| {
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+        });
+    is_heading(line)
+        || is_code_fence(line)
+        || is_list_item
+        || line.starts_with("> ")
+        || (line.starts_with('|') && line.ends_with('|'))
+        || line.contains("](")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_markdown_file_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.md");
+        fs::write(&path, "# Notes").unwrap();
+
+        let plain = path.to_string_lossy().to_string();
+        assert_eq!(
+            detect_clipboard_markdown(&format!("  {}\n", plain)),
+            Some(ClipboardMarkdown::File(path.clone()))
+        );
+        assert_eq!(
+            detect_clipboard_markdown(&format!("\"{}\"", plain)),
+            Some(ClipboardMarkdown::File(path.clone()))
+        );
+        assert_eq!(
+            detect_clipboard_markdown(&format!("file://{}", plain)),
+            Some(ClipboardMarkdown::File(path))
+        );
+    }
+
+    #[test]
+    fn test_detect_ignores_missing_or_non_markdown_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let text_file = temp_dir.path().join("notes.txt");
+        fs::write(&text_file, "# Notes").unwrap();
+
+        assert_eq!(
+            detect_clipboard_markdown(&text_file.to_string_lossy()),
+            None
+        );
+        assert_eq!(
+            detect_clipboard_markdown(&temp_dir.path().join("missing.md").to_string_lossy()),
+            None
+        );
+        assert_eq!(detect_clipboard_markdown("README.md"), None);
+    }
+
+    #[test]
+    fn test_detect_markdown_text() {
+        let text = "# Title\n\nSome text with a [link](https://example.com).\n";
+        assert_eq!(
+            detect_clipboard_markdown(text),
+            Some(ClipboardMarkdown::Text(text.trim().to_string()))
+        );
+
+        let code = "```rust\nfn main() {}\n```";
+        assert!(matches!(
+            detect_clipboard_markdown(code),
+            Some(ClipboardMarkdown::Text(_))
+        ));
+    }
+
+    #[test]
+    fn test_detect_ignores_plain_text() {
+        assert_eq!(detect_clipboard_markdown(""), None);
+        assert_eq!(detect_clipboard_markdown("Just a sentence."), None);
+        assert_eq!(detect_clipboard_markdown("#hashtag and more"), None);
+        // Lists alone are common in plain text
+        assert_eq!(detect_clipboard_markdown("- milk\n- eggs\n- bread"), None);
+        // A single heading-like line is not enough
+        assert_eq!(detect_clipboard_markdown("# just a comment"), None);
+    }
+}
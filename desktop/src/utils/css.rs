@@ -0,0 +1,257 @@
+/// Rewrite a stylesheet so its rules only apply inside `scope`.
+///
+/// Every selector is prefixed with `scope` (`h1, p` → `.scope h1, .scope p`),
+/// and `:root`, `html` and `body` are mapped to `scope` itself. Rules inside
+/// `@media`, `@supports`, `@container` and `@layer` blocks are scoped too, and
+/// `@keyframes` and `@page` are kept as is, and so is `@font-face` apart from
+/// declarations loading a remote `url()`, so opening a document can't make
+/// network requests. Any other block
+/// at-rule (`@scope`, `@starting-style`, …) is dropped since its rules would
+/// escape the scope, and so is `@import`, so a stylesheet can't pull in other
+/// files.
+pub fn scope_css(css: &str, scope: &str) -> String {
+    let css = strip_comments(css);
+    let mut output = String::new();
+    scope_rules(&css, scope, &mut output);
+    output
+}
+
+/// At-rules whose blocks contain ordinary style rules
+const NESTED_AT_RULES: &[&str] = &["@media", "@supports", "@container", "@layer"];
+
+/// At-rules whose blocks hold no selectors, kept unchanged
+const PASSTHROUGH_AT_RULES: &[&str] = &["@keyframes", "@-webkit-keyframes", "@page"];
+
+fn scope_rules(css: &str, scope: &str, output: &mut String) {
+    let mut rest = css;
+    while let Some(end) = find_unquoted(rest, &['{', ';']) {
+        let prelude = rest[..end].trim();
+        if rest.as_bytes()[end] == b';' {
+            // Statement at-rule (`@import url(…);`, `@charset "…";`)
+            if !prelude.is_empty() && !prelude.starts_with("@import") {
+                output.push_str(prelude);
+                output.push_str(";\n");
+            }
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        let body_start = end + 1;
+        let body_end = matching_brace(rest, body_start).unwrap_or(rest.len());
+        let body = &rest[body_start..body_end];
+        rest = rest.get(body_end + 1..).unwrap_or("");
+
+        let at_rule = prelude.split_whitespace().next().unwrap_or("");
+        if NESTED_AT_RULES.contains(&at_rule) {
+            output.push_str(prelude);
+            output.push_str(" {\n");
+            scope_rules(body, scope, output);
+            output.push_str("}\n");
+        } else if at_rule == "@font-face" {
+            output.push_str(&format!("{} {{{}}}\n", prelude, without_remote_urls(body)));
+        } else if PASSTHROUGH_AT_RULES.contains(&at_rule) {
+            output.push_str(&format!("{} {{{}}}\n", prelude, body));
+        } else if prelude.starts_with('@') {
+            // Unknown block at-rules may contain selectors we can't scope
+        } else {
+            output.push_str(&format!(
+                "{} {{{}}}\n",
+                scope_selectors(prelude, scope),
+                body
+            ));
+        }
+    }
+}
+
+/// Drop the declarations of a block that load a remote `url()`
+fn without_remote_urls(body: &str) -> String {
+    let mut output = String::with_capacity(body.len());
+    let mut rest = body;
+    loop {
+        let end = find_unquoted(rest, &[';']).map_or(rest.len(), |end| end + 1);
+        let declaration = &rest[..end];
+        if !has_remote_url(declaration) {
+            output.push_str(declaration);
+        }
+        if end == rest.len() {
+            return output;
+        }
+        rest = &rest[end..];
+    }
+}
+
+/// Whether `declaration` has a `url()` with a scheme (other than `data:`)
+/// or a protocol-relative `//` URL
+fn has_remote_url(declaration: &str) -> bool {
+    let lower = declaration.to_ascii_lowercase();
+    lower.match_indices("url(").any(|(index, _)| {
+        let url = lower[index + 4..]
+            .trim_start()
+            .trim_start_matches(['"', '\'']);
+        let scheme = url.find(':').map(|colon| &url[..colon]).filter(|scheme| {
+            scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        });
+        url.starts_with("//") || scheme.is_some_and(|scheme| scheme != "data")
+    })
+}
+
+fn scope_selectors(selectors: &str, scope: &str) -> String {
+    split_selectors(selectors)
+        .into_iter()
+        .map(str::trim)
+        .filter(|selector| !selector.is_empty())
+        .map(|selector| {
+            for root in [":root", "html", "body"] {
+                if let Some(rest) = selector.strip_prefix(root) {
+                    if rest.is_empty() || rest.starts_with([' ', '>', '+', '~', ':', '.', '[']) {
+                        return format!("{}{}", scope, rest);
+                    }
+                }
+            }
+            format!("{} {}", scope, selector)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Split a selector list at commas outside parentheses (`:is(a, b)`)
+fn split_selectors(selectors: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in selectors.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&selectors[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&selectors[start..]);
+    parts
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = rest[start + 2..]
+            .find("*/")
+            .map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Byte index of the first of `targets` outside quoted strings
+fn find_unquoted(css: &str, targets: &[char]) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in css.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if targets.contains(&c) => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Byte index of the `}` closing the block that starts at `start`
+fn matching_brace(css: &str, start: usize) -> Option<usize> {
+    let mut depth = 1usize;
+    let mut offset = start;
+    while let Some(index) = find_unquoted(&css[offset..], &['{', '}']) {
+        let index = offset + index;
+        if css.as_bytes()[index] == b'{' {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+        offset = index + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_css_prefixes_selectors() {
+        let css = "h1, p > a:hover { color: red; }\nbody { margin: 0 }";
+        assert_eq!(
+            scope_css(css, ".doc"),
+            ".doc h1, .doc p > a:hover { color: red; }\n.doc { margin: 0 }\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_css_nested_and_other_at_rules() {
+        let css = r#"
+            /* comment { */
+            @import url("other.css");
+            @media (max-width: 600px) { :is(h1, h2) { font-size: 1em; } }
+            @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+            p::before { content: "{"; }
+        "#;
+        assert_eq!(
+            scope_css(css, ".doc"),
+            "@media (max-width: 600px) {\n.doc :is(h1, h2) { font-size: 1em; }\n}\n\
+             @keyframes fade { from { opacity: 0 } to { opacity: 1 } }\n\
+             .doc p::before { content: \"{\"; }\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_css_drops_unknown_block_at_rules() {
+        let css = r#"
+            @scope (html) { .left-sidebar { display: none } }
+            @starting-style { .app { opacity: 0 } }
+            @media print { @scope (body) { nav { display: none } } h1 { color: red } }
+            @font-face { font-family: Doc; src: url(doc.woff2); }
+            @page { margin: 1cm }
+        "#;
+        assert_eq!(
+            scope_css(css, ".doc"),
+            "@media print {\n.doc h1 { color: red }\n}\n\
+             @font-face { font-family: Doc; src: url(doc.woff2); }\n\
+             @page { margin: 1cm }\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_css_drops_remote_font_urls() {
+        let css = r#"
+            @font-face {
+                font-family: Doc;
+                src: url("https://fonts.example.com/doc.woff2") format("woff2");
+                font-weight: bold;
+            }
+            @font-face { font-family: Cdn; src: URL(//cdn.example.com/a.woff) }
+            @font-face { font-family: Local; src: url(fonts/doc.woff2), url('data:font/woff2;base64,AA') }
+        "#;
+        assert_eq!(
+            scope_css(css, ".doc"),
+            "@font-face {\n                font-family: Doc;\n                font-weight: bold;\n            }\n\
+             @font-face { font-family: Cdn;}\n\
+             @font-face { font-family: Local; src: url(fonts/doc.woff2), url('data:font/woff2;base64,AA') }\n"
+        );
+    }
+}
@@ -1,4 +1,14 @@
-use std::path::Path;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use crate::config::SidebarConfig;
+use crate::utils::gitignore::Gitignore;
 
 /// Check if a file path has a markdown extension (.md or .markdown)
 pub fn is_markdown_file(path: impl AsRef<Path>) -> bool {
@@ -8,3 +18,625 @@ pub fn is_markdown_file(path: impl AsRef<Path>) -> bool {
         .map(|ext| ext == "md" || ext == "markdown")
         .unwrap_or(false)
 }
+
+/// Check if a file path has a reStructuredText extension (.rst)
+pub fn is_rst_file(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext == "rst")
+        .unwrap_or(false)
+}
+
+/// Number of leading bytes searched for NUL bytes when telling text from binary data
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Check if a file path has an image extension the viewer can display
+pub fn is_image_file(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            matches!(
+                ext.to_ascii_lowercase().as_str(),
+                "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp" | "ico"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Extensions of macOS bundles: directories Finder shows as a single item
+const BUNDLE_EXTENSIONS: &[&str] = &[
+    "app",
+    "appex",
+    "bundle",
+    "component",
+    "docset",
+    "framework",
+    "kext",
+    "key",
+    "mdimporter",
+    "mpkg",
+    "numbers",
+    "pages",
+    "photoslibrary",
+    "pkg",
+    "playground",
+    "plugin",
+    "prefpane",
+    "qlgenerator",
+    "rtfd",
+    "saver",
+    "xcarchive",
+    "xcodeproj",
+    "xcworkspace",
+    "xpc",
+];
+
+/// Check if a path is a macOS bundle directory (`.app`, `.bundle`, ...)
+///
+/// Bundles are recognized by their extension, or for unknown extensions by
+/// the `Contents/Info.plist` that application-style bundles contain.
+pub fn is_bundle(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+    if !path.is_dir() {
+        return false;
+    }
+    BUNDLE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        || path.join("Contents").join("Info.plist").is_file()
+}
+
+/// Decode file contents as text
+///
+/// Returns `None` for binary data: content that isn't valid UTF-8 or has a
+/// NUL byte near the start.
+pub fn decode_text(bytes: Vec<u8>) -> Option<String> {
+    let head = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+    if head.contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Check if a path is itself a symlink (without following it)
+pub fn is_symlink(path: impl AsRef<Path>) -> bool {
+    fs::symlink_metadata(path)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// Check if a symlink points at one of its own ancestor directories.
+///
+/// Expanding such a link would recurse forever (`a/link/link/link/...`),
+/// so the explorer must not descend into it. Only links to an ancestor are
+/// caught: directories linking to each other (`a/l -> b`, `b/l -> a`) are
+/// not, so walks over whole trees use [`walk_files`], which reads each
+/// canonical directory once.
+pub fn is_symlink_loop(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    if !is_symlink(path) {
+        return false;
+    }
+    let Ok(target) = fs::canonicalize(path) else {
+        // Broken link: nothing to descend into, so not a loop
+        return false;
+    };
+    path.parent()
+        .and_then(|parent| fs::canonicalize(parent).ok())
+        .is_some_and(|parent| parent.starts_with(&target))
+}
+
+/// Resolve a path according to the symlink-follow preference.
+///
+/// Non-symlink paths are returned unchanged. Symlinks are resolved to their
+/// canonical target when `follow` is true, and rejected otherwise; the error
+/// explains why, for showing to the user.
+///
+/// Only the leaf is checked: a file inside a symlinked directory is not a
+/// symlink itself and opens even when `follow` is false. Rejecting every
+/// path with a symlinked ancestor would also refuse system links such as
+/// macOS's `/tmp` and `/var`, or a symlinked sidebar root.
+pub fn resolve_symlink(path: impl AsRef<Path>, follow: bool) -> Result<PathBuf, String> {
+    let path = path.as_ref();
+    if !is_symlink(path) {
+        return Ok(path.to_path_buf());
+    }
+    let name = path
+        .file_name()
+        .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy());
+    if !follow {
+        tracing::info!(?path, "Not following symlink (followSymlinks is disabled)");
+        return Err(format!(
+            "Not following symlink {} (Follow Symlinks is off)",
+            name
+        ));
+    }
+    fs::canonicalize(path).map_err(|err| {
+        tracing::warn!(?path, %err, "Failed to resolve symlink");
+        format!("Cannot follow symlink {}: {}", name, err)
+    })
+}
+
+/// Directory entries a tree walk reads before giving up on very large trees
+pub const MAX_SCANNED_ENTRIES: usize = 20_000;
+
+/// Which entries [`walk_files`] skips
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Whether hidden (dot) files and directories are walked
+    pub include_hidden: bool,
+    /// Entry names matching one of these patterns are skipped
+    pub ignore_patterns: Vec<String>,
+    /// Whether entries ignored by `.gitignore` are skipped
+    pub respect_gitignore: bool,
+}
+
+impl ScanOptions {
+    /// Skip what the file explorer hides by configuration, and hidden entries
+    pub fn from_sidebar(sidebar: &SidebarConfig) -> Self {
+        Self {
+            include_hidden: false,
+            ignore_patterns: sidebar.ignore_patterns.clone(),
+            respect_gitignore: sidebar.respect_gitignore,
+        }
+    }
+
+    fn is_skipped(&self, name: &OsStr) -> bool {
+        let name = name.to_string_lossy();
+        (!self.include_hidden && name.starts_with('.'))
+            || self
+                .ignore_patterns
+                .iter()
+                .any(|pattern| matches_name_pattern(&name, pattern))
+    }
+}
+
+/// Visit the files under `root`, skipping the entries `options` hides
+/// (blocking; call off the UI thread)
+///
+/// Each directory is read once by its canonical path, so symlinks leading
+/// back into the tree can't make the walk repeat itself. The walk ends after
+/// `limit` entries or when `visit` breaks; returns `true` only if the limit
+/// cut it short.
+pub fn walk_files(
+    root: &Path,
+    options: &ScanOptions,
+    limit: usize,
+    mut visit: impl FnMut(PathBuf) -> ControlFlow<()>,
+) -> bool {
+    let mut visited = HashSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    let mut scanned = 0;
+
+    while let Some(dir) = pending.pop() {
+        let Ok(canonical) = fs::canonicalize(&dir) else {
+            continue;
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let gitignore = options
+            .respect_gitignore
+            .then(|| Gitignore::for_directory(&dir, Some(root)));
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            if scanned == limit {
+                tracing::debug!(?root, scanned, "Directory walk stopped early");
+                return true;
+            }
+            scanned += 1;
+
+            if options.is_skipped(&entry.file_name()) {
+                continue;
+            }
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            if gitignore
+                .as_ref()
+                .is_some_and(|gitignore| gitignore.is_ignored(&path, is_dir))
+            {
+                continue;
+            }
+            if is_dir {
+                pending.push(path);
+            } else if visit(path).is_break() {
+                return false;
+            }
+        }
+    }
+    false
+}
+
+/// Maximum number of directory entries examined by [`single_markdown_file`]
+const SINGLE_FILE_SCAN_LIMIT: usize = 1000;
+
+/// Find the only markdown file under `dir`, if there is exactly one.
+///
+/// Hidden files and directories are ignored, as in the tag index. Returns
+/// `None` when there are none, more than one, or the directory is too large
+/// to scan quickly (a single-document folder is small by nature).
+pub fn single_markdown_file(dir: impl AsRef<Path>) -> Option<PathBuf> {
+    only_file(dir.as_ref(), SINGLE_FILE_SCAN_LIMIT, |path| {
+        is_markdown_file(path)
+    })
+}
+
+/// The only file under `dir` accepted by `matches`, skipping hidden entries;
+/// `None` if there are none, several, or more than `limit` entries to read
+fn only_file(dir: &Path, limit: usize, matches: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    let mut found = None;
+    let mut several = false;
+    let partial = walk_files(dir, &ScanOptions::default(), limit, |path| {
+        if !matches(&path) {
+            return ControlFlow::Continue(());
+        }
+        several = found.replace(path).is_some();
+        if several {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    found.filter(|_| !partial && !several)
+}
+
+/// Maximum number of directory entries examined by [`find_moved_file`]
+const MOVED_FILE_SCAN_LIMIT: usize = 20_000;
+
+/// How long a search that found nothing (or too much) is remembered
+const MOVED_FILE_MISS_TTL: Duration = Duration::from_secs(30);
+
+/// Results of [`find_moved_file`] by search root and file name
+type MovedFileCache = HashMap<(PathBuf, OsString), (Option<PathBuf>, Instant)>;
+
+static MOVED_FILE_CACHE: LazyLock<Mutex<MovedFileCache>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Find the file named `name` under `dir`, for links whose target was moved.
+///
+/// Hidden directories are skipped as in [`single_markdown_file`].
+/// Returns `None` when there is no match, more than one, or the tree is too
+/// large to scan. Results are cached: found paths until they disappear,
+/// misses for a short while so new files are picked up.
+pub fn find_moved_file(dir: impl AsRef<Path>, name: &OsStr) -> Option<PathBuf> {
+    let key = (dir.as_ref().to_path_buf(), name.to_os_string());
+    if let Some((found, searched_at)) = MOVED_FILE_CACHE.lock().get(&key) {
+        match found {
+            Some(path) if path.is_file() => return Some(path.clone()),
+            None if searched_at.elapsed() < MOVED_FILE_MISS_TTL => return None,
+            _ => {}
+        }
+    }
+
+    let found = search_file_by_name(&key.0, name);
+    MOVED_FILE_CACHE
+        .lock()
+        .insert(key, (found.clone(), Instant::now()));
+    found
+}
+
+fn search_file_by_name(dir: &Path, name: &OsStr) -> Option<PathBuf> {
+    only_file(dir, MOVED_FILE_SCAN_LIMIT, |path| {
+        path.file_name() == Some(name)
+    })
+}
+
+/// Get `path` relative to `root`, or `path` itself if it is not inside `root`.
+pub fn path_relative_to<'a>(path: &'a Path, root: Option<&Path>) -> &'a Path {
+    root.and_then(|root| path.strip_prefix(root).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .unwrap_or(path)
+}
+
+/// Check whether a file name matches a pattern with `*` (any run of
+/// characters) and `?` (any single character) wildcards.
+pub fn matches_name_pattern(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut n, mut p) = (0, 0);
+    // Position of the last `*` and the name position it currently covers up to
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                n += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character and retry
+                Some((star, covered)) => {
+                    backtrack = Some((star, covered + 1));
+                    p = star + 1;
+                    n = covered + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_bundle() {
+        let temp = TempDir::new().unwrap();
+        let app = temp.path().join("Arto.APP");
+        let custom = temp.path().join("Tool.custombundle");
+        let folder = temp.path().join("docs.v2");
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(custom.join("Contents")).unwrap();
+        fs::write(custom.join("Contents").join("Info.plist"), "").unwrap();
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(temp.path().join("notes.app"), "").unwrap();
+
+        assert!(is_bundle(&app));
+        assert!(is_bundle(&custom));
+        assert!(!is_bundle(&folder));
+        assert!(!is_bundle(temp.path()));
+        // A plain file with a bundle extension is just a file
+        assert!(!is_bundle(temp.path().join("notes.app")));
+    }
+
+    #[test]
+    fn test_is_image_file() {
+        assert!(is_image_file("photo.PNG"));
+        assert!(is_image_file("/a/b/diagram.svg"));
+        assert!(!is_image_file("notes.md"));
+        assert!(!is_image_file("image"));
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(
+            decode_text(b"# Title\n".to_vec()),
+            Some("# Title\n".to_string())
+        );
+        assert_eq!(
+            decode_text("日本語".as_bytes().to_vec()),
+            Some("日本語".to_string())
+        );
+        assert_eq!(decode_text(Vec::new()), Some(String::new()));
+        assert_eq!(decode_text(b"\x7fELF\x02\x01\x01\x00".to_vec()), None);
+        assert_eq!(decode_text(vec![0xff, 0xfe, b'a']), None);
+    }
+
+    #[test]
+    fn test_find_moved_file() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("assets/diagrams")).unwrap();
+        fs::create_dir_all(temp.path().join("old")).unwrap();
+        fs::create_dir_all(temp.path().join(".cache")).unwrap();
+        fs::write(temp.path().join("assets/diagrams/flow.png"), "").unwrap();
+        fs::write(temp.path().join(".cache/flow.png"), "").unwrap();
+        fs::write(temp.path().join("assets/logo.png"), "").unwrap();
+        fs::write(temp.path().join("old/logo.png"), "").unwrap();
+
+        assert_eq!(
+            find_moved_file(temp.path(), OsStr::new("flow.png")),
+            Some(temp.path().join("assets/diagrams/flow.png"))
+        );
+        // Ambiguous and missing names are not guessed
+        assert_eq!(find_moved_file(temp.path(), OsStr::new("logo.png")), None);
+        assert_eq!(find_moved_file(temp.path(), OsStr::new("none.png")), None);
+    }
+
+    #[test]
+    fn test_is_symlink() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.md");
+        fs::write(&file, "# A").unwrap();
+        let link = temp.path().join("link.md");
+        symlink(&file, &link).unwrap();
+
+        assert!(!is_symlink(&file));
+        assert!(is_symlink(&link));
+        assert!(!is_symlink(temp.path().join("missing.md")));
+    }
+
+    #[test]
+    fn test_is_symlink_loop_detects_ancestor_target() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("docs");
+        fs::create_dir(&dir).unwrap();
+        let loop_link = dir.join("self");
+        symlink(&dir, &loop_link).unwrap();
+
+        assert!(is_symlink_loop(&loop_link));
+    }
+
+    #[test]
+    fn test_is_symlink_loop_ignores_sibling_target() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("docs");
+        let other = temp.path().join("other");
+        fs::create_dir(&dir).unwrap();
+        fs::create_dir(&other).unwrap();
+        let link = dir.join("other");
+        symlink(&other, &link).unwrap();
+
+        assert!(!is_symlink_loop(&link));
+        assert!(!is_symlink_loop(&dir));
+    }
+
+    #[test]
+    fn test_resolve_symlink_respects_follow_flag() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.md");
+        fs::write(&file, "# A").unwrap();
+        let link = temp.path().join("link.md");
+        symlink(&file, &link).unwrap();
+
+        assert_eq!(
+            resolve_symlink(&link, true),
+            Ok(fs::canonicalize(&file).unwrap())
+        );
+        assert_eq!(
+            resolve_symlink(&link, false),
+            Err("Not following symlink link.md (Follow Symlinks is off)".to_string())
+        );
+        assert_eq!(resolve_symlink(&file, false), Ok(file.clone()));
+
+        // A broken link can't be followed either
+        let broken = temp.path().join("broken.md");
+        symlink(temp.path().join("missing.md"), &broken).unwrap();
+        assert!(resolve_symlink(&broken, true)
+            .unwrap_err()
+            .starts_with("Cannot follow symlink broken.md"));
+    }
+
+    fn walked(root: &Path, options: &ScanOptions, limit: usize) -> (Vec<PathBuf>, bool) {
+        let mut files = Vec::new();
+        let partial = walk_files(root, options, limit, |path| {
+            files.push(path);
+            ControlFlow::Continue(())
+        });
+        files.sort();
+        (files, partial)
+    }
+
+    #[test]
+    fn test_walk_files_skips_hidden_and_ignored_entries() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for dir in [".cache", "node_modules", "build", "docs"] {
+            fs::create_dir(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "build/\n").unwrap();
+        for file in [
+            ".cache/a.md",
+            "node_modules/b.md",
+            "build/c.md",
+            "docs/d.md",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let options = ScanOptions {
+            ignore_patterns: vec!["node_*".to_string()],
+            respect_gitignore: true,
+            ..ScanOptions::default()
+        };
+        assert_eq!(
+            walked(root, &options, MAX_SCANNED_ENTRIES),
+            (vec![root.join("docs/d.md")], false)
+        );
+
+        let (files, _) = walked(root, &ScanOptions::default(), MAX_SCANNED_ENTRIES);
+        assert_eq!(
+            files,
+            vec![
+                root.join("build/c.md"),
+                root.join("docs/d.md"),
+                root.join("node_modules/b.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_files_stops_at_limit() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for name in ["a.md", "b.md", "c.md"] {
+            fs::write(root.join(name), "").unwrap();
+        }
+        let options = ScanOptions::default();
+
+        let (files, partial) = walked(root, &options, 2);
+        assert_eq!(files.len(), 2);
+        assert!(partial);
+
+        let (files, partial) = walked(root, &options, 3);
+        assert_eq!(files.len(), 3);
+        assert!(!partial);
+    }
+
+    #[test]
+    fn test_walk_files_reads_mutually_linked_directories_once() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("a")).unwrap();
+        fs::create_dir(root.join("b")).unwrap();
+        fs::write(root.join("a/one.md"), "").unwrap();
+        fs::write(root.join("b/two.md"), "").unwrap();
+        symlink(root.join("b"), root.join("a/l")).unwrap();
+        symlink(root.join("a"), root.join("b/l")).unwrap();
+        // Neither link points at its own ancestor
+        assert!(!is_symlink_loop(root.join("a/l")));
+
+        let (files, partial) = walked(root, &ScanOptions::default(), MAX_SCANNED_ENTRIES);
+
+        assert_eq!(files.len(), 2);
+        assert!(!partial);
+    }
+
+    #[test]
+    fn test_single_markdown_file() {
+        let temp = TempDir::new().unwrap();
+        let docs = temp.path().join("docs");
+        fs::create_dir(&docs).unwrap();
+        fs::create_dir(temp.path().join(".github")).unwrap();
+        fs::write(docs.join("guide.md"), "# Guide").unwrap();
+        fs::write(temp.path().join("notes.txt"), "notes").unwrap();
+        fs::write(temp.path().join(".github").join("hidden.md"), "# Hidden").unwrap();
+
+        assert_eq!(
+            single_markdown_file(temp.path()),
+            Some(docs.join("guide.md"))
+        );
+
+        fs::write(temp.path().join("README.md"), "# Readme").unwrap();
+
+        assert_eq!(single_markdown_file(temp.path()), None);
+    }
+
+    #[test]
+    fn test_single_markdown_file_empty_directory() {
+        let temp = TempDir::new().unwrap();
+
+        assert_eq!(single_markdown_file(temp.path()), None);
+    }
+
+    #[test]
+    fn test_path_relative_to() {
+        let path = Path::new("/project/docs/guide.md");
+
+        assert_eq!(
+            path_relative_to(path, Some(Path::new("/project"))),
+            Path::new("docs/guide.md")
+        );
+        assert_eq!(path_relative_to(path, Some(Path::new("/other"))), path);
+        assert_eq!(path_relative_to(path, Some(path)), path);
+        assert_eq!(path_relative_to(path, None), path);
+    }
+
+    #[test]
+    fn test_matches_name_pattern() {
+        assert!(matches_name_pattern("node_modules", "node_modules"));
+        assert!(!matches_name_pattern("node_modules2", "node_modules"));
+        assert!(matches_name_pattern("debug.log", "*.log"));
+        assert!(!matches_name_pattern("debug.log.md", "*.log"));
+        assert!(matches_name_pattern("a.b.log", "*.log"));
+        assert!(matches_name_pattern("img001.png", "img???.png"));
+        assert!(!matches_name_pattern("img01.png", "img???.png"));
+        assert!(matches_name_pattern("anything", "*"));
+        assert!(matches_name_pattern(".DS_Store", ".DS_*"));
+        assert!(matches_name_pattern("abcbcd", "a*bcd"));
+        assert!(!matches_name_pattern("", "?"));
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::Path;
 use std::process::Command;
 
+use crate::config::{OpenBundleBehavior, CONFIG};
+use crate::utils::file::is_bundle;
+
 /// Reveal a file in Finder (macOS) or file explorer
 pub fn reveal_in_finder(path: impl AsRef<Path>) {
     let path = path.as_ref();
@@ -23,3 +26,69 @@ pub fn reveal_in_finder(path: impl AsRef<Path>) {
         }
     }
 }
+
+/// Open a file with the application the system associates with it
+pub fn open_with_default_app(path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if let Err(e) = open::that(path) {
+        tracing::error!(%e, ?path, "Failed to open with the default application");
+    }
+}
+
+/// Open a file for editing with the configured editor command, or the
+/// system's default text editor
+pub fn open_in_editor(path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let command = CONFIG.read().editor.command.clone();
+    let mut parts = command.as_deref().unwrap_or_default().split_whitespace();
+
+    let result = match parts.next() {
+        Some(program) => Command::new(program)
+            .args(parts)
+            .arg(path)
+            .spawn()
+            .map(|_| ()),
+        None => open_in_default_text_editor(path),
+    };
+    if let Err(e) = result {
+        tracing::error!(%e, ?path, "Failed to open in editor");
+    }
+}
+
+fn open_in_default_text_editor(path: &Path) -> std::io::Result<()> {
+    // `open -t` picks the default text editor rather than the app owning the extension
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg("-t").arg(path).spawn().map(|_| ())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        open::that(path)
+    }
+}
+
+/// Divert a macOS bundle opened as a directory, as configured by
+/// `directory.onOpenBundle`
+///
+/// Returns `true` if the bundle was revealed, opened or refused (with
+/// `notify` receiving the notice) instead of being browsed; `false` for
+/// ordinary directories and bundles that should be browsed.
+pub fn divert_bundle(path: &Path, notify: impl FnOnce(String)) -> bool {
+    if !is_bundle(path) {
+        return false;
+    }
+    match CONFIG.read().directory.on_open_bundle {
+        OpenBundleBehavior::Browse => return false,
+        OpenBundleBehavior::Reveal => reveal_in_finder(path),
+        OpenBundleBehavior::Open => open_with_default_app(path),
+        OpenBundleBehavior::Refuse => {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            notify(format!("{name} is a bundle, not a folder"));
+        }
+    }
+    true
+}
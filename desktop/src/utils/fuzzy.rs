@@ -0,0 +1,163 @@
+//! fzf-style fuzzy matching for quick file search.
+//!
+//! The query's characters must appear in the candidate in order. Matches
+//! score higher when they are consecutive or start a word or path segment,
+//! and lower for every character skipped in between.
+
+const SCORE_MATCH: i64 = 16;
+const GAP_START: i64 = -3;
+const GAP_EXTENSION: i64 = -1;
+/// At the start of the candidate or after whitespace
+const BONUS_BOUNDARY_WHITE: i64 = 10;
+/// After a path separator
+const BONUS_BOUNDARY_PATH: i64 = 9;
+/// After `-`, `_`, `.` and other punctuation
+const BONUS_BOUNDARY: i64 = 8;
+/// An uppercase letter following a lowercase one
+const BONUS_CAMEL: i64 = 7;
+/// Minimum bonus for extending a run of matched characters
+const BONUS_CONSECUTIVE: i64 = -(GAP_START + GAP_EXTENSION);
+/// The first query character's bonus counts this many times
+const BONUS_FIRST_CHAR_MULTIPLIER: i64 = 2;
+
+/// Bonus for matching `current`, which follows `previous` in the candidate
+fn boundary_bonus(previous: Option<char>, current: char) -> i64 {
+    match previous {
+        None => BONUS_BOUNDARY_WHITE,
+        Some(c) if c.is_whitespace() => BONUS_BOUNDARY_WHITE,
+        Some('/' | '\\') => BONUS_BOUNDARY_PATH,
+        Some(c) if !c.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(c) if c.is_lowercase() && current.is_uppercase() => BONUS_CAMEL,
+        Some(c) if !c.is_numeric() && current.is_numeric() => BONUS_CAMEL,
+        Some(_) => 0,
+    }
+}
+
+/// A query character matched at some position of the candidate
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    score: i64,
+    /// Bonus of the first character of the run this match belongs to
+    run_bonus: i64,
+}
+
+/// Score how well `query` fuzzy-matches `candidate`, or `None` if it doesn't
+///
+/// Matching ignores case unless the query contains an uppercase letter
+/// ("smart case"), and whitespace in the query is ignored. An empty query
+/// matches everything with a score of 0. The best-scoring alignment of the
+/// query is used, so `"api"` prefers `api.md` over `apps/index.md`.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = chars.iter().copied().map(fold).collect();
+
+    // Cheap rejection before scoring alignments
+    let mut remaining = folded.iter();
+    if !query.iter().all(|q| remaining.any(|c| c == q)) {
+        return None;
+    }
+
+    let bonuses: Vec<i64> = chars
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| boundary_bonus(j.checked_sub(1).map(|k| chars[k]), c))
+        .collect();
+
+    // previous[k]: best alignment of the query so far with its last
+    // character matched at position k
+    let mut previous: Vec<Option<Cell>> = Vec::new();
+    for (i, &q) in query.iter().enumerate() {
+        let mut row: Vec<Option<Cell>> = vec![None; chars.len()];
+        for j in i..chars.len() {
+            if folded[j] != q {
+                continue;
+            }
+            let bonus = bonuses[j];
+            if i == 0 {
+                row[j] = Some(Cell {
+                    score: SCORE_MATCH + bonus * BONUS_FIRST_CHAR_MULTIPLIER,
+                    run_bonus: bonus,
+                });
+                continue;
+            }
+
+            row[j] = (i - 1..j)
+                .filter_map(|k| previous[k].map(|cell| (k, cell)))
+                .map(|(k, cell)| {
+                    if k + 1 == j {
+                        // Extending a run keeps the bonus of where it started
+                        Cell {
+                            score: cell.score
+                                + SCORE_MATCH
+                                + bonus.max(cell.run_bonus).max(BONUS_CONSECUTIVE),
+                            run_bonus: bonus.max(cell.run_bonus),
+                        }
+                    } else {
+                        let gap = (j - k - 1) as i64;
+                        Cell {
+                            score: cell.score
+                                + SCORE_MATCH
+                                + bonus
+                                + GAP_START
+                                + GAP_EXTENSION * (gap - 1),
+                            run_bonus: bonus,
+                        }
+                    }
+                })
+                .max_by_key(|cell| cell.score);
+        }
+        previous = row;
+    }
+
+    previous.into_iter().flatten().map(|cell| cell.score).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_matching() {
+        assert!(fuzzy_score("rdme", "docs/README.md").is_some());
+        assert!(fuzzy_score("docs read", "docs/README.md").is_some());
+        // Characters must appear in order
+        assert_eq!(fuzzy_score("mdr", "readme"), None);
+        assert_eq!(fuzzy_score("readmes", "readme.md"), None);
+        // Smart case: an uppercase query letter must match exactly
+        assert!(fuzzy_score("Readme", "README.md").is_none());
+        assert!(fuzzy_score("README", "README.md").is_some());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranking() {
+        let score = |query, candidate| fuzzy_score(query, candidate).unwrap();
+
+        // Consecutive matches beat scattered ones
+        assert!(score("api", "api.md") > score("api", "apps/index.md"));
+        assert!(score("guide", "user-guide.md") > score("guide", "graphs/ui/design.md"));
+        // Matches at word and path segment starts beat ones mid-word
+        assert!(score("rm", "docs/release-map.md") > score("rm", "docs/charm.md"));
+        assert!(score("setup", "docs/setup.md") > score("setup", "docs/resetupdate.md"));
+        assert!(score("ns", "NotesSummary.md") > score("ns", "onions.md"));
+        // Fewer skipped characters score higher
+        assert!(score("ab", "a-b.md") > score("ab", "a---b.md"));
+    }
+}
@@ -0,0 +1,179 @@
+//! `.gitignore` rules for hiding ignored entries in the file explorer.
+//!
+//! Each `.gitignore` is parsed by the `ignore` crate into a matcher rooted at
+//! its own directory, so its patterns are relative to where it lives. Nested
+//! files take precedence over their parents, and the repository's
+//! `.git/info/exclude` comes last.
+
+use ignore::gitignore::{Gitignore as Matcher, GitignoreBuilder};
+use std::path::Path;
+
+/// The ignore rules that apply to the entries of one directory
+#[derive(Debug, Clone, Default)]
+pub struct Gitignore {
+    /// One matcher per ignore file, from the lowest to the highest precedence
+    matchers: Vec<Matcher>,
+}
+
+impl Gitignore {
+    /// Collect the rules for the entries of `dir`
+    ///
+    /// Reads `.gitignore` from the top of the repository `dir` belongs to down
+    /// to `dir` itself, plus the repository's `.git/info/exclude`. Outside a
+    /// repository the files from `root` (the sidebar root) down are used.
+    pub fn for_directory(dir: &Path, root: Option<&Path>) -> Self {
+        let repository = dir.ancestors().find(|dir| dir.join(".git").exists());
+        let top = repository
+            .or(root.filter(|root| dir.starts_with(root)))
+            .unwrap_or(dir);
+
+        let mut gitignore = Self::default();
+        if let Some(repository) = repository {
+            gitignore.add_file(repository, &repository.join(".git/info/exclude"));
+        }
+        let mut dirs: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(top))
+            .collect();
+        dirs.reverse();
+        for dir in dirs {
+            gitignore.add_file(dir, &dir.join(".gitignore"));
+        }
+        gitignore
+    }
+
+    /// Add the rules of an ignore file whose patterns are relative to `base`
+    fn add_file(&mut self, base: &Path, file: &Path) {
+        if !file.is_file() {
+            return;
+        }
+        let mut builder = GitignoreBuilder::new(base);
+        if let Some(err) = builder.add(file) {
+            tracing::warn!(%err, ?file, "Skipping invalid .gitignore patterns");
+        }
+        self.push(&builder);
+    }
+
+    fn push(&mut self, builder: &GitignoreBuilder) {
+        match builder.build() {
+            Ok(matcher) if !matcher.is_empty() => self.matchers.push(matcher),
+            Ok(_) => {}
+            Err(err) => tracing::warn!(%err, "Failed to build .gitignore matcher"),
+        }
+    }
+
+    /// Whether `path` is ignored; the `.git` directory always is
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if path.file_name().is_some_and(|name| name == ".git") {
+            return true;
+        }
+        self.matchers
+            .iter()
+            .rev()
+            .filter(|matcher| path.starts_with(matcher.path()))
+            .map(|matcher| matcher.matched(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn gitignore(content: &str) -> Gitignore {
+        let mut builder = GitignoreBuilder::new("/repo");
+        for line in content.lines() {
+            builder.add_line(None, line).unwrap();
+        }
+        let mut gitignore = Gitignore::default();
+        gitignore.push(&builder);
+        gitignore
+    }
+
+    #[test]
+    fn test_is_ignored_patterns() {
+        let gitignore = gitignore(
+            "# build output\n\
+             target/\n\
+             *.log\n\
+             !keep.log\n\
+             /docs/draft-*.md\n\
+             **/cache/**\n",
+        );
+        let ignored = |path: &str, is_dir| gitignore.is_ignored(Path::new(path), is_dir);
+
+        assert!(ignored("/repo/target", true));
+        assert!(ignored("/repo/crates/app/target", true));
+        // Directory-only patterns leave files alone
+        assert!(!ignored("/repo/target", false));
+        assert!(ignored("/repo/logs/debug.log", false));
+        assert!(!ignored("/repo/keep.log", false));
+        // Anchored patterns match relative to the .gitignore
+        assert!(ignored("/repo/docs/draft-intro.md", false));
+        assert!(!ignored("/repo/notes/docs/draft-intro.md", false));
+        assert!(ignored("/repo/a/cache/data.json", false));
+        assert!(!ignored("/repo/a/cache", true));
+        assert!(ignored("/repo/.git", true));
+        assert!(!ignored("/repo/README.md", false));
+        // Paths outside the .gitignore's directory are unaffected
+        assert!(!ignored("/other/debug.log", false));
+    }
+
+    #[test]
+    fn test_is_ignored_character_classes_and_escapes() {
+        let gitignore = gitignore("*.py[cod]\n[Bb]in/\n\\*.md\nspace\\ \n");
+        let ignored = |path: &str, is_dir| gitignore.is_ignored(Path::new(path), is_dir);
+
+        assert!(ignored("/repo/app/module.pyc", false));
+        assert!(ignored("/repo/module.pyo", false));
+        assert!(!ignored("/repo/module.py", false));
+        assert!(ignored("/repo/bin", true));
+        assert!(ignored("/repo/tools/Bin", true));
+        assert!(!ignored("/repo/sbin", true));
+        // An escaped wildcard is literal
+        assert!(ignored("/repo/*.md", false));
+        assert!(!ignored("/repo/README.md", false));
+        // An escaped trailing space is kept
+        assert!(ignored("/repo/space ", false));
+        assert!(!ignored("/repo/space", false));
+    }
+
+    #[test]
+    fn test_for_directory_nested_gitignore() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        fs::create_dir_all(repo.join(".git/info")).unwrap();
+        fs::create_dir_all(repo.join("docs/api")).unwrap();
+        fs::write(repo.join(".gitignore"), "*.tmp\ngenerated/\n").unwrap();
+        fs::write(repo.join("docs/.gitignore"), "!notes.tmp\napi/\n").unwrap();
+        fs::write(repo.join(".git/info/exclude"), "private.md\n").unwrap();
+
+        let docs = Gitignore::for_directory(&repo.join("docs"), None);
+        assert!(docs.is_ignored(&repo.join("docs/scratch.tmp"), false));
+        // The nested file re-includes what the parent ignores
+        assert!(!docs.is_ignored(&repo.join("docs/notes.tmp"), false));
+        assert!(docs.is_ignored(&repo.join("docs/api"), true));
+        assert!(docs.is_ignored(&repo.join("docs/private.md"), false));
+
+        // Rules of a nested .gitignore don't apply above it
+        let top = Gitignore::for_directory(repo, None);
+        assert!(top.is_ignored(&repo.join("generated"), true));
+        assert!(!top.is_ignored(&repo.join("api"), true));
+    }
+
+    #[test]
+    fn test_for_directory_without_repository_uses_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join(".gitignore"), "out/\n").unwrap();
+
+        let with_root = Gitignore::for_directory(&root.join("sub"), Some(root));
+        assert!(with_root.is_ignored(&root.join("sub/out"), true));
+        let without_root = Gitignore::for_directory(&root.join("sub"), None);
+        assert!(!without_root.is_ignored(&root.join("sub/out"), true));
+    }
+}
@@ -0,0 +1,130 @@
+//! Shared HTTP client for remote fetches.
+//!
+//! All remote requests go through a single `ureq::Agent` so connections are
+//! pooled, and through a download gate that caps how many transfers run at
+//! once. The agent is rebuilt whenever the network configuration changes.
+//!
+//! Use [`get`] rather than `agent().get()` so custom headers configured for
+//! the request's host are attached.
+
+use parking_lot::{Condvar, Mutex};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+use ureq::http::{Response, Uri};
+use ureq::{Agent, Body, Proxy};
+
+use crate::config::{NetworkConfig, CONFIG};
+
+static AGENT: LazyLock<Mutex<Option<(NetworkConfig, Agent)>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Get the shared agent, (re)building it if the network config changed
+pub fn agent() -> Agent {
+    let config = CONFIG.read().network.clone();
+    let mut cached = AGENT.lock();
+    if let Some((cached_config, agent)) = cached.as_ref() {
+        if *cached_config == config {
+            return agent.clone();
+        }
+    }
+
+    let agent = build_agent(&config);
+    *cached = Some((config, agent.clone()));
+    agent
+}
+
+fn build_agent(config: &NetworkConfig) -> Agent {
+    let mut builder = Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(config.request_timeout_secs)))
+        .user_agent(config.user_agent.as_str());
+
+    // Without an explicit proxy, ureq falls back to HTTP(S)_PROXY env vars
+    if let Some(proxy) = config.proxy.as_deref().filter(|p| !p.is_empty()) {
+        match Proxy::new(proxy) {
+            Ok(proxy) => builder = builder.proxy(Some(proxy)),
+            Err(e) => tracing::warn!(%e, %proxy, "Invalid proxy in config, using environment"),
+        }
+    }
+
+    builder.build().into()
+}
+
+/// Custom headers to send with a request to `url`
+fn headers_for_url(config: &NetworkConfig, url: &str) -> BTreeMap<String, String> {
+    url.parse::<Uri>()
+        .ok()
+        .and_then(|uri| config.headers_for_host(uri.host()?).cloned())
+        .unwrap_or_default()
+}
+
+/// Perform a GET request with the custom headers configured for the URL's host
+pub fn get(url: &str) -> Result<Response<Body>, ureq::Error> {
+    let headers = headers_for_url(&CONFIG.read().network, url);
+    headers
+        .iter()
+        .fold(agent().get(url), |request, (name, value)| {
+            request.header(name, value)
+        })
+        .call()
+}
+
+// ============================================================================
+// Download concurrency gate
+// ============================================================================
+
+static ACTIVE_DOWNLOADS: Mutex<usize> = Mutex::new(0);
+static DOWNLOAD_SLOT_FREED: Condvar = Condvar::new();
+
+/// RAII permit for one in-flight download; releases its slot on drop
+pub struct DownloadPermit(());
+
+impl Drop for DownloadPermit {
+    fn drop(&mut self) {
+        *ACTIVE_DOWNLOADS.lock() -= 1;
+        DOWNLOAD_SLOT_FREED.notify_one();
+    }
+}
+
+/// Block until a download slot is free (see `network.maxConcurrentDownloads`).
+///
+/// Must be called from a background thread, never the UI thread.
+pub fn acquire_download_permit() -> DownloadPermit {
+    let limit = CONFIG.read().network.max_concurrent_downloads.max(1);
+    let mut active = ACTIVE_DOWNLOADS.lock();
+    while *active >= limit {
+        DOWNLOAD_SLOT_FREED.wait(&mut active);
+    }
+    *active += 1;
+    DownloadPermit(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_permit_releases_slot_on_drop() {
+        let before = *ACTIVE_DOWNLOADS.lock();
+        let permit = acquire_download_permit();
+        assert_eq!(*ACTIVE_DOWNLOADS.lock(), before + 1);
+        drop(permit);
+        assert_eq!(*ACTIVE_DOWNLOADS.lock(), before);
+    }
+
+    #[test]
+    fn test_headers_for_url_matches_host_only() {
+        let mut config = NetworkConfig::default();
+        config.headers.insert(
+            "api.example.com".to_string(),
+            [("Authorization".to_string(), "Bearer t".to_string())].into(),
+        );
+
+        let headers = headers_for_url(&config, "https://api.example.com:8443/img.png");
+        assert_eq!(headers["Authorization"], "Bearer t");
+        // Unrelated hosts get no custom headers
+        assert!(headers_for_url(&config, "https://images.other.org/a.png").is_empty());
+        assert!(headers_for_url(&config, "https://example.com/a.png").is_empty());
+        assert!(headers_for_url(&config, "https://api.example.com@evil.org/a.png").is_empty());
+        assert!(headers_for_url(&config, "not a url").is_empty());
+    }
+}
@@ -1,25 +1,26 @@
 //! Image utility functions for saving and processing images.
 //!
 //! This module provides utilities for:
-//! - Saving images from data URLs or HTTP/HTTPS URLs to files
+//! - Saving images from data URLs, HTTP/HTTPS URLs or served local files to files
 //! - Extracting information from data URLs (MIME type, base64 data)
 //! - Downloading images from external URLs
 
 use base64::Engine;
-use std::time::Duration;
+
+use super::http;
+use super::media::{image_type, issued_image_path};
+use crate::cache;
 
 /// Maximum allowed image size (20 MiB) to prevent memory exhaustion.
 const MAX_IMAGE_SIZE: u64 = 20 * 1024 * 1024;
 
-/// HTTP request timeout in seconds.
-const REQUEST_TIMEOUT_SECS: u64 = 30;
-
 /// Save an image from a URL (data URL or HTTP/HTTPS) to a file using a native save dialog.
 ///
 /// Opens a file save dialog and writes the image to the selected path.
 /// Supports:
 /// - Data URLs: `data:image/png;base64,<base64-data>`
 /// - HTTP/HTTPS URLs: `https://example.com/image.png`
+/// - Local images loaded from disk: `/arto-media/<encoded path>`
 ///
 /// # Examples
 ///
@@ -88,6 +89,22 @@ pub fn save_image(src: impl AsRef<str>) {
         // Extract filename from URL or use default
         let filename = extract_filename_from_url(src).unwrap_or_else(|| format!("image.{}", ext));
 
+        (bytes, filter_name, extensions, filename)
+    } else if let Some(path) = issued_image_path(src) {
+        // Large local image loaded from disk by the webview
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!(%e, ?path, "Failed to read image file");
+                return;
+            }
+        };
+        let (filter_name, extensions, ext) = get_file_info_from_mime_type(image_type(&path));
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("image.{}", ext));
+
         (bytes, filter_name, extensions, filename)
     } else {
         tracing::error!(%src, "Unsupported image source format");
@@ -129,22 +146,28 @@ fn get_file_info_from_mime_type(
 /// Download an image from an HTTP/HTTPS URL.
 ///
 /// Returns the image bytes and the content-type header if available.
+/// Images are served from the on-disk image cache while fresh, and stored
+/// there after downloading (see `imageCache` in the config).
 ///
 /// # Security
 ///
 /// This function includes several safety mechanisms:
-/// - Request timeout to prevent indefinite hangs
+/// - Request timeout to prevent indefinite hangs (configured on the shared agent)
+/// - Concurrency limit so many images don't hammer the same server
 /// - Maximum content length check to prevent memory exhaustion
 /// - Content-type validation to ensure the response is an image
-fn download_image(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
+pub fn download_image(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
     use std::io::Read;
 
-    let response = ureq::get(url)
-        .config()
-        .timeout_global(Some(Duration::from_secs(REQUEST_TIMEOUT_SECS)))
-        .build()
-        .call()
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    if let Some(cached) = cache::get_image(url) {
+        tracing::debug!(%url, "Using cached image");
+        return Ok((cached.bytes, cached.content_type));
+    }
+
+    // Held until the body has been read completely
+    let _permit = http::acquire_download_permit();
+
+    let response = http::get(url).map_err(|e| format!("HTTP request failed: {}", e))?;
 
     // Extract and normalize content-type
     let content_type = response
@@ -178,6 +201,16 @@ fn download_image(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
         }
     }
 
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let cache_control = header("cache-control");
+    let expires = header("expires");
+
     // Read the response body with a hard cap on the number of bytes
     let reader = response.into_body().into_reader();
     let mut bytes = Vec::new();
@@ -195,6 +228,16 @@ fn download_image(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
         ));
     }
 
+    cache::store_image(
+        url,
+        &bytes,
+        content_type.as_deref(),
+        cache::CacheHeaders {
+            cache_control: cache_control.as_deref(),
+            expires: expires.as_deref(),
+        },
+    );
+
     Ok((bytes, content_type))
 }
 
@@ -0,0 +1,341 @@
+//! Serving local audio, video and large image files to the webview.
+//!
+//! Media files are usually too large to inline as data URLs, and so are
+//! images above `images.inlineMaxSize`. Their sources are instead rewritten
+//! to `/arto-media/<encoded path>` URLs, which the window's asset handler
+//! streams from disk. Range requests are honored so that seeking works, and
+//! each response is capped at [`MAX_CHUNK_SIZE`] bytes.
+//!
+//! Only files the renderer issued a URL for are served, so page content can't
+//! read arbitrary files by requesting `/arto-media/<any path>`.
+
+use dioxus::desktop::wry::http::{header, Request, Response, StatusCode};
+use parking_lot::RwLock;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Name of the asset handler serving media files (first URL path segment)
+pub const MEDIA_ASSET_HANDLER: &str = "arto-media";
+
+/// Maximum number of bytes sent in a single response (4 MiB).
+const MAX_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Files a URL was built for with [`media_url`]
+static ISSUED_PATHS: LazyLock<RwLock<HashSet<PathBuf>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// Kind of media element used to play a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Video,
+    Audio,
+}
+
+impl MediaKind {
+    /// HTML tag name of the player element
+    pub fn tag_name(self) -> &'static str {
+        match self {
+            Self::Video => "video",
+            Self::Audio => "audio",
+        }
+    }
+}
+
+/// Get the media kind and MIME type of a file based on its extension
+pub fn media_type(path: &Path) -> Option<(MediaKind, &'static str)> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let media = match extension.as_str() {
+        "mp4" | "m4v" => (MediaKind::Video, "video/mp4"),
+        "webm" => (MediaKind::Video, "video/webm"),
+        "ogv" => (MediaKind::Video, "video/ogg"),
+        "mov" => (MediaKind::Video, "video/quicktime"),
+        "mp3" => (MediaKind::Audio, "audio/mpeg"),
+        "m4a" => (MediaKind::Audio, "audio/mp4"),
+        "aac" => (MediaKind::Audio, "audio/aac"),
+        "wav" => (MediaKind::Audio, "audio/wav"),
+        "ogg" | "oga" => (MediaKind::Audio, "audio/ogg"),
+        "opus" => (MediaKind::Audio, "audio/opus"),
+        "flac" => (MediaKind::Audio, "audio/flac"),
+        _ => return None,
+    };
+    Some(media)
+}
+
+/// Get the MIME type of an image file based on its extension
+pub fn image_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mime_type = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        _ => return None,
+    };
+    Some(mime_type)
+}
+
+/// Build the URL under which the asset handler serves a local media file
+///
+/// The file is registered as issued, which allows the handler to serve it.
+pub fn media_url(path: &Path) -> String {
+    ISSUED_PATHS.write().insert(path.to_path_buf());
+    format!(
+        "/{}/{}",
+        MEDIA_ASSET_HANDLER,
+        utf8_percent_encode(&path.to_string_lossy(), NON_ALPHANUMERIC)
+    )
+}
+
+/// Serve a request for a URL built by [`media_url`]
+pub fn serve_media(request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    read_media(request).unwrap_or_else(|status| {
+        tracing::debug!(uri = %request.uri(), %status, "Failed to serve media file");
+        Response::builder().status(status).body(Vec::new()).unwrap()
+    })
+}
+
+/// Get the local file a URL built by [`media_url`] points to
+///
+/// Accepts the URL as written in the document as well as the absolute form
+/// the webview resolves it to (e.g. an `<img>` element's `src` property).
+pub fn served_file_path(url: &str) -> Option<PathBuf> {
+    let prefix = format!("/{}/", MEDIA_ASSET_HANDLER);
+    let encoded = &url[url.find(&prefix)? + prefix.len()..];
+    let encoded = encoded.split(['?', '#']).next().unwrap_or_default();
+    percent_decode_str(encoded)
+        .decode_utf8()
+        .ok()
+        .map(|decoded| PathBuf::from(decoded.as_ref()))
+}
+
+/// Whether [`media_url`] was called for `path`
+pub fn is_issued(path: &Path) -> bool {
+    ISSUED_PATHS.read().contains(path)
+}
+
+/// Get the local image a URL built by [`media_url`] points to, for reading
+/// it back (exports, copying and saving images)
+///
+/// Only issued files with an image extension qualify, so a `/arto-media/`
+/// URL written into a document can't pull in other files.
+pub fn issued_image_path(url: &str) -> Option<PathBuf> {
+    served_file_path(url).filter(|path| is_issued(path) && image_type(path).is_some())
+}
+
+fn read_media(request: &Request<Vec<u8>>) -> Result<Response<Vec<u8>>, StatusCode> {
+    let path = served_file_path(request.uri().path()).ok_or(StatusCode::BAD_REQUEST)?;
+    if !is_issued(&path) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Only media and image files are served; anything else stays off-limits
+    // to the webview
+    let mime_type = media_type(&path)
+        .map(|(_, mime_type)| mime_type)
+        .or_else(|| image_type(&path))
+        .ok_or(StatusCode::FORBIDDEN)?;
+
+    let mut file = File::open(&path).map_err(|_| StatusCode::NOT_FOUND)?;
+    let len = file.metadata().map_err(|_| StatusCode::NOT_FOUND)?.len();
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, mime_type)
+        .header(header::ACCEPT_RANGES, "bytes");
+    if len == 0 {
+        return Ok(response.body(Vec::new()).unwrap());
+    }
+
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let (start, end) = match range {
+        Some(range) => parse_range(range, len).ok_or(StatusCode::RANGE_NOT_SATISFIABLE)?,
+        None => (0, len - 1),
+    };
+    let end = end.min(start + MAX_CHUNK_SIZE - 1);
+
+    let mut body = vec![0; (end - start + 1) as usize];
+    file.seek(SeekFrom::Start(start))
+        .and_then(|_| file.read_exact(&mut body))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = if range.is_some() || end + 1 < len {
+        response.status(StatusCode::PARTIAL_CONTENT).header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end, len),
+        )
+    } else {
+        response.status(StatusCode::OK)
+    };
+    Ok(response.body(body).unwrap())
+}
+
+/// Parse a `Range: bytes=...` header value into inclusive byte offsets.
+///
+/// Only the first range of a multi-range request is honored.
+fn parse_range(value: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    let (start, end) = spec.split(',').next()?.trim().split_once('-')?;
+    let last = len.checked_sub(1)?;
+
+    let (start, end) = if start.is_empty() {
+        // Suffix range: the last N bytes
+        let suffix: u64 = end.parse().ok()?;
+        if suffix == 0 {
+            return None;
+        }
+        (len.saturating_sub(suffix), last)
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end = if end.is_empty() {
+            last
+        } else {
+            end.parse::<u64>().ok()?.min(last)
+        };
+        (start, end)
+    };
+
+    (start <= end).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_media_type() {
+        assert_eq!(
+            media_type(Path::new("clip.MP4")),
+            Some((MediaKind::Video, "video/mp4"))
+        );
+        assert_eq!(
+            media_type(Path::new("song.mp3")),
+            Some((MediaKind::Audio, "audio/mpeg"))
+        );
+        assert_eq!(media_type(Path::new("notes.md")), None);
+        assert_eq!(media_type(Path::new("noext")), None);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=500-", 1000), Some((500, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=900-5000", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=0-1, 5-6", 1000), Some((0, 1)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=-0", 1000), None);
+        assert_eq!(parse_range("items=0-1", 1000), None);
+    }
+
+    #[test]
+    fn test_serve_media_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clip.mp4");
+        fs::write(&path, b"0123456789").unwrap();
+
+        let request = Request::builder()
+            .uri(format!("dioxus://index.html{}", media_url(&path)))
+            .header(header::RANGE, "bytes=2-5")
+            .body(Vec::new())
+            .unwrap();
+        let response = serve_media(&request);
+
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.body(), b"2345");
+        assert_eq!(
+            response.headers().get(header::CONTENT_RANGE).unwrap(),
+            "bytes 2-5/10"
+        );
+    }
+
+    #[test]
+    fn test_serve_media_rejects_non_media_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("secret.txt");
+        fs::write(&path, b"secret").unwrap();
+
+        let request = Request::builder()
+            .uri(format!("dioxus://index.html{}", media_url(&path)))
+            .body(Vec::new())
+            .unwrap();
+
+        assert_eq!(serve_media(&request).status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_serve_media_rejects_paths_not_issued() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("private.mp4");
+        fs::write(&path, b"private").unwrap();
+
+        // A URL written by page content rather than built by media_url
+        let url = format!(
+            "dioxus://index.html/{}/{}",
+            MEDIA_ASSET_HANDLER,
+            utf8_percent_encode(&path.to_string_lossy(), NON_ALPHANUMERIC)
+        );
+        let request = Request::builder().uri(url).body(Vec::new()).unwrap();
+
+        assert_eq!(serve_media(&request).status(), StatusCode::FORBIDDEN);
+        assert!(!is_issued(&path));
+    }
+
+    #[test]
+    fn test_serve_media_serves_images() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("large photo.JPG");
+        fs::write(&path, b"jpeg").unwrap();
+
+        let request = Request::builder()
+            .uri(format!("dioxus://index.html{}", media_url(&path)))
+            .body(Vec::new())
+            .unwrap();
+        let response = serve_media(&request);
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/jpeg"
+        );
+        assert_eq!(response.body(), b"jpeg");
+    }
+
+    #[test]
+    fn test_served_file_path() {
+        let path = Path::new("/docs/images/a b#1.png");
+        let url = media_url(path);
+
+        assert_eq!(served_file_path(&url).as_deref(), Some(path));
+        assert_eq!(
+            served_file_path(&format!("dioxus://index.html{url}?v=2")).as_deref(),
+            Some(path)
+        );
+        assert_eq!(served_file_path("data:image/png;base64,abc"), None);
+        assert_eq!(served_file_path("https://example.com/a.png"), None);
+    }
+
+    #[test]
+    fn test_issued_image_path() {
+        let image = Path::new("/docs/issued.png");
+        let url = media_url(image);
+        assert_eq!(issued_image_path(&url).as_deref(), Some(image));
+
+        // Issued, but not an image
+        let video_url = media_url(Path::new("/docs/clip.mp4"));
+        assert_eq!(issued_image_path(&video_url), None);
+
+        // Written by hand rather than built by media_url
+        let forged = format!("/{}/%2Fdocs%2Fforged.png", MEDIA_ASSET_HANDLER);
+        assert_eq!(issued_image_path(&forged), None);
+    }
+}
@@ -0,0 +1,278 @@
+use anyhow::Result;
+use dioxus::desktop::wry::WebView;
+use std::path::Path;
+use tokio::sync::oneshot;
+
+/// Page geometry of a PDF export, in PostScript points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfPageSetup {
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+}
+
+/// A bookmark in a PDF's outline, with the bookmarks nested under it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineEntry {
+    pub title: String,
+    pub children: Vec<OutlineEntry>,
+}
+
+/// A PDF print job started by [`print_to_pdf`]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct PdfPrintJob {
+    done: oneshot::Receiver<bool>,
+    /// Receives the completion callback; the print operation doesn't retain it
+    #[cfg(target_os = "macos")]
+    _delegate: objc2::rc::Retained<macos::PrintDelegate>,
+}
+
+impl PdfPrintJob {
+    /// Wait for the job to finish; `false` if the PDF could not be written
+    pub async fn finish(self) -> bool {
+        self.done.await.unwrap_or(false)
+    }
+}
+
+/// Print the content of a webview into a paginated PDF file.
+///
+/// On macOS this runs WebKit's print operation with a "save" job disposition,
+/// so no print panel is shown. The operation runs as a window-modal sheet on
+/// its own thread; the returned job reports when the file has been written.
+#[cfg(target_os = "macos")]
+pub fn print_to_pdf(
+    webview: &WebView,
+    title: &str,
+    target: &Path,
+    setup: &PdfPageSetup,
+) -> Result<PdfPrintJob> {
+    use anyhow::Context;
+    use dioxus::desktop::wry::WebViewExtMacOS;
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{msg_send, sel, MainThreadMarker};
+    use objc2_app_kit::{
+        NSPrintInfo, NSPrintJobSavingURL, NSPrintOperation, NSPrintSaveJob,
+        NSPrintingPaginationMode,
+    };
+    use objc2_foundation::{NSSize, NSString, NSURL};
+
+    let mtm = MainThreadMarker::new().context("PDF export must start on the main thread")?;
+    let url = NSURL::from_file_path(target).context("Invalid PDF file path")?;
+    let wk_webview = webview.webview();
+    let window = wk_webview
+        .window()
+        .context("Webview is not attached to a window")?;
+
+    // Work on a copy so the shared print settings stay untouched
+    let shared = NSPrintInfo::sharedPrintInfo();
+    // SAFETY: NSPrintInfo conforms to NSCopying and `copy` returns a retained
+    // NSPrintInfo.
+    let info: Retained<NSPrintInfo> = unsafe { msg_send![&*shared, copy] };
+    info.setPaperSize(NSSize::new(setup.width, setup.height));
+    info.setTopMargin(setup.margin);
+    info.setBottomMargin(setup.margin);
+    info.setLeftMargin(setup.margin);
+    info.setRightMargin(setup.margin);
+    info.setHorizontalPagination(NSPrintingPaginationMode::Fit);
+    info.setVerticalPagination(NSPrintingPaginationMode::Automatic);
+    // SAFETY: the statics are NSString constants exported by AppKit and the
+    // saving URL entry expects an NSURL.
+    unsafe {
+        info.setJobDisposition(NSPrintSaveJob);
+        let url: &AnyObject = url.as_ref();
+        info.dictionary().insert(NSPrintJobSavingURL, url);
+    }
+
+    // SAFETY: `printOperationWithPrintInfo:` is a WKWebView method (macOS
+    // 11+) returning an autoreleased NSPrintOperation.
+    let operation: Retained<NSPrintOperation> =
+        unsafe { msg_send![&*wk_webview, printOperationWithPrintInfo: &*info] };
+    operation.setJobTitle(Some(&NSString::from_str(title)));
+    operation.setShowsPrintPanel(false);
+    operation.setShowsProgressPanel(true);
+    operation.setCanSpawnSeparateThread(true);
+
+    let (done_tx, done) = oneshot::channel();
+    let delegate = macos::PrintDelegate::new(mtm, done_tx);
+    let delegate_object: &AnyObject = &delegate;
+    // WKWebView prints blank pages from a plain `runOperation`; it needs to
+    // be run modally for its window.
+    // SAFETY: the delegate implements the selector with the signature AppKit
+    // calls it with, and is kept alive by the returned job until then. The
+    // context is unused.
+    unsafe {
+        operation.runOperationModalForWindow_delegate_didRunSelector_contextInfo(
+            &window,
+            Some(delegate_object),
+            Some(sel!(printOperationDidRun:success:contextInfo:)),
+            std::ptr::null_mut(),
+        );
+    }
+    Ok(PdfPrintJob {
+        done,
+        _delegate: delegate,
+    })
+}
+
+/// Print the content of a webview into a PDF file (unsupported on other
+/// platforms).
+#[cfg(not(target_os = "macos"))]
+pub fn print_to_pdf(
+    _webview: &WebView,
+    _title: &str,
+    _target: &Path,
+    _setup: &PdfPageSetup,
+) -> Result<PdfPrintJob> {
+    anyhow::bail!("PDF export is only supported on macOS")
+}
+
+/// Replace the outline (bookmarks) of the PDF file at `target` (blocking)
+///
+/// Each entry points at the first occurrence of its title in the text that
+/// follows the previous entry's, so entries must be in document order. An
+/// entry whose title isn't found points where the previous one does.
+#[cfg(target_os = "macos")]
+pub fn add_outline(target: &Path, entries: &[OutlineEntry]) -> Result<()> {
+    use anyhow::Context;
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use objc2_foundation::NSURL;
+
+    let url = NSURL::from_file_path(target).context("Invalid PDF file path")?;
+    // SAFETY: PDFKit is linked (see `macos`), the selectors are PDFDocument
+    // and PDFOutline methods, and the argument and return types match their
+    // declarations.
+    unsafe {
+        let document: Allocated<AnyObject> = msg_send![class!(PDFDocument), alloc];
+        let document: Option<Retained<AnyObject>> = msg_send![document, initWithURL: &*url];
+        let document = document.context("Failed to open the exported PDF")?;
+
+        let root: Retained<AnyObject> = msg_send![class!(PDFOutline), new];
+        let mut cursor = macos::OutlineCursor::default();
+        macos::add_outline_items(&document, &root, entries, &mut cursor);
+        let _: () = msg_send![&*document, setOutlineRoot: &*root];
+
+        let written: bool = msg_send![&*document, writeToURL: &*url];
+        anyhow::ensure!(written, "Failed to write the PDF outline");
+    }
+    Ok(())
+}
+
+/// Replace the outline of a PDF file (unsupported on other platforms)
+#[cfg(not(target_os = "macos"))]
+pub fn add_outline(_target: &Path, _entries: &[OutlineEntry]) -> Result<()> {
+    anyhow::bail!("PDF outlines are only supported on macOS")
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::{AnyObject, Bool, NSObject};
+    use objc2::{class, define_class, msg_send, DefinedClass, MainThreadMarker, MainThreadOnly};
+    use objc2_app_kit::NSPrintOperation;
+    use objc2_foundation::{NSPoint, NSRect, NSString, NSStringCompareOptions, NSUInteger};
+    use std::cell::Cell;
+    use std::ffi::c_void;
+    use tokio::sync::oneshot;
+
+    use super::OutlineEntry;
+
+    // PDFDocument and PDFOutline are looked up by name, so link PDFKit
+    #[link(name = "PDFKit", kind = "framework")]
+    extern "C" {}
+
+    pub struct PrintDelegateIvars {
+        done: Cell<Option<oneshot::Sender<bool>>>,
+    }
+
+    define_class!(
+        // SAFETY: NSObject has no subclassing requirements and the class
+        // doesn't implement Drop.
+        #[unsafe(super(NSObject))]
+        #[thread_kind = MainThreadOnly]
+        #[name = "ArtoPdfPrintDelegate"]
+        #[ivars = PrintDelegateIvars]
+        pub struct PrintDelegate;
+
+        impl PrintDelegate {
+            #[unsafe(method(printOperationDidRun:success:contextInfo:))]
+            fn print_operation_did_run(
+                &self,
+                _operation: &NSPrintOperation,
+                success: Bool,
+                _context: *mut c_void,
+            ) {
+                if let Some(done) = self.ivars().done.take() {
+                    done.send(success.as_bool()).ok();
+                }
+            }
+        }
+    );
+
+    impl PrintDelegate {
+        pub fn new(mtm: MainThreadMarker, done: oneshot::Sender<bool>) -> Retained<Self> {
+            let this = Self::alloc(mtm).set_ivars(PrintDelegateIvars {
+                done: Cell::new(Some(done)),
+            });
+            // SAFETY: NSObject's designated initializer
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    /// Where the search for the next outline entry's title starts
+    #[derive(Default)]
+    pub struct OutlineCursor {
+        selection: Option<Retained<AnyObject>>,
+        destination: Option<Retained<AnyObject>>,
+    }
+
+    /// Append `entries` (and their children) to the outline item `parent`
+    ///
+    /// # Safety
+    ///
+    /// `document` must be a PDFDocument and `parent` a PDFOutline.
+    pub unsafe fn add_outline_items(
+        document: &AnyObject,
+        parent: &AnyObject,
+        entries: &[OutlineEntry],
+        cursor: &mut OutlineCursor,
+    ) {
+        for (index, entry) in entries.iter().enumerate() {
+            let item: Retained<AnyObject> = msg_send![class!(PDFOutline), new];
+            let label = NSString::from_str(&entry.title);
+            let _: () = msg_send![&*item, setLabel: &*label];
+
+            let found: Option<Retained<AnyObject>> = msg_send![
+                document,
+                findString: &*label,
+                fromSelection: cursor.selection.as_deref(),
+                withOptions: NSStringCompareOptions::CaseInsensitiveSearch
+            ];
+            if let Some(selection) = found {
+                let page: Option<Retained<AnyObject>> = {
+                    let pages: Retained<AnyObject> = msg_send![&*selection, pages];
+                    msg_send![&*pages, firstObject]
+                };
+                if let Some(page) = page {
+                    let bounds: NSRect = msg_send![&*selection, boundsForPage: &*page];
+                    // Page coordinates grow upwards; point at the top of the title
+                    let point = NSPoint::new(bounds.origin.x, bounds.origin.y + bounds.size.height);
+                    let destination: Allocated<AnyObject> =
+                        msg_send![class!(PDFDestination), alloc];
+                    let destination: Retained<AnyObject> =
+                        msg_send![destination, initWithPage: &*page, atPoint: point];
+                    cursor.destination = Some(destination);
+                }
+                cursor.selection = Some(selection);
+            }
+            if let Some(destination) = &cursor.destination {
+                let _: () = msg_send![&*item, setDestination: &**destination];
+            }
+
+            let _: () = msg_send![parent, insertChild: &*item, atIndex: index as NSUInteger];
+            add_outline_items(document, &item, &entry.children, cursor);
+        }
+    }
+}
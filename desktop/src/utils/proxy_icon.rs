@@ -0,0 +1,25 @@
+use dioxus::desktop::tao::window::Window;
+use std::path::Path;
+
+/// Bind the window's title bar proxy icon to a file.
+///
+/// On macOS the proxy icon can be dragged out of the title bar to Finder or
+/// other apps as a native file reference. Passing `None` (no file, Preferences,
+/// welcome page) removes the icon so there is nothing to drag.
+#[cfg(target_os = "macos")]
+pub fn set_represented_file(window: &Window, path: Option<&Path>) {
+    use dioxus::desktop::tao::platform::macos::WindowExtMacOS;
+    use objc2_app_kit::NSWindow;
+    use objc2_foundation::NSURL;
+
+    let url = path.filter(|p| p.is_file()).and_then(NSURL::from_file_path);
+    let ns_window = window.ns_window() as *const NSWindow;
+    // SAFETY: tao guarantees `ns_window()` points to the live NSWindow backing
+    // this window, and we are called from the main thread (component context).
+    let ns_window = unsafe { &*ns_window };
+    ns_window.setRepresentedURL(url.as_deref());
+}
+
+/// Bind the window's title bar proxy icon to a file (no-op on other platforms).
+#[cfg(not(target_os = "macos"))]
+pub fn set_represented_file(_window: &Window, _path: Option<&Path>) {}
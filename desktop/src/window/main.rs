@@ -5,7 +5,7 @@ use dioxus::prelude::*;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use crate::state::AppState;
@@ -13,7 +13,7 @@ use crate::state::AppState;
 use crate::assets::MAIN_STYLE;
 use crate::components::app::{App, AppProps};
 use crate::components::right_sidebar::RightSidebarTab;
-use crate::config::{WindowPositionOffset, CONFIG};
+use crate::config::{ReaderProfile, WindowPositionOffset, CONFIG};
 use crate::state::Tab;
 use crate::theme::Theme;
 use crate::utils::screen::get_current_display_bounds;
@@ -52,6 +52,7 @@ pub struct CreateMainWindowConfigParams {
     pub toc_width: f64,
     pub toc_tab: RightSidebarTab,
     pub zoom_level: f64,
+    pub reader_profile: Option<ReaderProfile>,
     pub size: LogicalSize<u32>,
     pub position: LogicalPosition<i32>,
     /// Skip position shifting for overlap avoidance.
@@ -68,6 +69,7 @@ impl CreateMainWindowConfigParams {
         let sidebar_pref = settings::get_sidebar_preference(is_first_window);
         let toc_pref = settings::get_toc_preference(is_first_window);
         let zoom_pref = settings::get_zoom_preference(is_first_window);
+        let profile_pref = settings::get_reader_profile_preference(is_first_window);
         let size_pref = settings::get_window_size_preference(is_first_window);
         let position_pref = settings::get_window_position_preference(is_first_window);
 
@@ -81,11 +83,23 @@ impl CreateMainWindowConfigParams {
             toc_width: toc_pref.width,
             toc_tab: toc_pref.tab,
             zoom_level: zoom_pref.zoom_level,
+            reader_profile: profile_pref.profile,
             size: size_pref.size,
             position: position_pref.position,
             skip_position_shift: false,
         }
     }
+
+    /// Use the window geometry remembered for a root directory, if any
+    pub fn with_directory_geometry(mut self, directory: &Path) -> Self {
+        if let Some(geometry) = settings::get_directory_geometry_preference(directory) {
+            self.size = geometry.size;
+            self.position = geometry.position;
+            // Restore the exact placement instead of cascading from other windows
+            self.skip_position_shift = true;
+        }
+        self
+    }
 }
 
 impl Default for CreateMainWindowConfigParams {
@@ -175,6 +189,35 @@ pub fn show_main_app_window() {
     ctx.window.set_focus();
 }
 
+/// Show and focus the last focused main window if it still exists.
+///
+/// Returns false if that window has been destroyed (or none was focused yet).
+pub fn show_last_focused_main_window() -> bool {
+    let Some(window_id) = get_last_focused_window() else {
+        return false;
+    };
+    list_main_windows()
+        .into_iter()
+        .find(|ctx| ctx.window.id() == window_id)
+        .map(|ctx| {
+            ctx.window.set_visible(true);
+            ctx.window.set_focus();
+        })
+        .is_some()
+}
+
+/// Show every hidden main window, focusing the last focused one.
+pub fn show_all_main_windows() {
+    for ctx in list_main_windows() {
+        if !ctx.window.is_visible() {
+            ctx.window.set_visible(true);
+        }
+    }
+    if !show_last_focused_main_window() {
+        show_main_app_window();
+    }
+}
+
 /// Focus a specific window by its ID
 /// Returns true if the window was found and focused
 ///
@@ -192,6 +235,66 @@ pub fn focus_window(window_id: WindowId) -> bool {
         .unwrap_or(false)
 }
 
+/// Focus the next (or previous) visible main window, in the order they were opened
+///
+/// The preview window of a tab drag is skipped. Returns false when there is no
+/// other window to move to.
+pub fn cycle_main_window_focus(forward: bool) -> bool {
+    let preview = super::preview::get_preview_window_id();
+    let windows: Vec<Rc<DesktopService>> = list_visible_main_windows()
+        .into_iter()
+        .filter(|ctx| Some(ctx.window.id()) != preview)
+        .collect();
+    let window_ids: Vec<WindowId> = windows.iter().map(|ctx| ctx.window.id()).collect();
+    let current = windows
+        .iter()
+        .find(|ctx| ctx.window.is_focused())
+        .map(|ctx| ctx.window.id())
+        .or_else(get_last_focused_window);
+
+    match cycle_target(&window_ids, current, forward) {
+        Some(window_id) if Some(window_id) != current => focus_window(window_id),
+        _ => false,
+    }
+}
+
+/// The item after (or before) `current`, wrapping around; the first (or last)
+/// item when `current` isn't in the list
+fn cycle_target<T: Copy + PartialEq>(items: &[T], current: Option<T>, forward: bool) -> Option<T> {
+    let position = current.and_then(|current| items.iter().position(|&item| item == current));
+    let index = match (position, forward) {
+        (Some(index), true) => (index + 1) % items.len(),
+        (Some(index), false) => (index + items.len() - 1) % items.len(),
+        (None, true) => 0,
+        (None, false) => items.len().checked_sub(1)?,
+    };
+    items.get(index).copied()
+}
+
+/// Find a visible main window, other than `exclude`, whose sidebar is rooted at `directory`
+pub fn find_window_with_root_directory(directory: &Path, exclude: WindowId) -> Option<WindowId> {
+    list_visible_main_windows()
+        .into_iter()
+        .map(|ctx| ctx.window.id())
+        .filter(|&window_id| window_id != exclude)
+        .find(|&window_id| {
+            get_window_state(window_id).is_some_and(|state| {
+                state.sidebar.read().root_directory.as_deref() == Some(directory)
+            })
+        })
+}
+
+/// Number of visible main windows and of the tabs open in them
+pub fn count_open_windows_and_tabs() -> (usize, usize) {
+    let windows = list_visible_main_windows();
+    let tabs = windows
+        .iter()
+        .filter_map(|ctx| get_window_state(ctx.window.id()))
+        .map(|state| state.tabs.read().len())
+        .sum();
+    (windows.len(), tabs)
+}
+
 pub fn close_all_main_windows() {
     let windows = list_main_windows();
     windows.iter().for_each(|w| w.close());
@@ -268,6 +371,8 @@ fn build_window_dom_and_config(
             toc_width: params.toc_width,
             toc_tab: params.toc_tab,
             zoom_level: params.zoom_level,
+            reader_profile: params.reader_profile.take(),
+            background_tabs: Vec::new(),
         },
     );
 
@@ -450,6 +555,22 @@ mod tests {
     use super::*;
     use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
 
+    #[test]
+    fn test_cycle_target() {
+        let items = [1, 2, 3];
+
+        assert_eq!(cycle_target(&items, Some(1), true), Some(2));
+        assert_eq!(cycle_target(&items, Some(3), true), Some(1));
+        assert_eq!(cycle_target(&items, Some(1), false), Some(3));
+        assert_eq!(cycle_target(&items, Some(2), false), Some(1));
+        // Unknown or no current item starts from either end
+        assert_eq!(cycle_target(&items, Some(7), true), Some(1));
+        assert_eq!(cycle_target(&items, None, false), Some(3));
+        assert_eq!(cycle_target(&[1], Some(1), true), Some(1));
+        assert_eq!(cycle_target::<i32>(&[], None, true), None);
+        assert_eq!(cycle_target::<i32>(&[], None, false), None);
+    }
+
     #[test]
     fn test_shift_position_if_needed_no_offset() {
         let base = LogicalPosition::new(10, 10);
@@ -1,12 +1,13 @@
 use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
 use dioxus::prelude::*;
 use mouse_position::mouse_position::Mouse;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::components::right_sidebar::RightSidebarTab;
 use crate::config::{
-    NewWindowBehavior, StartupBehavior, WindowDimension, WindowDimensionUnit, WindowPosition,
-    WindowPositionMode, WindowSize, CONFIG,
+    HomeDocumentBehavior, HomeDocumentConfig, NewWindowBehavior, ReaderProfile, StartupBehavior,
+    WindowDimension, WindowDimensionUnit, WindowPosition, WindowPositionMode, WindowSize, CONFIG,
 };
 use crate::state::{PersistedState, Position, Size};
 use crate::theme::Theme;
@@ -61,6 +62,43 @@ pub struct ZoomPreference {
     pub zoom_level: f64,
 }
 
+pub struct ReaderProfilePreference {
+    pub profile: Option<ReaderProfile>,
+}
+
+pub struct HomeDocumentPreference {
+    pub document: Option<PathBuf>,
+    pub with_welcome: bool,
+}
+
+pub struct DirectoryGeometryPreference {
+    pub size: LogicalSize<u32>,
+    pub position: LogicalPosition<i32>,
+}
+
+// ============================================================================
+// Launch Overrides
+// ============================================================================
+
+/// Settings given on the command line (`--theme`, `--profile`), applied to
+/// the first window instead of the saved preferences
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOverrides {
+    pub theme: Option<Theme>,
+    pub profile: Option<ReaderProfile>,
+}
+
+static LAUNCH_OVERRIDES: OnceLock<LaunchOverrides> = OnceLock::new();
+
+/// Record the command-line overrides (only the first call takes effect)
+pub fn set_launch_overrides(overrides: LaunchOverrides) {
+    let _ = LAUNCH_OVERRIDES.set(overrides);
+}
+
+fn launch_overrides() -> Option<&'static LaunchOverrides> {
+    LAUNCH_OVERRIDES.get()
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -236,6 +274,13 @@ fn resolve_window_settings(
 // ============================================================================
 
 pub fn get_theme_preference(is_first_window: bool) -> ThemePreference {
+    if let Some(theme) = launch_overrides()
+        .filter(|_| is_first_window)
+        .and_then(|overrides| overrides.theme)
+    {
+        return ThemePreference { theme };
+    }
+
     let cfg = CONFIG.read();
     let theme = choose_by_behavior(
         is_first_window,
@@ -348,6 +393,49 @@ pub fn get_zoom_preference(is_first_window: bool) -> ZoomPreference {
     }
 }
 
+/// Reader profile for a new window: the one given on the command line for
+/// the first window, otherwise `readerProfiles.defaultProfile`
+pub fn get_reader_profile_preference(is_first_window: bool) -> ReaderProfilePreference {
+    if let Some(profile) = launch_overrides()
+        .filter(|_| is_first_window)
+        .and_then(|overrides| overrides.profile.clone())
+    {
+        return ReaderProfilePreference {
+            profile: Some(profile),
+        };
+    }
+
+    let cfg = CONFIG.read();
+    let profile = cfg
+        .reader_profiles
+        .default_profile
+        .as_deref()
+        .and_then(|name| cfg.reader_profiles.find(name))
+        .cloned();
+    ReaderProfilePreference { profile }
+}
+
+/// Home document to open on launch when no file or directory was given
+pub fn get_home_document_preference() -> HomeDocumentPreference {
+    resolve_home_document(&CONFIG.read().home_document)
+}
+
+/// A configured home document that no longer exists is skipped, so the
+/// welcome screen shows as usual
+fn resolve_home_document(config: &HomeDocumentConfig) -> HomeDocumentPreference {
+    let document = config.path.clone().filter(|path| {
+        let exists = path.is_file();
+        if !exists {
+            tracing::warn!(?path, "Home document not found, showing welcome screen");
+        }
+        exists
+    });
+    HomeDocumentPreference {
+        document,
+        with_welcome: config.on_startup == HomeDocumentBehavior::WithWelcome,
+    }
+}
+
 pub fn get_window_size_preference(is_first_window: bool) -> WindowSizePreference {
     let (_, _, size) = resolve_window_settings(is_first_window);
     let (_, screen_size) = get_current_display_bounds()
@@ -375,6 +463,28 @@ pub fn get_window_position_preference(is_first_window: bool) -> WindowPositionPr
     }
 }
 
+/// Window geometry last used for a root directory.
+///
+/// Returns `None` unless remembering per-directory geometry is enabled and the
+/// directory has been used as a window root before.
+pub fn get_directory_geometry_preference(directory: &Path) -> Option<DirectoryGeometryPreference> {
+    if !CONFIG.read().directory.remember_window_geometry {
+        return None;
+    }
+    let persisted = PersistedState::load();
+    let geometry = persisted.directory_geometry(directory)?;
+    let (screen_origin, screen_size) = get_current_display_bounds()
+        .unwrap_or_else(|| (LogicalPosition::new(0, 0), LogicalSize::new(1000, 800)));
+    let size = resolve_window_size(window_size_from_state(geometry.size), screen_size);
+    let position = resolve_window_position(
+        window_position_from_state(geometry.position),
+        screen_origin,
+        screen_size,
+        size,
+    );
+    Some(DirectoryGeometryPreference { size, position })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -436,7 +546,11 @@ mod tests {
         assert!(result.width > 0.0);
         assert!(matches!(
             result.tab,
-            RightSidebarTab::Contents | RightSidebarTab::Search
+            RightSidebarTab::Contents
+                | RightSidebarTab::Search
+                | RightSidebarTab::Tags
+                | RightSidebarTab::Lint
+                | RightSidebarTab::Notes
         ));
     }
 
@@ -447,7 +561,11 @@ mod tests {
         assert!(result.width > 0.0);
         assert!(matches!(
             result.tab,
-            RightSidebarTab::Contents | RightSidebarTab::Search
+            RightSidebarTab::Contents
+                | RightSidebarTab::Search
+                | RightSidebarTab::Tags
+                | RightSidebarTab::Lint
+                | RightSidebarTab::Notes
         ));
     }
 
@@ -463,6 +581,35 @@ mod tests {
         assert!(result.zoom_level > 0.0);
     }
 
+    #[test]
+    fn test_resolve_home_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("index.md");
+        std::fs::write(&home, "# Home").unwrap();
+
+        let result = resolve_home_document(&HomeDocumentConfig {
+            path: Some(home.clone()),
+            on_startup: HomeDocumentBehavior::WithWelcome,
+        });
+        assert_eq!(result.document, Some(home));
+        assert!(result.with_welcome);
+
+        // A missing document falls back to the welcome screen
+        let result = resolve_home_document(&HomeDocumentConfig {
+            path: Some(dir.path().join("missing.md")),
+            on_startup: HomeDocumentBehavior::InsteadOfWelcome,
+        });
+        assert_eq!(result.document, None);
+        assert!(!result.with_welcome);
+
+        // So does a directory
+        let result = resolve_home_document(&HomeDocumentConfig {
+            path: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        });
+        assert_eq!(result.document, None);
+    }
+
     #[test]
     fn test_get_window_size_preference_first_window() {
         let result = get_window_size_preference(true);
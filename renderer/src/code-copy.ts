@@ -2,6 +2,44 @@ import iconCopy from "@tabler/icons/outline/copy.svg?raw";
 import iconCheck from "@tabler/icons/outline/check.svg?raw";
 import iconX from "@tabler/icons/outline/x.svg?raw";
 import iconPhoto from "@tabler/icons/outline/photo.svg?raw";
+import iconTerminal from "@tabler/icons/outline/terminal.svg?raw";
+
+export interface CodeCopyOptions {
+  /** Whether shell/console blocks get a "copy without prompts" button */
+  stripPrompts: boolean;
+}
+
+let options: CodeCopyOptions = { stripPrompts: false };
+
+/** Languages whose code blocks are treated as terminal sessions */
+const SHELL_LANGUAGES = new Set([
+  "sh",
+  "bash",
+  "zsh",
+  "fish",
+  "shell",
+  "console",
+  "shell-session",
+  "shellsession",
+  "terminal",
+  "powershell",
+  "ps1",
+  "pwsh",
+  "pycon",
+]);
+
+/** Prompt starting a command: `$ `, `% `, `❯ `, `>>> `, `PS C:\> ` */
+const PROMPT = /^\s*(?:\$|%|❯|>>>|PS(?: [^>]*)?>)(?: |$)/;
+
+/** Prompt continuing the previous command: `> ` (PS2), `... ` (Python) */
+const CONTINUATION_PROMPT = /^\s*(?:>|\.\.\.)(?: |$)/;
+
+/**
+ * Update the code copy options (called from Rust on startup).
+ */
+export function configure(next: CodeCopyOptions): void {
+  options = { ...next };
+}
 
 /**
  * Add copy buttons to code blocks
@@ -28,10 +66,13 @@ function addCopyButton(pre: HTMLPreElement): void {
   // Check if this is a Mermaid diagram
   const isMermaid = pre.classList.contains("preprocessed-mermaid");
 
-  // Create text copy button
-  const textButton = document.createElement("button");
-  textButton.className = isMermaid ? "copy-button copy-button-text" : "copy-button";
-  textButton.setAttribute("aria-label", "Copy code to clipboard");
+  // Code blocks come with their button from Rust; others get a new one
+  const renderedButton = findRenderedCopyButton(pre);
+  const textButton = renderedButton ?? document.createElement("button");
+  if (!renderedButton) {
+    textButton.className = isMermaid ? "copy-button copy-button-text" : "copy-button";
+    textButton.setAttribute("aria-label", "Copy code to clipboard");
+  }
   textButton.innerHTML = getCopyIcon();
 
   // Handle click event
@@ -42,12 +83,81 @@ function addCopyButton(pre: HTMLPreElement): void {
   });
 
   // Add button to pre element
-  pre.appendChild(textButton);
+  if (!renderedButton) {
+    pre.appendChild(textButton);
+  }
 
   // Add image copy button for Mermaid
   if (isMermaid) {
     addImageCopyButton(pre);
+  } else if (options.stripPrompts && isShellBlock(pre)) {
+    addCommandsCopyButton(pre);
+  }
+}
+
+function addCommandsCopyButton(pre: HTMLPreElement): void {
+  const button = document.createElement("button");
+  button.className = "copy-button copy-button-commands";
+  button.setAttribute("aria-label", "Copy commands without prompts");
+  button.title = "Copy commands without prompts";
+  button.innerHTML = getTerminalIcon();
+
+  button.addEventListener("click", async (e) => {
+    e.preventDefault();
+    e.stopPropagation();
+    try {
+      await navigator.clipboard.writeText(stripPrompts(getContentToCopy(pre)));
+      showSuccessFeedback(button, getTerminalIcon());
+    } catch (error) {
+      console.error("Failed to copy commands to clipboard", error);
+      showErrorFeedback(button, getTerminalIcon());
+    }
+  });
+
+  pre.appendChild(button);
+}
+
+/** Check the `language-xxx` class of the code element for a shell language */
+function isShellBlock(pre: HTMLPreElement): boolean {
+  const code = pre.querySelector("code");
+  if (!code) {
+    return false;
+  }
+  return Array.from(code.classList).some(
+    (name) => name.startsWith("language-") && SHELL_LANGUAGES.has(name.slice(9).toLowerCase()),
+  );
+}
+
+/**
+ * Keep only the commands of a terminal session, without their prompts.
+ *
+ * Lines starting with a prompt are commands; continuation prompts and lines
+ * following a trailing backslash belong to the command before them. Anything
+ * else is treated as output and dropped. Text without any prompt is returned
+ * unchanged, since it is a script rather than a session.
+ */
+export function stripPrompts(text: string): string {
+  const lines = text.replace(/\n$/, "").split("\n");
+  if (!lines.some((line) => PROMPT.test(line))) {
+    return text;
   }
+
+  const commands: string[] = [];
+  let inCommand = false;
+  for (const line of lines) {
+    const previous = commands[commands.length - 1];
+    if (PROMPT.test(line)) {
+      commands.push(line.replace(PROMPT, ""));
+      inCommand = true;
+    } else if (inCommand && CONTINUATION_PROMPT.test(line)) {
+      commands.push(line.replace(CONTINUATION_PROMPT, ""));
+    } else if (inCommand && previous?.endsWith("\\")) {
+      commands.push(line);
+    } else {
+      inCommand = false;
+    }
+  }
+  return commands.join("\n") + "\n";
 }
 
 function addImageCopyButton(pre: HTMLPreElement): void {
@@ -76,7 +186,22 @@ async function copyToClipboard(pre: HTMLPreElement, button: HTMLButtonElement):
   }
 }
 
+/** Copy button rendered around a code block, holding its source in `data-code` */
+function findRenderedCopyButton(pre: HTMLPreElement): HTMLButtonElement | null {
+  const wrapper = pre.parentElement;
+  if (!wrapper?.classList.contains("code-block")) {
+    return null;
+  }
+  return wrapper.querySelector<HTMLButtonElement>(":scope > .copy-button[data-code]");
+}
+
 function getContentToCopy(pre: HTMLPreElement): string {
+  // Raw source of code blocks, unaffected by highlighting markup
+  const code = findRenderedCopyButton(pre)?.dataset.code;
+  if (code !== undefined) {
+    return code;
+  }
+
   // Check if data-original-content exists (for math and mermaid)
   const originalContent = pre.dataset.originalContent;
   if (originalContent) {
@@ -92,24 +217,24 @@ function getContentToCopy(pre: HTMLPreElement): string {
   return pre.textContent || "";
 }
 
-function showSuccessFeedback(button: HTMLButtonElement): void {
+function showSuccessFeedback(button: HTMLButtonElement, icon = getCopyIcon()): void {
   button.innerHTML = getCheckIcon();
   button.classList.add("copied");
 
   // Reset after 2 seconds
   setTimeout(() => {
-    button.innerHTML = getCopyIcon();
+    button.innerHTML = icon;
     button.classList.remove("copied");
   }, 2000);
 }
 
-function showErrorFeedback(button: HTMLButtonElement): void {
+function showErrorFeedback(button: HTMLButtonElement, icon = getCopyIcon()): void {
   button.innerHTML = getErrorIcon();
   button.classList.add("error");
 
   // Reset after 2 seconds
   setTimeout(() => {
-    button.innerHTML = getCopyIcon();
+    button.innerHTML = icon;
     button.classList.remove("error");
   }, 2000);
 }
@@ -131,6 +256,10 @@ function getPhotoIcon(): string {
   return iconPhoto;
 }
 
+function getTerminalIcon(): string {
+  return iconTerminal;
+}
+
 async function copyMermaidAsImage(pre: HTMLPreElement, button: HTMLButtonElement): Promise<void> {
   if (!navigator.clipboard?.write) {
     showErrorFeedback(button);
@@ -0,0 +1,81 @@
+/**
+ * Highlighting of code block lines longer than the ruler column.
+ *
+ * The ruler itself is plain CSS (injected from Rust with the other document
+ * styles). Long lines are marked with bands in an overlay behind the code, so
+ * the code element, its text and copy buttons are left untouched.
+ */
+
+export interface CodeRulerOptions {
+  /** Whether the ruler is shown */
+  enabled: boolean;
+  /** Line length limit in columns */
+  column: number;
+  /** Whether lines longer than `column` are highlighted */
+  highlightLongLines: boolean;
+}
+
+let options: CodeRulerOptions = { enabled: false, column: 80, highlightLongLines: false };
+
+/**
+ * Update the ruler options (called from Rust on startup).
+ */
+export function configure(next: CodeRulerOptions): void {
+  options = { ...next };
+}
+
+/**
+ * Add long-line bands to code blocks.
+ */
+export function markLongLines(container: Element): void {
+  if (!options.enabled || !options.highlightLongLines || options.column <= 0) {
+    return;
+  }
+  const blocks = container.querySelectorAll<HTMLElement>(
+    'pre:not([class*="preprocessed-"]):not([data-long-lines-checked]) > code',
+  );
+  blocks.forEach((code) => {
+    const pre = code.parentElement as HTMLPreElement;
+    pre.dataset.longLinesChecked = "yes";
+    markBlock(pre, code);
+  });
+}
+
+function markBlock(pre: HTMLPreElement, code: HTMLElement): void {
+  const style = getComputedStyle(pre);
+  const tabSize = Number.parseInt(style.tabSize, 10) || 8;
+  const lines = (code.textContent || "").replace(/\n$/, "").split("\n");
+  const longLines = lines
+    .map((line, index) => (columnWidth(line, tabSize) > options.column ? index : -1))
+    .filter((index) => index >= 0);
+  if (longLines.length === 0) {
+    return;
+  }
+
+  const fontSize = Number.parseFloat(style.fontSize) || 12;
+  const lineHeight = Number.parseFloat(style.lineHeight) || fontSize * 1.45;
+
+  const overlay = document.createElement("div");
+  overlay.className = "code-long-lines";
+  overlay.setAttribute("aria-hidden", "true");
+  overlay.style.top = style.paddingTop;
+  overlay.style.width = `${pre.scrollWidth}px`;
+  for (const index of longLines) {
+    const band = document.createElement("div");
+    band.className = "code-long-line";
+    band.style.top = `${index * lineHeight}px`;
+    band.style.height = `${lineHeight}px`;
+    overlay.appendChild(band);
+  }
+  pre.style.position = "relative";
+  pre.prepend(overlay);
+}
+
+/** Display width of a line in columns, with tabs expanded to tab stops */
+function columnWidth(line: string, tabSize: number): number {
+  let width = 0;
+  for (const char of line) {
+    width = char === "\t" ? width + tabSize - (width % tabSize) : width + 1;
+  }
+  return width;
+}
@@ -16,6 +16,8 @@ export interface ContextMenuData {
   y: number;
   has_selection: boolean;
   selected_text: string;
+  /** Whether the click is in the side pane ("Open to the Side") */
+  side_pane: boolean;
 }
 
 /**
@@ -208,6 +210,7 @@ export function setup(sendToRust: (data: ContextMenuData) => void): void {
       y: event.clientY,
       has_selection: hasSelection,
       selected_text: selectedText,
+      side_pane: markdownBody.closest(".side-pane") !== null,
     };
 
     sendToRust(data);
@@ -1,3 +1,24 @@
+import { reveal } from "./long-tables";
+import { scrollToElement } from "./scroll-target";
+
+/**
+ * Positioning of the active match when navigating between matches.
+ */
+export interface SearchOptions {
+  /**
+   * Source lines of context kept above the match's block (0 centers the
+   * match instead)
+   */
+  contextLines: number;
+  /** Whether the matched block briefly flashes after navigation */
+  flash: boolean;
+}
+
+let options: SearchOptions = { contextLines: 0, flash: false };
+
+/** Duration of the block flash, matching the CSS animation */
+const FLASH_DURATION_MS = 1000;
+
 /**
  * Pinned search definition from Rust.
  */
@@ -226,7 +247,7 @@ function navigateToMatch(direction: "next" | "prev"): number {
   // Add active class to new current and scroll into view
   const next = state.highlightElements[state.currentIndex];
   next?.classList.add("search-highlight-active");
-  next?.scrollIntoView({ behavior: "smooth", block: "center" });
+  revealMatch(next);
 
   return state.currentIndex + 1; // 1-based for display
 }
@@ -303,6 +324,77 @@ export function setup(cb: SearchCallback): void {
   callback = cb;
 }
 
+/**
+ * Update the match positioning options (called from Rust on startup).
+ */
+export function configure(next: SearchOptions): void {
+  options = {
+    contextLines: Math.max(Math.floor(next.contextLines), 0),
+    flash: next.flash,
+  };
+}
+
+/**
+ * Scroll to a match, keeping `contextLines` source lines of context above
+ * its block, and flash the block if enabled.
+ *
+ * The context is measured from `data-source-line` of the enclosing block, so
+ * every match lands the same way regardless of the previous scroll position.
+ */
+function revealMatch(match: HTMLElement | undefined): void {
+  if (!match) {
+    return;
+  }
+  // Show the match's table row before measuring where it is
+  reveal(match);
+  const block = match.closest<HTMLElement>(".markdown-body [data-source-line]");
+  const contextTarget = block ? findContextElement(block) : null;
+  const content = document.querySelector<HTMLElement>(".content");
+
+  // Fall back to centering when the context would push the match out of view
+  const fits =
+    contextTarget &&
+    content &&
+    match.getBoundingClientRect().bottom - contextTarget.getBoundingClientRect().top <
+      content.clientHeight / 2;
+  if (fits) {
+    scrollToElement(contextTarget, "start");
+  } else {
+    scrollToElement(match, "center");
+  }
+
+  if (options.flash) {
+    const flashed = block ?? match;
+    // Restart the animation when the same block is flashed again
+    flashed.classList.remove("search-result-flash");
+    void flashed.offsetWidth;
+    flashed.classList.add("search-result-flash");
+    setTimeout(() => flashed.classList.remove("search-result-flash"), FLASH_DURATION_MS);
+  }
+}
+
+/**
+ * First block starting at most `contextLines` lines above `block`.
+ */
+function findContextElement(block: HTMLElement): HTMLElement | null {
+  if (options.contextLines === 0) {
+    return null;
+  }
+  const line = Number(block.dataset.sourceLine);
+  if (!Number.isFinite(line)) {
+    return null;
+  }
+  const firstLine = line - options.contextLines;
+  const blocks = document.querySelectorAll<HTMLElement>(".markdown-body [data-source-line]");
+  for (const candidate of blocks) {
+    const candidateLine = Number(candidate.dataset.sourceLine);
+    if (candidateLine >= firstLine && candidateLine <= line) {
+      return candidate;
+    }
+  }
+  return block;
+}
+
 /**
  * Re-apply the current search query and pinned searches after DOM changes (e.g., tab switch).
  * This preserves highlights across tab navigation.
@@ -338,7 +430,7 @@ export function navigateTo(index: number): void {
   state.currentIndex = index;
   const target = state.highlightElements[index];
   target?.classList.add("search-highlight-active");
-  target?.scrollIntoView({ behavior: "smooth", block: "center" });
+  revealMatch(target);
 
   // Notify callback with unified format
   const newCurrent = index + 1;
@@ -381,7 +473,7 @@ export function scrollToPinnedMatch(pinnedId: string, index: number): void {
   }
 
   const target = elements[index];
-  target?.scrollIntoView({ behavior: "smooth", block: "center" });
+  scrollToElement(target, "center");
 
   // Brief highlight effect
   target?.classList.add("pinned-highlight-flash");
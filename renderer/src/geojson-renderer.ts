@@ -0,0 +1,293 @@
+/**
+ * Rendering `geojson` code blocks as interactive maps.
+ *
+ * Blocks are preprocessed in Rust into `pre.preprocessed-geojson` with the
+ * raw GeoJSON in `data-original-content`. Geometries are drawn into an SVG in
+ * Web Mercator, without map tiles so documents render offline. The map can
+ * be panned by dragging and zoomed with the wheel; double-click resets it.
+ * Hovering a feature shows its `name` (or all properties).
+ *
+ * If the source does not parse, the block keeps showing the raw GeoJSON.
+ */
+
+type Position = number[];
+
+interface Geometry {
+  type: string;
+  coordinates?: unknown;
+  geometries?: Geometry[];
+}
+
+interface Shape {
+  geometry: Geometry;
+  label: string | null;
+}
+
+const SVG_NS = "http://www.w3.org/2000/svg";
+
+/** Width of the map's coordinate space; height follows the data's aspect */
+const VIEW_WIDTH = 600;
+const MIN_VIEW_HEIGHT = 200;
+const MAX_VIEW_HEIGHT = 450;
+/** Padding around the data as a fraction of the view */
+const PADDING = 0.05;
+/** Point marker radius in screen pixels */
+const POINT_RADIUS = 4;
+
+export function renderGeoJson(container: Element): void {
+  const blocks = container.querySelectorAll<HTMLElement>(
+    "pre.preprocessed-geojson:not([data-rendered])",
+  );
+  blocks.forEach((element) => {
+    try {
+      renderMap(element);
+      element.dataset.rendered = "true";
+    } catch (error) {
+      console.error("Failed to render GeoJSON map:", error);
+      // Keep the raw source visible as a fallback
+      element.dataset.rendered = "error";
+      element.title = `GeoJSON error: ${error}`;
+    }
+  });
+}
+
+function renderMap(element: HTMLElement): void {
+  const source = element.dataset.originalContent || element.textContent || "";
+  const shapes = collectShapes(JSON.parse(source));
+
+  const points: Array<[number, number]> = [];
+  for (const shape of shapes) {
+    forEachPosition(shape.geometry, (position) => points.push(project(position)));
+  }
+  if (points.length === 0) {
+    throw new Error("no coordinates found");
+  }
+
+  // Fit the data into the view, keeping its aspect ratio
+  const xs = points.map(([x]) => x);
+  const ys = points.map(([, y]) => y);
+  const minX = Math.min(...xs);
+  const minY = Math.min(...ys);
+  const spanX = Math.max(Math.max(...xs) - minX, 1e-9);
+  const spanY = Math.max(Math.max(...ys) - minY, 1e-9);
+  const height = Math.min(
+    Math.max((VIEW_WIDTH * spanY) / spanX, MIN_VIEW_HEIGHT),
+    MAX_VIEW_HEIGHT,
+  );
+  const scale = Math.min(
+    (VIEW_WIDTH * (1 - 2 * PADDING)) / spanX,
+    (height * (1 - 2 * PADDING)) / spanY,
+  );
+  const offsetX = (VIEW_WIDTH - spanX * scale) / 2;
+  const offsetY = (height - spanY * scale) / 2;
+  const toView = ([x, y]: [number, number]): [number, number] => [
+    offsetX + (x - minX) * scale,
+    // SVG y grows downwards
+    height - (offsetY + (y - minY) * scale),
+  ];
+
+  const svg = document.createElementNS(SVG_NS, "svg");
+  svg.classList.add("geojson-map");
+  svg.setAttribute("viewBox", `0 0 ${VIEW_WIDTH} ${height}`);
+  svg.setAttribute("role", "img");
+
+  for (const shape of shapes) {
+    drawGeometry(svg, shape, (position) => toView(project(position)));
+  }
+
+  element.innerHTML = "";
+  element.appendChild(svg);
+  makeInteractive(svg, VIEW_WIDTH, height);
+}
+
+/**
+ * Flatten a GeoJSON object into geometries with their hover labels.
+ */
+function collectShapes(value: unknown): Shape[] {
+  if (!value || typeof value !== "object") {
+    throw new Error("not a GeoJSON object");
+  }
+  const object = value as Record<string, unknown>;
+  switch (object.type) {
+    case "FeatureCollection":
+      return ((object.features as unknown[]) || []).flatMap(collectShapes);
+    case "Feature": {
+      if (!object.geometry) {
+        return [];
+      }
+      return [{ geometry: object.geometry as Geometry, label: featureLabel(object.properties) }];
+    }
+    default:
+      return [{ geometry: object as unknown as Geometry, label: null }];
+  }
+}
+
+function featureLabel(properties: unknown): string | null {
+  if (!properties || typeof properties !== "object") {
+    return null;
+  }
+  const props = properties as Record<string, unknown>;
+  if (typeof props.name === "string") {
+    return props.name;
+  }
+  const entries = Object.entries(props);
+  if (entries.length === 0) {
+    return null;
+  }
+  return entries.map(([key, value]) => `${key}: ${JSON.stringify(value)}`).join("\n");
+}
+
+function forEachPosition(geometry: Geometry, callback: (position: Position) => void): void {
+  if (geometry.type === "GeometryCollection") {
+    (geometry.geometries || []).forEach((child) => forEachPosition(child, callback));
+    return;
+  }
+  const visit = (value: unknown): void => {
+    if (!Array.isArray(value)) {
+      return;
+    }
+    if (typeof value[0] === "number") {
+      callback(value as Position);
+    } else {
+      value.forEach(visit);
+    }
+  };
+  visit(geometry.coordinates);
+}
+
+/**
+ * Web Mercator projection of [longitude, latitude] (y grows northwards).
+ */
+function project([lon, lat]: Position): [number, number] {
+  const clamped = Math.max(Math.min(lat, 85), -85);
+  const x = (lon * Math.PI) / 180;
+  const y = Math.log(Math.tan(Math.PI / 4 + (clamped * Math.PI) / 360));
+  return [x, y];
+}
+
+function drawGeometry(
+  svg: SVGSVGElement,
+  shape: Shape,
+  toView: (position: Position) => [number, number],
+): void {
+  const { geometry } = shape;
+  const coordinates = geometry.coordinates as never;
+  const ring = (positions: Position[], close: boolean): string =>
+    positions
+      .map((position, index) => `${index === 0 ? "M" : "L"}${toView(position).join(",")}`)
+      .join("") + (close ? "Z" : "");
+
+  const add = (el: SVGElement, className: string) => {
+    el.classList.add(className);
+    if (shape.label) {
+      const title = document.createElementNS(SVG_NS, "title");
+      title.textContent = shape.label;
+      el.appendChild(title);
+    }
+    svg.appendChild(el);
+  };
+  const path = (d: string, className: string) => {
+    const el = document.createElementNS(SVG_NS, "path");
+    el.setAttribute("d", d);
+    add(el, className);
+  };
+  const point = (position: Position) => {
+    const [cx, cy] = toView(position);
+    const el = document.createElementNS(SVG_NS, "circle");
+    el.setAttribute("cx", String(cx));
+    el.setAttribute("cy", String(cy));
+    el.setAttribute("r", String(POINT_RADIUS));
+    add(el, "geojson-point");
+  };
+
+  switch (geometry.type) {
+    case "Point":
+      point(coordinates);
+      break;
+    case "MultiPoint":
+      (coordinates as Position[]).forEach(point);
+      break;
+    case "LineString":
+      path(ring(coordinates, false), "geojson-line");
+      break;
+    case "MultiLineString":
+      path((coordinates as Position[][]).map((line) => ring(line, false)).join(""), "geojson-line");
+      break;
+    case "Polygon":
+      path((coordinates as Position[][]).map((r) => ring(r, true)).join(""), "geojson-polygon");
+      break;
+    case "MultiPolygon":
+      path(
+        (coordinates as Position[][][]).flatMap((polygon) => polygon.map((r) => ring(r, true))).join(""),
+        "geojson-polygon",
+      );
+      break;
+    case "GeometryCollection":
+      (geometry.geometries || []).forEach((child) =>
+        drawGeometry(svg, { geometry: child, label: shape.label }, toView),
+      );
+      break;
+    default:
+      throw new Error(`unsupported geometry type: ${geometry.type}`);
+  }
+}
+
+/**
+ * Pan by dragging, zoom with the wheel around the cursor, reset on double-click.
+ */
+function makeInteractive(svg: SVGSVGElement, width: number, height: number): void {
+  let view = { x: 0, y: 0, width, height };
+  const apply = () => {
+    svg.setAttribute("viewBox", `${view.x} ${view.y} ${view.width} ${view.height}`);
+    // Keep point markers the same size on screen
+    const radius = String((POINT_RADIUS * view.width) / width);
+    svg.querySelectorAll("circle").forEach((circle) => circle.setAttribute("r", radius));
+  };
+  const unitsPerPixel = () => view.width / svg.getBoundingClientRect().width;
+
+  svg.addEventListener(
+    "wheel",
+    (event) => {
+      event.preventDefault();
+      const rect = svg.getBoundingClientRect();
+      const fx = (event.clientX - rect.left) / rect.width;
+      const fy = (event.clientY - rect.top) / rect.height;
+      const factor = Math.exp(event.deltaY * 0.002);
+      const nextWidth = Math.min(Math.max(view.width * factor, width / 50), width * 4);
+      const nextHeight = (nextWidth * height) / width;
+      view = {
+        x: view.x + (view.width - nextWidth) * fx,
+        y: view.y + (view.height - nextHeight) * fy,
+        width: nextWidth,
+        height: nextHeight,
+      };
+      apply();
+    },
+    { passive: false },
+  );
+
+  svg.addEventListener("mousedown", (event) => {
+    event.preventDefault();
+    let lastX = event.clientX;
+    let lastY = event.clientY;
+    const onMove = (move: MouseEvent) => {
+      const units = unitsPerPixel();
+      view.x -= (move.clientX - lastX) * units;
+      view.y -= (move.clientY - lastY) * units;
+      lastX = move.clientX;
+      lastY = move.clientY;
+      apply();
+    };
+    const onUp = () => {
+      document.removeEventListener("mousemove", onMove);
+      document.removeEventListener("mouseup", onUp);
+    };
+    document.addEventListener("mousemove", onMove);
+    document.addEventListener("mouseup", onUp);
+  });
+
+  svg.addEventListener("dblclick", () => {
+    view = { x: 0, y: 0, width, height };
+    apply();
+  });
+}
@@ -0,0 +1,38 @@
+/**
+ * `#` links that Rust puts at the start of each heading.
+ *
+ * Clicking one scrolls to its heading, puts the fragment in the address and
+ * copies it, without a navigation that would reset the viewer.
+ */
+
+import { scrollToElement } from "./scroll-target";
+
+const COPIED_DURATION_MS = 1500;
+
+/**
+ * Handle the heading links of every document.
+ */
+export function init(): void {
+  document.addEventListener("click", (event) => {
+    const link = (event.target as Element | null)?.closest<HTMLAnchorElement>("a.heading-anchor");
+    const fragment = link?.getAttribute("href");
+    if (!link || !fragment?.startsWith("#")) {
+      return;
+    }
+    event.preventDefault();
+    scrollToElement(link.parentElement, "start");
+    history.replaceState(history.state, "", fragment);
+    void copyFragment(link, fragment);
+  });
+}
+
+async function copyFragment(link: HTMLAnchorElement, fragment: string): Promise<void> {
+  try {
+    await navigator.clipboard.writeText(fragment);
+  } catch (error) {
+    console.error("Failed to copy heading link to clipboard", error);
+    return;
+  }
+  link.classList.add("copied");
+  setTimeout(() => link.classList.remove("copied"), COPIED_DURATION_MS);
+}
@@ -0,0 +1,25 @@
+/**
+ * Inline table of contents that Rust renders in place of a `[TOC]` line.
+ *
+ * Its links scroll to their heading the same way the sidebar TOC does,
+ * instead of a fragment navigation.
+ */
+
+import { scrollToElement } from "./scroll-target";
+
+/**
+ * Handle the links of every inline table of contents.
+ */
+export function init(): void {
+  document.addEventListener("click", (event) => {
+    const link = (event.target as Element | null)?.closest<HTMLAnchorElement>(
+      ".inline-toc a[href^='#']",
+    );
+    const id = link?.getAttribute("href")?.slice(1);
+    if (!id) {
+      return;
+    }
+    event.preventDefault();
+    scrollToElement(document.getElementById(decodeURIComponent(id)), "start");
+  });
+}
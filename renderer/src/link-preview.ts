@@ -0,0 +1,134 @@
+/**
+ * Previews of linked markdown documents shown when hovering `.md-link` spans.
+ *
+ * After the hover delay, the link target (`data-md-link`, relative to the
+ * current file) is sent to Rust, which reads the document and answers via
+ * `show()` with its title and an excerpt. Answers are cached until the next
+ * `setup()` call, which happens whenever the viewed file changes.
+ */
+
+export interface LinkPreview {
+  title: string | null;
+  excerpt: string;
+}
+
+export interface LinkPreviewOptions {
+  /** Hover time in milliseconds before the preview is requested */
+  delayMs: number;
+}
+
+type RequestCallback = (href: string) => void;
+
+let request: RequestCallback | null = null;
+let options: LinkPreviewOptions = { delayMs: 400 };
+/** Previews by href; `null` marks targets that could not be previewed */
+const cache = new Map<string, LinkPreview | null>();
+
+let hovered: HTMLElement | null = null;
+let hoverTimer: number | null = null;
+let popover: HTMLElement | null = null;
+let listening = false;
+
+/**
+ * Enable previews with `requestPreview` asking Rust for a target, or disable
+ * them with `null`.
+ */
+export function setup(next: LinkPreviewOptions | null, requestPreview?: RequestCallback): void {
+  cache.clear();
+  hide();
+  request = next && requestPreview ? requestPreview : null;
+  if (next) {
+    options = next;
+  }
+  if (!listening) {
+    document.addEventListener("mouseover", handleMouseOver);
+    document.addEventListener("mouseout", handleMouseOut);
+    document.addEventListener("mousedown", () => hide(), true);
+    listening = true;
+  }
+}
+
+/**
+ * Receive the preview of `href` from Rust (`null` if it can't be previewed).
+ */
+export function show(href: string, preview: LinkPreview | null): void {
+  cache.set(href, preview);
+  if (hovered && hovered.dataset.mdLink === href) {
+    render(hovered, preview);
+  }
+}
+
+function handleMouseOver(event: MouseEvent): void {
+  if (!request) {
+    return;
+  }
+  const link = (event.target as HTMLElement).closest<HTMLElement>(".markdown-body [data-md-link]");
+  if (!link || link === hovered) {
+    return;
+  }
+  hide();
+  hovered = link;
+  hoverTimer = window.setTimeout(() => {
+    hoverTimer = null;
+    const href = link.dataset.mdLink!;
+    if (cache.has(href)) {
+      render(link, cache.get(href)!);
+    } else {
+      request?.(href);
+    }
+  }, options.delayMs);
+}
+
+function handleMouseOut(event: MouseEvent): void {
+  if (!hovered) {
+    return;
+  }
+  // Ignore moves between children of the link
+  const next = event.relatedTarget as Node | null;
+  if (next && hovered.contains(next)) {
+    return;
+  }
+  hide();
+}
+
+function render(link: HTMLElement, preview: LinkPreview | null): void {
+  if (!preview || (!preview.title && !preview.excerpt)) {
+    return;
+  }
+  popover?.remove();
+  popover = document.createElement("div");
+  popover.className = "link-preview";
+  popover.setAttribute("role", "tooltip");
+  if (preview.title) {
+    const title = document.createElement("div");
+    title.className = "link-preview-title";
+    title.textContent = preview.title;
+    popover.appendChild(title);
+  }
+  if (preview.excerpt) {
+    const excerpt = document.createElement("div");
+    excerpt.className = "link-preview-excerpt";
+    excerpt.textContent = preview.excerpt;
+    popover.appendChild(excerpt);
+  }
+  document.body.appendChild(popover);
+
+  // Below the link, or above it if there is no room
+  const rect = link.getBoundingClientRect();
+  const { offsetWidth: width, offsetHeight: height } = popover;
+  const left = Math.min(Math.max(rect.left, 8), window.innerWidth - width - 8);
+  const top =
+    rect.bottom + 6 + height > window.innerHeight ? rect.top - height - 6 : rect.bottom + 6;
+  popover.style.left = `${left}px`;
+  popover.style.top = `${Math.max(top, 8)}px`;
+}
+
+function hide(): void {
+  if (hoverTimer !== null) {
+    window.clearTimeout(hoverTimer);
+    hoverTimer = null;
+  }
+  hovered = null;
+  popover?.remove();
+  popover = null;
+}
@@ -0,0 +1,44 @@
+/**
+ * Long tables collapsed beyond `tables.maxRows`.
+ *
+ * Rust marks the extra body rows with `table-row-collapsed` and puts a
+ * "Show all rows" button after the table. The rows stay in the document, so
+ * search still finds them; navigating to something inside a collapsed row
+ * expands its table first.
+ */
+
+const COLLAPSED_ROW_CLASS = "table-row-collapsed";
+
+/**
+ * Handle the "Show all rows" buttons of every document.
+ */
+export function init(): void {
+  document.addEventListener("click", (event) => {
+    const button = (event.target as Element | null)?.closest(".table-show-all");
+    const toggle = button?.closest(".table-collapsed-toggle");
+    const table = toggle?.previousElementSibling;
+    if (table instanceof HTMLTableElement) {
+      expand(table);
+    }
+  });
+}
+
+/**
+ * Expand the table if `target` is inside one of its collapsed rows.
+ */
+export function reveal(target: Element): void {
+  const table = target.closest(`tr.${COLLAPSED_ROW_CLASS}`)?.closest("table");
+  if (table) {
+    expand(table);
+  }
+}
+
+function expand(table: HTMLTableElement): void {
+  table.querySelectorAll(`tr.${COLLAPSED_ROW_CLASS}`).forEach((row) => {
+    row.classList.remove(COLLAPSED_ROW_CLASS);
+  });
+  const toggle = table.nextElementSibling;
+  if (toggle?.classList.contains("table-collapsed-toggle")) {
+    toggle.remove();
+  }
+}
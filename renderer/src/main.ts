@@ -7,6 +7,18 @@ import * as mermaidRenderer from "./mermaid-renderer";
 import { renderCoordinator } from "./render-coordinator";
 import { setup as setupContextMenu, restoreSelection } from "./context-menu-handler";
 import * as findInPage from "./find-in-page";
+import * as splitPreview from "./split-preview";
+import * as codeCopy from "./code-copy";
+import * as codeRuler from "./code-ruler";
+import * as scrollTarget from "./scroll-target";
+import * as scrollAnchor from "./scroll-anchor";
+import * as scrollSpy from "./scroll-spy";
+import * as zoomAnchor from "./zoom-anchor";
+import * as linkPreview from "./link-preview";
+import * as longTables from "./long-tables";
+import * as headingAnchors from "./heading-anchors";
+import * as inlineToc from "./inline-toc";
+import * as themedPictures from "./themed-pictures";
 
 // Declare global Arto namespace
 declare global {
@@ -18,6 +30,7 @@ declare global {
       onRenderComplete: (callback: () => void) => void;
       search: {
         setup: typeof findInPage.setup;
+        configure: typeof findInPage.configure;
         find: typeof findInPage.find;
         navigate: typeof findInPage.navigate;
         navigateTo: typeof findInPage.navigateTo;
@@ -26,6 +39,38 @@ declare global {
         setPinned: typeof findInPage.setPinned;
         scrollToPinnedMatch: typeof findInPage.scrollToPinnedMatch;
       };
+      splitPreview: {
+        setup: typeof splitPreview.setup;
+        teardown: typeof splitPreview.teardown;
+        scrollToLine: typeof splitPreview.scrollToLine;
+      };
+      scroll: {
+        configure: typeof scrollTarget.configure;
+        toElement: typeof scrollTarget.scrollToElement;
+      };
+      scrollAnchor: {
+        capture: typeof scrollAnchor.capture;
+        resolve: typeof scrollAnchor.resolve;
+      };
+      scrollSpy: {
+        setup: typeof scrollSpy.setup;
+        refresh: typeof scrollSpy.refresh;
+        teardown: typeof scrollSpy.teardown;
+      };
+      zoomAnchor: {
+        capture: typeof zoomAnchor.capture;
+        restore: typeof zoomAnchor.restore;
+      };
+      codeCopy: {
+        configure: typeof codeCopy.configure;
+      };
+      codeRuler: {
+        configure: typeof codeRuler.configure;
+      };
+      linkPreview: {
+        setup: typeof linkPreview.setup;
+        show: typeof linkPreview.show;
+      };
     };
   }
 }
@@ -46,6 +91,7 @@ export function setCurrentTheme(theme: Theme) {
   markdownViewer.setTheme(theme);
   syntaxHighlighter.setTheme(theme);
   mermaidRenderer.setTheme(theme);
+  themedPictures.setTheme(theme);
   renderCoordinator.forceRenderMermaid();
 }
 
@@ -54,6 +100,9 @@ export function init(): void {
   syntaxHighlighter.mount();
   mermaidRenderer.init();
   renderCoordinator.init();
+  longTables.init();
+  headingAnchors.init();
+  inlineToc.init();
 
   // Expose Arto API on window for Rust interop
   window.Arto = {
@@ -62,6 +111,7 @@ export function init(): void {
     onRenderComplete: (callback) => renderCoordinator.onRenderComplete(callback),
     search: {
       setup: findInPage.setup,
+      configure: findInPage.configure,
       find: findInPage.find,
       navigate: findInPage.navigate,
       navigateTo: findInPage.navigateTo,
@@ -70,6 +120,38 @@ export function init(): void {
       setPinned: findInPage.setPinned,
       scrollToPinnedMatch: findInPage.scrollToPinnedMatch,
     },
+    splitPreview: {
+      setup: splitPreview.setup,
+      teardown: splitPreview.teardown,
+      scrollToLine: splitPreview.scrollToLine,
+    },
+    scroll: {
+      configure: scrollTarget.configure,
+      toElement: scrollTarget.scrollToElement,
+    },
+    scrollAnchor: {
+      capture: scrollAnchor.capture,
+      resolve: scrollAnchor.resolve,
+    },
+    scrollSpy: {
+      setup: scrollSpy.setup,
+      refresh: scrollSpy.refresh,
+      teardown: scrollSpy.teardown,
+    },
+    zoomAnchor: {
+      capture: zoomAnchor.capture,
+      restore: zoomAnchor.restore,
+    },
+    codeCopy: {
+      configure: codeCopy.configure,
+    },
+    codeRuler: {
+      configure: codeRuler.configure,
+    },
+    linkPreview: {
+      setup: linkPreview.setup,
+      show: linkPreview.show,
+    },
   };
 
   // Listen for theme changes from Rust
@@ -2,6 +2,9 @@ import * as mathRenderer from "./math-renderer";
 import * as mermaidRenderer from "./mermaid-renderer";
 import * as syntaxHighlighter from "./syntax-highlighter";
 import * as codeCopy from "./code-copy";
+import * as geojsonRenderer from "./geojson-renderer";
+import * as codeRuler from "./code-ruler";
+import * as themedPictures from "./themed-pictures";
 
 class RenderCoordinator {
   #rafId: number | null = null;
@@ -134,6 +137,9 @@ class RenderCoordinator {
         Array.from(markdownBodies).map(async (markdownBody) => {
           mathRenderer.renderMath(markdownBody);
           syntaxHighlighter.highlightCodeBlocks(markdownBody);
+          codeRuler.markLongLines(markdownBody);
+          themedPictures.selectSources(markdownBody);
+          geojsonRenderer.renderGeoJson(markdownBody);
           await mermaidRenderer.renderDiagrams(markdownBody);
           codeCopy.addCopyButtons(markdownBody);
         }),
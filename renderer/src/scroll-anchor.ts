@@ -0,0 +1,91 @@
+/**
+ * Keeping the reader's place when a file reloads after an edit.
+ *
+ * Before reloading, Rust captures an anchor describing what is at the top of
+ * the rendered view; after the new content renders, the anchor is resolved
+ * back to a scroll offset. Anchors that refer to content (heading, source
+ * line) survive edits above the viewport better than a raw offset.
+ */
+
+import { renderedOffsetForLine, topRenderedLine } from "./split-preview";
+
+/** How the scroll position is restored after a reload */
+export type ReloadScrollMode = "offset" | "fraction" | "heading" | "source_line";
+
+export interface ScrollAnchor {
+  mode: ReloadScrollMode;
+  /** `scrollTop` at capture time (also the fallback) */
+  scrollTop: number;
+  /** `scrollTop` as a fraction of the scrollable height */
+  fraction: number;
+  /** Id of the last heading at or above the top of the viewport */
+  headingId: string | null;
+  /** Fractional source line at the top of the viewport */
+  line: number;
+}
+
+/**
+ * Describe the current scroll position of `.content` for `mode`.
+ */
+export function capture(mode: ReloadScrollMode): ScrollAnchor | null {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return null;
+  }
+  const maxScroll = content.scrollHeight - content.clientHeight;
+  return {
+    mode,
+    scrollTop: content.scrollTop,
+    fraction: maxScroll > 0 ? content.scrollTop / maxScroll : 0,
+    headingId: mode === "heading" ? topHeadingId(content) : null,
+    line: mode === "source_line" ? topRenderedLine(content) : 1,
+  };
+}
+
+/**
+ * Compute the `scrollTop` that restores `anchor` in the current content.
+ */
+export function resolve(anchor: ScrollAnchor): number {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return anchor.scrollTop;
+  }
+
+  switch (anchor.mode) {
+    case "fraction":
+      return anchor.fraction * Math.max(content.scrollHeight - content.clientHeight, 0);
+    case "heading": {
+      // Snap to the heading; fall back to the old offset if it was removed
+      const heading = anchor.headingId ? document.getElementById(anchor.headingId) : null;
+      if (!heading || !content.contains(heading)) {
+        return anchor.headingId ? anchor.scrollTop : 0;
+      }
+      return content.scrollTop + offsetFromTop(content, heading);
+    }
+    case "source_line":
+      return content.scrollTop + renderedOffsetForLine(content, anchor.line);
+    default:
+      return anchor.scrollTop;
+  }
+}
+
+/**
+ * Id of the last heading whose top is at or above the top of the viewport.
+ */
+function topHeadingId(content: HTMLElement): string | null {
+  let id: string | null = null;
+  for (const heading of content.querySelectorAll<HTMLElement>(
+    ".markdown-body :is(h1, h2, h3, h4, h5, h6)[id]",
+  )) {
+    // Allow a pixel of slack for headings scrolled exactly to the top
+    if (offsetFromTop(content, heading) > 1) {
+      break;
+    }
+    id = heading.id;
+  }
+  return id;
+}
+
+function offsetFromTop(content: HTMLElement, el: HTMLElement): number {
+  return el.getBoundingClientRect().top - content.getBoundingClientRect().top;
+}
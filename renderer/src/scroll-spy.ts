@@ -0,0 +1,109 @@
+/**
+ * Scroll-spy for the table of contents.
+ *
+ * Reports the id of the heading the reader is currently in: the last heading
+ * whose top has passed a line slightly below the top of the view. Scroll
+ * events are throttled to one check per interval, with a trailing check so
+ * the final position after rapid scrolling is always reported.
+ */
+
+/** Minimum time between two checks while scrolling */
+const THROTTLE_MS = 100;
+
+/** Headings this close to the top of the view already count as current */
+const ACTIVATION_OFFSET = 80;
+
+const HEADING_SELECTOR = "h1[id], h2[id], h3[id], h4[id], h5[id], h6[id]";
+
+let cleanup: (() => void) | null = null;
+let check: (() => void) | null = null;
+
+/**
+ * Start reporting the current heading of the rendered view.
+ * Safe to call repeatedly; the previous listener is removed first.
+ */
+export function setup(onChange: (id: string | null) => void): void {
+  teardown();
+
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return;
+  }
+
+  let lastId: string | null | undefined;
+  let lastCheck = 0;
+  let timer: number | null = null;
+
+  const run = () => {
+    timer = null;
+    lastCheck = performance.now();
+    const id = currentHeadingId(content);
+    if (id !== lastId) {
+      lastId = id;
+      onChange(id);
+    }
+  };
+
+  const onScroll = () => {
+    if (timer !== null) {
+      return;
+    }
+    const wait = Math.max(0, THROTTLE_MS - (performance.now() - lastCheck));
+    timer = window.setTimeout(run, wait);
+  };
+
+  content.addEventListener("scroll", onScroll, { passive: true });
+  check = run;
+  cleanup = () => {
+    content.removeEventListener("scroll", onScroll);
+    if (timer !== null) {
+      window.clearTimeout(timer);
+    }
+  };
+
+  run();
+}
+
+/**
+ * Check again after the document changed (e.g. a new file was rendered).
+ */
+export function refresh(): void {
+  check?.();
+}
+
+/**
+ * Stop reporting the current heading.
+ */
+export function teardown(): void {
+  cleanup?.();
+  cleanup = null;
+  check = null;
+}
+
+function currentHeadingId(content: HTMLElement): string | null {
+  const body = content.querySelector(".markdown-body");
+  if (!body) {
+    return null;
+  }
+
+  const headings = Array.from(body.querySelectorAll<HTMLElement>(HEADING_SELECTOR));
+  if (headings.length === 0) {
+    return null;
+  }
+
+  // At the end of the document, the last heading is current even when it
+  // cannot scroll up to the activation line
+  if (content.scrollTop > 0 && content.scrollTop + content.clientHeight >= content.scrollHeight - 1) {
+    return headings[headings.length - 1].id;
+  }
+
+  const limit = content.getBoundingClientRect().top + ACTIVATION_OFFSET;
+  let current: string | null = null;
+  for (const heading of headings) {
+    if (heading.getBoundingClientRect().top > limit) {
+      break;
+    }
+    current = heading.id;
+  }
+  return current;
+}
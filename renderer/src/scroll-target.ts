@@ -0,0 +1,69 @@
+/**
+ * Scrolling the rendered view to a navigation target.
+ *
+ * Heading jumps, search matches and lint issues all go through here so they
+ * land consistently. With typewriter scrolling enabled, every target is placed
+ * at the same vertical fraction of the viewport (the focal line) instead of
+ * the top or center.
+ */
+
+import { reveal } from "./long-tables";
+
+export interface TypewriterOptions {
+  /** Whether targets are placed at the focal line */
+  enabled: boolean;
+  /** Vertical position of the focal line as a fraction of the viewport (0-1) */
+  focalPoint: number;
+}
+
+let options: TypewriterOptions = { enabled: false, focalPoint: 0.4 };
+
+/**
+ * Update the typewriter scrolling options (called from Rust on startup).
+ */
+export function configure(next: TypewriterOptions): void {
+  options = {
+    enabled: next.enabled,
+    focalPoint: Math.min(Math.max(next.focalPoint, 0), 1),
+  };
+}
+
+/**
+ * Scroll `target` into view.
+ * `block` is where the target goes when typewriter scrolling is off.
+ */
+export function scrollToElement(
+  target: Element | null | undefined,
+  block: ScrollLogicalPosition = "start",
+): void {
+  if (!target) {
+    return;
+  }
+  // Targets in collapsed table rows have no position until shown
+  reveal(target);
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!options.enabled || !content || !content.contains(target)) {
+    target.scrollIntoView({ behavior: "smooth", block });
+    return;
+  }
+  const offset = target.getBoundingClientRect().top - content.getBoundingClientRect().top;
+  scrollToOffset(offset, block);
+}
+
+/**
+ * Scroll `.content` so that the point `offset` pixels below the top of the
+ * viewport ends up at the top (or at the focal line in typewriter mode).
+ */
+export function scrollToOffset(offset: number, block: ScrollLogicalPosition = "start"): void {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return;
+  }
+  let shift = 0;
+  if (options.enabled) {
+    shift = content.clientHeight * options.focalPoint;
+  } else if (block === "center") {
+    shift = content.clientHeight / 2;
+  }
+  content.scrollTo({ top: content.scrollTop + offset - shift, behavior: "smooth" });
+}
@@ -0,0 +1,198 @@
+/**
+ * Scroll synchronization for the split preview.
+ *
+ * Rendered block elements carry the 1-based line they start on in
+ * `data-source-line`, and the source pane renders one element per line.
+ * Whichever pane the user scrolls drives the other, interpolating between
+ * neighbouring blocks so both move smoothly.
+ */
+
+import { scrollToOffset } from "./scroll-target";
+
+interface Anchor {
+  /** Source line the block starts on */
+  line: number;
+  /** Offset of the block's top from the top of the rendered view */
+  top: number;
+}
+
+let cleanup: (() => void) | null = null;
+
+/**
+ * Attach scroll listeners to the rendered view and the source pane.
+ * Safe to call repeatedly; previous listeners are removed first.
+ */
+export function setup(): void {
+  teardown();
+
+  const content = document.querySelector<HTMLElement>(".content");
+  const source = document.querySelector<HTMLElement>(".source-pane");
+  if (!content || !source) {
+    return;
+  }
+
+  // Panes whose next scroll event was caused by us rather than the user
+  const suppressed = new Set<HTMLElement>();
+
+  const scrollTo = (el: HTMLElement, top: number) => {
+    const before = el.scrollTop;
+    el.scrollTop = top;
+    if (el.scrollTop !== before) {
+      suppressed.add(el);
+    }
+  };
+
+  const onContentScroll = () => {
+    if (suppressed.delete(content)) {
+      return;
+    }
+    scrollTo(source, sourceOffsetForLine(source, topRenderedLine(content)));
+  };
+
+  const onSourceScroll = () => {
+    if (suppressed.delete(source)) {
+      return;
+    }
+    const offset = renderedOffsetForLine(content, topSourceLine(source));
+    scrollTo(content, content.scrollTop + offset);
+  };
+
+  content.addEventListener("scroll", onContentScroll, { passive: true });
+  source.addEventListener("scroll", onSourceScroll, { passive: true });
+
+  cleanup = () => {
+    content.removeEventListener("scroll", onContentScroll);
+    source.removeEventListener("scroll", onSourceScroll);
+  };
+
+  // Line up the source pane with wherever the rendered view currently is
+  onContentScroll();
+}
+
+/**
+ * Detach the scroll listeners installed by {@link setup}.
+ */
+export function teardown(): void {
+  cleanup?.();
+  cleanup = null;
+}
+
+/**
+ * Scroll the rendered view so that source `line` is at the top
+ * (or at the focal line with typewriter scrolling).
+ * Also used outside the split preview (e.g. jumping to lint issues).
+ */
+export function scrollToLine(line: number): void {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return;
+  }
+  scrollToOffset(renderedOffsetForLine(content, line));
+}
+
+/**
+ * Collect rendered blocks in document order, keeping only those that
+ * advance both in line and in position so interpolation stays monotonic.
+ */
+function renderedAnchors(content: HTMLElement): Anchor[] {
+  const viewTop = content.getBoundingClientRect().top;
+  const anchors: Anchor[] = [];
+
+  content.querySelectorAll<HTMLElement>("[data-source-line]").forEach((el) => {
+    const line = Number(el.dataset.sourceLine);
+    const top = el.getBoundingClientRect().top - viewTop;
+    const last = anchors[anchors.length - 1];
+    if (!last || (line > last.line && top > last.top)) {
+      anchors.push({ line, top });
+    }
+  });
+
+  return anchors;
+}
+
+/**
+ * Fractional source line shown at the top of the rendered view.
+ */
+export function topRenderedLine(content: HTMLElement): number {
+  const anchors = renderedAnchors(content);
+  const index = findLastIndex(anchors, (anchor) => anchor.top <= 0);
+  if (index < 0) {
+    return 1;
+  }
+
+  const current = anchors[index];
+  const next = anchors[index + 1];
+  if (!next) {
+    return current.line;
+  }
+  const progress = -current.top / (next.top - current.top);
+  return current.line + (next.line - current.line) * progress;
+}
+
+/**
+ * Offset from the top of the rendered view to where `line` is rendered.
+ */
+export function renderedOffsetForLine(content: HTMLElement, line: number): number {
+  const anchors = renderedAnchors(content);
+  const index = findLastIndex(anchors, (anchor) => anchor.line <= line);
+  if (index < 0) {
+    // Above the first block (e.g. frontmatter): go back to the top
+    return -content.scrollTop;
+  }
+
+  const current = anchors[index];
+  const next = anchors[index + 1];
+  if (!next) {
+    return current.top;
+  }
+  const progress = (line - current.line) / (next.line - current.line);
+  return current.top + (next.top - current.top) * progress;
+}
+
+/**
+ * Fractional source line shown at the top of the source pane.
+ */
+function topSourceLine(source: HTMLElement): number {
+  const lines = source.children;
+  let low = 0;
+  let high = lines.length - 1;
+  let index = 0;
+
+  // Binary search for the last line starting at or above the scroll position
+  while (low <= high) {
+    const mid = (low + high) >> 1;
+    if ((lines[mid] as HTMLElement).offsetTop <= source.scrollTop) {
+      index = mid;
+      low = mid + 1;
+    } else {
+      high = mid - 1;
+    }
+  }
+
+  const el = lines[index] as HTMLElement | undefined;
+  if (!el || el.offsetHeight === 0) {
+    return index + 1;
+  }
+  return index + 1 + (source.scrollTop - el.offsetTop) / el.offsetHeight;
+}
+
+/**
+ * Scroll offset of the source pane that puts `line` at the top.
+ */
+function sourceOffsetForLine(source: HTMLElement, line: number): number {
+  const whole = Math.floor(line);
+  const el = source.children[whole - 1] as HTMLElement | undefined;
+  if (!el) {
+    return source.scrollTop;
+  }
+  return el.offsetTop + el.offsetHeight * (line - whole);
+}
+
+function findLastIndex<T>(items: T[], predicate: (item: T) => boolean): number {
+  for (let i = items.length - 1; i >= 0; i--) {
+    if (predicate(items[i])) {
+      return i;
+    }
+  }
+  return -1;
+}
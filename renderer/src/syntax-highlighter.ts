@@ -50,6 +50,12 @@ function highlightCodeBlock(element: HTMLElement): void {
     return;
   }
 
+  // Blocks flagged `nohl` or in a disabled language stay plain
+  if (element.classList.contains("nohighlight")) {
+    element.dataset.highlighted = "yes";
+    return;
+  }
+
   // Extract language from class name (e.g., "language-rust" -> "rust")
   const langMatch = element.className.match(/language-([\w-]+)/);
   if (langMatch) {
@@ -64,7 +70,7 @@ function highlightCodeBlock(element: HTMLElement): void {
     if (hljs.getLanguage(lang)) {
       try {
         // Highlight the code block
-        hljs.highlightElement(element);
+        highlight(element, lang);
         console.debug(`Highlighted code block with language: ${lang}`);
       } catch (error) {
         console.warn(`Failed to highlight code block (${lang}):`, error);
@@ -78,7 +84,7 @@ function highlightCodeBlock(element: HTMLElement): void {
   }
 
   try {
-    hljs.highlightElement(element);
+    highlight(element);
     console.debug("Highlighted code block with auto-detection");
   } catch (error) {
     console.warn("Failed to highlight code block (auto):", error);
@@ -86,3 +92,55 @@ function highlightCodeBlock(element: HTMLElement): void {
     element.dataset.highlighted = "yes";
   }
 }
+
+function highlight(element: HTMLElement, language?: string): void {
+  if (element.parentElement?.classList.contains("code-lines")) {
+    highlightCodeLines(element, language);
+  } else {
+    hljs.highlightElement(element);
+  }
+}
+
+// Blocks with a line gutter or highlighted lines wrap each line in a
+// span.code-line; highlight the whole code and split it back into the lines
+function highlightCodeLines(element: HTMLElement, language?: string): void {
+  const lines = Array.from(element.querySelectorAll<HTMLElement>(".code-line"));
+  const code = lines.map((line) => line.textContent ?? "").join("\n");
+  const result = language
+    ? hljs.highlight(code, { language, ignoreIllegals: true })
+    : hljs.highlightAuto(code);
+
+  splitHighlightedLines(result.value).forEach((html, index) => {
+    if (lines[index]) {
+      lines[index].innerHTML = html;
+    }
+  });
+  element.classList.add("hljs");
+  element.dataset.highlighted = "yes";
+}
+
+/**
+ * Split highlighted HTML into lines, closing the spans still open at each
+ * line break and reopening them on the next line.
+ */
+export function splitHighlightedLines(html: string): string[] {
+  const lines: string[] = [];
+  const open: string[] = [];
+  let line = "";
+  for (const token of html.split(/(<[^>]*>|\n)/)) {
+    if (token === "\n") {
+      lines.push(line + "</span>".repeat(open.length));
+      line = open.join("");
+    } else if (token.startsWith("</")) {
+      open.pop();
+      line += token;
+    } else if (token.startsWith("<")) {
+      open.push(token);
+      line += token;
+    } else {
+      line += token;
+    }
+  }
+  lines.push(line);
+  return lines;
+}
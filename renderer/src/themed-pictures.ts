@@ -0,0 +1,31 @@
+/**
+ * Light and dark variants of `<picture>` images.
+ *
+ * `prefers-color-scheme` follows the system, not the theme chosen in Arto, so
+ * Rust marks sources with such a media query with `data-color-scheme`, and
+ * their `media` is set here to match the current theme instead.
+ */
+
+import { type Theme, getSystemTheme } from "./theme";
+
+let currentTheme: Theme = getSystemTheme();
+
+export function setTheme(theme: Theme): void {
+  currentTheme = theme;
+  document.querySelectorAll(".markdown-body").forEach(selectSources);
+}
+
+/**
+ * Enable the sources of the current theme in `container`.
+ */
+export function selectSources(container: Element): void {
+  container
+    .querySelectorAll<HTMLSourceElement>("picture > source[data-color-scheme]")
+    .forEach((source) => {
+      const media = source.dataset.colorScheme === currentTheme ? "all" : "not all";
+      // Only touch changed sources, so the render observer settles
+      if (source.media !== media) {
+        source.media = media;
+      }
+    });
+}
@@ -0,0 +1,87 @@
+/**
+ * Keeping the reader's place when zooming.
+ *
+ * Zooming reflows the document, so a raw scroll offset lands somewhere else
+ * afterwards. Before the zoom changes, Rust captures the `data-source-line`
+ * block at the focal line (top or center of the view); once the new zoom is
+ * rendered, that block is scrolled back to the same spot on screen.
+ */
+
+/** Where the focal line is in the viewport */
+export type ZoomAnchorPosition = "top" | "center";
+
+export interface ZoomAnchor {
+  /** Source line of the anchored block */
+  line: number;
+  /** Index among the blocks that start on `line` (nested blocks share it) */
+  index: number;
+  /** How far into the block the focal line was, as a fraction of its height */
+  fraction: number;
+  /** Distance of the focal line from the top of the view */
+  offset: number;
+}
+
+/**
+ * Describe the block at the focal line of `.content`.
+ */
+export function capture(position: ZoomAnchorPosition): ZoomAnchor | null {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return null;
+  }
+
+  const viewTop = content.getBoundingClientRect().top;
+  const offset = position === "center" ? content.clientHeight / 2 : 0;
+  const focal = viewTop + offset;
+
+  // The innermost block containing the focal line comes last in document
+  // order; between blocks, fall back to the last one above it
+  let anchor: HTMLElement | null = null;
+  let containing: HTMLElement | null = null;
+  for (const el of content.querySelectorAll<HTMLElement>("[data-source-line]")) {
+    const rect = el.getBoundingClientRect();
+    if (rect.top > focal) {
+      break;
+    }
+    anchor = el;
+    if (rect.bottom > focal) {
+      containing = el;
+    }
+  }
+  anchor = containing ?? anchor;
+  if (!anchor) {
+    return null;
+  }
+
+  const line = anchor.dataset.sourceLine ?? "";
+  const rect = anchor.getBoundingClientRect();
+  return {
+    line: Number(line),
+    index: Array.from(blocksOnLine(content, line)).indexOf(anchor),
+    fraction: rect.height > 0 ? Math.min(Math.max((focal - rect.top) / rect.height, 0), 1) : 0,
+    offset,
+  };
+}
+
+/**
+ * Scroll `.content` so the captured block is back at the focal line.
+ */
+export function restore(anchor: ZoomAnchor): void {
+  const content = document.querySelector<HTMLElement>(".content");
+  if (!content) {
+    return;
+  }
+
+  const el = blocksOnLine(content, String(anchor.line))[Math.max(anchor.index, 0)];
+  if (!el) {
+    return;
+  }
+
+  const rect = el.getBoundingClientRect();
+  const focal = content.getBoundingClientRect().top + anchor.offset;
+  content.scrollTop += rect.top + rect.height * anchor.fraction - focal;
+}
+
+function blocksOnLine(content: HTMLElement, line: string): NodeListOf<HTMLElement> {
+  return content.querySelectorAll<HTMLElement>(`[data-source-line="${CSS.escape(line)}"]`);
+}